@@ -28,11 +28,16 @@ fn load_key(raw: &[u8]) -> Result<AeadKey, CryptoError> {
 
 // Encrypts the data in-place and appends a 128bit auth tag
 pub fn encrypt(iv: &IV, secret: &[u8], in_out: &mut Vec<u8>) -> Result<(), CryptoError> {
+    encrypt_with_aad(iv, secret, &[], in_out)
+}
+
+// Encrypts the data in-place and appends a 128bit auth tag that also covers `aad`
+pub fn encrypt_with_aad(iv: &IV, secret: &[u8], aad: &[u8], in_out: &mut Vec<u8>) -> Result<(), CryptoError> {
     let nonce = ring::aead::Nonce::assume_unique_for_key(*iv);
     let key = load_key(secret)?;
 
     key.0
-        .seal_in_place_append_tag(nonce, ring::aead::Aad::empty(), in_out)
+        .seal_in_place_append_tag(nonce, ring::aead::Aad::from(aad), in_out)
         .map_err(|_| CryptoError::AeadEncryptError)?;
     Ok(())
 }
@@ -42,6 +47,16 @@ pub fn decrypt<'in_out>(
     iv: &[u8],
     secret: &[u8],
     in_out: &'in_out mut [u8],
+) -> Result<&'in_out mut [u8], CryptoError> {
+    decrypt_with_aad(iv, secret, &[], in_out)
+}
+
+// Decrypts the cipher in-place like `decrypt`, failing unless it was sealed with the same `aad`.
+pub fn decrypt_with_aad<'in_out>(
+    iv: &[u8],
+    secret: &[u8],
+    aad: &[u8],
+    in_out: &'in_out mut [u8],
 ) -> Result<&'in_out mut [u8], CryptoError> {
     let mut iv_arr = [0_u8; IV_BYTES];
     iv_arr.copy_from_slice(&iv[..IV_BYTES]);
@@ -49,7 +64,7 @@ pub fn decrypt<'in_out>(
     let nonce = ring::aead::Nonce::assume_unique_for_key(iv_arr);
 
     key.0
-        .open_in_place(nonce, ring::aead::Aad::empty(), in_out)
+        .open_in_place(nonce, ring::aead::Aad::from(aad), in_out)
         .map_err(|_| CryptoError::AeadDecryptError)
 }
 
@@ -79,4 +94,19 @@ mod test {
 
         assert_eq!(decrypted_messgae, message);
     }
+
+    #[test]
+    fn decrypt_checks_the_associated_data() {
+        let iv = generate_random_iv();
+        let secret = [233_u8; 32];
+        let message = [233_u8; 64];
+
+        let mut encrypted_message = message.to_vec();
+        encrypt_with_aad(&iv, &secret, b"alice", &mut encrypted_message).unwrap();
+
+        assert!(decrypt_with_aad(&iv, &secret, b"mallory", &mut encrypted_message.clone()[..]).is_err());
+        assert!(decrypt(&iv, &secret, &mut encrypted_message.clone()[..]).is_err());
+        let decrypted_message = decrypt_with_aad(&iv, &secret, b"alice", &mut encrypted_message[..]).unwrap();
+        assert_eq!(decrypted_message, message);
+    }
 }
@@ -235,6 +235,15 @@ pub struct DecodedMessage<T> {
     pub payload: T,
 }
 
+/// An outbound message retained on chain, located by the block it was pushed in and its
+/// position among the messages of that block.
+#[derive(Encode, Decode, TypeInfo, Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RetainedMessage<BlockNumber> {
+    pub block_number: BlockNumber,
+    pub index: u32,
+    pub message: Message,
+}
+
 #[derive(Encode, Decode, TypeInfo, Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct SignedMessage {
     pub message: Message,
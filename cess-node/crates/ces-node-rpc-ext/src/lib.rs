@@ -23,6 +23,7 @@ pub use storage_changes::{
     StorageChangesWithRoot,
 };
 
+mod mq_retained;
 mod mq_seq;
 mod storage_changes;
 
@@ -124,6 +125,20 @@ pub trait NodeRpcExtApi<BlockHash> {
     /// count.
     #[method(name = "getMqNextSequence")]
     fn get_mq_seq(&self, sender_hex: String) -> Result<u64, Error>;
+
+    /// Return the outbound mq messages still retained on chain, pushed in blocks `from` to
+    /// `to`(both inclusive). Blocks out of the retention window are skipped. The sender is given
+    /// as `hex_encode(scale_encode(MessageOrigin))` and the topic as `hex_encode(path)`, both
+    /// optional.
+    /// Returns `hex_encode(scale_encode(Vec<RetainedMessage>))`
+    #[method(name = "getMqRetainedMessages")]
+    fn get_mq_retained_messages(
+        &self,
+        sender_hex: Option<String>,
+        topic_hex: Option<String>,
+        from: u64,
+        to: u64,
+    ) -> Result<String, Error>;
 }
 
 /// Stuffs for custom RPC
@@ -215,4 +230,14 @@ where
         let result = mq_seq::get_mq_seq(&*self.client, &self.pool, sender_hex);
         Ok(result?)
     }
+
+    fn get_mq_retained_messages(
+        &self,
+        sender_hex: Option<String>,
+        topic_hex: Option<String>,
+        from: u64,
+        to: u64,
+    ) -> Result<String, Error> {
+        mq_retained::get_mq_retained_messages(&*self.client, sender_hex, topic_hex, from, to)
+    }
 }
@@ -0,0 +1,66 @@
+use super::*;
+use ces_mq::{MessageOrigin, Path};
+use ces_pallet_mq_runtime_api::MqApi;
+use parity_scale_codec::Decode;
+use sp_runtime::traits::{NumberFor, UniqueSaturatedFrom};
+
+pub(super) fn get_mq_retained_messages<Client, BE, Block>(
+    client: &Client,
+    sender_hex: Option<String>,
+    topic_hex: Option<String>,
+    from: u64,
+    to: u64,
+) -> Result<String, Error>
+where
+    BE: Backend<Block>,
+    Client: StorageProvider<Block, BE>
+        + HeaderBackend<Block>
+        + BlockBackend<Block>
+        + HeaderMetadata<Block, Error = sp_blockchain::Error>
+        + ProvideRuntimeApi<Block>,
+    Block: BlockT + 'static,
+    Client::Api: sp_api::Metadata<Block> + ApiExt<Block>,
+    Client::Api: MqApi<Block>,
+    <<Block as BlockT>::Header as Header>::Number: Into<u64>,
+{
+    if from > to {
+        return Err(Error::InvalidBlockRange {
+            from: from.to_string(),
+            to: to.to_string(),
+        });
+    }
+
+    let sender = sender_hex
+        .map(|sender_hex| {
+            let sender_scl = hex::decode(sender_hex).map_err(|_| Error::InvalidSender)?;
+            MessageOrigin::decode(&mut &sender_scl[..]).map_err(|_| Error::InvalidSender)
+        })
+        .transpose()?;
+    let topic: Option<Path> = topic_hex
+        .map(|topic_hex| {
+            hex::decode(topic_hex).map_err(|_| Error::StringError("invalid topic".into()))
+        })
+        .transpose()?;
+
+    let api = client.runtime_api();
+    let best_hash = client.info().best_hash;
+
+    let api_version = api.api_version::<dyn MqApi<Block>>(best_hash)?.unwrap_or(0);
+    if api_version < 2 {
+        return Err(Error::Unavailable(
+            "The runtime does not retain outbound messages.".into(),
+        ));
+    }
+
+    let messages = api.retained_messages(
+        best_hash,
+        sender,
+        topic,
+        NumberFor::<Block>::unique_saturated_from(from),
+        NumberFor::<Block>::unique_saturated_from(to),
+    )?;
+
+    log::debug!(target: "rpc-ext", "{} retained messages in #{}..=#{}", messages.len(), from, to);
+
+    Ok(impl_serde::serialize::to_hex(&messages.encode(), false))
+}
@@ -0,0 +1,173 @@
+//! Throughput of commit and space proof verification for idle file sets of `IDLE_SET_LEN` clusters of
+//! `CLUSTER_SIZE` files each, verified one proof at a time and as a batch on thread pools of different sizes.
+//!
+//! The expanders default to the production parameters, which take a while and about 16 GiB of disk per idle file
+//! set to generate. Set `CES_POIS_BENCH_N` to a smaller number of nodes per layer (at least 16384, a power of two)
+//! for a quick run.
+
+use std::{env, fs, path::PathBuf};
+
+use ces_pois::{
+    acc::rsa_keygen,
+    pois::{
+        prove::{CommitProof, Prover, SpaceProof},
+        verify::{ProverNode, Verifier, CLUSTER_SIZE, IDLE_SET_LEN},
+    },
+};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use threadpool::ThreadPool;
+
+// production expanders: `k` matches `CLUSTER_SIZE`, one layer holds 1 Mi nodes of 64 parents
+const N: i64 = 1024 * 1024;
+const D: i64 = 64;
+const COMMITS: usize = 1;
+// files covered by a single space proof
+const BLOCK_SIZE: i64 = 32;
+
+struct Fixture {
+    dir: PathBuf,
+    verifier: Verifier,
+    p_node: ProverNode,
+    chals: Vec<i64>,
+    proofs: Vec<SpaceProof>,
+    /// A verifier holding the commits of the last idle file set, with their challenges and proofs.
+    commit_verifier: Verifier,
+    commit_chals: Vec<Vec<i64>>,
+    commit_proofs: Vec<Vec<CommitProof>>,
+}
+
+fn expanders_n() -> i64 {
+    env::var("CES_POIS_BENCH_N")
+        .ok()
+        .map(|n| n.parse().expect("CES_POIS_BENCH_N should be a number"))
+        .unwrap_or(N)
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn setup() -> Fixture {
+    let dir = env::temp_dir().join(format!("ces-pois-bench-{}", hex::encode(rand::random::<[u8; 8]>())));
+    fs::create_dir_all(&dir).unwrap();
+    let n = expanders_n();
+    let verifier = Verifier::new(CLUSTER_SIZE, n, D);
+    let commit_verifier = Verifier::new(CLUSTER_SIZE, n, D);
+    let id = rand::random::<[u8; 32]>();
+    let mut prover = Prover::new(CLUSTER_SIZE, n, D, &id, rsa_keygen(2048), &dir).unwrap();
+
+    let (mut commit_chals, mut commit_proofs) = (Vec::new(), Vec::new());
+    for _ in 0..COMMITS {
+        prover.generate_idle_file_set().unwrap();
+        let commits = prover.get_idle_file_set_commits().unwrap();
+        verifier.register_prover_node_empty(&prover.id);
+        assert!(verifier.receive_commits(&prover.id, &commits));
+        let chals = verifier.commit_challenges(&prover.id).unwrap();
+        let (proofs, acc_proof) = prover.prove_commit_and_acc(&chals).unwrap();
+        verifier.update_prover_node_force(&prover.id, prover.key(), &prover.acc(), prover.front, prover.rear);
+        verifier.verify_commit_proofs(&prover.id, chals.clone(), proofs.clone()).unwrap();
+        let id_str = hex::encode(&prover.id);
+        commit_verifier.nodes.put(&id_str, verifier.nodes.get(&id_str).unwrap());
+        (commit_chals, commit_proofs) = (chals.clone(), proofs);
+        verifier.verify_acc(&prover.id, chals, acc_proof).unwrap();
+        let (_, _, rear) = verifier.logout_prover_node(&prover.id).unwrap();
+        prover.update_status(rear - prover.rear, false).unwrap();
+    }
+    assert_eq!(prover.rear, COMMITS as i64 * CLUSTER_SIZE * IDLE_SET_LEN);
+
+    let p_node = ProverNode::new(&prover.id, prover.key(), &prover.acc(), prover.front, prover.rear);
+    let chals = verifier.space_challenges(CLUSTER_SIZE).unwrap();
+    let proofs = (prover.front + 1..=prover.rear)
+        .step_by(BLOCK_SIZE as usize)
+        .map(|left| {
+            prover
+                .prove_space(&chals, left, (left + BLOCK_SIZE).min(prover.rear + 1))
+                .unwrap()
+        })
+        .collect();
+    Fixture { dir, verifier, p_node, chals, proofs, commit_verifier, commit_chals, commit_proofs }
+}
+
+fn commit_verify(c: &mut Criterion, fixture: &Fixture) {
+    let id = &fixture.p_node.id;
+
+    let mut group = c.benchmark_group("verify_commit");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements((CLUSTER_SIZE * IDLE_SET_LEN) as u64));
+
+    group.bench_function("sequential", |b| {
+        b.iter_batched(
+            || (fixture.commit_chals.clone(), fixture.commit_proofs.clone()),
+            |(chals, proofs)| fixture.commit_verifier.verify_commit_proofs(id, chals, proofs).unwrap(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    for threads in [1, 2, 4, 8] {
+        let pool = ThreadPool::new(threads);
+        group.bench_with_input(BenchmarkId::new("batch", threads), &pool, |b, pool| {
+            b.iter_batched(
+                || (fixture.commit_chals.clone(), fixture.commit_proofs.clone()),
+                |(chals, proofs)| {
+                    let results =
+                        fixture.commit_verifier.verify_commit_proofs_batch(id, chals, proofs, pool.clone()).unwrap();
+                    assert!(results.iter().all(|result| result.is_ok()));
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn pois_verify(c: &mut Criterion) {
+    let fixture = setup();
+    commit_verify(c, &fixture);
+    space_verify(c, &fixture);
+}
+
+fn space_verify(c: &mut Criterion, fixture: &Fixture) {
+    let files = fixture.p_node.record.as_ref().unwrap().rear as u64;
+
+    let mut group = c.benchmark_group("verify_space");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(files));
+
+    group.bench_function("sequential", |b| {
+        b.iter_batched(
+            || fixture.proofs.clone(),
+            |proofs| {
+                for mut proof in proofs {
+                    fixture
+                        .verifier
+                        .verify_space(&fixture.p_node, fixture.chals.clone(), &mut proof)
+                        .unwrap();
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    for threads in [1, 2, 4, 8] {
+        let pool = ThreadPool::new(threads);
+        group.bench_with_input(BenchmarkId::new("batch", threads), &pool, |b, pool| {
+            b.iter_batched(
+                || fixture.proofs.clone(),
+                |proofs| {
+                    let results =
+                        fixture
+                            .verifier
+                            .verify_space_batch(&fixture.p_node, &fixture.chals, proofs, pool.clone());
+                    assert!(results.iter().all(|result| result.is_ok()));
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, pois_verify);
+criterion_main!(benches);
@@ -48,4 +48,23 @@ pub fn generate_acc(key: &RsaKey, acc: &[u8], elems: Vec<Vec<u8>>) -> Option<Vec
     }
 
     Some(g.to_bytes_be())
-}
\ No newline at end of file
+}
+
+/// Generate the witness of every element whose prime representative is in `primes`, that is `g` raised to the product
+/// of all the other primes. The work is split recursively so that the whole set costs O(n log n) exponentiations.
+pub fn generate_witness(g: &BigUint, n: &BigUint, primes: &[BigUint]) -> Vec<BigUint> {
+    if primes.is_empty() {
+        return Vec::new();
+    }
+    if primes.len() == 1 {
+        return vec![g.clone()];
+    }
+
+    let (left, right) = primes.split_at(primes.len() / 2);
+    let g_left = right.iter().fold(g.clone(), |acc, p| acc.modpow(p, n));
+    let g_right = left.iter().fold(g.clone(), |acc, p| acc.modpow(p, n));
+
+    let mut wits = generate_witness(&g_left, n, left);
+    wits.extend(generate_witness(&g_right, n, right));
+    wits
+}
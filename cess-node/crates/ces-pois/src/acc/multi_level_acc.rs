@@ -1,8 +1,11 @@
+use anyhow::{bail, Result};
+use dashmap::DashSet;
 use num_bigint_dig::BigUint;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-use super::{generate_acc, hash_2_prime::h_prime, RsaKey};
+use super::{generate_acc, generate_witness, hash_2_prime::h_prime, RsaKey};
 
 const DEFAULT_LEVEL: i32 = 3;
 const DEFAULT_ELEMS_NUM: i32 = 256;
@@ -86,6 +89,68 @@ pub fn verify_mutilevel_acc_for_batch(
     base_idx: i64,
     wits: Vec<WitnessNode>,
     acc: &[u8],
+) -> bool {
+    verify_mutilevel_acc_for_batch_cached(key, base_idx, &wits, acc, &WitnessCache::default())
+}
+
+/// Witnesses already proven against their accumulators.
+///
+/// Proofs over the same multi-level accumulator share its upper levels, a cache shared between them saves
+/// the prime hashing and modular exponentiation of those levels.
+#[derive(Debug, Default)]
+pub struct WitnessCache {
+    verified: DashSet<[u8; 32]>,
+}
+
+impl WitnessCache {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.verified.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.verified.is_empty()
+    }
+
+    fn verify_acc(&self, key: &RsaKey, acc: &[u8], u: &[u8], wit: &[u8]) -> bool {
+        let mut hasher = Sha256::new();
+        for data in [&key.n.to_bytes_be()[..], acc, u, wit] {
+            hasher.update((data.len() as u64).to_be_bytes());
+            hasher.update(data);
+        }
+        let id: [u8; 32] = hasher.finalize().into();
+        if self.verified.contains(&id) {
+            return true;
+        }
+        if !verify_acc(key, acc, u, wit) {
+            return false;
+        }
+        self.verified.insert(id);
+        true
+    }
+
+    fn verify_mutilevel_acc(&self, key: &RsaKey, wits: &WitnessNode, acc: &[u8]) -> bool {
+        let mut current_wit = wits;
+        while let Some(acc_node) = &current_wit.acc {
+            if !self.verify_acc(key, &acc_node.elem, &current_wit.elem, &current_wit.wit) {
+                return false;
+            }
+            current_wit = acc_node;
+        }
+        current_wit.elem.eq(acc)
+    }
+}
+
+/// Same as [`verify_mutilevel_acc_for_batch`], reusing and extending the witnesses verified in `cache`.
+pub fn verify_mutilevel_acc_for_batch_cached(
+    key: &RsaKey,
+    base_idx: i64,
+    wits: &[WitnessNode],
+    acc: &[u8],
+    cache: &WitnessCache,
 ) -> bool {
     let mut sub_acc: Option<Vec<u8>> = None;
     let default_elems_num = DEFAULT_ELEMS_NUM as i64;
@@ -97,7 +162,7 @@ pub fn verify_mutilevel_acc_for_batch(
         }
 
         if (i as i64 + base_idx) % default_elems_num == 0 || i == wits.len() - 1 {
-            if !verify_mutilevel_acc(key, Some(&mut witness.clone()), acc) {
+            if !cache.verify_mutilevel_acc(key, witness, acc) {
                 return false;
             }
             sub_acc = None;
@@ -135,14 +200,17 @@ pub fn verify_delete_update(
         return false;
     }
 
+    // Verify that the original accumulator is recovered by adding the deleted elements back
+    // into the new accumulator
     let mut sub_acc = generate_acc(&key, &accs[0], elems);
-    if sub_acc.eq(&Some(exist.elem.clone())) {
+    if !sub_acc.eq(&Some(exist.elem.clone())) {
         return false;
     }
     let mut p = exist;
     let mut count = 1;
     while p.acc.is_some() {
-        if accs[count - 1].eq(&key.g.to_bytes_be()) {
+        // an empty child accumulator has been removed from its parent
+        if !accs[count - 1].eq(&key.g.to_bytes_be()) {
             sub_acc = generate_acc(&key, &p.wit, vec![accs[count - 1].clone()]);
         } else {
             sub_acc = Some(p.wit.clone());
@@ -155,4 +223,194 @@ pub fn verify_delete_update(
     }
 
     true
-}
\ No newline at end of file
+}
+
+/// A node of the prover side multi-level accumulator. Level-1 nodes accumulate the elements themselves, every upper
+/// node accumulates the values of its children.
+#[derive(Clone, Debug, Default)]
+pub struct AccNode {
+    pub value: Vec<u8>,
+    pub children: Vec<AccNode>,
+    /// The witness of `value` in the parent node.
+    pub wit: Vec<u8>,
+    /// The prime representative of `value`, cleared whenever `value` changes.
+    pub prime: Option<BigUint>,
+}
+
+/// The prover side of the multi-level accumulator, it keeps every element together with the witness chain that the
+/// verifier functions above consume.
+#[derive(Clone, Debug)]
+pub struct MultiLevelAcc {
+    pub key: RsaKey,
+    pub accs: AccNode,
+    pub elem_nums: usize,
+}
+
+impl MultiLevelAcc {
+    pub fn new(key: RsaKey) -> Self {
+        let accs = AccNode { value: key.g.to_bytes_be(), ..Default::default() };
+        MultiLevelAcc { key, accs, elem_nums: 0 }
+    }
+
+    /// The current accumulator value.
+    pub fn acc(&self) -> Vec<u8> {
+        self.accs.value.clone()
+    }
+
+    /// Insert `elems` into the last level-1 node, or into a new one when they do not fit. Returns the witness chain of
+    /// that node before the insertion and the updated accumulators of every level, as expected by
+    /// [`verify_insert_update`].
+    pub fn add_elements_and_proof(&mut self, elems: Vec<Vec<u8>>) -> Result<(WitnessNode, Vec<Vec<u8>>)> {
+        let elems_num = DEFAULT_ELEMS_NUM as usize;
+        if elems.is_empty() || elems.len() > elems_num {
+            bail!("add elements error: bad number of elements");
+        }
+        let g = self.key.g.to_bytes_be();
+
+        let root = &mut self.accs;
+        let new_leaf = match root.children.last().and_then(|mid| mid.children.last()) {
+            Some(leaf) => leaf.children.len() + elems.len() > elems_num,
+            None => true,
+        };
+        if new_leaf {
+            let new_mid = match root.children.last() {
+                Some(mid) => mid.children.len() >= elems_num,
+                None => true,
+            };
+            if new_mid {
+                let mid = AccNode { value: g.clone(), wit: root.value.clone(), ..Default::default() };
+                root.children.push(mid);
+            }
+            let mid = root.children.last_mut().unwrap();
+            let leaf = AccNode { value: g.clone(), wit: mid.value.clone(), ..Default::default() };
+            mid.children.push(leaf);
+        }
+
+        let mid = root.children.last().unwrap();
+        let exist = witness_chain(&[mid.children.last().unwrap(), mid, &*root]);
+
+        let mid = root.children.last_mut().unwrap();
+        let leaf = mid.children.last_mut().unwrap();
+        leaf.children
+            .extend(elems.into_iter().map(|elem| AccNode { value: elem, ..Default::default() }));
+        update_node(&self.key, leaf);
+        let leaf_value = leaf.value.clone();
+        update_node(&self.key, mid);
+        let mid_value = mid.value.clone();
+        update_node(&self.key, root);
+        let accs = vec![leaf_value, mid_value, root.value.clone()];
+
+        self.elem_nums = self.count_elems();
+        Ok((exist, accs))
+    }
+
+    /// Delete the first `num` elements, which must all belong to the first level-1 node. Returns the witness chain of
+    /// that node before the deletion and the updated accumulators of every level, as expected by
+    /// [`verify_delete_update`].
+    pub fn delete_elements_and_proof(&mut self, num: usize) -> Result<(WitnessNode, Vec<Vec<u8>>)> {
+        let g = self.key.g.to_bytes_be();
+        let root = &mut self.accs;
+        let lens = match root.children.first().and_then(|mid| mid.children.first()) {
+            Some(leaf) => leaf.children.len(),
+            None => bail!("delete elements error: no element in the accumulator"),
+        };
+        if num == 0 || num > lens {
+            bail!("delete elements error: the elements to delete must be in the same accumulator node");
+        }
+
+        let mid = root.children.first().unwrap();
+        let exist = witness_chain(&[mid.children.first().unwrap(), mid, &*root]);
+
+        let mid = root.children.first_mut().unwrap();
+        let leaf = mid.children.first_mut().unwrap();
+        leaf.children.drain(..num);
+        let leaf_value = if leaf.children.is_empty() {
+            mid.children.remove(0);
+            g.clone()
+        } else {
+            update_node(&self.key, leaf);
+            leaf.value.clone()
+        };
+        let mid_value = if mid.children.is_empty() {
+            root.children.remove(0);
+            g
+        } else {
+            update_node(&self.key, mid);
+            mid.value.clone()
+        };
+        update_node(&self.key, root);
+        let accs = vec![leaf_value, mid_value, root.value.clone()];
+
+        self.elem_nums = self.count_elems();
+        Ok((exist, accs))
+    }
+
+    /// Get the witness chains of `num` consecutive elements starting from the `index`-th (0-based) element currently
+    /// in the accumulator.
+    pub fn get_witness_chains(&self, index: usize, num: usize) -> Result<Vec<WitnessNode>> {
+        if num == 0 || index + num > self.elem_nums {
+            bail!("get witness chains error: elements out of range");
+        }
+
+        let mut chains = Vec::with_capacity(num);
+        let mut skip = index;
+        for mid in &self.accs.children {
+            for leaf in &mid.children {
+                if skip >= leaf.children.len() {
+                    skip -= leaf.children.len();
+                    continue;
+                }
+                for elem in &leaf.children[skip..] {
+                    chains.push(witness_chain(&[elem, leaf, mid, &self.accs]));
+                    if chains.len() == num {
+                        return Ok(chains);
+                    }
+                }
+                skip = 0;
+            }
+        }
+        Ok(chains)
+    }
+
+    fn count_elems(&self) -> usize {
+        self.accs
+            .children
+            .iter()
+            .flat_map(|mid| mid.children.iter())
+            .map(|leaf| leaf.children.len())
+            .sum()
+    }
+}
+
+/// Recalculate the accumulator value of `node` and the witnesses of its children.
+fn update_node(key: &RsaKey, node: &mut AccNode) {
+    node.prime = None;
+    if node.children.is_empty() {
+        node.value = key.g.to_bytes_be();
+        return;
+    }
+    let primes = node
+        .children
+        .iter_mut()
+        .map(|child| {
+            child
+                .prime
+                .get_or_insert_with(|| h_prime(&BigUint::from_bytes_be(&child.value)))
+                .clone()
+        })
+        .collect::<Vec<_>>();
+    let wits = generate_witness(&key.g, &key.n, &primes);
+    node.value = wits[0].modpow(&primes[0], &key.n).to_bytes_be();
+    for (child, wit) in node.children.iter_mut().zip(wits) {
+        child.wit = wit.to_bytes_be();
+    }
+}
+
+/// Build a witness chain from a node up to the root, `nodes` is ordered from the bottom level to the root.
+fn witness_chain(nodes: &[&AccNode]) -> WitnessNode {
+    let mut chain: Option<Box<WitnessNode>> = None;
+    for node in nodes.iter().rev() {
+        chain = Some(Box::new(WitnessNode { elem: node.value.clone(), wit: node.wit.clone(), acc: chain }));
+    }
+    *chain.unwrap_or_default()
+}
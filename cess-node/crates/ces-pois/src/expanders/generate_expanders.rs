@@ -1,7 +1,57 @@
+use dashmap::DashMap;
 use sha2::{Digest, Sha512};
 
 use super::{Expanders, Node, NodeType};
 
+/// Expander parents already calculated, keyed by miner id, cluster, real layer and node index.
+///
+/// The parents of a node only depend on these, so verifications of the same miner can share
+/// them. The cache is emptied whenever it grows beyond its capacity.
+#[derive(Debug)]
+pub struct ParentCache {
+    parents: DashMap<(Vec<u8>, i64, i64, NodeType), Vec<NodeType>>,
+    capacity: usize,
+}
+
+impl ParentCache {
+    pub fn new(capacity: usize) -> Self {
+        ParentCache { parents: DashMap::new(), capacity }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Same as [`calc_parents`] for a node with room for all its parents, served from the cache
+    /// when possible.
+    pub fn parents(
+        &self,
+        expanders: &Expanders,
+        index: NodeType,
+        miner_id: &[u8],
+        count: i64,
+        rlayer: i64,
+    ) -> Vec<NodeType> {
+        let key = (miner_id.to_vec(), count, rlayer, index);
+        if let Some(parents) = self.parents.get(&key) {
+            return parents.clone();
+        }
+
+        let mut node = Node::new(index);
+        node.parents = Vec::with_capacity(expanders.d as usize + 1);
+        calc_parents(expanders, &mut node, miner_id, count, rlayer);
+        if self.parents.len() >= self.capacity {
+            self.parents.clear();
+        }
+        self.parents.insert(key, node.parents.clone());
+        node.parents
+    }
+}
+
 pub fn construct_stacked_expanders(k: i64, n: i64, d: i64) -> Expanders {
     Expanders::new(k, n, d)
 }
@@ -1,7 +1,21 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
 use sha2::{Digest, Sha256, Sha512};
 
+use super::{generate_expanders::calc_parents, get_bytes, Expanders, Node, NodeType};
+use crate::util::{add_data, copy_data};
+
 pub const HASH_SIZE: i32 = 64;
 
+pub const SET_DIR_NAME: &str = "idlefile-set";
+pub const CLUSTER_DIR_NAME: &str = "file-cluster";
+pub const FILE_NAME: &str = "sub-file";
+pub const LAYER_NAME: &str = "layer";
+
 pub enum Hasher {
     SHA256(Sha256),
     SHA512(Sha512),
@@ -36,4 +50,119 @@ pub fn get_hash(data: &[u8]) -> Vec<u8> {
             result.to_vec()
         }
     }
-}
\ No newline at end of file
+}
+
+/// The directory of the file cluster `cluster` (1-based), clusters are grouped into sets of `set_len`.
+pub fn cluster_dir(root_dir: &Path, set_len: i64, cluster: i64) -> PathBuf {
+    root_dir
+        .join(format!("{}-{}", SET_DIR_NAME, (cluster - 1) / set_len + 1))
+        .join(format!("{}-{}", CLUSTER_DIR_NAME, cluster))
+}
+
+/// The labels of `layer` in a file cluster. The first `k` layers are auxiliary layers of the stacked expanders, the
+/// following ones are the idle files of the cluster.
+pub fn layer_path(expanders: &Expanders, root_dir: &Path, set_len: i64, cluster: i64, layer: i64) -> PathBuf {
+    let dir = cluster_dir(root_dir, set_len, cluster);
+    if layer < expanders.k {
+        dir.join(format!("{}-{}", LAYER_NAME, layer))
+    } else {
+        dir.join(format!("{}-{}", FILE_NAME, layer - expanders.k))
+    }
+}
+
+pub fn read_labels(path: &Path, expanders: &Expanders) -> Result<Vec<u8>> {
+    let data = fs::read(path).with_context(|| format!("read labels from {} error", path.display()))?;
+    if data.len() != (expanders.n * expanders.hash_size) as usize {
+        bail!("read labels error: bad file size of {}", path.display());
+    }
+    Ok(data)
+}
+
+/// The layers whose labels are folded into the labels of idle file `layer` (`layer >= k`).
+pub fn elder_layers(expanders: &Expanders, layer: i64) -> Vec<i64> {
+    ((layer - expanders.k / 2) / expanders.k..expanders.k).step_by(2).collect()
+}
+
+impl Expanders {
+    /// Generate the idle file set made of the `set_len` clusters following the first `start` ones. Each cluster holds
+    /// `k` auxiliary layers and `k` idle files of `n` labels each, and the labels are chained layer by layer across the
+    /// clusters of the set.
+    pub fn generate_idle_file_set(&self, miner_id: &[u8], start: i64, set_len: i64, root_dir: &Path) -> Result<()> {
+        if set_len <= 0 || start % set_len != 0 {
+            bail!("generate idle file set error: bad set range");
+        }
+        for cluster in start + 1..=start + set_len {
+            fs::create_dir_all(cluster_dir(root_dir, set_len, cluster))?;
+        }
+
+        let n = self.n as usize;
+        let hash_size = HASH_SIZE as usize;
+        let front_size = std::mem::size_of::<NodeType>() + miner_id.len() + 8 + 8;
+        let zero = vec![0; 2 * hash_size];
+        let mut label = vec![0; front_size + 2 * hash_size + hash_size];
+
+        let mut neighbor: Option<Vec<u8>> = None;
+        for layer in 0..self.k * 2 {
+            let logical_layer = layer.min(self.k);
+            for cluster in start + 1..=start + set_len {
+                let parent_labels = if logical_layer > 0 {
+                    read_labels(&layer_path(self, root_dir, set_len, cluster, logical_layer - 1), self)?
+                } else {
+                    Vec::new()
+                };
+                let elders = if layer >= self.k {
+                    elder_layers(self, layer)
+                        .into_iter()
+                        .map(|elder| read_labels(&layer_path(self, root_dir, set_len, cluster, elder), self))
+                        .collect::<Result<Vec<_>>>()?
+                } else {
+                    Vec::new()
+                };
+                let fidx = if layer >= self.k { (cluster - 1) * self.k + layer - self.k + 1 } else { 0 };
+
+                let mut labels = vec![0u8; n * hash_size];
+                for i in 0..n {
+                    let index = (logical_layer * self.n + i as i64) as NodeType;
+                    let mut node = Node::new(index);
+                    node.parents = Vec::with_capacity(self.d as usize + 1);
+                    calc_parents(self, &mut node, miner_id, cluster, layer);
+
+                    copy_data(&mut label, &[miner_id, &get_bytes(cluster), &get_bytes(fidx), &get_bytes(index), &zero]);
+                    for parent in &node.parents {
+                        let parent = *parent as i64;
+                        let plabel = if parent >= logical_layer * self.n {
+                            let j = (parent - logical_layer * self.n) as usize;
+                            &labels[j * hash_size..(j + 1) * hash_size]
+                        } else {
+                            let j = (parent - (logical_layer - 1) * self.n) as usize;
+                            &parent_labels[j * hash_size..(j + 1) * hash_size]
+                        };
+                        add_data(&mut label[front_size..front_size + hash_size], &[plabel]);
+                    }
+                    for elder in &elders {
+                        add_data(
+                            &mut label[front_size + hash_size..front_size + 2 * hash_size],
+                            &[&elder[i * hash_size..(i + 1) * hash_size]],
+                        );
+                    }
+
+                    let hash = match &neighbor {
+                        Some(neighbor) => {
+                            label[front_size + 2 * hash_size..]
+                                .copy_from_slice(&neighbor[i * hash_size..(i + 1) * hash_size]);
+                            get_hash(&label)
+                        },
+                        None => get_hash(&label[..front_size + 2 * hash_size]),
+                    };
+                    labels[i * hash_size..(i + 1) * hash_size].copy_from_slice(&hash);
+                }
+
+                fs::write(layer_path(self, root_dir, set_len, cluster, layer), &labels)?;
+                // every layer depends on the same layer of the previous cluster, or on the previous layer of the last
+                // cluster for the first cluster of the set
+                neighbor = Some(labels);
+            }
+        }
+        Ok(())
+    }
+}
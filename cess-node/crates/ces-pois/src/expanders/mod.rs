@@ -46,7 +46,7 @@ impl Node {
         if self.index == parent {
             return false;
         }
-        if self.parents.is_empty() || self.parents.len() >= self.parents.capacity() {
+        if self.parents.capacity() == 0 || self.parents.len() >= self.parents.capacity() {
             return false;
         }
 
@@ -54,15 +54,8 @@ impl Node {
         if ok {
             return false;
         }
-        self.parents.push(0);
-        let lens = self.parents.len();
-        if lens == 1 || i == lens as i32 - 1 {
-            self.parents[i as usize] = parent;
-            return true;
-        }
-        self.parents
-            .copy_within(i as usize + 1..lens - 1, i as usize);
-        self.parents[i as usize] = parent;
+        // keep the parents sorted, the capacity has been checked so no reallocation happens here
+        self.parents.insert(i as usize, parent);
 
         true
     }
@@ -75,24 +68,10 @@ impl Node {
         if self.no_parents() {
             return (0, false);
         }
-        let lens = self.parents.len();
-        let (mut l, mut r) = (0, lens - 1);
-        while l <= r {
-            let mid = (l + r) / 2;
-            if self.parents[mid] == parent {
-                return (0, true);
-            }
-            if self.parents[mid] > parent {
-                r = mid - 1;
-            } else {
-                l = mid + 1;
-            }
-        }
-        let mut i = (l + r) / 2;
-        if self.parents[i] < parent {
-            i += 1;
+        match self.parents.binary_search(&parent) {
+            Ok(_) => (0, true),
+            Err(i) => (i as i32, false),
         }
-        (i as i32, false)
     }
 }
 
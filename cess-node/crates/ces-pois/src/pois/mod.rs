@@ -1,3 +1,4 @@
 pub mod challenge;
 pub mod prove;
+pub mod store;
 pub mod verify;
\ No newline at end of file
@@ -1,6 +1,27 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Result};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-use crate::{acc::multi_level_acc::WitnessNode, expanders::NodeType};
+use super::verify::IDLE_SET_LEN;
+use crate::{
+    acc::{
+        multi_level_acc::{MultiLevelAcc, WitnessNode},
+        RsaKey,
+    },
+    expanders::{
+        generate_expanders::calc_parents,
+        generate_idle_file::{cluster_dir, elder_layers, get_hash, layer_path, read_labels, HASH_SIZE},
+        get_bytes, Expanders, Node, NodeType,
+    },
+    tree::{LightMht, DEFAULT_HASH_SIZE},
+    util::copy_data,
+};
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Commits {
@@ -45,4 +66,320 @@ pub struct DeletionProof {
     pub roots: Vec<Vec<u8>>,
     pub wit_chain: WitnessNode,
     pub acc_path: Vec<Vec<u8>>,
-}
\ No newline at end of file
+}
+/// The prover side of PoIS. It generates idle file sets on disk, commits them, and answers the commit, space and
+/// deletion challenges of a [`Verifier`](super::verify::Verifier).
+pub struct Prover {
+    pub id: Vec<u8>,
+    pub expanders: Expanders,
+    pub front: i64,
+    pub rear: i64,
+    cluster_size: i64,
+    set_len: i64,
+    root_dir: PathBuf,
+    acc_manager: MultiLevelAcc,
+    // the accumulator updated by the last commit or deletion proof, applied by `update_status`
+    pending_acc: Option<MultiLevelAcc>,
+    generated: i64,
+}
+
+type LayerCache = HashMap<(i64, i64), (Vec<u8>, LightMht)>;
+
+impl Prover {
+    pub fn new(k: i64, n: i64, d: i64, id: &[u8], key: RsaKey, root_dir: impl AsRef<Path>) -> Result<Self> {
+        let root_dir = root_dir.as_ref().to_path_buf();
+        fs::create_dir_all(&root_dir)?;
+        Ok(Prover {
+            id: id.to_vec(),
+            expanders: Expanders::new(k, n, d),
+            front: 0,
+            rear: 0,
+            cluster_size: k,
+            set_len: IDLE_SET_LEN,
+            root_dir,
+            acc_manager: MultiLevelAcc::new(key),
+            pending_acc: None,
+            generated: 0,
+        })
+    }
+
+    pub fn key(&self) -> RsaKey {
+        self.acc_manager.key.clone()
+    }
+
+    pub fn acc(&self) -> Vec<u8> {
+        self.acc_manager.acc()
+    }
+
+    /// Generate the next idle file set after the committed files.
+    pub fn generate_idle_file_set(&mut self) -> Result<()> {
+        if self.generated > self.rear {
+            bail!("generate idle file set error: the last idle file set has not been committed");
+        }
+        let start = self.rear / self.cluster_size;
+        self.expanders
+            .generate_idle_file_set(&self.id, start, self.set_len, &self.root_dir)?;
+        self.generated = self.rear + self.set_len * self.cluster_size;
+        Ok(())
+    }
+
+    /// Get the commits of the generated idle file set: the merkle roots of every layer of every cluster, ordered
+    /// layer by layer, followed by the hash of all of them.
+    pub fn get_idle_file_set_commits(&self) -> Result<Commits> {
+        if self.generated <= self.rear {
+            bail!("get commits error: no idle file set to commit");
+        }
+        let start = self.rear / self.cluster_size;
+        let mut commits = Commits {
+            file_indexs: (self.rear + 1..=self.generated).collect(),
+            roots: Vec::with_capacity(((self.expanders.k + self.cluster_size) * self.set_len + 1) as usize),
+        };
+        for layer in 0..self.expanders.k + self.cluster_size {
+            for cluster in start + 1..=start + self.set_len {
+                let (_, tree) = self.load_layer(cluster, layer)?;
+                commits.roots.push(tree.root());
+            }
+        }
+        let mut hash = Sha256::new();
+        for root in &commits.roots {
+            hash.update(root);
+        }
+        commits.roots.push(hash.finalize().to_vec());
+        Ok(commits)
+    }
+
+    /// Answer the commit challenges of the generated idle file set, and insert the labels of its files into a copy of
+    /// the accumulator. The new accumulator is kept until `update_status` is called.
+    pub fn prove_commit_and_acc(&mut self, chals: &[Vec<i64>]) -> Result<(Vec<Vec<CommitProof>>, AccProof)> {
+        if self.generated <= self.rear || chals.len() != self.set_len as usize {
+            bail!("prove commit error: bad challenges");
+        }
+        let cluster_size = self.cluster_size as usize;
+        let k = self.expanders.k;
+        let n = self.expanders.n;
+
+        let mut proofs = Vec::with_capacity(chals.len());
+        for chal in chals {
+            if chal.len() != (k + self.cluster_size + 1) as usize {
+                bail!("prove commit error: bad challenge length");
+            }
+            let mut cache = LayerCache::new();
+            let mut row: Vec<CommitProof> = Vec::with_capacity(chal.len() - 1);
+            for j in 1..chal.len() {
+                let (index, layer) = if j <= cluster_size {
+                    (chal[j], k + j as i64 - 1)
+                } else if j == cluster_size + 1 {
+                    (chal[j], chal[j] / n)
+                } else {
+                    let parent = row[j - 2]
+                        .parents
+                        .get(chal[j] as usize)
+                        .ok_or_else(|| anyhow!("prove commit error: bad parent challenge"))?;
+                    (parent.index as i64, parent.index as i64 / n)
+                };
+                row.push(self.prove_node(&mut cache, chal[0], layer, index)?);
+            }
+            proofs.push(row);
+        }
+
+        let mut acc_proof = AccProof::default();
+        let mut labels = Vec::with_capacity(chals.len() * cluster_size);
+        for chal in chals {
+            let cluster = chal[0];
+            for j in 0..self.cluster_size {
+                let fidx = (cluster - 1) * self.cluster_size + j + 1;
+                let (_, tree) = self.load_layer(cluster, k + j)?;
+                acc_proof.indexs.push(fidx);
+                labels.push(self.file_label(fidx, &tree.root()));
+            }
+        }
+        acc_proof.labels = labels.clone();
+
+        let mut acc = self.acc_manager.clone();
+        let (exist, accs) = acc.add_elements_and_proof(labels)?;
+        acc_proof.wit_chains = Some(Box::new(exist));
+        acc_proof.acc_path = accs;
+        self.pending_acc = Some(acc);
+        Ok((proofs, acc_proof))
+    }
+
+    /// Answer a space challenge for the committed files in `[left, right)`.
+    pub fn prove_space(&self, chals: &[i64], left: i64, right: i64) -> Result<SpaceProof> {
+        if chals.is_empty() || left <= self.front || left >= right || right > self.rear + 1 {
+            bail!("prove space error: bad file range");
+        }
+        let k = self.expanders.k;
+        let mut proof = SpaceProof {
+            left,
+            right,
+            wit_chains: self
+                .acc_manager
+                .get_witness_chains((left - self.front - 1) as usize, (right - left) as usize)?,
+            ..Default::default()
+        };
+        for fidx in left..right {
+            let cluster = (fidx - 1) / self.cluster_size + 1;
+            let (labels, tree) = self.load_layer(cluster, k + (fidx - 1) % self.cluster_size)?;
+            let mut proofs = Vec::with_capacity(chals.len());
+            for chal in chals {
+                if *chal < k * self.expanders.n || *chal >= (k + 1) * self.expanders.n {
+                    bail!("prove space error: bad challenge");
+                }
+                proofs.push(mht_proof(&labels, &tree, *chal as NodeType, (*chal % self.expanders.n) as usize)?);
+            }
+            proof.proofs.push(proofs);
+            proof.roots.push(tree.root());
+        }
+        Ok(proof)
+    }
+
+    /// Prove the deletion of the first `num` committed files. The new accumulator is kept until `update_status` is
+    /// called.
+    pub fn prove_deletion(&mut self, num: i64) -> Result<DeletionProof> {
+        if num <= 0 || num > self.rear - self.front {
+            bail!("prove deletion error: bad file number");
+        }
+        let mut proof = DeletionProof::default();
+        for fidx in self.front + 1..=self.front + num {
+            let cluster = (fidx - 1) / self.cluster_size + 1;
+            let (_, tree) = self.load_layer(cluster, self.expanders.k + (fidx - 1) % self.cluster_size)?;
+            proof.roots.push(tree.root());
+        }
+
+        let mut acc = self.acc_manager.clone();
+        let (exist, accs) = acc.delete_elements_and_proof(num as usize)?;
+        proof.wit_chain = exist;
+        proof.acc_path = accs;
+        self.pending_acc = Some(acc);
+        Ok(proof)
+    }
+
+    /// Apply the result of the last commit or deletion proof once the verifier has accepted it. Committed clusters
+    /// drop their auxiliary layers, and deleted idle files are removed from disk.
+    pub fn update_status(&mut self, num: i64, is_delete: bool) -> Result<()> {
+        let acc = self
+            .pending_acc
+            .take()
+            .ok_or_else(|| anyhow!("update status error: no pending proof"))?;
+        if is_delete {
+            if acc.elem_nums as i64 != self.acc_manager.elem_nums as i64 - num {
+                bail!("update status error: bad number of deleted files");
+            }
+            for fidx in self.front + 1..=self.front + num {
+                let cluster = (fidx - 1) / self.cluster_size + 1;
+                if fidx % self.cluster_size == 0 {
+                    fs::remove_dir_all(cluster_dir(&self.root_dir, self.set_len, cluster))?;
+                } else {
+                    let layer = self.expanders.k + (fidx - 1) % self.cluster_size;
+                    fs::remove_file(layer_path(&self.expanders, &self.root_dir, self.set_len, cluster, layer))?;
+                }
+            }
+            self.front += num;
+        } else {
+            if acc.elem_nums as i64 != self.acc_manager.elem_nums as i64 + num || self.rear + num != self.generated {
+                bail!("update status error: bad number of committed files");
+            }
+            for cluster in self.rear / self.cluster_size + 1..=self.generated / self.cluster_size {
+                for layer in 0..self.expanders.k {
+                    fs::remove_file(layer_path(&self.expanders, &self.root_dir, self.set_len, cluster, layer))?;
+                }
+            }
+            self.rear += num;
+        }
+        self.acc_manager = acc;
+        Ok(())
+    }
+
+    fn prove_node(&self, cache: &mut LayerCache, cluster: i64, layer: i64, index: i64) -> Result<CommitProof> {
+        let k = self.expanders.k;
+        let n = self.expanders.n;
+        let pos = (index % n) as usize;
+        let logical_layer = layer.min(k);
+
+        let node = self.cached_mht_proof(cache, cluster, layer, index as NodeType, pos)?;
+
+        let mut parents = Vec::new();
+        let mut expanders_node = Node::new(index as NodeType);
+        expanders_node.parents = Vec::with_capacity(self.expanders.d as usize + 1);
+        calc_parents(&self.expanders, &mut expanders_node, &self.id, cluster, layer);
+        for parent in expanders_node.parents {
+            let proof = if parent as i64 >= logical_layer * n {
+                self.cached_mht_proof(cache, cluster, layer, parent, (parent as i64 - logical_layer * n) as usize)?
+            } else {
+                let parent_layer = logical_layer - 1;
+                self.cached_mht_proof(
+                    cache,
+                    cluster,
+                    parent_layer,
+                    parent,
+                    (parent as i64 - parent_layer * n) as usize,
+                )?
+            };
+            parents.push(proof);
+        }
+
+        // the neighbor node comes first, followed by the elder nodes of idle files
+        let mut elders = Vec::new();
+        if (cluster - 1) % self.set_len + layer > 0 {
+            let (neighbor_cluster, neighbor_layer) = if (cluster - 1) % self.set_len > 0 {
+                (cluster - 1, layer)
+            } else {
+                (cluster + self.set_len - 1, layer - 1)
+            };
+            let neighbor_index = (neighbor_layer.min(k) * n) as NodeType + pos as NodeType;
+            elders.push(self.cached_mht_proof(cache, neighbor_cluster, neighbor_layer, neighbor_index, pos)?);
+        }
+        if layer >= k {
+            for elder in elder_layers(&self.expanders, layer) {
+                elders.push(self.cached_mht_proof(
+                    cache,
+                    cluster,
+                    elder,
+                    (elder * n) as NodeType + pos as NodeType,
+                    pos,
+                )?);
+            }
+        }
+
+        Ok(CommitProof { node, parents, elders })
+    }
+
+    fn cached_mht_proof(
+        &self,
+        cache: &mut LayerCache,
+        cluster: i64,
+        layer: i64,
+        index: NodeType,
+        pos: usize,
+    ) -> Result<MhtProof> {
+        let (labels, tree) = match cache.entry((cluster, layer)) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(self.load_layer(cluster, layer)?),
+        };
+        mht_proof(labels, tree, index, pos)
+    }
+
+    fn load_layer(&self, cluster: i64, layer: i64) -> Result<(Vec<u8>, LightMht)> {
+        let path = layer_path(&self.expanders, &self.root_dir, self.set_len, cluster, layer);
+        let labels = read_labels(&path, &self.expanders)?;
+        let tree = LightMht::new(&labels, HASH_SIZE as usize)?;
+        Ok((labels, tree))
+    }
+
+    fn file_label(&self, fidx: i64, root: &[u8]) -> Vec<u8> {
+        let mut label = vec![0; self.id.len() + 8 + DEFAULT_HASH_SIZE as usize];
+        copy_data(&mut label, &[&self.id, &get_bytes(fidx), root]);
+        get_hash(&label)
+    }
+}
+
+fn mht_proof(labels: &[u8], tree: &LightMht, index: NodeType, pos: usize) -> Result<MhtProof> {
+    let hash_size = HASH_SIZE as usize;
+    let path_proof = tree.path_proof(pos)?;
+    Ok(MhtProof {
+        index,
+        label: labels[pos * hash_size..(pos + 1) * hash_size].to_vec(),
+        paths: path_proof.path,
+        locs: path_proof.locs,
+    })
+}
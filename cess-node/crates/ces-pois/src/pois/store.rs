@@ -0,0 +1,97 @@
+use anyhow::Result;
+use dashmap::DashMap;
+use std::fmt::Debug;
+use std::time::{Duration, SystemTime};
+
+use super::verify::ProverNode;
+
+/// Read-modify-write closure applied to a stored node by [`ProverNodeStore::update`].
+pub type NodeUpdate<'a> = Box<dyn FnOnce(&mut ProverNode) -> Result<()> + 'a>;
+
+/// Storage backend for the prover nodes tracked by a [`Verifier`](super::verify::Verifier).
+///
+/// Nodes are keyed by the hex encoded prover id. Implementations must be safe to share
+/// between the concurrent requests served by a verifier.
+pub trait ProverNodeStore: Debug + Send + Sync {
+    fn get(&self, id: &str) -> Option<ProverNode>;
+
+    /// Inserts or replaces the node, refreshing its last update time.
+    fn put(&self, id: &str, node: ProverNode);
+
+    fn remove(&self, id: &str) -> Option<ProverNode>;
+
+    /// Applies `f` to the node stored under `id` as one atomic read-modify-write, refreshing its last
+    /// update time.
+    ///
+    /// The node is left untouched if `f` fails. Returns `None` if there is no node under `id`.
+    fn update(&self, id: &str, f: NodeUpdate<'_>) -> Option<Result<()>>;
+
+    fn contains(&self, id: &str) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Evicts every node that has not been updated for longer than `ttl` and returns their ids.
+    fn expire(&self, ttl: Duration) -> Vec<String>;
+}
+
+/// A [`ProverNodeStore`] that keeps everything in memory and is lost on restart.
+#[derive(Debug, Default)]
+pub struct MemoryNodeStore {
+    nodes: DashMap<String, (ProverNode, SystemTime)>,
+}
+
+impl MemoryNodeStore {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+impl ProverNodeStore for MemoryNodeStore {
+    fn get(&self, id: &str) -> Option<ProverNode> {
+        self.nodes.get(id).map(|entry| entry.0.clone())
+    }
+
+    fn put(&self, id: &str, node: ProverNode) {
+        self.nodes.insert(id.to_string(), (node, SystemTime::now()));
+    }
+
+    fn remove(&self, id: &str) -> Option<ProverNode> {
+        self.nodes.remove(id).map(|(_, (node, _))| node)
+    }
+
+    fn update(&self, id: &str, f: NodeUpdate<'_>) -> Option<Result<()>> {
+        let mut entry = self.nodes.get_mut(id)?;
+        let mut node = entry.0.clone();
+        if let Err(e) = f(&mut node) {
+            return Some(Err(e));
+        }
+        *entry = (node, SystemTime::now());
+        Some(Ok(()))
+    }
+
+    fn contains(&self, id: &str) -> bool {
+        self.nodes.contains_key(id)
+    }
+
+    fn expire(&self, ttl: Duration) -> Vec<String> {
+        let now = SystemTime::now();
+        let expired: Vec<String> = self
+            .nodes
+            .iter()
+            .filter(|entry| now.duration_since(entry.1).is_ok_and(|elapsed| elapsed > ttl))
+            .map(|entry| entry.key().clone())
+            .collect();
+        for id in expired.iter() {
+            self.nodes.remove(id);
+        }
+        expired
+    }
+}
@@ -1,28 +1,33 @@
 use anyhow::{anyhow, bail, Context, Result};
-use dashmap::DashMap;
 use rand::Rng;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::mem;
+use std::sync::{mpsc::channel, Arc};
+use threadpool::ThreadPool;
 
 use super::prove::{AccProof, CommitProof, Commits, DeletionProof, SpaceProof};
+use super::store::{MemoryNodeStore, ProverNodeStore};
 use crate::acc::multi_level_acc::{
-    verify_delete_update, verify_insert_update, verify_mutilevel_acc_for_batch,
+    verify_delete_update, verify_insert_update, verify_mutilevel_acc_for_batch_cached, WitnessCache,
 };
 use crate::acc::RsaKey;
 use crate::expanders::generate_idle_file::{get_hash, HASH_SIZE};
 use crate::expanders::{
-    generate_expanders::calc_parents as generate_expanders_calc_parents,
+    generate_expanders::ParentCache,
     generate_idle_file::Hasher as ExpanderHasher, Expanders,
 };
 use crate::expanders::{get_bytes, NodeType};
 use crate::tree::{check_index_path, verify_path_proof, PathProof, DEFAULT_HASH_SIZE};
 use crate::util::{add_data, copy_data};
-use crate::{acc, expanders};
+use crate::acc;
 
 pub const CLUSTER_SIZE: i64 = 8;
 pub const IDLE_SET_LEN: i64 = 32;
 pub const PICK: i32 = 4;
+/// Nodes whose expander parents are kept by a verifier, enough for the challenged nodes of a few
+/// hundred commits.
+pub const PARENT_CACHE_CAPACITY: usize = 1 << 16;
 
 #[derive(Clone, Default, Debug)]
 pub struct Record {
@@ -43,23 +48,31 @@ pub struct Verifier {
     cluster_size: i64,
     space_chals: i64,
     pub expanders: Expanders,
-    pub nodes: DashMap<String, ProverNode>,
+    /// Shared between clones of the verifier.
+    pub nodes: Arc<dyn ProverNodeStore>,
+    /// Shared between clones of the verifier.
+    pub parent_cache: Arc<ParentCache>,
 }
 
 impl Verifier {
     pub fn new(k: i64, n: i64, d: i64) -> Self {
+        Self::with_store(k, n, d, Arc::new(MemoryNodeStore::new()))
+    }
+
+    pub fn with_store(k: i64, n: i64, d: i64, nodes: Arc<dyn ProverNodeStore>) -> Self {
         Verifier {
             cluster_size: k,
             space_chals: k,
             expanders: Expanders::new(k, n, d),
-            nodes: DashMap::new(),
+            nodes,
+            parent_cache: Arc::new(ParentCache::new(PARENT_CACHE_CAPACITY)),
         }
     }
 
     pub fn register_prover_node(&self, id: &[u8], key: RsaKey, acc: &[u8], front: i64, rear: i64) {
         let node = ProverNode::new(id, key, acc, front, rear);
         let id = hex::encode(id);
-        self.nodes.insert(id, node);
+        self.nodes.put(&id, node);
     }
 
     pub fn register_prover_node_empty(&self, id: &[u8]) {
@@ -68,7 +81,7 @@ impl Verifier {
             ..Default::default()
         };
         let id = hex::encode(id);
-        self.nodes.insert(id, node);
+        self.nodes.put(&id, node);
     }
     pub fn update_prover_node_force(
         &self,
@@ -78,43 +91,36 @@ impl Verifier {
         front: i64,
         rear: i64,
     ) {
-        //todo :update node info
         let mut node = ProverNode::new(id, key, acc, front, rear);
         let id = hex::encode(id);
-        node.commit_buf = self.nodes.get(&id).unwrap().commit_buf.clone();
-        self.nodes.insert(id, node);
+        if let Some(old) = self.nodes.get(&id) {
+            node.commit_buf = old.commit_buf;
+        }
+        self.nodes.put(&id, node);
     }
 
     pub fn get_node(&self, id: &[u8]) -> Result<ProverNode> {
         let id = hex::encode(id);
-        let node = self
-            .nodes
+        self.nodes
             .get(&id)
-            .with_context(|| "prover node not found.")?;
-        let result = node.clone();
-        Ok(result)
+            .with_context(|| "prover node not found.")
     }
 
     pub fn is_logout(&self, id: &[u8]) -> bool {
         let id = hex::encode(id);
-        !self.nodes.contains_key(&id)
+        !self.nodes.contains(&id)
     }
 
+    /// Evicts the prover node from the store, returning its final acc, front and rear.
     pub fn logout_prover_node(&self, id: &[u8]) -> Result<(Vec<u8>, i64, i64)> {
         let id_str = hex::encode(id);
-        let node = self.nodes.get_mut(&id_str);
 
-        match node {
-            Some(mut node) => {
+        match self.nodes.get(&id_str) {
+            Some(node) => {
                 let (mut acc, front, rear) = match &node.record {
                     Some(record) => (record.acc.clone(), record.front, record.rear),
                     None => return Err(anyhow!("Record not found")),
                 };
-                node.commit_buf = Default::default();
-                if node.record.is_some() {
-                    node.record.as_mut().unwrap().key = Default::default();
-                    node.record.as_mut().unwrap().acc = Default::default();
-                }
 
                 if acc.len() < 256 {
                     let zeros_to_prepend = vec![0; 256 - acc.len()];
@@ -126,6 +132,7 @@ impl Verifier {
 
                     acc = new_acc;
                 }
+                self.nodes.remove(&id_str);
                 Ok((acc, front, rear))
             }
             None => Ok((vec![], 0, 0)),
@@ -135,15 +142,16 @@ impl Verifier {
     pub fn receive_commits(&self, id: &[u8], commits: &Commits) -> bool {
         let id = hex::encode(id);
 
-        match self.nodes.get_mut(&id) {
-            Some(mut p_node) => {
-                if !p_node.id.eq(&hex::decode(id).unwrap()) {
-                    return false;
+        let result = self.nodes.update(
+            &id,
+            Box::new(|p_node| {
+                if !p_node.id.eq(&hex::decode(&id).unwrap()) {
+                    bail!("prover id mismatch");
                 }
 
                 let root_num = (self.cluster_size + self.expanders.k) * IDLE_SET_LEN + 1;
                 if commits.roots.len() != root_num as usize {
-                    return false;
+                    bail!("bad number of roots");
                 }
 
                 let hash = ExpanderHasher::SHA256(Sha256::new());
@@ -169,14 +177,14 @@ impl Verifier {
                     }
                 };
                 if !commits.roots[commits.roots.len() - 1].eq(&result) {
-                    return false;
+                    bail!("bad roots hash");
                 }
 
                 p_node.commit_buf = commits.clone();
-                true
-            }
-            None => false,
-        }
+                Ok(())
+            }),
+        );
+        matches!(result, Some(Ok(())))
     }
 
     pub fn commit_challenges(&self, id: &[u8]) -> Result<Vec<Vec<i64>>> {
@@ -246,7 +254,7 @@ impl Verifier {
         proofs: Vec<Vec<CommitProof>>,
     ) -> Result<()> {
         let id_str = hex::encode(id);
-        let p_node = if let Some(value) = self.nodes.get_mut(&id_str) {
+        let p_node = if let Some(value) = self.nodes.get(&id_str) {
             value
         } else {
             bail!("verify commit proofs error : prover node not found.");
@@ -266,6 +274,77 @@ impl Verifier {
             bail!("verify commit proofs error {}", err);
         }
 
+        for (chal, proofs) in chals.iter().zip(proofs.iter()) {
+            self.verify_cluster_commit_proofs(id, &p_node, chal, proofs)?;
+        }
+        Ok(())
+    }
+
+    /// Verifies the commit proofs of every challenged cluster on `pool`, one result per cluster in
+    /// the order of `chals`.
+    ///
+    /// Unlike [`Verifier::verify_commit_proofs`], which samples `PICK` nodes, the parents of every
+    /// proven node are checked, the expander parents being shared through the parent cache.
+    pub fn verify_commit_proofs_batch(
+        &self,
+        id: &[u8],
+        chals: Vec<Vec<i64>>,
+        proofs: Vec<Vec<CommitProof>>,
+        pool: ThreadPool,
+    ) -> Result<Vec<Result<()>>> {
+        let id_str = hex::encode(id);
+        let p_node = self
+            .nodes
+            .get(&id_str)
+            .with_context(|| "verify commit proofs error : prover node not found.")?;
+
+        if chals.len() != proofs.len()
+            || chals.len() != IDLE_SET_LEN as usize
+            || p_node.commit_buf.file_indexs.len() != (CLUSTER_SIZE * IDLE_SET_LEN) as usize
+            || p_node.commit_buf.roots.len()
+                != ((self.expanders.k + CLUSTER_SIZE) * IDLE_SET_LEN + 1) as usize
+        {
+            let err = anyhow!("bad proof data");
+            bail!("verify commit proofs error: {}", err);
+        }
+
+        let p_node = Arc::new(p_node);
+        let id = Arc::new(id.to_vec());
+        let num = chals.len();
+
+        let (tx, rx) = channel();
+        for (i, (chal, proofs)) in chals.into_iter().zip(proofs).enumerate() {
+            let (tx, verifier, p_node, id) = (tx.clone(), self.clone(), p_node.clone(), id.clone());
+            pool.execute(move || {
+                let result = proofs
+                    .iter()
+                    .enumerate()
+                    .try_for_each(|(j, proof)| verifier.verify_node_parents(&id, chal[0], j, proof))
+                    .and_then(|_| verifier.verify_cluster_commit_proofs(&id, &p_node, &chal, &proofs));
+                tx.send((i, result)).ok();
+            });
+        }
+        drop(tx);
+
+        // a malformed proof may panic its worker before it reports back
+        let mut results: Vec<Option<Result<()>>> = (0..num).map(|_| None).collect();
+        for (i, result) in rx.iter() {
+            results[i] = Some(result);
+        }
+        Ok(results
+            .into_iter()
+            .map(|result| result.unwrap_or_else(|| Err(anyhow!("verify commit proofs error: verification aborted"))))
+            .collect())
+    }
+
+    /// Verifies the commit proofs of the cluster challenged by `chal`.
+    fn verify_cluster_commit_proofs(
+        &self,
+        id: &[u8],
+        p_node: &ProverNode,
+        chal: &[i64],
+        proofs: &[CommitProof],
+    ) -> Result<()> {
         let front_size = (mem::size_of::<NodeType>() + id.len() + 8 + 8) as i32;
         let hash_size = HASH_SIZE;
         let mut label = vec![0; front_size as usize + 2 * hash_size as usize];
@@ -276,143 +355,141 @@ impl Verifier {
         let mut hash: Vec<u8>;
         let mut idx: NodeType;
         let mut fidx: i64;
-        for i in 0..proofs.len() {
-            for j in 1..cluster_size as usize + 1 {
-                if chals[i][j] != proofs[i][j - 1].node.index as i64 {
-                    let err = anyhow!("bad expanders node index");
-                    bail!("verify commit proofs error {}", err);
-                }
+        for j in 1..cluster_size as usize + 1 {
+            if chal[j] != proofs[j - 1].node.index as i64 {
+                let err = anyhow!("bad expanders node index");
+                bail!("verify commit proofs error {}", err);
             }
+        }
 
-            for j in 1..chals[i].len() {
-                fidx = 0;
-                if j <= cluster_size as usize + 1 {
-                    idx = chals[i][j] as NodeType;
-                } else {
-                    idx = proofs[i][j - 2].parents[chals[i][j] as usize].index as NodeType;
-                }
-
-                let layer: i64 = if j <= cluster_size as usize {
-                    self.expanders.k + j as i64 - 1
-                } else {
-                    idx as i64 / self.expanders.n
-                };
-                let mut root = &p_node.commit_buf.roots[layer as usize * IDLE_SET_LEN as usize
-                    + (chals[i][0] as usize - 1) % IDLE_SET_LEN as usize];
-                let mut path_proof = PathProof {
-                    locs: proofs[i][j - 1].node.locs.clone(),
-                    path: proofs[i][j - 1].node.paths.clone(),
-                };
-                if !verify_path_proof(root, &proofs[i][j - 1].node.label, path_proof) {
-                    let err = anyhow!("verify path proof error");
-                    bail!("verify commit proofs error: {}", err);
-                }
+        for j in 1..chal.len() {
+            fidx = 0;
+            if j <= cluster_size as usize + 1 {
+                idx = chal[j] as NodeType;
+            } else {
+                idx = proofs[j - 2].parents[chal[j] as usize].index as NodeType;
+            }
 
-                if layer >= self.expanders.k {
-                    fidx = (chals[i][0] - 1) * cluster_size + j as i64;
-                }
+            let layer: i64 = if j <= cluster_size as usize {
+                self.expanders.k + j as i64 - 1
+            } else {
+                idx as i64 / self.expanders.n
+            };
+            let mut root = &p_node.commit_buf.roots[layer as usize * IDLE_SET_LEN as usize
+                + (chal[0] as usize - 1) % IDLE_SET_LEN as usize];
+            let mut path_proof = PathProof {
+                locs: proofs[j - 1].node.locs.clone(),
+                path: proofs[j - 1].node.paths.clone(),
+            };
+            if !verify_path_proof(root, &proofs[j - 1].node.label, path_proof) {
+                let err = anyhow!("verify path proof error");
+                bail!("verify commit proofs error: {}", err);
+            }
 
-                copy_data(
-                    &mut label,
-                    &[
-                        id,
-                        &get_bytes(chals[i][0]),
-                        &get_bytes(fidx),
-                        &get_bytes(idx),
-                        &zero,
-                    ],
-                );
-
-                if layer > 0 {
-                    let mut logical_layer = layer;
-                    if logical_layer > self.expanders.k {
-                        logical_layer = self.expanders.k;
-                    }
+            if layer >= self.expanders.k {
+                fidx = (chal[0] - 1) * cluster_size + j as i64;
+            }
 
-                    for p in &proofs[i][j - 1].parents {
-                        if p.index as i64 >= logical_layer * self.expanders.n {
-                            root = &p_node.commit_buf.roots[layer as usize * IDLE_SET_LEN as usize
-                                + (chals[i][0] - 1) as usize % IDLE_SET_LEN as usize]
-                        } else {
-                            root = &p_node.commit_buf.roots[(logical_layer as usize - 1)
-                                * IDLE_SET_LEN as usize
-                                + (chals[i][0] - 1) as usize % IDLE_SET_LEN as usize];
-                        }
-                        if p.index % 6 == 0 {
-                            let path_proof = PathProof {
-                                locs: p.locs.clone(),
-                                path: p.paths.clone(),
-                            };
-                            if !verify_path_proof(root, &p.label, path_proof) {
-                                let err = anyhow!("verify parent path proof error");
-                                bail!("verify commit proofs error: {}", err);
-                            }
-                        }
-                        add_data(
-                            &mut label[front_size as usize..(front_size + hash_size) as usize],
-                            &[&p.label],
-                        );
-                    }
+            copy_data(
+                &mut label,
+                &[
+                    id,
+                    &get_bytes(chal[0]),
+                    &get_bytes(fidx),
+                    &get_bytes(idx),
+                    &zero,
+                ],
+            );
 
-                    let mut l = 1;
-                    while layer >= self.expanders.k && l < proofs[i][j - 1].elders.len() {
-                        path_proof = PathProof {
-                            locs: proofs[i][j - 1].elders[l].locs.clone(),
-                            path: proofs[i][j - 1].elders[l].paths.clone(),
-                        };
+            if layer > 0 {
+                let mut logical_layer = layer;
+                if logical_layer > self.expanders.k {
+                    logical_layer = self.expanders.k;
+                }
 
-                        let ridx = ((layer - self.expanders.k / 2) as usize
-                            / self.expanders.k as usize
-                            + 2 * (l - 1))
+                for p in &proofs[j - 1].parents {
+                    if p.index as i64 >= logical_layer * self.expanders.n {
+                        root = &p_node.commit_buf.roots[layer as usize * IDLE_SET_LEN as usize
+                            + (chal[0] - 1) as usize % IDLE_SET_LEN as usize]
+                    } else {
+                        root = &p_node.commit_buf.roots[(logical_layer as usize - 1)
                             * IDLE_SET_LEN as usize
-                            + (chals[i][0] as usize - 1) % IDLE_SET_LEN as usize;
-                        if !verify_path_proof(
-                            &p_node.commit_buf.roots[ridx],
-                            &proofs[i][j - 1].elders[l].label,
-                            path_proof,
-                        ) {
-                            let err = anyhow!("verify elder node path proof error");
+                            + (chal[0] - 1) as usize % IDLE_SET_LEN as usize];
+                    }
+                    if p.index % 6 == 0 {
+                        let path_proof = PathProof {
+                            locs: p.locs.clone(),
+                            path: p.paths.clone(),
+                        };
+                        if !verify_path_proof(root, &p.label, path_proof) {
+                            let err = anyhow!("verify parent path proof error");
                             bail!("verify commit proofs error: {}", err);
                         }
-                        add_data(
-                            &mut label[(front_size + hash_size) as usize
-                                ..(front_size + 2 * hash_size) as usize],
-                            &[&proofs[i][j - 1].elders[l].label.as_slice()],
-                        );
-                        l += 1;
                     }
+                    add_data(
+                        &mut label[front_size as usize..(front_size + hash_size) as usize],
+                        &[&p.label],
+                    );
                 }
 
-                if (chals[i][0] - 1) % IDLE_SET_LEN + layer > 0 {
+                let mut l = 1;
+                while layer >= self.expanders.k && l < proofs[j - 1].elders.len() {
                     path_proof = PathProof {
-                        locs: proofs[i][j - 1].elders[0].locs.clone(),
-                        path: proofs[i][j - 1].elders[0].paths.clone(),
+                        locs: proofs[j - 1].elders[l].locs.clone(),
+                        path: proofs[j - 1].elders[l].paths.clone(),
                     };
-                    let ridx = layer * IDLE_SET_LEN + (chals[i][0] - 1) % IDLE_SET_LEN - 1;
+
+                    let ridx = ((layer - self.expanders.k / 2) as usize
+                        / self.expanders.k as usize
+                        + 2 * (l - 1))
+                        * IDLE_SET_LEN as usize
+                        + (chal[0] as usize - 1) % IDLE_SET_LEN as usize;
                     if !verify_path_proof(
-                        &p_node.commit_buf.roots[ridx as usize],
-                        &proofs[i][j - 1].elders[0].label,
+                        &p_node.commit_buf.roots[ridx],
+                        &proofs[j - 1].elders[l].label,
                         path_proof,
                     ) {
-                        let err = anyhow!("verify neighbor node path proof error");
+                        let err = anyhow!("verify elder node path proof error");
                         bail!("verify commit proofs error: {}", err);
                     }
-                    let mut concatenated_label: Vec<u8> = Vec::new();
-                    concatenated_label.extend_from_slice(&label);
-                    if let Some(elder_label) =
-                        proofs[i][j - 1].elders.first().map(|elder| &elder.label)
-                    {
-                        concatenated_label.extend_from_slice(elder_label);
-                    }
-                    hash = get_hash(&concatenated_label);
-                } else {
-                    hash = get_hash(&label);
+                    add_data(
+                        &mut label[(front_size + hash_size) as usize
+                            ..(front_size + 2 * hash_size) as usize],
+                        &[&proofs[j - 1].elders[l].label.as_slice()],
+                    );
+                    l += 1;
                 }
+            }
 
-                if !hash.eq(&proofs[i][j - 1].node.label) {
-                    let err = anyhow!("verify label error");
+            if (chal[0] - 1) % IDLE_SET_LEN + layer > 0 {
+                path_proof = PathProof {
+                    locs: proofs[j - 1].elders[0].locs.clone(),
+                    path: proofs[j - 1].elders[0].paths.clone(),
+                };
+                let ridx = layer * IDLE_SET_LEN + (chal[0] - 1) % IDLE_SET_LEN - 1;
+                if !verify_path_proof(
+                    &p_node.commit_buf.roots[ridx as usize],
+                    &proofs[j - 1].elders[0].label,
+                    path_proof,
+                ) {
+                    let err = anyhow!("verify neighbor node path proof error");
                     bail!("verify commit proofs error: {}", err);
                 }
+                let mut concatenated_label: Vec<u8> = Vec::new();
+                concatenated_label.extend_from_slice(&label);
+                if let Some(elder_label) =
+                    proofs[j - 1].elders.first().map(|elder| &elder.label)
+                {
+                    concatenated_label.extend_from_slice(elder_label);
+                }
+                hash = get_hash(&concatenated_label);
+            } else {
+                hash = get_hash(&label);
+            }
+
+            if !hash.eq(&proofs[j - 1].node.label) {
+                let err = anyhow!("verify label error");
+                bail!("verify commit proofs error: {}", err);
             }
         }
         Ok(())
@@ -440,35 +517,27 @@ impl Verifier {
             let r1 = rng.gen_range(0..proofs.len());
             let r2 = rng.gen_range(0..proofs[r1].len());
 
-            let index = r2;
-            let proof = &proofs[r1][index];
-            let mut node = expanders::Node::new(proof.node.index);
-            let layer = proof.node.index as i64 / self.expanders.n;
-            let cluster_size = self.cluster_size;
-            if index < cluster_size as usize {
-                generate_expanders_calc_parents(
-                    &self.expanders,
-                    &mut node,
-                    id,
-                    chals[r1][0],
-                    self.expanders.k + index as i64,
-                );
-            } else {
-                generate_expanders_calc_parents(
-                    &self.expanders,
-                    &mut node,
-                    id,
-                    chals[r1][0],
-                    layer,
-                );
-            }
+            self.verify_node_parents(id, chals[r1][0], r2, &proofs[r1][r2])?;
+        }
+        Ok(())
+    }
 
-            for j in 0..node.parents.len() {
-                if node.parents[j] != proof.parents[j].index {
-                    let err = anyhow!("node relationship mismatch");
-                    bail!("verify node dependencies error: {}", err);
-                }
-            }
+    /// Checks the parents of the `index`-th proven node of cluster `count` against the expander graph.
+    fn verify_node_parents(&self, id: &[u8], count: i64, index: usize, proof: &CommitProof) -> Result<()> {
+        let rlayer = if index < self.cluster_size as usize {
+            self.expanders.k + index as i64
+        } else {
+            proof.node.index as i64 / self.expanders.n
+        };
+        let parents = self
+            .parent_cache
+            .parents(&self.expanders, proof.node.index, id, count, rlayer);
+
+        if parents.len() != proof.parents.len()
+            || parents.iter().zip(proof.parents.iter()).any(|(parent, p)| *parent != p.index)
+        {
+            let err = anyhow!("node relationship mismatch");
+            bail!("verify node dependencies error: {}", err);
         }
         Ok(())
     }
@@ -476,8 +545,9 @@ impl Verifier {
     pub fn verify_acc(&self, id: &[u8], chals: Vec<Vec<i64>>, proof: AccProof) -> Result<()> {
         let id_str = hex::encode(id);
         let cluster_size = self.cluster_size;
-        match self.nodes.get_mut(&id_str) {
-            Some(mut p_node) => {
+        let result = self.nodes.update(
+            &id_str,
+            Box::new(|p_node| {
                 if chals.len() != proof.indexs.len() / cluster_size as usize
                     || chals.len() != IDLE_SET_LEN as usize
                 {
@@ -535,13 +605,16 @@ impl Verifier {
                 };
 
                 p_node.record.as_mut().unwrap().rear += chals.len() as i64 * cluster_size;
-            }
+                Ok(())
+            }),
+        );
+        match result {
+            Some(result) => result,
             None => {
                 let err = anyhow!("prover node not found");
                 bail!("verify acc proofs error: {}", err);
             }
         }
-        Ok(())
     }
 
     pub fn verify_space(
@@ -550,100 +623,149 @@ impl Verifier {
         chals: Vec<i64>,
         proof: &mut SpaceProof,
     ) -> Result<()> {
-        if chals.is_empty()
-            || proof.left <= p_node.record.as_ref().unwrap().front
-            || p_node.record.as_ref().unwrap().rear + 1 < proof.right
-        {
-            let err = anyhow!("bad proof data");
-            bail!("verify space proofs error: {}", err);
-        }
-        let mut label: Vec<u8> = vec![0; p_node.id.len() + 8 + DEFAULT_HASH_SIZE as usize];
-        for i in 0..proof.roots.len() {
-            for (j, v) in chals.iter().enumerate() {
-                if *v != proof.proofs[i][j].index as i64 {
-                    let err = anyhow!("bad file index");
-                    bail!("verify space proofs error: {}", err);
-                }
-                let path_proof = PathProof {
-                    locs: proof.proofs[i][j].locs.clone(),
-                    path: proof.proofs[i][j].paths.clone(),
-                };
-
-                if !check_index_path(*v, &path_proof.locs) {
-                    let err = anyhow!("verify index path error");
-                    bail!("verify space proofs error: {}", err);
-                }
-                if !verify_path_proof(&proof.roots[i], &proof.proofs[i][j].label, path_proof) {
-                    let err = anyhow!("verify path proof error");
-                    bail!("verify space proofs error: {}", err);
-                }
-            }
-            copy_data(
-                &mut label,
-                &[
-                    &p_node.id,
-                    &get_bytes(proof.left + i as i64),
-                    &proof.roots[i],
-                ],
-            );
+        verify_space_proof(p_node, &chals, proof, &WitnessCache::new())
+    }
 
-            if !get_hash(&label).eq(&proof.wit_chains[i].elem) {
-                let err = anyhow!("verify file label error");
-                bail!("verify space proofs error: {}", err);
-            }
+    /// Verifies many space proofs of one prover node against the same challenges on `pool`.
+    ///
+    /// The accumulator witnesses proven by one proof are shared with the others, so proofs of neighbouring
+    /// blocks only check the upper accumulator levels once. Results are returned in the order of `proofs`.
+    pub fn verify_space_batch(
+        &self,
+        p_node: &ProverNode,
+        chals: &[i64],
+        proofs: Vec<SpaceProof>,
+        pool: ThreadPool,
+    ) -> Vec<Result<()>> {
+        let p_node = Arc::new(p_node.clone());
+        let chals = Arc::new(chals.to_vec());
+        let cache = Arc::new(WitnessCache::new());
+        let num = proofs.len();
+
+        let (tx, rx) = channel();
+        for (i, mut proof) in proofs.into_iter().enumerate() {
+            let (tx, p_node, chals, cache) = (tx.clone(), p_node.clone(), chals.clone(), cache.clone());
+            pool.execute(move || {
+                let result = verify_space_proof(&p_node, &chals, &mut proof, &cache);
+                tx.send((i, result)).ok();
+            });
         }
-        //VerifyMutilevelAcc
-        if !verify_mutilevel_acc_for_batch(
-            &p_node.record.as_ref().unwrap().key,
-            proof.left,
-            proof.wit_chains.clone(),
-            &p_node.record.as_ref().unwrap().acc,
-        ) {
-            let err = anyhow!("verify acc proof error");
-            bail!("verify space proofs error: {}", err);
+        drop(tx);
+
+        // a malformed proof may panic its worker before it reports back
+        let mut results: Vec<Option<Result<()>>> = (0..num).map(|_| None).collect();
+        for (i, result) in rx.iter() {
+            results[i] = Some(result);
         }
-        Ok(())
+        results
+            .into_iter()
+            .map(|result| result.unwrap_or_else(|| Err(anyhow!("verify space proofs error: verification aborted"))))
+            .collect()
     }
 
     pub fn verify_deletion(&self, id: &[u8], proof: &mut DeletionProof) -> Result<()> {
         let id_str = hex::encode(id);
-        let mut p_node = self
-            .nodes
-            .get_mut(&id_str)
-            .with_context(|| "verify deletion proofs error: prover node not found")?;
-
-        let lens = proof.roots.len();
-        let record = p_node.record.as_ref().unwrap();
-        if lens > (record.rear - record.front) as usize {
-            let err = anyhow!("file number out of range");
-            bail!("verify deletion proofs error: {}", err);
-        }
-        let mut labels: Vec<Vec<u8>> = Vec::new();
-        for i in 0..lens {
-            let mut label: Vec<u8> = vec![0; id.len() + 8 + DEFAULT_HASH_SIZE as usize];
-            copy_data(
-                &mut label,
-                &[id, &get_bytes(record.front + i as i64 + 1), &proof.roots[i]],
-            );
+        self.nodes
+            .update(
+                &id_str,
+                Box::new(|p_node| {
+                    let lens = proof.roots.len();
+                    let record = p_node.record.as_ref().unwrap();
+                    if lens > (record.rear - record.front) as usize {
+                        let err = anyhow!("file number out of range");
+                        bail!("verify deletion proofs error: {}", err);
+                    }
+                    let mut labels: Vec<Vec<u8>> = Vec::new();
+                    for i in 0..lens {
+                        let mut label: Vec<u8> = vec![0; id.len() + 8 + DEFAULT_HASH_SIZE as usize];
+                        copy_data(
+                            &mut label,
+                            &[id, &get_bytes(record.front + i as i64 + 1), &proof.roots[i]],
+                        );
 
-            labels.push(get_hash(&label));
-        }
+                        labels.push(get_hash(&label));
+                    }
 
-        if verify_delete_update(
-            record.key.clone(),
-            &mut proof.wit_chain,
-            labels,
-            proof.acc_path.clone(),
-            &record.acc,
-        ) {
-            let err = anyhow!("verify acc proof error");
-            bail!("verify deletion proofs error: {}", err);
-        }
+                    if !verify_delete_update(
+                        record.key.clone(),
+                        &mut proof.wit_chain,
+                        labels,
+                        proof.acc_path.clone(),
+                        &record.acc,
+                    ) {
+                        let err = anyhow!("verify acc proof error");
+                        bail!("verify deletion proofs error: {}", err);
+                    }
 
-        p_node.record.as_mut().unwrap().front += lens as i64;
-        p_node.record.as_mut().unwrap().acc = proof.acc_path[proof.acc_path.len() - 1].clone();
-        Ok(())
+                    p_node.record.as_mut().unwrap().front += lens as i64;
+                    p_node.record.as_mut().unwrap().acc = proof.acc_path[proof.acc_path.len() - 1].clone();
+                    Ok(())
+                }),
+            )
+            .with_context(|| "verify deletion proofs error: prover node not found")?
+    }
+}
+
+fn verify_space_proof(
+    p_node: &ProverNode,
+    chals: &[i64],
+    proof: &mut SpaceProof,
+    cache: &WitnessCache,
+) -> Result<()> {
+    if chals.is_empty()
+        || proof.left <= p_node.record.as_ref().unwrap().front
+        || p_node.record.as_ref().unwrap().rear + 1 < proof.right
+    {
+        let err = anyhow!("bad proof data");
+        bail!("verify space proofs error: {}", err);
+    }
+    let mut label: Vec<u8> = vec![0; p_node.id.len() + 8 + DEFAULT_HASH_SIZE as usize];
+    for i in 0..proof.roots.len() {
+        for (j, v) in chals.iter().enumerate() {
+            if *v != proof.proofs[i][j].index as i64 {
+                let err = anyhow!("bad file index");
+                bail!("verify space proofs error: {}", err);
+            }
+            let path_proof = PathProof {
+                locs: proof.proofs[i][j].locs.clone(),
+                path: proof.proofs[i][j].paths.clone(),
+            };
+
+            if !check_index_path(*v, &path_proof.locs) {
+                let err = anyhow!("verify index path error");
+                bail!("verify space proofs error: {}", err);
+            }
+            if !verify_path_proof(&proof.roots[i], &proof.proofs[i][j].label, path_proof) {
+                let err = anyhow!("verify path proof error");
+                bail!("verify space proofs error: {}", err);
+            }
+        }
+        copy_data(
+            &mut label,
+            &[
+                &p_node.id,
+                &get_bytes(proof.left + i as i64),
+                &proof.roots[i],
+            ],
+        );
+
+        if !get_hash(&label).eq(&proof.wit_chains[i].elem) {
+            let err = anyhow!("verify file label error");
+            bail!("verify space proofs error: {}", err);
+        }
+    }
+    //VerifyMutilevelAcc
+    if !verify_mutilevel_acc_for_batch_cached(
+        &p_node.record.as_ref().unwrap().key,
+        proof.left,
+        &proof.wit_chains,
+        &p_node.record.as_ref().unwrap().acc,
+        cache,
+    ) {
+        let err = anyhow!("verify acc proof error");
+        bail!("verify space proofs error: {}", err);
     }
+    Ok(())
 }
 
 impl ProverNode {
@@ -1,3 +1,4 @@
+use anyhow::{bail, Result};
 use sha2::{Digest, Sha256, Sha512};
 
 #[derive(Debug)]
@@ -8,6 +9,73 @@ pub struct PathProof {
 
 pub const DEFAULT_HASH_SIZE: u32 = 32;
 
+/// A lightweight merkle hash tree built over fixed-size labels, `levels[0]` holds the leaf hashes and the last level
+/// holds the root.
+#[derive(Clone, Debug, Default)]
+pub struct LightMht {
+    levels: Vec<Vec<Vec<u8>>>,
+}
+
+impl LightMht {
+    /// Build the tree from `data`, which is split into `size`-byte labels. The number of labels must be a power of two.
+    pub fn new(data: &[u8], size: usize) -> Result<Self> {
+        if size == 0 || data.is_empty() || data.len() % size != 0 {
+            bail!("calc light mht error: bad data length");
+        }
+        let lens = data.len() / size;
+        if !lens.is_power_of_two() {
+            bail!("calc light mht error: the number of labels must be a power of two");
+        }
+
+        let mut levels = Vec::with_capacity(lens.trailing_zeros() as usize + 1);
+        levels.push(
+            data.chunks(size)
+                .map(|label| Sha256::digest(label).to_vec())
+                .collect::<Vec<_>>(),
+        );
+        while levels.last().unwrap().len() > 1 {
+            let level = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| {
+                    let mut hash = Sha256::new();
+                    hash.update(&pair[0]);
+                    hash.update(&pair[1]);
+                    hash.finalize().to_vec()
+                })
+                .collect::<Vec<_>>();
+            levels.push(level);
+        }
+        Ok(LightMht { levels })
+    }
+
+    pub fn root(&self) -> Vec<u8> {
+        self.levels.last().map(|level| level[0].clone()).unwrap_or_default()
+    }
+
+    /// Get the path proof of the label at `index`, it can be checked by [`verify_path_proof`] and
+    /// [`check_index_path`].
+    pub fn path_proof(&self, index: usize) -> Result<PathProof> {
+        if self.levels.is_empty() || index >= self.levels[0].len() {
+            bail!("get path proof error: index out of range");
+        }
+        let depth = self.levels.len() - 1;
+        let mut proof = PathProof { locs: Vec::with_capacity(depth), path: Vec::with_capacity(depth) };
+        let mut index = index;
+        for level in &self.levels[..depth] {
+            if index & 1 == 0 {
+                proof.locs.push(1);
+            } else {
+                proof.locs.push(0);
+            }
+            proof.path.push(level[index ^ 1].clone());
+            index /= 2;
+        }
+        Ok(proof)
+    }
+}
+
 pub fn verify_path_proof(root: &[u8], data: &[u8], proof: PathProof) -> bool {
     if proof.locs.len() != proof.path.len() {
         return false;
@@ -78,7 +146,7 @@ pub fn verify_path_proof(root: &[u8], data: &[u8], proof: PathProof) -> bool {
 pub fn check_index_path(index: i64, locs: &[u8]) -> bool {
     let mut index = index;
     for v in locs {
-        if (index + 1) % 2 == 0 {
+        if index & 1 == 1 {
             if *v != 0 {
                 return false;
             }
@@ -93,4 +161,4 @@ pub fn check_index_path(index: i64, locs: &[u8]) -> bool {
 pub enum Hasher {
     SHA256(Sha256),
     SHA512(Sha512),
-}
\ No newline at end of file
+}
@@ -0,0 +1,266 @@
+use std::{env, fs, path::PathBuf, sync::Arc, thread, time::Duration};
+
+use ces_pois::{
+    acc::{
+        multi_level_acc::{verify_delete_update, verify_insert_update, verify_mutilevel_acc, MultiLevelAcc},
+        rsa_keygen, RsaKey,
+    },
+    pois::{
+        prove::Prover,
+        store::{MemoryNodeStore, ProverNodeStore},
+        verify::{ProverNode, Verifier},
+    },
+};
+use num_bigint_dig::BigUint;
+use rand::{seq::SliceRandom, Rng};
+use threadpool::ThreadPool;
+
+// The smallest expanders accepted by the verifier: `k` must match `CLUSTER_SIZE`, and `n / d` must hold 256 groups.
+const K: i64 = 8;
+const N: i64 = 256;
+const D: i64 = 1;
+const ELEMS_NUM: usize = 256;
+
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new() -> Self {
+        let dir = env::temp_dir().join(format!("ces-pois-{}", hex::encode(rand::random::<[u8; 8]>())));
+        fs::create_dir_all(&dir).unwrap();
+        TempDir(dir)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn new_prover(dir: &TempDir) -> Prover {
+    let id = rand::random::<[u8; 32]>();
+    Prover::new(K, N, D, &id, rsa_keygen(512), &dir.0).unwrap()
+}
+
+fn same_acc(a: &[u8], b: &[u8]) -> bool {
+    BigUint::from_bytes_be(a) == BigUint::from_bytes_be(b)
+}
+
+fn commit_idle_file_set(prover: &mut Prover, verifier: &Verifier) {
+    prover.generate_idle_file_set().unwrap();
+    let commits = prover.get_idle_file_set_commits().unwrap();
+
+    verifier.register_prover_node_empty(&prover.id);
+    assert!(verifier.receive_commits(&prover.id, &commits));
+    let chals = verifier.commit_challenges(&prover.id).unwrap();
+
+    let (commit_proofs, acc_proof) = prover.prove_commit_and_acc(&chals).unwrap();
+    verifier.update_prover_node_force(&prover.id, prover.key(), &prover.acc(), prover.front, prover.rear);
+    verifier.verify_commit_proofs(&prover.id, chals.clone(), commit_proofs.clone()).unwrap();
+    let results = verifier
+        .verify_commit_proofs_batch(&prover.id, chals.clone(), commit_proofs, ThreadPool::new(4))
+        .unwrap();
+    assert!(results.iter().all(Result::is_ok));
+    assert!(!verifier.parent_cache.is_empty());
+    verifier.verify_acc(&prover.id, chals, acc_proof).unwrap();
+
+    let (acc, front, rear) = verifier.logout_prover_node(&prover.id).unwrap();
+    assert!(verifier.is_logout(&prover.id));
+    let num = rear - prover.rear;
+    prover.update_status(num, false).unwrap();
+    assert_eq!((front, rear), (prover.front, prover.rear));
+    assert!(same_acc(&acc, &prover.acc()));
+}
+
+fn prove_and_verify_space(prover: &Prover, verifier: &Verifier) {
+    let p_node = ProverNode::new(&prover.id, prover.key(), &prover.acc(), prover.front, prover.rear);
+    let chals = verifier.space_challenges(K).unwrap();
+    // space proofs are made block by block, a block covers the files of one accumulator node
+    let mut proofs = Vec::new();
+    let mut left = prover.front + 1;
+    while left <= prover.rear {
+        let right = (((left - 1) / ELEMS_NUM as i64 + 1) * ELEMS_NUM as i64 + 1).min(prover.rear + 1);
+        let mut proof = prover.prove_space(&chals, left, right).unwrap();
+        verifier.verify_space(&p_node, chals.clone(), &mut proof).unwrap();
+        proofs.push(proof);
+        left = right;
+    }
+    let results = verifier.verify_space_batch(&p_node, &chals, proofs, ThreadPool::new(4));
+    assert!(results.iter().all(Result::is_ok));
+}
+
+fn delete_and_verify(prover: &mut Prover, verifier: &Verifier, num: i64) {
+    verifier.register_prover_node(&prover.id, prover.key(), &prover.acc(), prover.front, prover.rear);
+    let mut proof = prover.prove_deletion(num).unwrap();
+    verifier.verify_deletion(&prover.id, &mut proof).unwrap();
+
+    let (acc, front, rear) = verifier.logout_prover_node(&prover.id).unwrap();
+    prover.update_status(num, true).unwrap();
+    assert_eq!((front, rear), (prover.front, prover.rear));
+    assert!(same_acc(&acc, &prover.acc()));
+}
+
+#[test]
+fn commit_space_and_deletion_round_trip() {
+    let dir = TempDir::new();
+    let verifier = Verifier::new(K, N, D);
+    let mut prover = new_prover(&dir);
+
+    commit_idle_file_set(&mut prover, &verifier);
+    assert_eq!((prover.front, prover.rear), (0, K * 32));
+    prove_and_verify_space(&prover, &verifier);
+
+    let mut rng = rand::thread_rng();
+    while prover.front < prover.rear {
+        let num = rng.gen_range(1..=(prover.rear - prover.front).min(128));
+        delete_and_verify(&mut prover, &verifier, num);
+        if prover.front < prover.rear {
+            prove_and_verify_space(&prover, &verifier);
+        }
+    }
+}
+
+#[test]
+fn tampered_proofs_are_rejected() {
+    let dir = TempDir::new();
+    let verifier = Verifier::new(K, N, D);
+    let mut prover = new_prover(&dir);
+
+    prover.generate_idle_file_set().unwrap();
+    let commits = prover.get_idle_file_set_commits().unwrap();
+    verifier.register_prover_node_empty(&prover.id);
+    let mut bad_commits = commits.clone();
+    bad_commits.roots[0][0] ^= 1;
+    assert!(!verifier.receive_commits(&prover.id, &bad_commits));
+    assert!(verifier.receive_commits(&prover.id, &commits));
+
+    let chals = verifier.commit_challenges(&prover.id).unwrap();
+    let (commit_proofs, acc_proof) = prover.prove_commit_and_acc(&chals).unwrap();
+    verifier.update_prover_node_force(&prover.id, prover.key(), &prover.acc(), prover.front, prover.rear);
+    let mut rng = rand::thread_rng();
+    let row = rng.gen_range(0..commit_proofs.len());
+    let col = rng.gen_range(0..commit_proofs[row].len());
+    let mut bad_commit_proofs = commit_proofs.clone();
+    bad_commit_proofs[row][col].node.label[0] ^= 1;
+    assert!(verifier.verify_commit_proofs(&prover.id, chals.clone(), bad_commit_proofs.clone()).is_err());
+
+    // a bad cluster fails alone in a batch, and every parent relationship is checked
+    let results = verifier
+        .verify_commit_proofs_batch(&prover.id, chals.clone(), bad_commit_proofs, ThreadPool::new(4))
+        .unwrap();
+    assert!(results.iter().enumerate().all(|(i, result)| result.is_ok() == (i != row)));
+    let mut bad_commit_proofs = commit_proofs;
+    let parents = &mut bad_commit_proofs[row][col].parents;
+    parents[0].index = parents[parents.len() - 1].index + 1;
+    let results = verifier
+        .verify_commit_proofs_batch(&prover.id, chals.clone(), bad_commit_proofs, ThreadPool::new(4))
+        .unwrap();
+    assert!(results.iter().enumerate().all(|(i, result)| result.is_ok() == (i != row)));
+
+    let mut bad_acc_proof = acc_proof;
+    bad_acc_proof.acc_path.last_mut().unwrap()[0] ^= 1;
+    assert!(verifier.verify_acc(&prover.id, chals, bad_acc_proof).is_err());
+    verifier.logout_prover_node(&prover.id).unwrap();
+
+    let mut prover = new_prover(&dir);
+    commit_idle_file_set(&mut prover, &verifier);
+
+    let p_node = ProverNode::new(&prover.id, prover.key(), &prover.acc(), prover.front, prover.rear);
+    let chals = verifier.space_challenges(K).unwrap();
+    let mut proof = prover.prove_space(&chals, 1, 17).unwrap();
+    let i = rng.gen_range(0..proof.proofs.len());
+    let j = rng.gen_range(0..proof.proofs[i].len());
+    proof.proofs[i][j].label[0] ^= 1;
+    assert!(verifier.verify_space(&p_node, chals.clone(), &mut proof).is_err());
+    let mut proof = prover.prove_space(&chals, 1, 17).unwrap();
+    proof.roots.swap(0, 1);
+    assert!(verifier.verify_space(&p_node, chals.clone(), &mut proof).is_err());
+
+    // a bad proof fails alone in a batch
+    let proofs = vec![prover.prove_space(&chals, 1, 17).unwrap(), proof, prover.prove_space(&chals, 17, 33).unwrap()];
+    let results = verifier.verify_space_batch(&p_node, &chals, proofs, ThreadPool::new(2));
+    assert_eq!(results.iter().map(Result::is_ok).collect::<Vec<_>>(), vec![true, false, true]);
+
+    verifier.register_prover_node(&prover.id, prover.key(), &prover.acc(), prover.front, prover.rear);
+    let mut proof = prover.prove_deletion(16).unwrap();
+    proof.roots.swap(2, 3);
+    assert!(verifier.verify_deletion(&prover.id, &mut proof).is_err());
+}
+
+#[test]
+fn prover_nodes_are_evicted_on_logout_and_expiry() {
+    let store = Arc::new(MemoryNodeStore::new());
+    let verifier = Verifier::with_store(K, N, D, store.clone());
+    let (a, b) = (rand::random::<[u8; 32]>(), rand::random::<[u8; 32]>());
+
+    // forcing an update of an unknown node registers it instead of panicking
+    verifier.update_prover_node_force(&a, RsaKey::default(), &[1], 0, 0);
+    verifier.register_prover_node(&b, RsaKey::default(), &[1], 0, 0);
+    assert_eq!(store.len(), 2);
+
+    let (acc, front, rear) = verifier.logout_prover_node(&a).unwrap();
+    assert_eq!((acc.len(), front, rear), (256, 0, 0));
+    assert!(verifier.is_logout(&a) && !verifier.is_logout(&b));
+    assert_eq!(verifier.logout_prover_node(&a).unwrap(), (vec![], 0, 0));
+
+    assert!(store.expire(Duration::from_secs(60)).is_empty());
+    thread::sleep(Duration::from_millis(10));
+    assert_eq!(store.expire(Duration::from_millis(1)), vec![hex::encode(b)]);
+    assert!(verifier.is_logout(&b) && store.is_empty());
+}
+
+#[test]
+fn logout_keeps_a_prover_node_without_record() {
+    let store = Arc::new(MemoryNodeStore::new());
+    let verifier = Verifier::with_store(K, N, D, store.clone());
+    let id = rand::random::<[u8; 32]>();
+    store.put(&hex::encode(id), ProverNode { id: id.to_vec(), ..Default::default() });
+
+    assert!(verifier.logout_prover_node(&id).is_err());
+    assert!(!verifier.is_logout(&id));
+    assert_eq!(store.len(), 1);
+}
+
+fn random_elems(num: usize) -> Vec<Vec<u8>> {
+    (0..num).map(|_| (0..64).map(|_| rand::random::<u8>()).collect()).collect()
+}
+
+fn check_witness_chains(acc: &MultiLevelAcc, key: &RsaKey) {
+    let chains = acc.get_witness_chains(0, acc.elem_nums).unwrap();
+    let picked = chains.choose_multiple(&mut rand::thread_rng(), 8);
+    for chain in picked {
+        assert!(verify_mutilevel_acc(key, Some(&mut chain.clone()), &acc.acc()));
+    }
+}
+
+#[test]
+fn multi_level_acc_updates_are_verifiable() {
+    let key = rsa_keygen(512);
+    let mut acc = MultiLevelAcc::new(key.clone());
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..12 {
+        let old = acc.acc();
+        if acc.elem_nums > 0 && rng.gen_bool(0.4) {
+            let first_node_len = acc.accs.children[0].children[0].children.len();
+            let num = rng.gen_range(1..=first_node_len);
+            let labels = acc
+                .get_witness_chains(0, num)
+                .unwrap()
+                .into_iter()
+                .map(|chain| chain.elem)
+                .collect();
+            let (mut exist, accs) = acc.delete_elements_and_proof(num).unwrap();
+            assert!(verify_delete_update(key.clone(), &mut exist, labels, accs.clone(), &old));
+            assert_eq!(accs.last().unwrap(), &acc.acc());
+        } else {
+            let elems = random_elems(rng.gen_range(1..=40));
+            let (exist, accs) = acc.add_elements_and_proof(elems.clone()).unwrap();
+            assert!(verify_insert_update(key.clone(), Some(Box::new(exist)), elems, accs.clone(), old));
+            assert_eq!(accs.last().unwrap(), &acc.acc());
+        }
+        if acc.elem_nums > 0 {
+            check_witness_chains(&acc, &key);
+        }
+    }
+}
@@ -103,6 +103,47 @@ pub mod messaging {
 	pub enum MasterKeyApply {
 		Apply(WorkerPublicKey, EcdhPublicKey),
 	}
+
+	/// The party able to open an `EncryptedEnvelope`
+	#[derive(Encode, Decode, Clone, Debug, PartialEq, Eq, TypeInfo)]
+	pub enum EnvelopeRecipient {
+		/// A single worker, sealed to the ecdh public key it registered on chain
+		Worker(WorkerPublicKey),
+		/// Any keyfairy, sealed to the master public key
+		Keyfairy,
+	}
+
+	// Messages: Encrypted user to worker messaging
+	bind_topic!(EncryptedEnvelope, b"cess/mq/envelope");
+	/// A message encrypted with AES-256-GCM algorithm
+	///
+	/// The encryption key is generated with sr25519-based ECDH between the sender's `ecdh_pubkey`
+	/// and the recipient's key. The opened payload is dispatched to the subscribers of `topic` on
+	/// the receiving ceseal, keeping the origin of the envelope as the message sender. The payload
+	/// is sealed with `associated_data` of that origin, so an envelope pushed again by another
+	/// sender does not open.
+	///
+	/// User --> MessageOrigin::Worker or MessageOrigin::Keyfairy
+	#[derive(Encode, Decode, Clone, Debug, PartialEq, Eq, TypeInfo)]
+	pub struct EncryptedEnvelope {
+		/// Who is able to open the envelope
+		pub recipient: EnvelopeRecipient,
+		/// The topic to dispatch the opened payload to
+		pub topic: Path,
+		/// The ecdh public key of the sender
+		pub ecdh_pubkey: EcdhPublicKey,
+		/// Payload encrypted with aead key
+		pub encrypted_payload: Vec<u8>,
+		/// Aead IV
+		pub iv: AeadIV,
+	}
+
+	impl EncryptedEnvelope {
+		/// The aead associated data of an envelope pushed by `origin`
+		pub fn associated_data(&self, origin: &MessageOrigin) -> Vec<u8> {
+			(EncryptedEnvelope::topic(), origin, &self.recipient, &self.topic, &self.ecdh_pubkey).encode()
+		}
+	}
 }
 
 // Types used in storage
@@ -6,6 +6,11 @@ use std::{fmt::Debug, path::Path};
 
 pub use ces_types::attestation::ExtendMeasurement;
 pub use cestory_api::crpc::MemoryUsage;
+#[cfg(any(test, feature = "simulated"))]
+pub use simulated::SimulatedPlatform;
+
+#[cfg(any(test, feature = "simulated"))]
+pub mod simulated;
 
 pub trait ErrorType: Debug + Into<anyhow::Error> {}
 impl<T: Debug + Into<anyhow::Error>> ErrorType for T {}
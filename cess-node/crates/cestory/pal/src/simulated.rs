@@ -0,0 +1,106 @@
+//! A platform for running ceseal outside of an enclave, e.g. for local end-to-end tests.
+//!
+//! Sealed data is written to plain files and remote attestation is not available, so a worker running on it can
+//! only be registered in the `AttestationProvider::Root` way, without a report.
+
+use crate::{AppInfo, AppVersion, ExtendMeasurement, Machine, MemoryStats, MemoryUsage, Sealing, RA};
+use anyhow::anyhow;
+use ces_types::{AttestationProvider, AttestationReport};
+use core::time::Duration;
+use parity_scale_codec::Encode;
+use serde::{Deserialize, Serialize};
+use std::{io::ErrorKind, path::Path};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulatedPlatform {
+    pub machine_id: Vec<u8>,
+    pub cpu_cores: u32,
+    pub cpu_feature_level: u32,
+}
+
+impl SimulatedPlatform {
+    pub fn new(machine_id: Vec<u8>, cpu_cores: u32) -> Self {
+        SimulatedPlatform { machine_id, cpu_cores, cpu_feature_level: 1 }
+    }
+}
+
+impl Default for SimulatedPlatform {
+    fn default() -> Self {
+        Self::new(vec![], 1)
+    }
+}
+
+impl Sealing for SimulatedPlatform {
+    type SealError = std::io::Error;
+    type UnsealError = std::io::Error;
+
+    fn seal_data(&self, path: impl AsRef<Path>, data: &[u8]) -> Result<(), Self::SealError> {
+        std::fs::write(path, data)
+    }
+
+    fn unseal_data(&self, path: impl AsRef<Path>) -> Result<Option<Vec<u8>>, Self::UnsealError> {
+        match std::fs::read(path) {
+            Err(err) if matches!(err.kind(), ErrorKind::NotFound) => Ok(None),
+            other => other.map(Some),
+        }
+    }
+}
+
+impl RA for SimulatedPlatform {
+    type Error = anyhow::Error;
+
+    fn create_attestation_report(
+        &self,
+        provider: Option<AttestationProvider>,
+        _data: &[u8],
+        _timeout: Duration,
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.quote_test(provider)?;
+        Ok(Encode::encode(&None::<AttestationReport>))
+    }
+
+    fn quote_test(&self, provider: Option<AttestationProvider>) -> Result<(), Self::Error> {
+        match provider {
+            Some(AttestationProvider::Root) | None => Ok(()),
+            _ => Err(anyhow!("Attestation provider `{:?}` is not available on the simulated platform", provider)),
+        }
+    }
+
+    fn mr_enclave(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn extend_measurement(&self) -> Result<ExtendMeasurement, Self::Error> {
+        Err(anyhow!("no measurement on the simulated platform"))
+    }
+}
+
+impl Machine for SimulatedPlatform {
+    fn machine_id(&self) -> Vec<u8> {
+        self.machine_id.clone()
+    }
+
+    fn cpu_core_num(&self) -> u32 {
+        self.cpu_cores
+    }
+
+    fn cpu_feature_level(&self) -> u32 {
+        self.cpu_feature_level
+    }
+}
+
+impl MemoryStats for SimulatedPlatform {
+    fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage::default()
+    }
+}
+
+impl AppInfo for SimulatedPlatform {
+    fn app_version() -> AppVersion {
+        AppVersion {
+            major: env!("CARGO_PKG_VERSION_MAJOR").parse().unwrap(),
+            minor: env!("CARGO_PKG_VERSION_MINOR").parse().unwrap(),
+            patch: env!("CARGO_PKG_VERSION_PATCH").parse().unwrap(),
+        }
+    }
+}
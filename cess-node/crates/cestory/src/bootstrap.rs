@@ -4,7 +4,7 @@ use super::{
 use anyhow::{anyhow, Result};
 use cestory_api::{crpc::ceseal_api_server::CesealApiServer, ecall_args::InitArgs};
 use serde::{de::DeserializeOwned, Serialize};
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc};
 use tokio::sync::{mpsc, oneshot};
 use tonic::transport::Server;
 
@@ -60,7 +60,7 @@ where
     Ok(())
 }
 
-pub(crate) fn spawn_external_server<Platform>(
+pub(crate) fn spawn_external_server<Platform: pal::Platform>(
     ceseal: &mut Ceseal<Platform>,
     ceseal_props: CesealProperties,
     shutdown_rx: oneshot::Receiver<()>,
@@ -92,6 +92,11 @@ pub(crate) fn spawn_external_server<Platform>(
         .ok_or(anyhow!("the pois expender param not config on chain"))?;
     info!("pois expender param on chain: {pois_param:?}");
 
+    let prover_node_store: Arc<dyn ces_pois::pois::store::ProverNodeStore> = Arc::new(
+        pois::SealedProverNodeStore::new(ceseal.platform.clone(), &ceseal.args.sealing_path)
+            .map_err(|e| anyhow!("create the sealed prover node store failed: {e}"))?,
+    );
+
     {
         use rsa::pkcs1::EncodeRsaPublicKey;
         debug!(
@@ -109,9 +114,11 @@ pub(crate) fn spawn_external_server<Platform>(
         let podr2v_srv = podr2::new_podr2_verifier_api_server(ceseal_expert.clone())
             .max_decoding_message_size(MAX_DECODED_MSG_SIZE)
             .max_encoding_message_size(MAX_ENCODED_MSG_SIZE);
-        let pois_srv = pois::new_pois_certifier_api_server(pois_param.clone(), ceseal_expert.clone())
-            .max_decoding_message_size(MAX_DECODED_MSG_SIZE)
-            .max_encoding_message_size(MAX_ENCODED_MSG_SIZE);
+        tokio::spawn(pois::run_prover_node_expiry(prover_node_store.clone()));
+        let pois_srv =
+            pois::new_pois_certifier_api_server(pois_param.clone(), prover_node_store, ceseal_expert.clone())
+                .max_decoding_message_size(MAX_DECODED_MSG_SIZE)
+                .max_encoding_message_size(MAX_ENCODED_MSG_SIZE);
         let poisv_srv = pois::new_pois_verifier_api_server(pois_param, ceseal_expert.clone())
             .max_decoding_message_size(MAX_DECODED_MSG_SIZE)
             .max_encoding_message_size(MAX_ENCODED_MSG_SIZE);
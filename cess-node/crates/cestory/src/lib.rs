@@ -62,6 +62,8 @@ pub mod podr2;
 pub mod pois;
 mod pubkeys;
 mod secret_channel;
+#[cfg(any(test, feature = "sim"))]
+pub mod sim;
 mod storage;
 mod system;
 mod types;
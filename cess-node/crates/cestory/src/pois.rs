@@ -1,4 +1,4 @@
-use crate::{expert::CesealExpertStub, verify_signature};
+use crate::{expert::CesealExpertStub, types::ThreadPoolSafeBox, verify_signature};
 use ces_crypto::sr25519::Signing;
 use ces_pdp::Keys;
 use ces_pois::{
@@ -6,6 +6,7 @@ use ces_pois::{
     pois::{
         challenge,
         prove::{AccProof, CommitProof, Commits, DeletionProof, MhtProof, SpaceProof},
+        store::ProverNodeStore,
         verify::{ProverNode, Verifier},
     },
 };
@@ -13,9 +14,10 @@ use cestory_api::pois::{
     pois_certifier_api_server::{self, PoisCertifierApi},
     pois_verifier_api_server::{self, PoisVerifierApi},
     AccWitnessNode, Challenge, Int64Slice, PoisStatus, ProofHashAndLeftRight, RequestMinerCommitGenChall,
-    RequestMinerInitParam, RequestSpaceProofVerify, RequestSpaceProofVerifyTotal, RequestVerifyCommitAndAccProof,
-    RequestVerifyDeletionProof, ResponseMinerInitParam, ResponseSpaceProofVerify, ResponseSpaceProofVerifyTotal,
-    ResponseVerifyCommitOrDeletionProof,
+    RequestMinerInitParam, RequestSpaceProofVerify, RequestSpaceProofVerifyBatch, RequestSpaceProofVerifyTotal,
+    RequestVerifyCommitAndAccProof, RequestVerifyDeletionProof, ResponseMinerInitParam, ResponseSpaceProofVerify,
+    ResponseSpaceProofVerifyBatch, ResponseSpaceProofVerifyTotal, ResponseVerifyCommitOrDeletionProof,
+    SpaceProofVerifyResult,
 };
 use crypto::{digest::Digest, sha2::Sha256};
 use dashmap::DashMap;
@@ -24,10 +26,15 @@ use parity_scale_codec::Encode;
 use prost::Message;
 use rsa::pkcs1::EncodeRsaPublicKey;
 use sp_core::{crypto::AccountId32, sr25519, ByteArray};
-use std::{fmt::Debug, time::Instant};
+use std::{
+    fmt::Debug,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tonic::{Request, Response, Status};
 use tracing::info;
 
+mod node_store;
 mod proxy;
 
 pub type PoisCertifierApiServer =
@@ -36,10 +43,28 @@ pub type PoisVerifierApiServer =
     pois_verifier_api_server::PoisVerifierApiServer<PoisVerifierApiServerProxy<PoisVerifierServer>>;
 pub type PoisResult<T> = Result<Response<T>, Status>;
 
+pub use node_store::SealedProverNodeStore;
 pub use proxy::{PoisCertifierApiServerProxy, PoisVerifierApiServerProxy};
 
+/// Prover nodes not updated within this period are considered abandoned by their miners.
+pub const PROVER_NODE_TTL: Duration = Duration::from_secs(6 * 3600);
+const PROVER_NODE_EXPIRE_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Periodically evicts the prover nodes which have outlived [`PROVER_NODE_TTL`] from the store.
+pub async fn run_prover_node_expiry(store: Arc<dyn ProverNodeStore>) {
+    let mut interval = tokio::time::interval(PROVER_NODE_EXPIRE_INTERVAL);
+    loop {
+        interval.tick().await;
+        let expired = store.expire(PROVER_NODE_TTL);
+        if !expired.is_empty() {
+            info!("evicted {} expired prover nodes: {:?}", expired.len(), expired);
+        }
+    }
+}
+
 pub fn new_pois_certifier_api_server(
     pois_param: (i64, i64, i64),
+    prover_node_store: Arc<dyn ProverNodeStore>,
     ceseal_expert: CesealExpertStub,
 ) -> PoisCertifierApiServer {
     let podr2_keys =
@@ -49,7 +74,7 @@ pub fn new_pois_certifier_api_server(
         inner: PoisCertifierServer {
             podr2_keys,
             master_key,
-            verifier: Verifier::new(pois_param.0, pois_param.1, pois_param.2),
+            verifier: Verifier::with_store(pois_param.0, pois_param.1, pois_param.2, prover_node_store),
             commit_acc_proof_chals_map: DashMap::new(),
             ceseal_identity_key: ceseal_expert.identify_public_key().0,
             ceseal_expert: ceseal_expert.clone(),
@@ -71,6 +96,7 @@ pub fn new_pois_verifier_api_server(
             podr2_keys,
             master_key,
             verifier: Verifier::new(pois_param.0, pois_param.1, pois_param.2),
+            threadpool: ceseal_expert.thread_pool(),
             ceseal_identity_key: ceseal_expert.identify_public_key().0,
         },
         ceseal_expert,
@@ -144,6 +170,7 @@ pub struct PoisVerifierServer {
     pub podr2_keys: Keys,
     pub master_key: sr25519::Pair,
     pub verifier: Verifier,
+    pub threadpool: ThreadPoolSafeBox,
     pub ceseal_identity_key: [u8; 32],
 }
 
@@ -399,16 +426,23 @@ impl PoisCertifierApi for PoisCertifierServer {
         } else {
             return Err(Status::data_loss("This miner does not have a corresponding commit challenge".to_string()))
         };
-        match self
-            .verifier
-            .verify_commit_proofs(&miner_id, chals.clone(), commit_proofs.clone())
+        let verifier = self.verifier.clone();
+        let threadpool = self.ceseal_expert.thread_pool();
+        let (id, commit_chals) = (miner_id.clone(), chals.clone());
+        let verify_results = tokio::task::spawn_blocking(move || {
+            let pool = threadpool
+                .lock()
+                .map_err(|e| Status::internal("lock global threadpool fail:".to_string() + &e.to_string()))?;
+            Ok::<_, Status>(verifier.verify_commit_proofs_batch(&id, commit_chals, commit_proofs, pool.clone()))
+        })
+        .await
+        .map_err(|_| Status::internal("Waiting for commit proofs verify fail".to_string()))??;
+        if let Some(e) = verify_results
+            .and_then(|results| results.into_iter().collect::<anyhow::Result<Vec<_>>>())
+            .err()
         {
-            Ok(_) => {},
-            Err(e) =>
-                return Err(Status::invalid_argument(
-                    format!("Error when verify commit :{:?}", e.to_string()).to_string(),
-                )),
-        };
+            return Err(Status::invalid_argument(format!("Error when verify commit :{:?}", e.to_string()).to_string()))
+        }
         let pb_acc_proof = commit_and_acc_proof
             .acc_proof
             .ok_or(Status::invalid_argument("Miner request data is invalid, loss parameter 'acc_proof'"))?;
@@ -622,6 +656,87 @@ impl PoisVerifierApi for PoisVerifierServer {
         );
         Ok(Response::new(ResponseSpaceProofVerify { signature }))
     }
+
+    async fn request_space_proof_verify_batch(
+        &self,
+        request: Request<RequestSpaceProofVerifyBatch>,
+    ) -> PoisResult<ResponseSpaceProofVerifyBatch> {
+        let now = Instant::now();
+        let req = request.into_inner();
+        let miner_id: Vec<u8> = req.miner_id;
+        let miner_pois_info = req
+            .pois_info
+            .ok_or(Status::invalid_argument("Miner request data is invalid, loss parameter 'pois_info'"))?;
+        if req.proofs.len() != req.miner_space_proof_hash_polkadot_sigs.len() {
+            return Err(Status::invalid_argument("Every space proof should come with a miner signature!"))
+        }
+        let miner_cess_address = get_ss58_address(&miner_id)?;
+        info!(
+            "[Pois Verify Space Proof Batch] miner {:?} verify {} space proofs...",
+            miner_cess_address,
+            req.proofs.len()
+        );
+
+        //the proofs without a valid miner signature are rejected before verifying
+        let mut results = vec![SpaceProofVerifyResult::default(); req.proofs.len()];
+        let mut accepted = Vec::new();
+        let mut space_proofs = Vec::new();
+        for (i, (proof, sig)) in req.proofs.iter().zip(req.miner_space_proof_hash_polkadot_sigs).enumerate() {
+            let miner_req_hash = try_into_proto_byte_hash(proof)?;
+            if !verify_signature(miner_id.clone(), sig, &miner_req_hash).unwrap_or(false) {
+                results[i].error = "The space proof signature verification provided by the miner failed!".to_string();
+                continue
+            }
+            let proof_hash_and_left_right = ProofHashAndLeftRight {
+                space_proof_hash: miner_req_hash,
+                left: proof.left,
+                right: proof.right,
+                tee_id: self.ceseal_identity_key.to_vec(),
+            };
+            accepted.push((i, try_into_proto_byte_hash(&proof_hash_and_left_right)?));
+            space_proofs.push(convert_to_space_proof(proof));
+        }
+
+        let p_node = ProverNode::new(
+            &miner_id,
+            RsaKey {
+                n: BigUint::from_bytes_be(&miner_pois_info.key_n),
+                g: BigUint::from_bytes_be(&miner_pois_info.key_g),
+            },
+            &miner_pois_info.acc,
+            miner_pois_info.front,
+            miner_pois_info.rear,
+        );
+        let verifier = self.verifier.clone();
+        let threadpool = self.threadpool.clone();
+        let verify_results = tokio::task::spawn_blocking(move || {
+            let pool = threadpool
+                .lock()
+                .map_err(|e| Status::internal("lock global threadpool fail:".to_string() + &e.to_string()))?;
+            Ok::<_, Status>(verifier.verify_space_batch(&p_node, &req.space_chals, space_proofs, pool.clone()))
+        })
+        .await
+        .map_err(|_| Status::internal("Waiting for space proofs verify fail".to_string()))??;
+
+        for ((i, proof_hash_and_left_right_hash), verify_result) in accepted.into_iter().zip(verify_results) {
+            if let Err(e) = verify_result {
+                results[i].error = "Error space proof verify fail:".to_string() + &e.to_string();
+                continue
+            }
+            results[i].signature = self.podr2_keys.sign_data(&proof_hash_and_left_right_hash).map_err(|e| {
+                Status::internal(
+                    "Error space proof verify compute signature fail:".to_string() + &e.error_code.to_string(),
+                )
+            })?;
+        }
+        info!(
+            "[Pois Verify Space Proof Batch] miner {:?} verify {} space proofs in:{:.2?}",
+            miner_cess_address,
+            results.len(),
+            now.elapsed()
+        );
+        Ok(Response::new(ResponseSpaceProofVerifyBatch { results }))
+    }
     async fn request_verify_space_total(
         &self,
         request: Request<RequestSpaceProofVerifyTotal>,
@@ -0,0 +1,338 @@
+use crate::pal::Sealing;
+use ces_pois::{
+    acc::RsaKey,
+    pois::{
+        prove::Commits,
+        store::{MemoryNodeStore, NodeUpdate, ProverNodeStore},
+        verify::{ProverNode, Record},
+    },
+};
+use num_bigint_dig::BigUint;
+use anyhow::Result;
+use parity_scale_codec::{Decode, Encode};
+use parking_lot::Mutex;
+use std::{
+    fmt::Debug,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+use tracing::warn;
+
+const PROVER_NODES_DIR: &str = "pois-prover-nodes";
+
+#[derive(Encode, Decode)]
+enum ProverNodeSeal {
+    V1(PersistentProverNode),
+}
+
+#[derive(Encode, Decode)]
+struct PersistentProverNode {
+    id: Vec<u8>,
+    file_indexs: Vec<i64>,
+    roots: Vec<Vec<u8>>,
+    record: Option<PersistentRecord>,
+}
+
+#[derive(Encode, Decode)]
+struct PersistentRecord {
+    key_n: Vec<u8>,
+    key_g: Vec<u8>,
+    acc: Vec<u8>,
+    front: i64,
+    rear: i64,
+}
+
+impl From<&ProverNode> for PersistentProverNode {
+    fn from(node: &ProverNode) -> Self {
+        PersistentProverNode {
+            id: node.id.clone(),
+            file_indexs: node.commit_buf.file_indexs.clone(),
+            roots: node.commit_buf.roots.clone(),
+            record: node.record.as_ref().map(|record| PersistentRecord {
+                key_n: record.key.n.to_bytes_be(),
+                key_g: record.key.g.to_bytes_be(),
+                acc: record.acc.clone(),
+                front: record.front,
+                rear: record.rear,
+            }),
+        }
+    }
+}
+
+impl From<PersistentProverNode> for ProverNode {
+    fn from(node: PersistentProverNode) -> Self {
+        ProverNode {
+            id: node.id,
+            commit_buf: Commits { file_indexs: node.file_indexs, roots: node.roots },
+            record: node.record.map(|record| Record {
+                key: RsaKey { n: BigUint::from_bytes_be(&record.key_n), g: BigUint::from_bytes_be(&record.key_g) },
+                acc: record.acc,
+                front: record.front,
+                rear: record.rear,
+            }),
+        }
+    }
+}
+
+/// A [`ProverNodeStore`] which seals every prover node to its own file under the sealing path, so that the miners'
+/// records and pending commits survive a ceseal restart.
+///
+/// Nodes are cached in memory once written or loaded. The modification time of a sealed file is the last update
+/// time of the node, which is what [`ProverNodeStore::expire`] is based on.
+pub struct SealedProverNodeStore<P> {
+    platform: Mutex<P>,
+    dir: PathBuf,
+    cache: MemoryNodeStore,
+    /// Serializes the writes that bypass the cache entry lock, so that a node loaded from disk never
+    /// replaces a newer one in the cache.
+    write_lock: Mutex<()>,
+}
+
+impl<P: Sealing> SealedProverNodeStore<P> {
+    pub fn new(platform: P, sealing_path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let dir = sealing_path.as_ref().join(PROVER_NODES_DIR);
+        fs::create_dir_all(&dir)?;
+        Ok(SealedProverNodeStore {
+            platform: Mutex::new(platform),
+            dir,
+            cache: MemoryNodeStore::new(),
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    fn node_file_path(&self, id: &str) -> PathBuf {
+        self.dir.join(id)
+    }
+
+    fn load(&self, id: &str) -> Option<ProverNode> {
+        let filepath = self.node_file_path(id);
+        let sealed_data = match self.platform.lock().unseal_data(&filepath) {
+            Ok(data) => data?,
+            Err(e) => {
+                warn!("unseal prover node {} error: {:?}", filepath.display(), e);
+                return None
+            },
+        };
+        match ProverNodeSeal::decode(&mut &sealed_data[..]) {
+            Ok(ProverNodeSeal::V1(node)) => Some(node.into()),
+            Err(e) => {
+                warn!("decode prover node {} error: {}", filepath.display(), e);
+                None
+            },
+        }
+    }
+
+    fn seal(&self, id: &str, node: &ProverNode) {
+        let filepath = self.node_file_path(id);
+        let data = ProverNodeSeal::V1(node.into());
+        if let Err(e) = self.platform.lock().seal_data(&filepath, &data.encode()) {
+            warn!("seal prover node {} error: {:?}", filepath.display(), e);
+        }
+    }
+
+    fn remove_file(&self, id: &str) {
+        let filepath = self.node_file_path(id);
+        if let Err(e) = fs::remove_file(&filepath) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("remove sealed prover node {} error: {}", filepath.display(), e);
+            }
+        }
+    }
+}
+
+impl<P> Debug for SealedProverNodeStore<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SealedProverNodeStore")
+            .field("dir", &self.dir)
+            .field("cache", &self.cache)
+            .finish()
+    }
+}
+
+impl<P: Sealing + Send> ProverNodeStore for SealedProverNodeStore<P> {
+    fn get(&self, id: &str) -> Option<ProverNode> {
+        if let Some(node) = self.cache.get(id) {
+            return Some(node)
+        }
+        let _guard = self.write_lock.lock();
+        if let Some(node) = self.cache.get(id) {
+            return Some(node)
+        }
+        let node = self.load(id)?;
+        self.cache.put(id, node.clone());
+        Some(node)
+    }
+
+    fn put(&self, id: &str, node: ProverNode) {
+        let _guard = self.write_lock.lock();
+        // replace a cached node under its entry lock, so that it is not interleaved with an update
+        let mut node = Some(node);
+        let replaced = self.cache.update(
+            id,
+            Box::new(|cached| {
+                *cached = node.take().expect("the node is only taken once; qed.");
+                self.seal(id, cached);
+                Ok(())
+            }),
+        );
+        if replaced.is_none() {
+            let node = node.expect("the node is not taken if nothing was cached; qed.");
+            self.seal(id, &node);
+            self.cache.put(id, node);
+        }
+    }
+
+    fn update(&self, id: &str, f: NodeUpdate<'_>) -> Option<Result<()>> {
+        // make sure the node is cached, the cache entry then guards the whole read-modify-write
+        self.get(id)?;
+        self.cache.update(
+            id,
+            Box::new(|node| {
+                f(node)?;
+                self.seal(id, node);
+                Ok(())
+            }),
+        )
+    }
+
+    fn remove(&self, id: &str) -> Option<ProverNode> {
+        let node = self.cache.remove(id).or_else(|| self.load(id));
+        self.remove_file(id);
+        node
+    }
+
+    fn contains(&self, id: &str) -> bool {
+        self.cache.contains(id) || self.node_file_path(id).exists()
+    }
+
+    fn expire(&self, ttl: Duration) -> Vec<String> {
+        let mut expired = self.cache.expire(ttl);
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("read prover nodes dir {} error: {}", self.dir.display(), e);
+                return expired
+            },
+        };
+        let now = SystemTime::now();
+        for entry in entries.flatten() {
+            let Some(id) = entry.file_name().to_str().map(str::to_string) else { continue };
+            let outdated = entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .is_ok_and(|modified| now.duration_since(modified).is_ok_and(|elapsed| elapsed > ttl));
+            if outdated {
+                self.cache.remove(&id);
+                self.remove_file(&id);
+                if !expired.contains(&id) {
+                    expired.push(id);
+                }
+            }
+        }
+        expired
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct MockSealer;
+    impl Sealing for MockSealer {
+        type SealError = std::io::Error;
+        type UnsealError = std::io::Error;
+
+        fn seal_data(&self, path: impl AsRef<Path>, data: &[u8]) -> Result<(), Self::SealError> {
+            fs::write(path, data)
+        }
+
+        fn unseal_data(&self, path: impl AsRef<Path>) -> Result<Option<Vec<u8>>, Self::UnsealError> {
+            match fs::read(path) {
+                Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => Ok(None),
+                other => other.map(Some),
+            }
+        }
+    }
+
+    struct CleanableDir(PathBuf);
+    impl CleanableDir {
+        fn new() -> Self {
+            CleanableDir(std::env::temp_dir().join(format!("ceseal-{}", hex::encode(rand::random::<[u8; 8]>()))))
+        }
+    }
+    impl Drop for CleanableDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_prover_node_survives_restart() {
+        let sealing_path = CleanableDir::new();
+        let id = hex::encode([1u8; 32]);
+        let mut node = ProverNode::new(&[1u8; 32], RsaKey { n: 77u32.into(), g: 5u32.into() }, &[9, 9], 16, 256);
+        node.commit_buf = Commits { file_indexs: vec![1, 2, 3], roots: vec![vec![4; 32]] };
+
+        let store = SealedProverNodeStore::new(MockSealer, &sealing_path.0).unwrap();
+        store.put(&id, node.clone());
+
+        let store = SealedProverNodeStore::new(MockSealer, &sealing_path.0).unwrap();
+        let restored = store.get(&id).unwrap();
+        assert_eq!(restored.commit_buf, node.commit_buf);
+        let (record, expected) = (restored.record.unwrap(), node.record.unwrap());
+        assert_eq!((record.key.n, record.key.g), (expected.key.n, expected.key.g));
+        assert_eq!((record.acc, record.front, record.rear), (expected.acc, expected.front, expected.rear));
+
+        assert!(store.remove(&id).is_some());
+        assert!(!store.contains(&id));
+        assert!(SealedProverNodeStore::new(MockSealer, &sealing_path.0)
+            .unwrap()
+            .get(&id)
+            .is_none());
+    }
+
+    #[test]
+    fn test_prover_node_update() {
+        let sealing_path = CleanableDir::new();
+        let store = SealedProverNodeStore::new(MockSealer, &sealing_path.0).unwrap();
+        let id = hex::encode([3u8; 32]);
+        store.put(&id, ProverNode::new(&[3u8; 32], RsaKey { n: 77u32.into(), g: 5u32.into() }, &[9], 0, 16));
+
+        let result = store.update(
+            &id,
+            Box::new(|node| {
+                node.record.as_mut().unwrap().rear = 32;
+                anyhow::bail!("rejected")
+            }),
+        );
+        assert!(result.unwrap().is_err());
+        assert_eq!(store.get(&id).unwrap().record.unwrap().rear, 16);
+
+        let result = store.update(
+            &id,
+            Box::new(|node| {
+                node.record.as_mut().unwrap().rear = 32;
+                Ok(())
+            }),
+        );
+        assert!(result.unwrap().is_ok());
+        let store = SealedProverNodeStore::new(MockSealer, &sealing_path.0).unwrap();
+        assert_eq!(store.get(&id).unwrap().record.unwrap().rear, 32);
+
+        assert!(store.update(&hex::encode([4u8; 32]), Box::new(|_| Ok(()))).is_none());
+    }
+
+    #[test]
+    fn test_prover_node_expire() {
+        let sealing_path = CleanableDir::new();
+        let store = SealedProverNodeStore::new(MockSealer, &sealing_path.0).unwrap();
+        let id = hex::encode([2u8; 32]);
+        store.put(&id, ProverNode { id: vec![2u8; 32], ..Default::default() });
+
+        assert!(store.expire(Duration::from_secs(60)).is_empty());
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(store.expire(Duration::from_millis(1)), vec![id.clone()]);
+        assert!(!store.contains(&id));
+    }
+}
@@ -2,9 +2,10 @@ use super::PoisResult;
 use crate::expert::{CesealExpertStub, ExternalResourceKind};
 use cestory_api::pois::{
     pois_certifier_api_server::PoisCertifierApi, pois_verifier_api_server::PoisVerifierApi, Challenge,
-    RequestMinerCommitGenChall, RequestMinerInitParam, RequestSpaceProofVerify, RequestSpaceProofVerifyTotal,
-    RequestVerifyCommitAndAccProof, RequestVerifyDeletionProof, ResponseMinerInitParam, ResponseSpaceProofVerify,
-    ResponseSpaceProofVerifyTotal, ResponseVerifyCommitOrDeletionProof,
+    RequestMinerCommitGenChall, RequestMinerInitParam, RequestSpaceProofVerify, RequestSpaceProofVerifyBatch,
+    RequestSpaceProofVerifyTotal, RequestVerifyCommitAndAccProof, RequestVerifyDeletionProof, ResponseMinerInitParam,
+    ResponseSpaceProofVerify, ResponseSpaceProofVerifyBatch, ResponseSpaceProofVerifyTotal,
+    ResponseVerifyCommitOrDeletionProof,
 };
 use tonic::Request;
 
@@ -90,4 +91,15 @@ where
             .await?;
         self.inner.request_verify_space_total(request).await
     }
+
+    async fn request_space_proof_verify_batch(
+        &self,
+        request: Request<RequestSpaceProofVerifyBatch>,
+    ) -> PoisResult<ResponseSpaceProofVerifyBatch> {
+        let _permit = self
+            .ceseal_expert
+            .try_acquire_permit(ExternalResourceKind::PoisSpaceProofVerify)
+            .await?;
+        self.inner.request_space_proof_verify_batch(request).await
+    }
 }
@@ -0,0 +1,187 @@
+//! An in-process ceseal on the [`SimulatedPlatform`], fed by a mock chain, for end-to-end tests which need neither
+//! SGX nor a running node.
+//!
+//! The mock chain is finalized by a single GRANDPA authority whose justifications are not produced, so the runtime
+//! is always initialized with `grandpa_note_stalled` enabled, which lets the light client accept them.
+
+use crate::{light_validation::utils::storage_prefix, ChainStorage, RpcService};
+use ces_mq::Message;
+use ces_types::AttestationProvider;
+use cestory_api::{
+    blocks::{AuthoritySet, BlockHeaderWithChanges, GenesisBlockInfo, HeaderToSync, StorageChanges, StorageState},
+    crpc::{self as pb, ceseal_api_server::CesealApi},
+    ecall_args::InitArgs,
+};
+use pal::SimulatedPlatform;
+use parity_scale_codec::Encode;
+use sp_consensus_grandpa::AuthorityId;
+use sp_runtime::{traits::Header as _, Digest};
+use std::{collections::BTreeMap, path::Path};
+use tonic::{Request, Status};
+
+/// The interval of the mock chain's timestamps.
+pub const BLOCK_TIME_MS: u64 = 6000;
+
+/// A chain producing a block whenever asked to, with the storage changes and messages queued since the last one.
+pub struct MockChain {
+    storage: ChainStorage,
+    genesis_state: StorageState,
+    authority_set: AuthoritySet,
+    headers: Vec<chain::Header>,
+    pending_changes: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+    pending_messages: Vec<Message>,
+}
+
+impl MockChain {
+    /// Creates the chain with the given genesis storage, to which the GRANDPA authorities are added.
+    pub fn new(mut genesis_state: StorageState) -> Self {
+        let authority_set =
+            AuthoritySet { list: vec![(AuthorityId::from(sp_core::ed25519::Public::from_raw([1; 32])), 1)], id: 0 };
+        genesis_state.push((storage_prefix("Grandpa", "Authorities").to_vec(), authority_set.list.encode()));
+        let storage = ChainStorage::from_pairs(genesis_state.iter().map(|(k, v)| (k, v)));
+        let genesis = chain::Header::new(0, Default::default(), *storage.root(), Default::default(), Digest::default());
+        MockChain {
+            storage,
+            genesis_state,
+            authority_set,
+            headers: vec![genesis],
+            pending_changes: Default::default(),
+            pending_messages: vec![],
+        }
+    }
+
+    pub fn genesis_info(&self) -> GenesisBlockInfo {
+        let authorities_key = storage_prefix("Grandpa", "Authorities");
+        let genesis = ChainStorage::from_pairs(self.genesis_state.iter().map(|(k, v)| (k, v)));
+        let proof = sp_state_machine::prove_read_on_trie_backend(genesis.inner().as_trie_backend(), [authorities_key])
+            .expect("the authorities are in the genesis state; qed.")
+            .into_iter_nodes()
+            .collect();
+        GenesisBlockInfo { block_header: self.headers[0].clone(), authority_set: self.authority_set.clone(), proof }
+    }
+
+    pub fn genesis_state(&self) -> StorageState {
+        self.genesis_state.clone()
+    }
+
+    pub fn best_number(&self) -> chain::BlockNumber {
+        self.headers.last().expect("the genesis header always exists; qed.").number
+    }
+
+    pub fn header(&self, number: chain::BlockNumber) -> Option<&chain::Header> {
+        self.headers.get(number as usize)
+    }
+
+    pub fn storage(&self) -> &ChainStorage {
+        &self.storage
+    }
+
+    /// Sets or removes a storage value in the next block.
+    pub fn set_storage(&mut self, key: impl Into<Vec<u8>>, value: Option<Vec<u8>>) {
+        self.pending_changes.insert(key.into(), value);
+    }
+
+    /// Sends a message to the ceseal in the next block, as if it was pushed by a pallet.
+    pub fn push_message(&mut self, message: Message) {
+        self.pending_messages.push(message);
+    }
+
+    /// Produces the next block out of the queued changes.
+    pub fn produce_block(&mut self) -> BlockHeaderWithChanges {
+        let number = self.best_number() + 1;
+        let mut changes = std::mem::take(&mut self.pending_changes);
+        changes.insert(storage_prefix("Timestamp", "Now").to_vec(), Some((number as u64 * BLOCK_TIME_MS).encode()));
+        let messages = std::mem::take(&mut self.pending_messages);
+        changes.insert(
+            storage_prefix("CesMq", "OutboundMessages").to_vec(),
+            (!messages.is_empty()).then(|| messages.encode()),
+        );
+        let main_storage_changes: Vec<_> = changes.into_iter().collect();
+
+        let (state_root, transaction) = self
+            .storage
+            .inner()
+            .calc_root_if_changes(&main_storage_changes, &Default::default());
+        self.storage.inner_mut().apply_changes(state_root, transaction);
+        let parent_hash = self.headers.last().expect("the genesis header always exists; qed.").hash();
+        let block_header = chain::Header::new(number, Default::default(), state_root, parent_hash, Digest::default());
+        self.headers.push(block_header.clone());
+        BlockHeaderWithChanges {
+            block_header,
+            storage_changes: StorageChanges { main_storage_changes, child_storage_changes: vec![] },
+        }
+    }
+}
+
+/// Drives a ceseal on the [`SimulatedPlatform`] through its [`CesealApi`] with the blocks of a [`MockChain`].
+pub struct SimCeseal {
+    pub service: RpcService<SimulatedPlatform>,
+    pub chain: MockChain,
+    headers_synced_to: Option<chain::BlockNumber>,
+}
+
+impl SimCeseal {
+    /// Creates the ceseal with its sealing and storage directories under `data_dir`.
+    pub fn new(platform: SimulatedPlatform, data_dir: impl AsRef<Path>, chain: MockChain) -> std::io::Result<Self> {
+        let sealing_path = data_dir.as_ref().join("protected_files");
+        let storage_path = data_dir.as_ref().join("storage_files");
+        std::fs::create_dir_all(&sealing_path)?;
+        std::fs::create_dir_all(&storage_path)?;
+        let args = InitArgs {
+            sealing_path: sealing_path.to_string_lossy().into(),
+            storage_path: storage_path.to_string_lossy().into(),
+            version: env!("CARGO_PKG_VERSION").into(),
+            enable_checkpoint: false,
+            cores: platform.cpu_cores,
+            no_rcu: true,
+            ..Default::default()
+        };
+        let service = RpcService::new(platform);
+        service
+            .lock_ceseal(true, true)
+            .expect("a new ceseal is never locked; qed.")
+            .init(args);
+        Ok(SimCeseal { service, chain, headers_synced_to: None })
+    }
+
+    /// Initializes the runtime at the genesis of the mock chain, without a remote attestation.
+    pub async fn init_runtime(&self, operator: Option<chain::AccountId>) -> Result<pb::InitRuntimeResponse, Status> {
+        let request = pb::InitRuntimeRequest::new(
+            true,
+            self.chain.genesis_info(),
+            None,
+            self.chain.genesis_state(),
+            operator,
+            Some(AttestationProvider::Root),
+            true,
+        );
+        Ok(self.service.init_runtime(Request::new(request)).await?.into_inner())
+    }
+
+    /// Produces `count` blocks, syncs their headers and dispatches them, returning the block synced to.
+    pub async fn produce_blocks(&mut self, count: u32) -> Result<chain::BlockNumber, Status> {
+        let blocks: Vec<_> = (0..count).map(|_| self.chain.produce_block()).collect();
+        self.sync_headers().await?;
+        let synced_to = self.service.dispatch_blocks(Request::new(pb::Blocks::new(blocks))).await?;
+        Ok(synced_to.into_inner().synced_to)
+    }
+
+    async fn sync_headers(&mut self) -> Result<(), Status> {
+        let best_number = self.chain.best_number();
+        // the genesis header has to be synced along with its first descendant
+        let from = self.headers_synced_to.map_or(0, |number| number + 1);
+        if best_number == 0 || from > best_number {
+            return Ok(())
+        }
+        let headers = (from..=best_number)
+            .map(|number| HeaderToSync {
+                header: self.chain.header(number).expect("produced above; qed.").clone(),
+                justification: (number == best_number).then(Vec::new),
+            })
+            .collect();
+        let request = pb::HeadersToSync::new(headers, None);
+        let synced_to = self.service.sync_header(Request::new(request)).await?.into_inner().synced_to;
+        self.headers_synced_to = Some(synced_to);
+        Ok(())
+    }
+}
@@ -3,13 +3,17 @@ mod master_key;
 
 use crate::{pal, secret_channel::ecdh_serde, types::BlockDispatchContext};
 use anyhow::{anyhow, Result};
-use ces_crypto::{ecdh::EcdhKey, key_share, rsa::RsaDer, sr25519::KDF, SecretKey};
+use ces_crypto::{aead, ecdh, ecdh::EcdhKey, key_share, rsa::RsaDer, sr25519::KDF, SecretKey};
 use ces_mq::{
-    traits::MessageChannel, MessageDispatcher, MessageOrigin, MessageSendQueue, SignedMessageChannel, TypedReceiver,
+    traits::MessageChannel, BindTopic, Message, MessageDispatcher, MessageOrigin, MessageSendQueue, SignedMessageChannel,
+    TypedReceiver,
 };
 use ces_serde_more as more;
 use ces_types::{
-    messaging::{AeadIV, DispatchMasterKeyEvent, MasterKeyApply, MasterKeyDistribution, MasterKeyLaunch, WorkerEvent},
+    messaging::{
+        AeadIV, DispatchMasterKeyEvent, EncryptedEnvelope, EnvelopeRecipient, MasterKeyApply, MasterKeyDistribution,
+        MasterKeyLaunch, WorkerEvent,
+    },
     EcdhPublicKey, WorkerPublicKey,
 };
 pub use cestory_api::{crpc::SystemInfo, ecall_args::InitArgs};
@@ -50,6 +54,7 @@ pub struct System<Platform> {
     master_key_launch_events: TypedReceiver<MasterKeyLaunch>,
     master_key_distribution_events: TypedReceiver<MasterKeyDistribution>,
     master_key_apply_events: TypedReceiver<MasterKeyApply>,
+    encrypted_envelopes: TypedReceiver<EncryptedEnvelope>,
     // Worker
     #[codec(skip)]
     pub(crate) identity_key: WorkerIdentityKey,
@@ -107,6 +112,7 @@ impl<Platform: pal::Platform> System<Platform> {
             master_key_launch_events: recv_mq.subscribe_bound(),
             master_key_distribution_events: recv_mq.subscribe_bound(),
             master_key_apply_events: recv_mq.subscribe_bound(),
+            encrypted_envelopes: recv_mq.subscribe_bound(),
             identity_key,
             ecdh_key,
             registered: false,
@@ -135,6 +141,11 @@ impl<Platform: pal::Platform> System<Platform> {
             (event, origin) = self.master_key_apply_events => {
                 self.process_master_key_apply_event(block, origin, event);
             },
+            (envelope, origin) = self.encrypted_envelopes => {
+                if let Err(err) = self.process_encrypted_envelope(block, origin, envelope) {
+                    error!("Failed to process encrypted envelope: {:?}", err);
+                }
+            },
         };
         Ok(ok.is_none())
     }
@@ -350,6 +361,49 @@ impl<Platform: pal::Platform> System<Platform> {
         }
     }
 
+    /// Open an envelope sealed to this worker or to the keyfairy, and dispatch the payload to
+    /// the subscribers of its inner topic on behalf of the envelope sender.
+    fn process_encrypted_envelope(
+        &mut self,
+        block: &mut BlockDispatchContext,
+        origin: MessageOrigin,
+        envelope: EncryptedEnvelope,
+    ) -> Result<()> {
+        if origin.is_offchain() {
+            anyhow::bail!("invalid envelope sender: {}", origin);
+        }
+        if envelope.topic == EncryptedEnvelope::topic() {
+            anyhow::bail!("nested envelope from {}", origin);
+        }
+
+        let keyfairy_ecdh_key;
+        let my_ecdh_key = match &envelope.recipient {
+            EnvelopeRecipient::Worker(pubkey) => {
+                if *pubkey != self.identity_key.public() {
+                    trace!("ignore EncryptedEnvelope that do not belong to you");
+                    return Ok(())
+                }
+                &self.ecdh_key
+            },
+            EnvelopeRecipient::Keyfairy => {
+                let Some(keyfairy) = &self.keyfairy else {
+                    trace!("ignore EncryptedEnvelope to keyfairy as the keyfairy is not inited");
+                    return Ok(())
+                };
+                keyfairy_ecdh_key = keyfairy
+                    .master_key()
+                    .sr25519_keypair()
+                    .derive_ecdh_key()
+                    .map_err(|e| anyhow!("failed to derive keyfairy ecdh key: {e:?}"))?;
+                &keyfairy_ecdh_key
+            },
+        };
+
+        let payload = open_envelope(my_ecdh_key, &origin, &envelope)?;
+        block.recv_mq.dispatch(Message::new(origin, envelope.topic, payload));
+        Ok(())
+    }
+
     pub fn is_registered(&self) -> bool {
         self.registered
     }
@@ -369,6 +423,44 @@ impl<Platform: pal::Platform> System<Platform> {
     }
 }
 
+/// Seal `payload` for `recipient`, whose ecdh public key is `recipient_ecdh_pubkey`, to be pushed
+/// by `origin` and dispatched to `topic` once opened.
+pub fn seal_envelope(
+    sender_ecdh_key: &EcdhKey,
+    origin: &MessageOrigin,
+    recipient: EnvelopeRecipient,
+    recipient_ecdh_pubkey: &EcdhPublicKey,
+    topic: Vec<u8>,
+    mut payload: Vec<u8>,
+    iv: AeadIV,
+) -> Result<EncryptedEnvelope> {
+    let secret = ecdh::agree(sender_ecdh_key, &recipient_ecdh_pubkey.0)
+        .map_err(|e| anyhow!("failed to agree the envelope key: {e:?}"))?;
+    let mut envelope = EncryptedEnvelope {
+        recipient,
+        topic,
+        ecdh_pubkey: EcdhPublicKey(sender_ecdh_key.public()),
+        encrypted_payload: Vec::new(),
+        iv,
+    };
+    aead::encrypt_with_aad(&iv, &secret, &envelope.associated_data(origin), &mut payload)
+        .map_err(|e| anyhow!("failed to encrypt the envelope: {e:?}"))?;
+    envelope.encrypted_payload = payload;
+    Ok(envelope)
+}
+
+/// Open an envelope `origin` pushed, failing if it was sealed for another sender.
+fn open_envelope(my_ecdh_key: &EcdhKey, origin: &MessageOrigin, envelope: &EncryptedEnvelope) -> Result<Vec<u8>> {
+    let secret = ecdh::agree(my_ecdh_key, &envelope.ecdh_pubkey.0)
+        .map_err(|e| anyhow!("failed to agree the envelope key: {e:?}"))?;
+    let mut payload = envelope.encrypted_payload.clone();
+    let len = aead::decrypt_with_aad(&envelope.iv, &secret, &envelope.associated_data(origin), &mut payload)
+        .map_err(|e| anyhow!("failed to decrypt the envelope from {origin}: {e:?}"))?
+        .len();
+    payload.truncate(len);
+    Ok(payload)
+}
+
 impl<P: pal::Platform> System<P> {
     pub fn on_restored(&mut self, _safe_mode_level: u8) -> Result<()> {
         if self.keyfairy.is_some() {
@@ -396,3 +488,43 @@ impl fmt::Display for Error {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use sp_core::H256;
+
+    fn ecdh_key(seed: u8) -> EcdhKey {
+        EcdhKey::create(&[seed; 32]).unwrap()
+    }
+
+    #[test]
+    fn test_seal_open_envelope() {
+        let sender_key = ecdh_key(1);
+        let worker_key = ecdh_key(2);
+        let alice = MessageOrigin::AccountId(H256::repeat_byte(0xa1));
+        let mallory = MessageOrigin::AccountId(H256::repeat_byte(0x66));
+        let payload = b"file key".to_vec();
+
+        let envelope = seal_envelope(
+            &sender_key,
+            &alice,
+            EnvelopeRecipient::Worker(sr25519::Public([3; 32])),
+            &EcdhPublicKey(worker_key.public()),
+            b"cess/filebank/key".to_vec(),
+            payload.clone(),
+            [7; 12],
+        )
+        .unwrap();
+        assert_ne!(envelope.encrypted_payload, payload);
+        assert_eq!(open_envelope(&worker_key, &alice, &envelope).unwrap(), payload);
+
+        // The same envelope pushed again from another account does not open.
+        assert!(open_envelope(&worker_key, &mallory, &envelope).is_err());
+        // Nor does it open redirected to another topic.
+        let redirected = EncryptedEnvelope { topic: b"cess/other".to_vec(), ..envelope.clone() };
+        assert!(open_envelope(&worker_key, &alice, &redirected).is_err());
+        // Nor for another worker.
+        assert!(open_envelope(&ecdh_key(4), &alice, &envelope).is_err());
+    }
+}
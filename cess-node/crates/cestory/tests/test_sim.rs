@@ -0,0 +1,52 @@
+use ces_mq::{BindTopic, Message, MessageOrigin};
+use ces_types::{messaging::WorkerEvent, AttestationReport};
+use cestory::sim::{MockChain, SimCeseal, BLOCK_TIME_MS};
+use cestory_api::crpc::{ceseal_api_server::CesealApi, GetRuntimeInfoRequest};
+use cestory_pal::SimulatedPlatform;
+use parity_scale_codec::{Decode, Encode};
+use tonic::Request;
+
+struct CleanableDir(std::path::PathBuf);
+impl Drop for CleanableDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[tokio::test]
+async fn simulated_ceseal_follows_mock_chain() {
+    let data_dir =
+        CleanableDir(std::env::temp_dir().join(format!("ceseal-sim-{}", hex::encode(rand::random::<[u8; 8]>()))));
+    let platform = SimulatedPlatform::new(b"sim-machine".to_vec(), 4);
+    let mut ceseal = SimCeseal::new(platform, &data_dir.0, MockChain::new(vec![])).unwrap();
+
+    let resp = ceseal.init_runtime(None).await.unwrap();
+    let runtime_info = resp.decode_runtime_info().unwrap();
+    assert_eq!(runtime_info.machine_id, b"sim-machine".to_vec());
+    assert_eq!(runtime_info.features[0], 4);
+
+    assert_eq!(ceseal.produce_blocks(3).await.unwrap(), 3);
+
+    let pubkey = resp.decode_public_key().unwrap();
+    ceseal.chain.push_message(Message::new(
+        MessageOrigin::Pallet(b"TeeWorker".to_vec()),
+        WorkerEvent::topic(),
+        WorkerEvent::new_worker(pubkey).encode(),
+    ));
+    assert_eq!(ceseal.produce_blocks(1).await.unwrap(), 4);
+
+    let info = ceseal.service.get_info(Request::new(())).await.unwrap().into_inner();
+    assert_eq!(info.blocknum, 5);
+    assert_eq!(info.current_block_time, 4 * BLOCK_TIME_MS);
+    assert!(info.system.unwrap().registered);
+
+    // the simulated platform attests without a report
+    let runtime_info = ceseal
+        .service
+        .get_runtime_info(Request::new(GetRuntimeInfoRequest::new(false, None)))
+        .await
+        .unwrap()
+        .into_inner();
+    let attestation = runtime_info.attestation.unwrap();
+    assert_eq!(Option::<AttestationReport>::decode(&mut &attestation.encoded_report[..]).unwrap(), None);
+}
@@ -8,6 +8,8 @@ use codec::{MaxEncodedLen, Decode, Encode};
 use scale_info::TypeInfo;
 use sp_core::RuntimeDebug;
 use sp_std::prelude::Box;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
 #[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo, PartialOrd, Ord)]
 pub struct Hash(pub [u8; 64]);
@@ -149,3 +151,36 @@ pub enum IpAddress {
 	IPV6([u16; 8], u16),
 }
 
+
+/// What an account may do with a bucket, or with a file through the buckets holding it.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, Copy, Default, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct BucketPermissions {
+	/// May change the bucket and its access policy.
+	pub manage: bool,
+	/// May upload files into the bucket and delete them.
+	pub write: bool,
+	/// May read the files of the bucket.
+	pub read: bool,
+}
+
+/// What a storage miner may claim, still vests, and owes.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct MinerEconomics<Balance, BlockNumber> {
+	/// Rewards the miner may receive now.
+	pub claimable: Balance,
+	/// Rewards still vesting.
+	pub vesting_locked: Balance,
+	/// The block the vesting rewards are fully released at.
+	pub vesting_end: BlockNumber,
+	/// The collateral of the miner.
+	pub collaterals: Balance,
+	/// The collateral its declaration space requires.
+	pub required_collateral: Balance,
+	/// Punishments its collateral could not cover.
+	pub debt: Balance,
+	/// Whether the miner is locked, preparing to exit.
+	pub locked: bool,
+	/// The block the miner exits at if it prepares to now, or is locked until. `None` unless positive or locked.
+	pub exit_block: Option<BlockNumber>,
+}
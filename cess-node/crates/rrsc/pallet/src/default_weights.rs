@@ -28,6 +28,10 @@ impl crate::WeightInfo for () {
 		DbWeight::get().writes(1)
 	}
 
+	fn set_score_weights() -> Weight {
+		DbWeight::get().writes(1)
+	}
+
 	fn report_equivocation(validator_count: u32, max_nominators_per_validator: u32) -> Weight {
 		// we take the validator set count from the membership proof to
 		// calculate the weight but we set a floor of 100 validators.
@@ -34,7 +34,7 @@ use cessp_consensus_rrsc::{
 	digests::{NextConfigDescriptor, NextEpochDescriptor, PreDigest},
 	AllowedSlots, RRSCAuthorityWeight, RRSCEpochConfiguration, ConsensusLog, Epoch,
 	EquivocationProof, Randomness as RRSCRandomness, Slot, RRSC_ENGINE_ID, RANDOMNESS_LENGTH,
-	RANDOMNESS_VRF_CONTEXT,
+	RANDOMNESS_VRF_CONTEXT, VrfScoreWeights,
 };
 use sp_core::crypto::Wraps;
 use sp_runtime::{
@@ -74,6 +74,7 @@ pub use pallet::*;
 
 pub trait WeightInfo {
 	fn plan_config_change() -> Weight;
+	fn set_score_weights() -> Weight;
 	fn report_equivocation(validator_count: u32, max_nominators_per_validator: u32) -> Weight;
 }
 
@@ -183,6 +184,8 @@ pub mod pallet {
 		DuplicateOffenceReport,
 		/// Submitted configuration is invalid.
 		InvalidConfiguration,
+		/// Submitted score weights do not add up to 100%.
+		InvalidScoreWeights,
 	}
 
 	/// Current epoch index.
@@ -321,6 +324,11 @@ pub mod pallet {
 	pub(super) type SkippedEpochs<T> =
 		StorageValue<_, BoundedVec<(u64, SessionIndex), ConstU32<100>>, ValueQuery>;
 
+	/// The weights the VRF solver applies to the credit, stake and random scores of validator
+	/// candidates.
+	#[pallet::storage]
+	pub type ScoreWeights<T> = StorageValue<_, VrfScoreWeights, ValueQuery>;
+
 	#[derive(frame_support::DefaultNoBound)]
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
@@ -477,6 +485,18 @@ pub mod pallet {
 			PendingEpochConfigChange::<T>::put(config);
 			Ok(())
 		}
+
+		/// Set the weights the VRF solver applies to the credit, stake and random scores of
+		/// validator candidates. The new weights take effect at the next election and must add
+		/// up to 100%.
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_score_weights())]
+		pub fn set_score_weights(origin: OriginFor<T>, weights: VrfScoreWeights) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(weights.is_valid(), Error::<T>::InvalidScoreWeights);
+			ScoreWeights::<T>::put(weights);
+			Ok(())
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -340,6 +340,37 @@ fn only_root_can_enact_config_change() {
 	});
 }
 
+#[test]
+fn only_root_can_set_valid_score_weights() {
+	use sp_runtime::{DispatchError, Percent};
+
+	new_test_ext(1).execute_with(|| {
+		assert_eq!(ScoreWeights::<Test>::get(), VrfScoreWeights::default());
+
+		let weights = VrfScoreWeights {
+			credit: Percent::from_percent(40),
+			stake: Percent::from_percent(40),
+			random: Percent::from_percent(20),
+		};
+
+		assert_noop!(
+			RRSC::set_score_weights(RuntimeOrigin::signed(1), weights),
+			DispatchError::BadOrigin
+		);
+
+		assert_noop!(
+			RRSC::set_score_weights(
+				RuntimeOrigin::root(),
+				VrfScoreWeights { random: Percent::from_percent(30), ..weights }
+			),
+			Error::<Test>::InvalidScoreWeights
+		);
+
+		assert_ok!(RRSC::set_score_weights(RuntimeOrigin::root(), weights));
+		assert_eq!(ScoreWeights::<Test>::get(), weights);
+	});
+}
+
 #[test]
 fn can_fetch_current_and_next_epoch_data() {
 	new_test_ext(5).execute_with(|| {
@@ -1,5 +1,5 @@
 use super::{Config, EpochIndex, ParentBlockRandomness};
-use cessp_consensus_rrsc::traits::ValidatorCredits;
+use cessp_consensus_rrsc::{traits::ValidatorCredits, CandidateScore, VrfScoreWeights};
 use codec::{alloc::string::ToString, Decode};
 use frame_election_provider_support::{Assignment, NposSolver, WeightInfo as NposWeightInfo};
 use frame_support::{traits::Randomness, weights::Weight};
@@ -9,6 +9,11 @@ use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 pub trait VrfSloverConfig {
 	/// A target whose vote weight is less than `min_electable_weight` will never be elected.
 	fn min_electable_weight() -> VoteWeight;
+
+	/// The weights applied to the credit, stake and random scores of a candidate.
+	fn score_weights() -> VrfScoreWeights {
+		VrfScoreWeights::default()
+	}
 }
 
 /// A wrapper for elect by vrf that implements [`NposSolver`].
@@ -32,8 +37,67 @@ impl<
 		targets: Vec<Self::AccountId>,
 		voters: Vec<(Self::AccountId, VoteWeight, impl IntoIterator<Item = Self::AccountId>)>,
 	) -> Result<ElectionResult<Self::AccountId, Self::Accuracy>, Self::Error> {
-		let to_elect = winners;
+		let (scores, assignments) = Self::score_candidates(winners, targets, voters);
+
+		let winners = scores
+			.into_iter()
+			.filter(|score| score.elected)
+			.map(|score| (score.account, score.backed_stake))
+			.collect::<Vec<_>>();
+		let winner_accounts = winners.clone().into_iter().map(|w| w.0).collect::<Vec<AccountId>>();
+
+		let assignments = assignments
+			.into_iter()
+			.filter_map(|assignment| {
+				let mut distribution: Vec<(AccountId, Accuracy)> = Vec::new();
+				for d in assignment.distribution {
+					if winner_accounts.contains(&d.0) {
+						distribution.push(d);
+					} // else {} would be wrong votes. We don't really care about it.
+				}
+				if distribution.is_empty() {
+					None
+				} else {
+					Some(Assignment { who: assignment.who, distribution })
+				}
+			})
+			.collect::<Vec<_>>();
+
+		log::debug!(target: "rrsc::vrf_solver", "[solve] winners: {:#?}", winners);
+		log::debug!(target: "rrsc::vrf_solver", "[solve] assignments: {:#?}", assignments);
+		Ok(ElectionResult { winners, assignments })
+	}
+
+	fn weight<W: NposWeightInfo>(voters: u32, targets: u32, vote_degree: u32) -> Weight {
+		W::phragmen(voters, targets, vote_degree)
+	}
+}
+
+impl<
+		AccountId: IdentifierT,
+		Accuracy: PerThing128,
+		T: Config,
+		Credits: ValidatorCredits<AccountId>,
+		SloverConfig: VrfSloverConfig,
+	> VrfSolver<AccountId, Accuracy, T, Credits, SloverConfig>
+{
+	/// Runs the scoring part of [`NposSolver::solve`] without building an election result,
+	/// returning every electable candidate ordered by rank.
+	pub fn dry_run(
+		to_elect: usize,
+		targets: Vec<AccountId>,
+		voters: Vec<(AccountId, VoteWeight, impl IntoIterator<Item = AccountId>)>,
+	) -> Vec<CandidateScore<AccountId>> {
+		Self::score_candidates(to_elect, targets, voters).0
+	}
 
+	/// Scores and ranks every electable candidate, marking the first `to_elect` of them as
+	/// elected. Also returns the cleaned-up voter assignments.
+	fn score_candidates(
+		to_elect: usize,
+		targets: Vec<AccountId>,
+		voters: Vec<(AccountId, VoteWeight, impl IntoIterator<Item = AccountId>)>,
+	) -> (Vec<CandidateScore<AccountId>>, Vec<Assignment<AccountId, Accuracy>>) {
 		let ElectionResult { winners, assignments } = Self::setup_inputs(targets, voters);
 		// max_stake is used as a benchmark value of 100 stake_score
 		let mut max_stake: ExtendedBalance = SloverConfig::min_electable_weight().into();
@@ -45,6 +109,7 @@ impl<
 
 		let credits = Credits::credits(EpochIndex::<T>::get());
 		let full_credit = Credits::full_credit();
+		let weights = SloverConfig::score_weights();
 
 		let mut account_scores = winners
 			.into_iter()
@@ -60,12 +125,8 @@ impl<
 				// random_score
 				let random_number = Self::random_number("authorities", &account_index);
 				let random_score = random_number % full_credit;
-				// final_score = `credit_score` * 50% + `stake_score` * 30% + `random_score` * 20%
-				let final_score = credit_score
-					.saturating_mul(5)
-					.saturating_add(stake_score.saturating_mul(3))
-					.saturating_add(random_score.saturating_mul(2))
-					.saturating_div(10);
+				// final_score = `credit_score` * credit + `stake_score` * stake + `random_score` * random
+				let final_score = weights.final_score(credit_score, stake_score, random_score);
 
 				log::debug!(
 					target: "rrsc::vrf_solver",
@@ -76,55 +137,30 @@ impl<
 					random_score,
 					final_score,
 				);
-				(account_id, backed_stake, final_score)
-			})
-			.collect::<Vec<(AccountId, ExtendedBalance, u32)>>();
-
-		account_scores.sort_by_key(|e| e.2);
-		account_scores.reverse();
-
-		let winners = account_scores
-			.into_iter()
-			.take(to_elect)
-			.map(|e| (e.0, e.1))
-			.collect::<Vec<_>>();
-		let winner_accounts = winners.clone().into_iter().map(|w| w.0).collect::<Vec<AccountId>>();
-
-		let assignments = assignments
-			.into_iter()
-			.filter_map(|assignment| {
-				let mut distribution: Vec<(AccountId, Accuracy)> = Vec::new();
-				for d in assignment.distribution {
-					if winner_accounts.contains(&d.0) {
-						distribution.push(d);
-					} // else {} would be wrong votes. We don't really care about it.
-				}
-				if distribution.is_empty() {
-					None
-				} else {
-					Some(Assignment { who: assignment.who, distribution })
+				CandidateScore {
+					account: account_id,
+					backed_stake,
+					credit_score,
+					stake_score,
+					random_score,
+					final_score,
+					rank: 0,
+					elected: false,
 				}
 			})
 			.collect::<Vec<_>>();
 
-		log::debug!(target: "rrsc::vrf_solver", "[solve] winners: {:#?}", winners);
-		log::debug!(target: "rrsc::vrf_solver", "[solve] assignments: {:#?}", assignments);
-		Ok(ElectionResult { winners, assignments })
-	}
+		account_scores.sort_by_key(|e| e.final_score);
+		account_scores.reverse();
 
-	fn weight<W: NposWeightInfo>(voters: u32, targets: u32, vote_degree: u32) -> Weight {
-		W::phragmen(voters, targets, vote_degree)
+		account_scores.iter_mut().enumerate().for_each(|(index, score)| {
+			score.rank = index.saturating_add(1) as u32;
+			score.elected = index < to_elect;
+		});
+
+		(account_scores, assignments)
 	}
-}
 
-impl<
-		AccountId: IdentifierT,
-		Accuracy: PerThing128,
-		T: Config,
-		Credits: ValidatorCredits<AccountId>,
-		SloverConfig: VrfSloverConfig,
-	> VrfSolver<AccountId, Accuracy, T, Credits, SloverConfig>
-{
 	pub fn random_number(context: &str, authority_index: &usize) -> u32 {
 		let mut b_context = context.to_string();
 		b_context.push_str(authority_index.to_string().as_str());
@@ -28,7 +28,7 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use sp_runtime::{traits::Header, ConsensusEngineId, RuntimeDebug};
+use sp_runtime::{traits::Header, ConsensusEngineId, PerThing, Percent, RuntimeDebug};
 use sp_std::vec::Vec;
 use sp_core::crypto::KeyTypeId;
 use crate::digests::{NextConfigDescriptor, NextEpochDescriptor};
@@ -257,6 +257,70 @@ pub struct RRSCEpochConfiguration {
 	pub allowed_slots: AllowedSlots,
 }
 
+/// The weights applied to each component score when the VRF solver ranks validator candidates.
+///
+/// The three weights must add up to exactly 100%.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VrfScoreWeights {
+	/// Weight of the credit score reported by the scheduler credit pallet.
+	pub credit: Percent,
+	/// Weight of the score derived from the candidate's backed stake.
+	pub stake: Percent,
+	/// Weight of the score derived from on-chain randomness.
+	pub random: Percent,
+}
+
+impl VrfScoreWeights {
+	/// Whether the weights add up to exactly 100%.
+	pub fn is_valid(&self) -> bool {
+		self.credit.deconstruct() as u16 + self.stake.deconstruct() as u16 + self.random.deconstruct() as u16 ==
+			Percent::ACCURACY as u16
+	}
+
+	/// Combines the component scores into the final score of a candidate.
+	pub fn final_score(&self, credit_score: u32, stake_score: u32, random_score: u32) -> u32 {
+		credit_score
+			.saturating_mul(self.credit.deconstruct() as u32)
+			.saturating_add(stake_score.saturating_mul(self.stake.deconstruct() as u32))
+			.saturating_add(random_score.saturating_mul(self.random.deconstruct() as u32))
+			.saturating_div(Percent::ACCURACY as u32)
+	}
+}
+
+impl Default for VrfScoreWeights {
+	fn default() -> Self {
+		VrfScoreWeights {
+			credit: Percent::from_percent(50),
+			stake: Percent::from_percent(30),
+			random: Percent::from_percent(20),
+		}
+	}
+}
+
+/// The score the VRF solver gives to a single validator candidate, as reported by an election
+/// dry run.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CandidateScore<AccountId> {
+	/// The candidate's stash account.
+	pub account: AccountId,
+	/// The stake backing the candidate, expressed as a vote weight.
+	pub backed_stake: u128,
+	/// The credit score reported by the scheduler credit pallet.
+	pub credit_score: u32,
+	/// The score derived from the candidate's backed stake.
+	pub stake_score: u32,
+	/// The score derived from on-chain randomness.
+	pub random_score: u32,
+	/// The weighted combination of the component scores.
+	pub final_score: u32,
+	/// The candidate's position in the ranking, starting at 1.
+	pub rank: u32,
+	/// Whether the candidate falls within the number of validators to elect.
+	pub elected: bool,
+}
+
 /// Verifies the equivocation proof by making sure that: both headers have
 /// different hashes, are targetting the same slot, and have valid signatures by
 /// the same authority.
@@ -425,4 +489,11 @@ sp_api::decl_runtime_apis! {
 			key_owner_proof: OpaqueKeyOwnershipProof,
 		) -> Option<()>;
 	}
+
+	/// API to inspect how the VRF solver ranks validator candidates.
+	pub trait RRSCElectionApi<AccountId> where AccountId: codec::Codec {
+		/// Runs the VRF solver against the current staking data and credit scores without
+		/// enacting anything, returning every electable candidate ordered by rank.
+		fn election_dry_run() -> Vec<CandidateScore<AccountId>>;
+	}
 }
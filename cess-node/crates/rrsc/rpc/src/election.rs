@@ -0,0 +1,61 @@
+//! RPC api for inspecting how the VRF solver ranks validator candidates.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use cessp_consensus_rrsc::{CandidateScore, RRSCElectionApi as RRSCElectionRuntimeApi};
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::{ErrorObject, ErrorObjectOwned},
+};
+use serde::Serialize;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+use crate::RRSC_ERROR;
+
+/// Provides rpc methods for inspecting validator elections.
+#[rpc(client, server)]
+pub trait RRSCElectionApi<BlockHash, AccountId> {
+	/// Runs the VRF solver against the current staking data and credit scores without enacting
+	/// anything, returning every candidate's component scores and rank.
+	#[method(name = "rrsc_electionDryRun")]
+	fn election_dry_run(&self, at: Option<BlockHash>) -> RpcResult<Vec<CandidateScore<AccountId>>>;
+}
+
+/// Provides RPC methods for inspecting validator elections.
+pub struct RRSCElection<C, B> {
+	/// shared reference to the client.
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> RRSCElection<C, B> {
+	/// Creates a new instance of the RRSC election Rpc handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId> RRSCElectionApiServer<<Block as BlockT>::Hash, AccountId> for RRSCElection<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: RRSCElectionRuntimeApi<Block, AccountId>,
+	AccountId: Codec + Serialize + Send + Sync + 'static,
+{
+	fn election_dry_run(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<CandidateScore<AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		self.client.runtime_api().election_dry_run(at).map_err(runtime_error)
+	}
+}
+
+fn runtime_error(e: sp_api::ApiError) -> ErrorObjectOwned {
+	ErrorObject::owned(RRSC_ERROR + 5, "Unable to run the election dry run.", Some(e.to_string()))
+}
@@ -18,6 +18,10 @@
 
 //! RPC api for rrsc.
 
+mod election;
+
+pub use election::{RRSCElection, RRSCElectionApiServer};
+
 use std::{collections::HashMap, sync::Arc};
 
 use futures::TryFutureExt;
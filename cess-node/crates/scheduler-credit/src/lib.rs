@@ -2,7 +2,130 @@
 # Some scheduler credit primitives
 */
 #![cfg_attr(not(feature = "std"), no_std)]
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::dispatch::DispatchResult;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{Percent, RuntimeDebug};
+
+/// The kinds of work a scheduler is credited for.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum CreditActivity {
+	/// Calculating tags for service fragments.
+	Tag,
+	/// Certifying idle space.
+	Cert,
+	/// Verifying idle space proofs.
+	IdleVerify,
+	/// Verifying service space proofs.
+	ServiceVerify,
+	/// Certifying idle space replaced by service space.
+	Replace,
+}
+
+impl CreditActivity {
+	/// Every kind of credited work.
+	pub const ALL: [CreditActivity; 5] = [
+		CreditActivity::Tag,
+		CreditActivity::Cert,
+		CreditActivity::IdleVerify,
+		CreditActivity::ServiceVerify,
+		CreditActivity::Replace,
+	];
+}
+
+/// The points a scheduler earned for each kind of work.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ActivityPoints {
+	pub tag: u64,
+	pub cert: u64,
+	pub idle_verify: u64,
+	pub service_verify: u64,
+	pub replace: u64,
+}
+
+impl ActivityPoints {
+	pub fn get(&self, activity: CreditActivity) -> u64 {
+		match activity {
+			CreditActivity::Tag => self.tag,
+			CreditActivity::Cert => self.cert,
+			CreditActivity::IdleVerify => self.idle_verify,
+			CreditActivity::ServiceVerify => self.service_verify,
+			CreditActivity::Replace => self.replace,
+		}
+	}
+
+	pub fn get_mut(&mut self, activity: CreditActivity) -> &mut u64 {
+		match activity {
+			CreditActivity::Tag => &mut self.tag,
+			CreditActivity::Cert => &mut self.cert,
+			CreditActivity::IdleVerify => &mut self.idle_verify,
+			CreditActivity::ServiceVerify => &mut self.service_verify,
+			CreditActivity::Replace => &mut self.replace,
+		}
+	}
+
+	/// Adds the points of `other` to each activity of `self`.
+	pub fn saturating_accrue(&mut self, other: &ActivityPoints) {
+		for activity in CreditActivity::ALL {
+			let points = self.get_mut(activity);
+			*points = points.saturating_add(other.get(activity));
+		}
+	}
+}
+
+/// The weight of each kind of work in a scheduler's credit value.
+///
+/// Weights are relative: activities nobody performed in a period are left out and the rest
+/// are scaled up accordingly.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ActivityWeights {
+	pub tag: Percent,
+	pub cert: Percent,
+	pub idle_verify: Percent,
+	pub service_verify: Percent,
+	pub replace: Percent,
+}
+
+impl ActivityWeights {
+	pub fn get(&self, activity: CreditActivity) -> Percent {
+		match activity {
+			CreditActivity::Tag => self.tag,
+			CreditActivity::Cert => self.cert,
+			CreditActivity::IdleVerify => self.idle_verify,
+			CreditActivity::ServiceVerify => self.service_verify,
+			CreditActivity::Replace => self.replace,
+		}
+	}
+}
+
+/// What a scheduler did during one credit period.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PeriodCredit {
+	/// The index of the period.
+	pub period: u32,
+	/// The points earned for each kind of work.
+	pub points: ActivityPoints,
+	/// The number of times the scheduler was punished.
+	pub punishment_count: u32,
+	/// The credit value figured at the end of the period, `None` while it is still running.
+	pub credit_value: Option<u32>,
+}
+
+/// The credit accounting of a scheduler for the current and the previous period.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SchedulerCreditBreakdown {
+	pub current: PeriodCredit,
+	/// `None` during the first period.
+	pub previous: Option<PeriodCredit>,
+}
+
 /// API necessary for Scheduler record ops about credit.
 pub trait SchedulerCreditCounter<SchedulerCtrlAccountId> {
 	fn increase_point_for_tag(scheduler_id: &SchedulerCtrlAccountId, space: u128) -> DispatchResult;
@@ -15,8 +138,8 @@ pub trait SchedulerCreditCounter<SchedulerCtrlAccountId> {
 
 	fn increase_point_for_replace(scheduler_id: &SchedulerCtrlAccountId, space: u128) -> DispatchResult;
 
-  /// Records the number of file bytes processed by the scheduler
-	fn record_proceed_block_size(scheduler_id: &SchedulerCtrlAccountId, block_size: u64) -> DispatchResult;
+  /// Records the points the scheduler earned for one kind of work
+	fn record_activity(scheduler_id: &SchedulerCtrlAccountId, activity: CreditActivity, points: u64) -> DispatchResult;
   
   /// Record the number of times the scheduler has been punished
 	fn record_punishment(scheduler_id: &SchedulerCtrlAccountId) -> DispatchResult;
@@ -0,0 +1,313 @@
+//! A file backed cache of DCAP quote collaterals.
+//!
+//! Collaterals are kept per FMSPC together with the window in which all of their parts (PCK CRL, root CA CRL,
+//! TCB info and QE identity) are valid, so that quotes can later be verified without reaching a PCCS.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use anyhow::{anyhow, Context, Result};
+use der::Decode;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use x509_cert::crl::CertificateList;
+
+use super::tcb_info::TcbInfo;
+use crate::types::SgxV30QuoteCollateral;
+
+/// The collateral of one FMSPC and the window it can be used to verify quotes in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedCollateral {
+    /// Upper case hex encoded FMSPC.
+    pub fmspc: String,
+    /// Unix timestamp of when the collateral was fetched from the PCCS.
+    pub fetched_at: i64,
+    /// The latest issue date of the collateral parts.
+    pub not_before: i64,
+    /// The earliest next update of the collateral parts.
+    pub not_after: i64,
+    pub collateral: SgxV30QuoteCollateral,
+}
+
+impl CachedCollateral {
+    pub fn new(collateral: SgxV30QuoteCollateral, fetched_at: i64) -> Result<Self> {
+        let tcb_info = pink_json::from_str::<TcbInfo>(&collateral.tcb_info)
+            .map_err(|_| anyhow!("TCB info should be a JSON"))?;
+        let qe_identity = pink_json::from_str::<QeIdentityDates>(&collateral.qe_identity)
+            .map_err(|_| anyhow!("QE identity should be a JSON"))?;
+
+        let mut not_before =
+            parse_rfc3339(&tcb_info.issue_date)?.max(parse_rfc3339(&qe_identity.issue_date)?);
+        let mut not_after =
+            parse_rfc3339(&tcb_info.next_update)?.min(parse_rfc3339(&qe_identity.next_update)?);
+        // The CRLs are not checked by `verify`, their window is only taken into account when they can be parsed.
+        for crl in [&collateral.pck_crl, &collateral.root_ca_crl] {
+            if let Some((this_update, next_update)) = crl_window(crl) {
+                not_before = not_before.max(this_update);
+                if let Some(next_update) = next_update {
+                    not_after = not_after.min(next_update);
+                }
+            }
+        }
+
+        Ok(Self {
+            fmspc: tcb_info.fmspc.to_uppercase(),
+            fetched_at,
+            not_before,
+            not_after,
+            collateral,
+        })
+    }
+
+    pub fn is_valid_at(&self, now: u64) -> bool {
+        let now = now as i64;
+        self.not_before <= now && now < self.not_after
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QeIdentityDates {
+    issue_date: String,
+    next_update: String,
+}
+
+/// The on-disk form of a [`CachedCollateral`], with the binary parts hex encoded.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredCollateral {
+    fmspc: String,
+    fetched_at: i64,
+    not_before: i64,
+    not_after: i64,
+    pck_crl_issuer_chain: String,
+    root_ca_crl: String,
+    pck_crl: String,
+    tcb_info_issuer_chain: String,
+    tcb_info: String,
+    tcb_info_signature: String,
+    qe_identity_issuer_chain: String,
+    qe_identity: String,
+    qe_identity_signature: String,
+}
+
+impl From<&CachedCollateral> for StoredCollateral {
+    fn from(cached: &CachedCollateral) -> Self {
+        let collateral = &cached.collateral;
+        Self {
+            fmspc: cached.fmspc.clone(),
+            fetched_at: cached.fetched_at,
+            not_before: cached.not_before,
+            not_after: cached.not_after,
+            pck_crl_issuer_chain: collateral.pck_crl_issuer_chain.clone(),
+            root_ca_crl: collateral.root_ca_crl.clone(),
+            pck_crl: collateral.pck_crl.clone(),
+            tcb_info_issuer_chain: collateral.tcb_info_issuer_chain.clone(),
+            tcb_info: collateral.tcb_info.clone(),
+            tcb_info_signature: hex::encode(&collateral.tcb_info_signature),
+            qe_identity_issuer_chain: collateral.qe_identity_issuer_chain.clone(),
+            qe_identity: collateral.qe_identity.clone(),
+            qe_identity_signature: hex::encode(&collateral.qe_identity_signature),
+        }
+    }
+}
+
+impl TryFrom<StoredCollateral> for CachedCollateral {
+    type Error = anyhow::Error;
+
+    fn try_from(stored: StoredCollateral) -> Result<Self> {
+        Ok(Self {
+            fmspc: stored.fmspc,
+            fetched_at: stored.fetched_at,
+            not_before: stored.not_before,
+            not_after: stored.not_after,
+            collateral: SgxV30QuoteCollateral {
+                pck_crl_issuer_chain: stored.pck_crl_issuer_chain,
+                root_ca_crl: stored.root_ca_crl,
+                pck_crl: stored.pck_crl,
+                tcb_info_issuer_chain: stored.tcb_info_issuer_chain,
+                tcb_info: stored.tcb_info,
+                tcb_info_signature: hex::decode(stored.tcb_info_signature)
+                    .context("TCB info signature should be a hex string")?,
+                qe_identity_issuer_chain: stored.qe_identity_issuer_chain,
+                qe_identity: stored.qe_identity,
+                qe_identity_signature: hex::decode(stored.qe_identity_signature)
+                    .context("QE identity signature should be a hex string")?,
+            },
+        })
+    }
+}
+
+/// Collaterals stored as one JSON file per FMSPC in a directory.
+#[derive(Debug, Clone)]
+pub struct CollateralStore {
+    dir: PathBuf,
+}
+
+impl CollateralStore {
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("create collateral store {}", dir.display()))?;
+        Ok(Self { dir })
+    }
+
+    fn file_path(&self, fmspc: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", fmspc.to_uppercase()))
+    }
+
+    /// Stores the collateral, replacing the one cached for the same FMSPC.
+    pub fn insert(
+        &self,
+        collateral: SgxV30QuoteCollateral,
+        fetched_at: i64,
+    ) -> Result<CachedCollateral> {
+        let cached = CachedCollateral::new(collateral, fetched_at)?;
+        let json = serde_json::to_string_pretty(&StoredCollateral::from(&cached))?;
+        let path = self.file_path(&cached.fmspc);
+        fs::write(&path, json).with_context(|| format!("write collateral {}", path.display()))?;
+        Ok(cached)
+    }
+
+    /// The collateral cached for `fmspc`, regardless of its validity.
+    pub fn get(&self, fmspc: &[u8]) -> Result<Option<CachedCollateral>> {
+        self.load(&self.file_path(&hex::encode_upper(fmspc)))
+    }
+
+    /// The collateral cached for `fmspc` if it is valid at `now`.
+    pub fn get_valid(&self, fmspc: &[u8], now: u64) -> Result<Option<CachedCollateral>> {
+        Ok(self.get(fmspc)?.filter(|cached| cached.is_valid_at(now)))
+    }
+
+    pub fn list(&self) -> Result<Vec<CachedCollateral>> {
+        let mut collaterals = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                collaterals.extend(self.load(&path)?);
+            }
+        }
+        collaterals.sort_by(|a, b| a.fmspc.cmp(&b.fmspc));
+        Ok(collaterals)
+    }
+
+    /// Removes the collaterals which are outdated at `now`, returning their FMSPCs.
+    pub fn remove_expired(&self, now: u64) -> Result<Vec<String>> {
+        let mut removed = Vec::new();
+        for cached in self.list()? {
+            if now as i64 >= cached.not_after {
+                fs::remove_file(self.file_path(&cached.fmspc))?;
+                removed.push(cached.fmspc);
+            }
+        }
+        Ok(removed)
+    }
+
+    fn load(&self, path: &Path) -> Result<Option<CachedCollateral>> {
+        let json = match fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("read collateral {}", path.display())),
+        };
+        let stored: StoredCollateral = serde_json::from_str(&json)
+            .with_context(|| format!("decode collateral {}", path.display()))?;
+        Ok(Some(stored.try_into()?))
+    }
+}
+
+fn parse_rfc3339(date: &str) -> Result<i64> {
+    Ok(chrono::DateTime::parse_from_rfc3339(date)
+        .map_err(|_| anyhow!("invalid date {date}"))?
+        .timestamp())
+}
+
+/// This and next update of a CRL given in PEM, hex encoded DER or raw DER.
+fn crl_window(crl: &str) -> Option<(i64, Option<i64>)> {
+    let der = if crl.trim_start().starts_with("-----BEGIN") {
+        pem::parse(crl).ok()?.into_contents()
+    } else {
+        hex::decode(crl.trim()).unwrap_or_else(|_| crl.as_bytes().to_vec())
+    };
+    let tbs = CertificateList::from_der(&der).ok()?.tbs_cert_list;
+    let this_update = tbs.this_update.to_unix_duration().as_secs() as i64;
+    let next_update = tbs
+        .next_update
+        .map(|time| time.to_unix_duration().as_secs() as i64);
+    Some((this_update, next_update))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn collateral(fmspc: &str, tcb_next_update: &str) -> SgxV30QuoteCollateral {
+        SgxV30QuoteCollateral {
+            pck_crl_issuer_chain: "pck crl chain".to_string(),
+            root_ca_crl: "not a crl".to_string(),
+            pck_crl: "not a crl".to_string(),
+            tcb_info_issuer_chain: "tcb info chain".to_string(),
+            tcb_info: format!(
+                r#"{{"id":"SGX","version":3,"issueDate":"2024-05-01T00:00:00Z","nextUpdate":"{tcb_next_update}","fmspc":"{fmspc}","pceId":"0000","tcbType":0,"tcbEvaluationDataNumber":16,"tcbLevels":[]}}"#
+            ),
+            tcb_info_signature: vec![1, 2, 3],
+            qe_identity_issuer_chain: "qe identity chain".to_string(),
+            qe_identity: r#"{"id":"QE","version":2,"issueDate":"2024-05-02T00:00:00Z","nextUpdate":"2024-06-02T00:00:00Z"}"#
+                .to_string(),
+            qe_identity_signature: vec![4, 5, 6],
+        }
+    }
+
+    fn timestamp(date: &str) -> u64 {
+        parse_rfc3339(date).unwrap() as u64
+    }
+
+    #[test]
+    fn collateral_store_round_trip() {
+        let dir = std::env::temp_dir().join(format!("collateral-store-{}", std::process::id()));
+        let store = CollateralStore::open(&dir).unwrap();
+
+        let cached = store
+            .insert(collateral("00906ed50000", "2024-05-31T00:00:00Z"), 42)
+            .unwrap();
+        assert_eq!(cached.fmspc, "00906ED50000");
+        // the window is the intersection of the TCB info and QE identity windows
+        assert_eq!(cached.not_before, timestamp("2024-05-02T00:00:00Z") as i64);
+        assert_eq!(cached.not_after, timestamp("2024-05-31T00:00:00Z") as i64);
+
+        let fmspc = hex::decode("00906ED50000").unwrap();
+        assert_eq!(store.get(&fmspc).unwrap(), Some(cached.clone()));
+        assert_eq!(
+            store
+                .get_valid(&fmspc, timestamp("2024-05-10T00:00:00Z"))
+                .unwrap(),
+            Some(cached)
+        );
+        assert_eq!(
+            store
+                .get_valid(&fmspc, timestamp("2024-05-01T12:00:00Z"))
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            store
+                .get_valid(&fmspc, timestamp("2024-05-31T00:00:00Z"))
+                .unwrap(),
+            None
+        );
+        assert_eq!(store.get(&[0u8; 6]).unwrap(), None);
+
+        store
+            .insert(collateral("00606A000000", "2024-07-01T00:00:00Z"), 43)
+            .unwrap();
+        assert_eq!(store.list().unwrap().len(), 2);
+        let removed = store
+            .remove_expired(timestamp("2024-06-01T00:00:00Z"))
+            .unwrap();
+        assert_eq!(removed, vec!["00906ED50000".to_string()]);
+        assert_eq!(store.list().unwrap().len(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
+pub mod collateral_store;
 mod constants;
 pub mod quote;
 
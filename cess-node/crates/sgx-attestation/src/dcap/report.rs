@@ -6,7 +6,7 @@ use anyhow::{anyhow, Result};
 use crate::{types::{Collateral,SgxV30QuoteCollateral,AttestationReport},gramine::create_quote_vec};
 use parity_scale_codec::Decode;
 
-use super::quote::Quote;
+use super::{collateral_store::CollateralStore, quote::Quote};
 fn get_header(resposne: &reqwest::Response, name: &str) -> Result<String> {
     let value = resposne
         .headers()
@@ -104,6 +104,25 @@ pub async fn get_collateral(
     })
 }
 
+/// Get collateral from the cache in `store` if it is still valid at `now`, otherwise fetch it from the PCCS server
+/// and cache it.
+pub async fn get_collateral_cached(
+    store: &CollateralStore,
+    pccs_url: &str,
+    quote: &[u8],
+    timeout: Duration,
+    now: u64,
+) -> Result<SgxV30QuoteCollateral> {
+    let fmspc = Quote::decode(&mut &quote[..])?
+        .fmspc()
+        .map_err(|_| anyhow!("get fmspc error"))?;
+    if let Some(cached) = store.get_valid(&fmspc, now)? {
+        return Ok(cached.collateral);
+    }
+    let collateral = get_collateral(pccs_url, quote, timeout).await?;
+    Ok(store.insert(collateral, now as i64)?.collateral)
+}
+
 pub fn create_attestation_report(
     data: &[u8],
     pccs_url: &str,
@@ -49,6 +49,11 @@ use types::*;
 mod constants;
 use constants::*;
 
+mod messages;
+pub use messages::VerificationResult;
+
+mod verify;
+
 // pub mod migrations;
 
 pub use pallet::*;
@@ -73,8 +78,8 @@ use frame_support::{
 	},
 	transactional, PalletId,
 };
-use frame_system::offchain::CreateSignedTransaction;
-use pallet_sminer::MinerControl;
+use frame_system::{offchain::CreateSignedTransaction, pallet_prelude::BlockNumberFor};
+use pallet_sminer::{MinerControl, MinerObligations};
 use pallet_storage_handler::StorageHandle;
 use pallet_tee_worker::TeeWorkerHandler;
 use scale_info::TypeInfo;
@@ -274,6 +279,8 @@ pub mod pallet {
 		TeeNoPermission,
 		/// Signature format conversion failed
 		MalformedSignature,
+		/// The message was not sent by a worker
+		InvalidSender,
 	}
 
 	#[pallet::storage]
@@ -465,99 +472,16 @@ pub mod pallet {
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			<ChallengeSnapShot<T>>::try_mutate(&sender, |challenge_info_opt| -> DispatchResult {
-				let challenge_info = challenge_info_opt.as_mut().ok_or(Error::<T>::NoChallenge)?;
-
-				let idle_prove =
-					challenge_info.prove_info.idle_prove.as_mut().ok_or(Error::<T>::UnSubmitted)?;
-
-				if tee_puk != idle_prove.tee_puk {
-					return Err(Error::<T>::NonExistentMission)?
-				}
-
-				if let Some(_) = idle_prove.verify_result {
-					return Err(Error::<T>::Submitted)?
-				}
-
-				let MinerSnapShot {
-					idle_space,
-					service_space,
-					service_bloom_filter: _,
-					space_proof_info,
-					tee_signature: _,
-				} = &challenge_info.miner_snapshot;
-
-				ensure!(
-					T::TeeWorkerHandler::can_verify(&tee_puk),
-					Error::<T>::TeeNoPermission
-				);
-				let verify_idle_info = VerifyIdleResultInfo::<T> {
-					miner: sender.clone(),
-					miner_prove: total_prove_hash.clone(),
-					front: space_proof_info.front,
-					rear: space_proof_info.rear,
-					accumulator: space_proof_info.accumulator,
-					space_challenge_param: challenge_info.challenge_element.space_param,
-					result: idle_result,
-					tee_puk: tee_puk.clone(),
-				};
-				
-				let encoding = verify_idle_info.encode();
-				let hashing = sp_io::hashing::sha2_256(&encoding);
-				let sig = 
-					sp_core::sr25519::Signature::try_from(signature.as_slice()).or(Err(Error::<T>::MalformedSignature))?;
-					
-				ensure!(
-					T::TeeWorkerHandler::verify_master_sig(&sig, hashing),
-					Error::<T>::VerifyTeeSigFailed
-				);
-
-				let now = <frame_system::Pallet<T>>::block_number();
-				T::TeeWorkerHandler::update_work_block(now, &tee_puk)?;
-
-				let idle_result = Self::check_idle_verify_param(
-					idle_result,
-					front,
-					rear,
-					&total_prove_hash,
-					&accumulator,
-					&challenge_info.miner_snapshot,
-					&idle_prove.idle_prove,
-				);
-
-				idle_prove.verify_result = Some(idle_result);
-
-				if let Some(service_prove) = &challenge_info.prove_info.service_prove {
-					if let Some(service_result) = service_prove.verify_result {
-						if idle_result && service_result {
-							T::MinerControl::record_snap_shot(
-								&sender,
-								*idle_space,
-								*service_space,
-							)?;
-						}
-					}
-				}
-
-				let count = challenge_info
-					.miner_snapshot
-					.space_proof_info
-					.rear
-					.checked_sub(challenge_info.miner_snapshot.space_proof_info.front)
-					.ok_or(Error::<T>::Overflow)?;
-				
-				let space = IDLE_SEG_SIZE.checked_mul(count as u128).ok_or(Error::<T>::Overflow)?;
-				let bond_stash = T::TeeWorkerHandler::get_stash(&tee_puk)?;
-				T::CreditCounter::increase_point_for_idle_verify(&bond_stash, space)?;
-
-				Self::deposit_event(Event::<T>::SubmitIdleVerifyResult {
-					tee: tee_puk.clone(),
-					miner: sender.clone(),
-					result: idle_result,
-				});
-
-				Ok(())
-			})
+			Self::apply_idle_verify_result(
+				sender,
+				total_prove_hash,
+				front,
+				rear,
+				accumulator,
+				idle_result,
+				Some(signature),
+				tee_puk,
+			)
 		}
 
 		/// Submit a verification result for service proofs in response to a challenge.
@@ -585,109 +509,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			<ChallengeSnapShot<T>>::try_mutate(&sender, |challenge_info_opt| -> DispatchResult {
-				let challenge_info = challenge_info_opt.as_mut().ok_or(Error::<T>::NoChallenge)?;
-
-				let service_prove = challenge_info
-					.prove_info
-					.service_prove
-					.as_mut()
-					.ok_or(Error::<T>::UnSubmitted)?;
-				if tee_puk != service_prove.tee_puk {
-					return Err(Error::<T>::NonExistentMission)?
-				}
-
-				if let Some(_) = service_prove.verify_result {
-					return Err(Error::<T>::Submitted)?
-				}
-
-				let MinerSnapShot {
-					idle_space,
-					service_space,
-					service_bloom_filter: s_service_bloom_filter,
-					space_proof_info: _,
-					tee_signature: _,
-				} = challenge_info.miner_snapshot;
-
-				ensure!(
-					T::TeeWorkerHandler::can_verify(&tee_puk),
-					Error::<T>::TeeNoPermission
-				);
-				let verify_service_info = VerifyServiceResultInfo::<T> {
-					miner: sender.clone(),
-					tee_puk: tee_puk.clone(),
-					miner_prove: service_prove.service_prove.clone(),
-					result: service_result,
-					chal: QElement {
-						random_index_list: challenge_info
-							.challenge_element
-							.service_param
-							.random_index_list
-							.clone(),
-						random_list: challenge_info
-							.challenge_element
-							.service_param
-							.random_list
-							.clone(),
-					},
-					service_bloom_filter: s_service_bloom_filter,
-				};
-
-				let encoding = verify_service_info.encode();
-				let hashing = sp_io::hashing::sha2_256(&encoding);
-				let sig = 
-					sp_core::sr25519::Signature::try_from(signature.as_slice()).or(Err(Error::<T>::MalformedSignature))?;
-
-				ensure!(
-					T::TeeWorkerHandler::verify_master_sig(&sig, hashing),
-					Error::<T>::VerifyTeeSigFailed
-				);
-
-				ensure!(
-					service_bloom_filter == s_service_bloom_filter,
-					Error::<T>::BloomFilterError,
-				);
-
-				let now = <frame_system::Pallet<T>>::block_number();
-				T::TeeWorkerHandler::update_work_block(now, &tee_puk)?;
-
-				service_prove.verify_result = Some(service_result);
-
-				if let Some(idle_prove) = &challenge_info.prove_info.idle_prove {
-					if let Some(idle_result) = idle_prove.verify_result {
-						if idle_result && service_result {
-							T::MinerControl::record_snap_shot(
-								&sender,
-								idle_space,
-								service_space,
-							)?;
-						}
-					}
-				}
-
-				if service_result {
-					<CountedServiceFailed<T>>::insert(&sender, u32::MIN);
-				} else {
-					let count = <CountedServiceFailed<T>>::get(&sender)
-						.checked_add(1)
-						.unwrap_or(SERVICE_FAULT_TOLERANT as u32);
-					if count >= SERVICE_FAULT_TOLERANT as u32 {
-						T::MinerControl::service_punish(&sender, service_space, service_space)?;
-					}
-					<CountedServiceFailed<T>>::insert(&sender, count);
-				}
-
-				let bond_stash = T::TeeWorkerHandler::get_stash(&tee_puk)?;
-				T::CreditCounter::increase_point_for_idle_verify(&bond_stash, challenge_info.miner_snapshot.service_space)?;
-
-				Self::deposit_event(Event::<T>::SubmitServiceVerifyResult {
-					tee: tee_puk.clone(),
-					miner: sender.clone(),
-					result: service_result,
-				});
-
-				Ok(())
-			})
+			Self::apply_service_verify_result(sender, service_result, Some(signature), service_bloom_filter, tee_puk)
 		}
 		// FOR TEST
 		/// Update and reset the counted clear value for a specific miner.
@@ -1338,7 +1160,7 @@ pub mod pallet {
 		/// Returns:
 		/// - A boolean flag (`idle_result`) indicating whether the idle verification parameters are
 		///   correct.
-		fn check_idle_verify_param(
+		pub(crate) fn check_idle_verify_param(
 			mut idle_result: bool,
 			front: u64,
 			rear: u64,
@@ -1367,3 +1189,10 @@ pub mod pallet {
 		}
 	}
 }
+
+impl<T: Config> MinerObligations<AccountOf<T>, BlockNumberFor<T>> for Pallet<T> {
+	// An open challenge lasts until its proofs are verified.
+	fn obligations_end(miner: &AccountOf<T>) -> Option<BlockNumberFor<T>> {
+		<ChallengeSnapShot<T>>::get(miner).map(|challenge_info| challenge_info.challenge_element.verify_slip)
+	}
+}
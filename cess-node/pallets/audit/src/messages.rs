@@ -0,0 +1,56 @@
+//! Verification results delivered through the message queue.
+//!
+//! A TEE worker may push its verification results to `VerificationResult::topic()` instead of
+//! having the miner submit them. `pallet-mq` has checked the sequence and the worker signature of
+//! such a message, so the result is applied without the master key signature the extrinsics carry.
+
+use super::*;
+use ces_types::messaging::{bind_topic, DecodedMessage, MessageOrigin};
+
+bind_topic!(VerificationResult<AccountId>, b"^cess/audit/verify_result");
+#[derive(Encode, Decode, TypeInfo, Clone, Debug)]
+pub enum VerificationResult<AccountId> {
+	/// MessageOrigin::Worker -> Pallet
+	///
+	/// The result of verifying the idle proof `miner` submitted for its challenge.
+	Idle {
+		miner: AccountId,
+		total_prove_hash: Vec<u8>,
+		front: u64,
+		rear: u64,
+		accumulator: Accumulator,
+		result: bool,
+	},
+	/// MessageOrigin::Worker -> Pallet
+	///
+	/// The result of verifying the service proof `miner` submitted for its challenge.
+	Service { miner: AccountId, result: bool, service_bloom_filter: BloomFilter },
+}
+
+impl<T: Config> Pallet<T> {
+	#[transactional]
+	pub fn on_message_received(message: DecodedMessage<VerificationResult<AccountOf<T>>>) -> DispatchResult {
+		let tee_puk = match &message.sender {
+			MessageOrigin::Worker(key) => *key,
+			_ => return Err(Error::<T>::InvalidSender.into()),
+		};
+
+		match message.payload {
+			VerificationResult::Idle { miner, total_prove_hash, front, rear, accumulator, result } => {
+				let total_prove_hash = total_prove_hash.try_into().map_err(|_| Error::<T>::BoundedVecError)?;
+				Self::apply_idle_verify_result(miner, total_prove_hash, front, rear, accumulator, result, None, tee_puk)
+			},
+			VerificationResult::Service { miner, result, service_bloom_filter } =>
+				Self::apply_service_verify_result(miner, result, None, service_bloom_filter, tee_puk),
+		}
+	}
+
+	/// The weight of handling `message`, the same as the extrinsic it stands in for.
+	pub fn message_weight(message: &DecodedMessage<VerificationResult<AccountOf<T>>>) -> Weight {
+		match message.payload {
+			VerificationResult::Idle { .. } => <T as pallet::Config>::WeightInfo::submit_verify_idle_result_reward(),
+			VerificationResult::Service { .. } =>
+				<T as pallet::Config>::WeightInfo::submit_verify_service_result_reward(),
+		}
+	}
+}
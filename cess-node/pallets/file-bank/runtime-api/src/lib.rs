@@ -0,0 +1,17 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use cp_cess_common::{BucketPermissions, Hash};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait FileBankApi<AccountId>
+	where
+		AccountId: Codec,
+	{
+		/// The permissions of `account` on the bucket `bucket_name` of `owner`.
+		fn bucket_permissions(account: AccountId, owner: AccountId, bucket_name: Vec<u8>) -> BucketPermissions;
+		/// The permissions of `account` on the file `file_hash`, through any of the buckets holding it.
+		fn file_permissions(account: AccountId, file_hash: Hash) -> BucketPermissions;
+	}
+}
@@ -663,4 +663,52 @@ benchmarks! {
 		let (_, space) = T::MinerControl::get_power(&miner2)?;
 		assert_eq!(space, FRAGMENT_SIZE);
 	}
+
+	delete_object_version {
+		log::info!("start delete_object_version");
+		initialize_file_from_scratch::<T>()?;
+		let user: AccountOf<T> = account("user1", 100, SEED);
+		let bucket_name: BoundedVec<u8, T::NameStrLimit> = "test-bucket1".as_bytes().to_vec().try_into().map_err(|_| "bucket name convert err")?;
+		let file_name: BoundedVec<u8, T::NameStrLimit> = "test-file".as_bytes().to_vec().try_into().map_err(|_| "file name convert err")?;
+		assert_eq!(FileBank::<T>::latest_object_version(&user, &bucket_name, &file_name), Some(Hash([80u8; 64])));
+	}: _(RawOrigin::Signed(user.clone()), user.clone(), bucket_name.clone(), file_name.clone(), Hash([80u8; 64]))
+	verify {
+		assert!(!<File<T>>::contains_key(&Hash([80u8; 64])));
+		assert_eq!(FileBank::<T>::latest_object_version(&user, &bucket_name, &file_name), None);
+	}
+
+	set_bucket_retention {
+		log::info!("start set_bucket_retention");
+		let caller: AccountOf<T> = account("user1", 100, SEED);
+		let name: BoundedVec<u8, T::NameStrLimit> = "test-bucket1".as_bytes().to_vec().try_into().map_err(|_| "name convert error")?;
+		FileBank::<T>::create_bucket(RawOrigin::Signed(caller.clone()).into(), caller.clone(), name.clone())?;
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), name.clone(), 1)
+	verify {
+		assert_eq!(FileBank::<T>::bucket_retention_limit(&caller, &name), 1);
+	}
+
+	set_bucket_access {
+		log::info!("start set_bucket_access");
+		let caller: AccountOf<T> = account("user1", 100, SEED);
+		let writer: AccountOf<T> = account("user2", 100, SEED);
+		let name: BoundedVec<u8, T::NameStrLimit> = "test-bucket1".as_bytes().to_vec().try_into().map_err(|_| "name convert error")?;
+		FileBank::<T>::create_bucket(RawOrigin::Signed(caller.clone()).into(), caller.clone(), name.clone())?;
+		buy_space::<T>(caller.clone())?;
+		let territory_name: TerrName = "t1".as_bytes().to_vec().try_into().map_err(|_| "boundedvec error")?;
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), name.clone(), writer.clone(), Some(BucketRole::Writer), Some(territory_name.clone()))
+	verify {
+		assert!(FileBank::<T>::bucket_permissions(&writer, &caller, &name).write);
+		assert_eq!(FileBank::<T>::writer_territory((&caller, &name, &writer)), Some(territory_name));
+	}
+
+	set_bucket_public_read {
+		log::info!("start set_bucket_public_read");
+		let caller: AccountOf<T> = account("user1", 100, SEED);
+		let reader: AccountOf<T> = account("user2", 100, SEED);
+		let name: BoundedVec<u8, T::NameStrLimit> = "test-bucket1".as_bytes().to_vec().try_into().map_err(|_| "name convert error")?;
+		FileBank::<T>::create_bucket(RawOrigin::Signed(caller.clone()).into(), caller.clone(), name.clone())?;
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), name.clone(), true)
+	verify {
+		assert!(FileBank::<T>::bucket_permissions(&reader, &caller, &name).read);
+	}
 }
\ No newline at end of file
@@ -1 +1,7 @@
-pub(super) const ONCE_MAX_CLEAR_FILE: u32 = 300;
\ No newline at end of file
+pub(super) const ONCE_MAX_CLEAR_FILE: u32 = 300;
+
+pub(super) const DEAL_EXPIRY_LIMIT: u32 = 200;
+
+pub(super) const EXPIRY_SEARCH: u32 = 10;
+
+pub(super) const RESTORAL_EXPIRY_LIMIT: u32 = 200;
@@ -0,0 +1,108 @@
+//! Deal deadlines.
+//!
+//! A deal gives miners `DealLife` blocks to report its fragments. If it is still incomplete at its deadline, it
+//! is reopened for another `DealLife` blocks so that other miners can take the fragments nobody reported. This
+//! happens at most `MaxDealReassignments` times; after that the deal is cancelled, unlocking the space of its user
+//! and of the miners who reported. Deadlines are queued by block in `DealExpiry`, at most `DEAL_EXPIRY_LIMIT` to a
+//! block, which bounds the work of `on_initialize`. A deal that fails to expire is tried again at the next block.
+
+use super::*;
+use frame_support::storage::with_storage_layer;
+
+impl<T: Config> Pallet<T> {
+	/// Gives a deal declared at `declared` a deadline `DealLife` blocks from now.
+	pub(super) fn schedule_deal_deadline(
+		deal_hash: &Hash,
+		reassigned: u32,
+		declared: BlockNumberFor<T>,
+	) -> DispatchResult {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let deadline = now.checked_add(&T::DealLife::get()).ok_or(Error::<T>::Overflow)?;
+		let deadline = Self::enqueue_deal_expiry(deal_hash, deadline)?;
+		<DealDeadlines<T>>::insert(deal_hash, DealDeadline { deadline, reassigned, declared });
+
+		Ok(())
+	}
+
+	// Queues a deal at the first block from `at` with room for it. A full block spills into the next one, and
+	// the blocks ahead fill only as fast as deals are declared.
+	fn enqueue_deal_expiry(deal_hash: &Hash, at: BlockNumberFor<T>) -> Result<BlockNumberFor<T>, DispatchError> {
+		let mut at = at;
+		loop {
+			if <DealExpiry<T>>::try_mutate(&at, |queue| queue.try_push(*deal_hash)).is_ok() {
+				return Ok(at);
+			}
+			at = at.checked_add(&1u32.saturated_into()).ok_or(Error::<T>::Overflow)?;
+		}
+	}
+
+	// Tries a deal that failed to expire again at the next block.
+	fn retry_deal_expiry(deal_hash: &Hash, now: BlockNumberFor<T>) -> DispatchResult {
+		let at = now.checked_add(&1u32.saturated_into()).ok_or(Error::<T>::Overflow)?;
+		let at = Self::enqueue_deal_expiry(deal_hash, at)?;
+		<DealDeadlines<T>>::try_mutate(deal_hash, |timer_opt| -> DispatchResult {
+			let timer = timer_opt.as_mut().ok_or(Error::<T>::NonExistent)?;
+			timer.deadline = at;
+			Ok(())
+		})
+	}
+
+	/// Reopens or cancels the deals expiring at `now`.
+	pub(super) fn expire_deals(now: BlockNumberFor<T>) -> Weight {
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+		for deal_hash in <DealExpiry<T>>::take(&now) {
+			weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			match with_storage_layer(|| Self::expire_deal(&deal_hash, now)) {
+				Ok(temp_weight) => weight = weight.saturating_add(temp_weight),
+				Err(e) => {
+					log::error!("[FileBank] -> [expire_deals]: deal {:?} failed {:?}, retrying", deal_hash, e);
+					if let Err(e) = Self::retry_deal_expiry(&deal_hash, now) {
+						log::error!("[FileBank] -> [expire_deals]: deal {:?} not requeued {:?}", deal_hash, e);
+					}
+					weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 2));
+				},
+			}
+		}
+
+		weight
+	}
+
+	fn expire_deal(deal_hash: &Hash, now: BlockNumberFor<T>) -> Result<Weight, DispatchError> {
+		let Some(timer) = <DealDeadlines<T>>::get(deal_hash) else {
+			return Ok(Weight::zero());
+		};
+		// Completed, or declared again with a later deadline, in the meantime.
+		if timer.deadline != now {
+			return Ok(Weight::zero());
+		}
+		let Ok(deal_info) = <DealMap<T>>::try_get(deal_hash) else {
+			<DealDeadlines<T>>::remove(deal_hash);
+			return Ok(T::DbWeight::get().reads_writes(1, 1));
+		};
+
+		let expired = T::StorageHandle::check_expired(&deal_info.user.user, &deal_info.user.territory_name);
+		if !expired && timer.reassigned < T::MaxDealReassignments::get() {
+			let missing: Vec<u8> = (1 ..= FRAGMENT_COUNT as u8)
+				.filter(|index| !deal_info.complete_list.iter().any(|info| info.index == *index))
+				.collect();
+			Self::schedule_deal_deadline(deal_hash, timer.reassigned.saturating_add(1), timer.declared)?;
+			Self::deposit_event(Event::<T>::DealReassigned { deal_hash: *deal_hash, missing });
+
+			return Ok(T::DbWeight::get().reads_writes(3, 2));
+		}
+
+		deal_info.force_unlock_space()?;
+		// The space of an expired territory is gone with it.
+		if !expired {
+			let needed_space = Self::cal_file_size(deal_info.segment_list.len() as u128);
+			T::StorageHandle::unlock_user_space(&deal_info.user.user, &deal_info.user.territory_name, needed_space)?;
+		}
+		<DealMap<T>>::remove(deal_hash);
+		<DealDeadlines<T>>::remove(deal_hash);
+		Self::deposit_event(Event::<T>::DealCancelled { deal_hash: *deal_hash, owner: deal_info.user.user });
+
+		let miners = deal_info.complete_list.len() as u64;
+		Ok(T::DbWeight::get().reads_writes(miners.saturating_add(3), miners.saturating_add(3)))
+	}
+}
@@ -1,6 +1,134 @@
 use super::*;
 
 impl<T: Config> Pallet<T> {
+    /// Marks the fragments of `tag_sig_info.file_hash` stored by `sender` as tagged.
+    ///
+    /// The caller has checked that the report is genuine, either by the master key signature
+    /// over it or because a worker pushed it through the message queue.
+    pub(crate) fn apply_calculate_report(
+        sender: AccountOf<T>,
+        tag_sig_info: TagSigInfo<AccountOf<T>>,
+    ) -> DispatchResult {
+        let mut tee_tag_counter: BTreeMap<WorkerPublicKey, u8> = Default::default();
+        let mut calculate_details: BTreeMap<Hash, Vec<WorkerPublicKey>> = Default::default();
+        for digest in tag_sig_info.digest {
+            ensure!(
+                T::TeeWorkerHandler::can_tag(&digest.tee_puk),
+                Error::<T>::TeeNoPermission
+            );
+            let res = calculate_details.get_mut(&digest.fragment);
+            match res {
+                Some(value) => value.push(digest.tee_puk),
+                None => {
+                    let value: Vec<WorkerPublicKey> = vec![digest.tee_puk];
+                    calculate_details.insert(digest.fragment, value);
+                },
+            };
+
+            let res = tee_tag_counter.get_mut(&digest.tee_puk);
+            match res {
+                Some(value) => *value = *value + 1,
+                None => { tee_tag_counter.insert(digest.tee_puk, 1); },
+            };
+        }
+
+        <File<T>>::try_mutate(&tag_sig_info.file_hash, |file_info_opt| -> DispatchResult {
+            let file_info = file_info_opt.as_mut().ok_or(Error::<T>::NonExistent)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            let mut fcount: u128 = 0;
+            let mut hash_list: Vec<Box<[u8; 256]>> = Default::default();
+            let mut fragment_counter: BTreeMap<Hash, u8> = Default::default();
+            for segment in file_info.segment_list.iter_mut() {
+                for fragment in segment.fragment_list.iter_mut() {
+                    if fragment.miner == sender {
+
+                        if fragment.tag.is_some() {
+                            continue;
+                        }
+
+                        let res = fragment_counter.get_mut(&fragment.hash);
+                        match res {
+                            Some(value) => *value = *value + 1,
+                            None => {
+                                fragment_counter.insert(fragment.hash, 1);
+                            },
+                        };
+
+                        fragment.tag = Some(now);
+                        fcount = fcount + 1;
+                        let hash_temp = fragment.hash.binary().map_err(|_| Error::<T>::BugInvalid)?;
+                        hash_list.push(hash_temp);
+                    }
+                }
+            }
+
+            for (hash, count) in fragment_counter.iter() {
+                let res = calculate_details.get_mut(hash);
+                match res {
+                    Some(value) => {
+                        ensure!(value.len() == (*count as usize), Error::<T>::DigestError)
+                    },
+                    None => Err(Error::<T>::DigestError)?,
+                };
+            }
+
+            let unlock_space = FRAGMENT_SIZE.checked_mul(fcount as u128).ok_or(Error::<T>::Overflow)?;
+            T::MinerControl::unlock_space_to_service(&sender, unlock_space)?;
+            T::MinerControl::insert_service_bloom(&sender, hash_list)?;
+
+            for (puk, count) in tee_tag_counter.iter() {
+                if T::TeeWorkerHandler::is_bonded(&puk) {
+                    let bond_stash = T::TeeWorkerHandler::get_stash(&puk)?;
+                    T::CreditCounter::increase_point_for_tag(&bond_stash, FRAGMENT_SIZE * (*count as u128))?;
+                }
+                let now = <frame_system::Pallet<T>>::block_number();
+                T::TeeWorkerHandler::update_work_block(now, &puk)?;
+            }
+
+            Self::deposit_event(Event::<T>::CalculateReport{ miner: sender, file_hash: tag_sig_info.file_hash});
+
+            Ok(())
+        })?;
+
+        Ok(())
+    }
+
+    /// Adds the idle space `tee_puk` certified to `sender`.
+    ///
+    /// The caller has checked that the certification is genuine, either by the master key
+    /// signature over it or because the worker pushed it through the message queue.
+    pub(crate) fn apply_idle_space_cert(
+        sender: AccountOf<T>,
+        idle_sig_info: SpaceProofInfo<AccountOf<T>>,
+        tee_sig: BoundedVec<u8, ConstU32<64>>,
+        tee_puk: WorkerPublicKey,
+    ) -> DispatchResult {
+        let now = <frame_system::Pallet<T>>::block_number();
+        T::TeeWorkerHandler::update_work_block(now, &tee_puk)?;
+
+        let sig = 
+            sp_core::sr25519::Signature::try_from(tee_sig.as_slice()).or(Err(Error::<T>::MalformedSignature))?;
+
+        let idle_space = T::MinerControl::add_miner_idle_space(
+            &sender, 
+            idle_sig_info.accumulator, 
+            idle_sig_info.front,
+            idle_sig_info.rear,
+            sig,
+        )?;
+
+        if T::TeeWorkerHandler::is_bonded(&tee_puk) {
+            let bond_stash = T::TeeWorkerHandler::get_stash(&tee_puk)?;
+            T::CreditCounter::increase_point_for_cert(&bond_stash, idle_space)?;
+        }
+
+        T::StorageHandle::add_total_idle_space(idle_space)?;
+
+        Self::deposit_event(Event::<T>::IdleSpaceCert{ acc: sender, space: idle_space });
+
+        Ok(())
+    }
+
     pub fn check_file_spec(seg_list: &BoundedVec<SegmentList<T>, T::SegmentCount>) -> bool {
         let spec_len = T::FragmentCount::get();
 
@@ -116,6 +244,8 @@ impl<T: Config> Pallet<T> {
         };
 
         DealMap::insert(&file_hash, deal);
+        let now = <frame_system::Pallet<T>>::block_number();
+        Self::schedule_deal_deadline(&file_hash, 0, now)?;
 
         Ok(())
     }
@@ -131,6 +261,7 @@ impl<T: Config> Pallet<T> {
 		}
 
 		<DealMap<T>>::remove(deal_hash);
+		<DealDeadlines<T>>::remove(deal_hash);
 
         Ok(())
     }
@@ -168,7 +299,8 @@ impl<T: Config> Pallet<T> {
                     let bucket = bucket_opt.as_mut().ok_or(Error::<T>::NonExistent)?;
                     bucket.object_list.retain(|file| file != file_hash);
                     Ok(())
-                })?
+                })?;
+                Self::forget_object_version(user_brief, file_hash);
             }
 		}
         
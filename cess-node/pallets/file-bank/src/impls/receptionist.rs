@@ -24,6 +24,8 @@ impl<T: Config> Receptionist<T> {
 
             Ok(())
         })?;
+        let now = <frame_system::Pallet<T>>::block_number();
+        Pallet::<T>::record_object_version(&user_brief, &file_hash, now)?;
 
         Ok(())
     }
@@ -69,7 +71,13 @@ impl<T: Config> Receptionist<T> {
                 Pallet::<T>::create_bucket_helper(&deal_info.user.user, &deal_info.user.bucket_name, Some(deal_hash))?;
             }
             Pallet::<T>::add_user_hold_fileslice(&deal_info.user.user, deal_hash.clone(), needed_space, deal_info.user.territory_name.clone())?;
+            // The version is as recent as its upload, not its completion.
+            let uploaded = <DealDeadlines<T>>::get(&deal_hash)
+                .map(|timer| timer.declared)
+                .unwrap_or_else(|| <frame_system::Pallet<T>>::block_number());
+            Pallet::<T>::record_object_version(&deal_info.user, &deal_hash, uploaded)?;
             <DealMap<T>>::remove(deal_hash);
+            <DealDeadlines<T>>::remove(deal_hash);
             Pallet::<T>::deposit_event(Event::<T>::StorageCompleted{ file_hash: deal_hash });
         }
 
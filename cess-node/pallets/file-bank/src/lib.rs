@@ -34,13 +34,26 @@ pub use pallet::*;
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
 pub mod weights;
-// pub mod migrations;
+pub mod migrations;
 
 mod types;
 pub use types::*;
 
 mod functions;
 
+mod versions;
+
+mod policy;
+
+mod deals;
+
+mod restoral;
+
+mod obligations;
+
+mod messages;
+pub use messages::WorkerCertification;
+
 mod constants;
 use constants::*;
 
@@ -74,7 +87,7 @@ use sp_std::{
 	str, 
 	collections::btree_map::BTreeMap
 };
-use pallet_sminer::MinerControl;
+use pallet_sminer::{MinerControl, MinerObligations};
 use pallet_tee_worker::TeeWorkerHandler;
 use pallet_oss::OssFindAuthor;
 use ces_types::WorkerPublicKey;
@@ -83,7 +96,7 @@ pub use weights::WeightInfo;
 
 type AccountOf<T> = <T as frame_system::Config>::AccountId;
 
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -153,6 +166,18 @@ pub mod pallet {
 
 		#[pallet::constant]
 		type MissionCount: Get<u32> + Clone + Eq + PartialEq;
+		// Maximum number of versions kept for an object, and the default retention of a bucket.
+		#[pallet::constant]
+		type MaxObjectVersions: Get<u32> + Clone + Eq + PartialEq;
+		// Number of blocks miners have to report the fragments of a deal.
+		#[pallet::constant]
+		type DealLife: Get<BlockNumberFor<Self>>;
+		// Number of times an incomplete deal is reopened to other miners before it is cancelled.
+		#[pallet::constant]
+		type MaxDealReassignments: Get<u32>;
+		// Bounty paid for restoring a fragment, out of the collateral of the miner that lost it.
+		#[pallet::constant]
+		type RestoralBounty: Get<u128>;
 	}
 
 	#[pallet::event]
@@ -186,6 +211,26 @@ pub mod pallet {
 		CalculateReport { miner: AccountOf<T>, file_hash: Hash },
 
 		TerritoryFileDelivery { file_hash: Hash, new_territory: TerrName },
+		//Successfully delete a version of an object
+		DeleteObjectVersion { operator: AccountOf<T>, owner: AccountOf<T>, bucket_name: Vec<u8>, file_name: Vec<u8>, file_hash: Hash },
+		//A version of an object was deleted for exceeding the retention of its bucket
+		PruneObjectVersion { owner: AccountOf<T>, bucket_name: Vec<u8>, file_name: Vec<u8>, file_hash: Hash },
+		//Successfully set the number of versions a bucket keeps for each object
+		SetBucketRetention { operator: AccountOf<T>, owner: AccountOf<T>, bucket_name: Vec<u8>, retention: u32 },
+		//Successfully change the access of an account to a bucket
+		SetBucketAccess { operator: AccountOf<T>, owner: AccountOf<T>, bucket_name: Vec<u8>, account: AccountOf<T>, role: Option<BucketRole> },
+		//Successfully make a bucket public-read or private
+		SetBucketPublicRead { operator: AccountOf<T>, owner: AccountOf<T>, bucket_name: Vec<u8>, public_read: bool },
+		//A deal reached its deadline incomplete and was reopened for the fragment indexes nobody reported
+		DealReassigned { deal_hash: Hash, missing: Vec<u8> },
+		//A deal reached its last deadline incomplete and was cancelled
+		DealCancelled { deal_hash: Hash, owner: AccountOf<T> },
+		//The claim of a restoral order expired and the order is offered again
+		RestoralOrderReoffered { miner: AccountOf<T>, order_id: Hash },
+		//The bounty of a restoral order was paid to the miner completing it
+		RestoralBountyPaid { miner: AccountOf<T>, order_id: Hash, amount: u128 },
+		//The bounty of a restoral order could not be paid out of the collateral of the miner that lost the fragment
+		RestoralBountyUnpaid { miner: AccountOf<T>, order_id: Hash, amount: u128 },
 	}
 
 	#[pallet::error]
@@ -257,12 +302,26 @@ pub mod pallet {
 		RulesNotAllowed,
 		/// The status of the file needs to be Active
 		NotActive,
+		/// The message was not sent by a worker
+		InvalidSender,
 	}
 
 	#[pallet::storage]
 	#[pallet::getter(fn deal_map)]
 	pub(super) type DealMap<T: Config> = StorageMap<_, Blake2_128Concat, Hash, DealInfo<T>>;
 
+	/// The deadline of each deal in progress.
+	#[pallet::storage]
+	#[pallet::getter(fn deal_deadlines)]
+	pub(super) type DealDeadlines<T: Config> =
+		StorageMap<_, Blake2_128Concat, Hash, DealDeadline<BlockNumberFor<T>>>;
+
+	/// The deals expiring at each block. Entries whose deadline has changed since are ignored.
+	#[pallet::storage]
+	#[pallet::getter(fn deal_expiry)]
+	pub(super) type DealExpiry<T: Config> =
+		StorageMap<_, Blake2_128Concat, BlockNumberFor<T>, BoundedVec<Hash, ConstU32<DEAL_EXPIRY_LIMIT>>, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn file)]
 	pub(super) type File<T: Config> =
@@ -307,6 +366,24 @@ pub mod pallet {
 	pub(super) type RestoralOrder<T: Config> = 
 		StorageMap<_, Blake2_128Concat, Hash, RestoralOrderInfo<T>>;
 
+	/// The bounty of each restoral order.
+	#[pallet::storage]
+	#[pallet::getter(fn restoral_bounties)]
+	pub(super) type RestoralBounties<T: Config> =
+		StorageMap<_, Blake2_128Concat, Hash, u128, ValueQuery>;
+
+	/// The restoral orders whose claim expires at each block.
+	#[pallet::storage]
+	#[pallet::getter(fn restoral_expiry)]
+	pub(super) type RestoralExpiry<T: Config> =
+		StorageMap<_, Blake2_128Concat, BlockNumberFor<T>, BoundedVec<Hash, ConstU32<RESTORAL_EXPIRY_LIMIT>>, ValueQuery>;
+
+	/// The last block each miner has a deal it reported fragments of or a restoral order it claimed open until.
+	#[pallet::storage]
+	#[pallet::getter(fn miner_obligation_end)]
+	pub(super) type MinerObligationEnd<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountOf<T>, BlockNumberFor<T>>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn clear_user_list)]
 	pub(super) type ClearUserList<T: Config> = 
@@ -317,14 +394,87 @@ pub mod pallet {
 	pub(super) type TaskFailedCount<T: Config> = 
 		StorageMap<_, Blake2_128Concat, AccountOf<T>, u8, ValueQuery>;
 
+	/// The version history of each object, by owner, bucket name and file name, oldest upload first.
+	#[pallet::storage]
+	#[pallet::getter(fn object_versions)]
+	pub(super) type ObjectVersions<T: Config> =
+		StorageNMap<
+			_,
+			(
+				NMapKey<Blake2_128Concat, AccountOf<T>>,
+				NMapKey<Blake2_128Concat, BoundedVec<u8, T::NameStrLimit>>,
+				NMapKey<Blake2_128Concat, BoundedVec<u8, T::NameStrLimit>>,
+			),
+			BoundedVec<ObjectVersion<BlockNumberFor<T>>, T::MaxObjectVersions>,
+			ValueQuery,
+		>;
+
+	/// The number of versions a bucket keeps for each object, `MaxObjectVersions` if not set.
+	#[pallet::storage]
+	#[pallet::getter(fn bucket_retention)]
+	pub(super) type BucketRetention<T: Config> =
+		StorageDoubleMap<
+			_,
+			Blake2_128Concat,
+			AccountOf<T>,
+			Blake2_128Concat,
+			BoundedVec<u8, T::NameStrLimit>,
+			u32,
+		>;
+
+	/// The accounts that may read the files of a bucket besides its owner and writers.
+	#[pallet::storage]
+	#[pallet::getter(fn bucket_readers)]
+	pub(super) type BucketReaders<T: Config> =
+		StorageDoubleMap<
+			_,
+			Blake2_128Concat,
+			AccountOf<T>,
+			Blake2_128Concat,
+			BoundedVec<u8, T::NameStrLimit>,
+			BoundedVec<AccountOf<T>, ConstU32<1032>>,
+			ValueQuery,
+		>;
+
+	/// The territory of the owner each writer of a bucket uploads into.
+	#[pallet::storage]
+	#[pallet::getter(fn writer_territory)]
+	pub(super) type WriterTerritory<T: Config> =
+		StorageNMap<
+			_,
+			(
+				NMapKey<Blake2_128Concat, AccountOf<T>>,
+				NMapKey<Blake2_128Concat, BoundedVec<u8, T::NameStrLimit>>,
+				NMapKey<Blake2_128Concat, AccountOf<T>>,
+			),
+			TerrName,
+		>;
+
+	/// Whether anyone may read the files of a bucket.
+	#[pallet::storage]
+	#[pallet::getter(fn public_read_buckets)]
+	pub(super) type PublicReadBuckets<T: Config> =
+		StorageDoubleMap<
+			_,
+			Blake2_128Concat,
+			AccountOf<T>,
+			Blake2_128Concat,
+			BoundedVec<u8, T::NameStrLimit>,
+			bool,
+			ValueQuery,
+		>;
+
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
 			let mut weight: Weight = Weight::zero();
+
+			weight = weight.saturating_add(Self::expire_deals(now));
+			weight = weight.saturating_add(Self::expire_restoral_claims(now));
 			
 			let (temp_weight, clear_list) = T::StorageHandle::frozen_task();
 			weight = weight.saturating_add(temp_weight);
@@ -385,6 +535,10 @@ pub mod pallet {
 			
 			weight
 		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
 	}
 
 	#[pallet::call]
@@ -398,7 +552,9 @@ pub mod pallet {
 		/// - `origin`: The origin of the transaction.
 		/// - `file_hash`: The unique hash identifier of the file.
 		/// - `deal_info`: A list of segment details for data storage.
-		/// - `user_brief`: A brief description of the user and the file's ownership.
+		/// - `user_brief`: A brief description of the user and the file's ownership. Its territory may be one
+		///   shared with the user, in which case the file also counts against the user's quota of it. The writers
+		///   of an existing bucket may upload into it, with the owner as the user.
 		/// - `file_size`: The size of the file in bytes.
 		#[pallet::call_index(0)]
 		#[transactional]
@@ -412,7 +568,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			// Check if you have operation permissions.
-			ensure!(Self::check_permission(sender.clone(), user_brief.user.clone()), Error::<T>::NoPermission);
+			Self::ensure_bucket_uploader(&sender, &user_brief)?;
 			// Check file specifications.
 			ensure!(Self::check_file_spec(&deal_info), Error::<T>::SpecError);
 			// Check whether the user-defined name meets the rules.
@@ -572,10 +728,14 @@ pub mod pallet {
 			if expired_flag {
 				deal_info.force_unlock_space()?;
 				<DealMap<T>>::remove(deal_hash);
+				<DealDeadlines<T>>::remove(deal_hash);
 				return Ok(());
 			}
 
 			Receptionist::<T>::qualification_report_processing(sender.clone(), deal_hash, &mut deal_info, index)?;
+			if let Some(end) = Self::deal_obligation_end(&deal_hash) {
+				Self::record_miner_obligation(&sender, end);
+			}
 
 			Self::deposit_event(Event::<T>::TransferReport{acc: sender, deal_hash: deal_hash});
 
@@ -604,88 +764,7 @@ pub mod pallet {
 			);
 			ensure!(tag_sig_info.miner == sender, Error::<T>::MinerError);
 
-			let mut tee_tag_counter: BTreeMap<WorkerPublicKey, u8> = Default::default();
-			let mut calculate_details: BTreeMap<Hash, Vec<WorkerPublicKey>> = Default::default();
-			for digest in tag_sig_info.digest {
-				ensure!(
-					T::TeeWorkerHandler::can_tag(&digest.tee_puk),
-					Error::<T>::TeeNoPermission
-				);
-				let res = calculate_details.get_mut(&digest.fragment);
-				match res {
-					Some(value) => value.push(digest.tee_puk),
-					None => {
-						let value: Vec<WorkerPublicKey> = vec![digest.tee_puk];
-						calculate_details.insert(digest.fragment, value);
-					},
-				};
-
-				let res = tee_tag_counter.get_mut(&digest.tee_puk);
-				match res {
-					Some(value) => *value = *value + 1,
-					None => { tee_tag_counter.insert(digest.tee_puk, 1); },
-				};
-			}
-
-			<File<T>>::try_mutate(&tag_sig_info.file_hash, |file_info_opt| -> DispatchResult {
-				let file_info = file_info_opt.as_mut().ok_or(Error::<T>::NonExistent)?;
-				let now = <frame_system::Pallet<T>>::block_number();
-				let mut fcount: u128 = 0;
-				let mut hash_list: Vec<Box<[u8; 256]>> = Default::default();
-				let mut fragment_counter: BTreeMap<Hash, u8> = Default::default();
-				for segment in file_info.segment_list.iter_mut() {
-					for fragment in segment.fragment_list.iter_mut() {
-						if fragment.miner == sender {
-							
-							if fragment.tag.is_some() {
-								continue;
-							}
-
-							let res = fragment_counter.get_mut(&fragment.hash);
-							match res {
-								Some(value) => *value = *value + 1,
-								None => {
-									fragment_counter.insert(fragment.hash, 1);
-								},
-							};
-
-							fragment.tag = Some(now);
-							fcount = fcount + 1;
-							let hash_temp = fragment.hash.binary().map_err(|_| Error::<T>::BugInvalid)?;
-							hash_list.push(hash_temp);
-						}
-					}
-				}
-
-				for (hash, count) in fragment_counter.iter() {
-					let res = calculate_details.get_mut(hash);
-					match res {
-						Some(value) => {
-							ensure!(value.len() == (*count as usize), Error::<T>::DigestError)
-						},
-						None => Err(Error::<T>::DigestError)?,
-					};
-				}
-
-				let unlock_space = FRAGMENT_SIZE.checked_mul(fcount as u128).ok_or(Error::<T>::Overflow)?;
-				T::MinerControl::unlock_space_to_service(&sender, unlock_space)?;
-				T::MinerControl::insert_service_bloom(&sender, hash_list)?;
-
-				for (puk, count) in tee_tag_counter.iter() {
-					if T::TeeWorkerHandler::is_bonded(&puk) {
-						let bond_stash = T::TeeWorkerHandler::get_stash(&puk)?;
-						T::CreditCounter::increase_point_for_tag(&bond_stash, FRAGMENT_SIZE * (*count as u128))?;
-					}
-					let now = <frame_system::Pallet<T>>::block_number();
-					T::TeeWorkerHandler::update_work_block(now, &puk)?;
-				}
-
-				Self::deposit_event(Event::<T>::CalculateReport{ miner: sender, file_hash: tag_sig_info.file_hash});
-
-				Ok(())
-			})?;
-
-			Ok(())
+			Self::apply_calculate_report(sender, tag_sig_info)
 		}
 
 		/// Replace Idle Space
@@ -761,7 +840,7 @@ pub mod pallet {
 		///
 		/// Parameters:
 		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization.
-		/// - `owner`: The owner of the file, representing the user who has permission to delete the file.
+		/// - `owner`: The owner of the file. The writers of the bucket holding it may delete it too.
 		/// - `file_hash`: The unique hash identifier of the file to be deleted.
 		#[pallet::call_index(6)]
 		#[transactional]
@@ -771,12 +850,9 @@ pub mod pallet {
 		pub fn delete_file(origin: OriginFor<T>, owner: AccountOf<T>, file_hash: Hash) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			// Check if you have operation permissions.
-			ensure!(Self::check_permission(sender.clone(), owner.clone()), Error::<T>::NoPermission);
+			Self::ensure_file_writer(&sender, &owner, &file_hash)?;
 
-			let file = <File<T>>::try_get(&file_hash).map_err(|_| Error::<T>::NonExistent)?;
-			let _ = Self::delete_user_file(&file_hash, &owner, &file)?;
-			Self::bucket_remove_file(&file_hash, &owner, &file)?;
-			Self::remove_user_hold_file_list(&file_hash, &owner)?;
+			Self::delete_object(&owner, &file_hash)?;
 			Self::deposit_event(Event::<T>::DeleteFile{ operator: sender, owner, file_hash });
 
 			Ok(())
@@ -822,30 +898,7 @@ pub mod pallet {
 				Error::<T>::VerifyTeeSigFailed
 			);
 
-			let now = <frame_system::Pallet<T>>::block_number();
-			T::TeeWorkerHandler::update_work_block(now, &tee_puk)?;
-
-			let sig = 
-				sp_core::sr25519::Signature::try_from(tee_sig.as_slice()).or(Err(Error::<T>::MalformedSignature))?;
-
-			let idle_space = T::MinerControl::add_miner_idle_space(
-				&sender, 
-				idle_sig_info.accumulator, 
-				idle_sig_info.front,
-				idle_sig_info.rear,
-				sig,
-			)?;
-
-			if T::TeeWorkerHandler::is_bonded(&tee_puk) {
-				let bond_stash = T::TeeWorkerHandler::get_stash(&tee_puk)?;
-				T::CreditCounter::increase_point_for_cert(&bond_stash, idle_space)?;
-			}
-
-			T::StorageHandle::add_total_idle_space(idle_space)?;
-
-			Self::deposit_event(Event::<T>::IdleSpaceCert{ acc: sender, space: idle_space });
-
-			Ok(())
+			Self::apply_idle_space_cert(sender, idle_sig_info, tee_sig, tee_puk)
 		}
 
 		/// Create a Data Storage Bucket
@@ -902,6 +955,10 @@ pub mod pallet {
 			ensure!(bucket.object_list.len() == 0, Error::<T>::NotEmpty);
 
 			<Bucket<T>>::remove(&owner, &name);
+			<BucketRetention<T>>::remove(&owner, &name);
+			<BucketReaders<T>>::remove(&owner, &name);
+			let _ = <WriterTerritory<T>>::clear_prefix((&owner, &name), u32::MAX, None);
+			<PublicReadBuckets<T>>::remove(&owner, &name);
 			<UserBucketList<T>>::try_mutate(&owner, |bucket_list| -> DispatchResult {
 				let mut index = 0;
 				for name_tmp in bucket_list.iter() {
@@ -965,6 +1022,7 @@ pub mod pallet {
 								fragment.avail = false;
 
 								<RestoralOrder<T>>::insert(&restoral_fragment, restoral_order);
+								<RestoralBounties<T>>::insert(&restoral_fragment, T::RestoralBounty::get());
 
 								Self::deposit_event(Event::<T>::GenerateRestoralOrder{ miner: sender, fragment_hash: restoral_fragment});
 
@@ -983,6 +1041,8 @@ pub mod pallet {
 		/// This function allows a network miner to claim a restoration order for a specific fragment of a file. 
 		/// A restoration order is generated when a user requests the restoration of a lost or corrupted fragment. 
 		/// Miners can claim these orders to provide the requested fragments.
+		/// A miner storing another fragment of the same segment may not claim it. A claim lasts
+		/// `RestoralOrderLife` blocks, after which the order is offered to other miners again.
 		///
 		/// Parameters:
 		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization. This function can only be called by authorized miners.
@@ -1003,11 +1063,15 @@ pub mod pallet {
 				let order = order_opt.as_mut().ok_or(Error::<T>::NonExistent)?;
 
 				ensure!(now > order.deadline, Error::<T>::SpecError);
+				let file = <File<T>>::try_get(&order.file_hash).map_err(|_| Error::<T>::NonExistent)?;
+				Self::ensure_restoral_eligible(&file, &restoral_fragment, &sender)?;
 
 				let life = T::RestoralOrderLife::get();
+				let deadline = now.checked_add(&life.saturated_into()).ok_or(Error::<T>::Overflow)?;
 				order.count = order.count.checked_add(1).ok_or(Error::<T>::Overflow)?;
-				order.deadline = now.checked_add(&life.saturated_into()).ok_or(Error::<T>::Overflow)?;
+				order.deadline = Self::enqueue_restoral_expiry(&restoral_fragment, deadline)?;
 				order.miner = sender.clone();
+				Self::record_miner_obligation(&sender, order.deadline);
 
 				Ok(())
 			})?;
@@ -1050,6 +1114,8 @@ pub mod pallet {
 				T::MinerControl::restoral_target_is_exist(&miner),
 				Error::<T>::NonExistent,
 			);
+			let file = <File<T>>::try_get(&file_hash).map_err(|_| Error::<T>::NonExistent)?;
+			Self::ensure_restoral_eligible(&file, &restoral_fragment, &sender)?;
 
 			<File<T>>::try_mutate(&file_hash, |file_opt| -> DispatchResult {
 				let file = file_opt.as_mut().ok_or(Error::<T>::NonExistent)?;
@@ -1060,6 +1126,8 @@ pub mod pallet {
 								let now = <frame_system::Pallet<T>>::block_number();
 								let life = T::RestoralOrderLife::get();
 								let deadline = now.checked_add(&life.saturated_into()).ok_or(Error::<T>::Overflow)?;
+								let deadline = Self::enqueue_restoral_expiry(&restoral_fragment, deadline)?;
+								Self::record_miner_obligation(&sender, deadline);
 								let restoral_order = RestoralOrderInfo::<T> {
 									count: u32::MIN,
 									miner: sender.clone(),
@@ -1073,6 +1141,7 @@ pub mod pallet {
 								fragment.avail = false;
 
 								<RestoralOrder<T>>::insert(&restoral_fragment, restoral_order);
+								<RestoralBounties<T>>::insert(&restoral_fragment, T::RestoralBounty::get());
 
 								return Ok(())
 							}
@@ -1094,6 +1163,7 @@ pub mod pallet {
 		/// indicating that they have successfully restored a fragment to its original state. 
 		/// Restoration orders are generated when users request the restoration of lost or corrupted fragments. 
 		/// Miners claim these orders and provide the requested fragments for restoration.
+		/// The miner is paid the bounty of the order out of the collateral of the miner that lost the fragment.
 		///
 		/// Parameters:
 		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization. This function can only be called by authorized miners.
@@ -1117,9 +1187,10 @@ pub mod pallet {
 
 			if !<File<T>>::contains_key(&order.file_hash) {
 				<RestoralOrder<T>>::remove(fragment_hash);
+				<RestoralBounties<T>>::remove(fragment_hash);
 				return Ok(());
 			} else {
-				<File<T>>::try_mutate(&order.file_hash, |file_opt| -> DispatchResult {
+				let restored = <File<T>>::try_mutate(&order.file_hash, |file_opt| -> Result<bool, DispatchError> {
 					let file = file_opt.as_mut().ok_or(Error::<T>::BugInvalid)?;
 
 					for segment in &mut file.segment_list {
@@ -1152,23 +1223,188 @@ pub mod pallet {
 									fragment.tag = Some(now);
 									fragment.avail = true;
 									fragment.miner = sender.clone();
-									return Ok(());
+									return Ok(true);
 								}
 							}
 						}
 					}
 
-					Ok(())
+					Ok(false)
 				})?;
+
+				if restored {
+					Self::pay_restoral_bounty(&order, &sender);
+				}
 			}
 
 			<RestoralOrder<T>>::remove(fragment_hash);
+			<RestoralBounties<T>>::remove(fragment_hash);
 
 			Self::deposit_event(Event::<T>::RecoveryCompleted{ miner: sender, order_id: fragment_hash});
 		
 			Ok(())
 		}
 
+		/// Delete a Version of an Object
+		///
+		/// This function allows an authorized user to delete one version of an object in a bucket. The
+		/// remaining versions are kept, the latest of them becoming the object's latest version.
+		///
+		/// Parameters:
+		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization.
+		/// - `owner`: The owner of the bucket.
+		/// - `bucket_name`: The name of the bucket holding the object.
+		/// - `file_name`: The name of the object.
+		/// - `file_hash`: The file hash of the version to be deleted.
+		#[pallet::call_index(17)]
+		#[transactional]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::delete_object_version())]
+		pub fn delete_object_version(
+			origin: OriginFor<T>,
+			owner: AccountOf<T>,
+			bucket_name: BoundedVec<u8, T::NameStrLimit>,
+			file_name: BoundedVec<u8, T::NameStrLimit>,
+			file_hash: Hash,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_bucket_writer(&sender, &owner, &bucket_name)?;
+			ensure!(
+				Self::is_object_version(&owner, &bucket_name, &file_name, &file_hash),
+				Error::<T>::NonExistent
+			);
+			let file = <File<T>>::try_get(&file_hash).map_err(|_| Error::<T>::NonExistent)?;
+			let user_brief = file.owner.iter()
+				.find(|brief| brief.user == owner && brief.bucket_name == bucket_name && brief.file_name == file_name)
+				.ok_or(Error::<T>::NonExistent)?;
+
+			Self::delete_object_version_of(user_brief, &file_hash)?;
+
+			Self::deposit_event(Event::<T>::DeleteObjectVersion {
+				operator: sender,
+				owner,
+				bucket_name: bucket_name.to_vec(),
+				file_name: file_name.to_vec(),
+				file_hash,
+			});
+
+			Ok(())
+		}
+
+		/// Set the Retention of a Bucket
+		///
+		/// This function sets how many versions of each object a bucket keeps, from one up to `MaxObjectVersions`.
+		/// It applies as objects get new versions, each of which deletes the oldest versions of its object beyond
+		/// the retention.
+		///
+		/// Parameters:
+		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization.
+		/// - `owner`: The owner of the bucket.
+		/// - `bucket_name`: The name of the bucket.
+		/// - `retention`: The number of versions to keep for each object.
+		#[pallet::call_index(18)]
+		#[transactional]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_bucket_retention())]
+		pub fn set_bucket_retention(
+			origin: OriginFor<T>,
+			owner: AccountOf<T>,
+			bucket_name: BoundedVec<u8, T::NameStrLimit>,
+			retention: u32,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::check_permission(sender.clone(), owner.clone()), Error::<T>::NoPermission);
+			ensure!(<Bucket<T>>::contains_key(&owner, &bucket_name), Error::<T>::NonExistent);
+			ensure!(retention > 0 && retention <= T::MaxObjectVersions::get(), Error::<T>::SpecError);
+
+			<BucketRetention<T>>::insert(&owner, &bucket_name, retention);
+
+			Self::deposit_event(Event::<T>::SetBucketRetention {
+				operator: sender,
+				owner,
+				bucket_name: bucket_name.to_vec(),
+				retention,
+			});
+
+			Ok(())
+		}
+
+		/// Set the Access of an Account to a Bucket
+		///
+		/// This function allows the owner of a bucket to make another account a writer or a reader of it, or to
+		/// take its access away. A writer uploads into the one territory of the owner it is given.
+		///
+		/// Parameters:
+		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization.
+		/// - `owner`: The owner of the bucket.
+		/// - `bucket_name`: The name of the bucket.
+		/// - `account`: The account whose access is changed.
+		/// - `role`: The role of the account, or `None` to take its access away.
+		/// - `territory`: The territory a writer uploads into, only given with `BucketRole::Writer`.
+		#[pallet::call_index(19)]
+		#[transactional]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_bucket_access())]
+		pub fn set_bucket_access(
+			origin: OriginFor<T>,
+			owner: AccountOf<T>,
+			bucket_name: BoundedVec<u8, T::NameStrLimit>,
+			account: AccountOf<T>,
+			role: Option<BucketRole>,
+			territory: Option<TerrName>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::check_permission(sender.clone(), owner.clone()), Error::<T>::NoPermission);
+
+			Self::set_bucket_role(&owner, &bucket_name, &account, role, territory)?;
+
+			Self::deposit_event(Event::<T>::SetBucketAccess {
+				operator: sender,
+				owner,
+				bucket_name: bucket_name.to_vec(),
+				account,
+				role,
+			});
+
+			Ok(())
+		}
+
+		/// Make a Bucket Public-Read or Private
+		///
+		/// This function allows the owner of a bucket to let anyone read its files, so that gateways can serve
+		/// them without an authorization of each reader.
+		///
+		/// Parameters:
+		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization.
+		/// - `owner`: The owner of the bucket.
+		/// - `bucket_name`: The name of the bucket.
+		/// - `public_read`: Whether anyone may read the files of the bucket.
+		#[pallet::call_index(20)]
+		#[transactional]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_bucket_public_read())]
+		pub fn set_bucket_public_read(
+			origin: OriginFor<T>,
+			owner: AccountOf<T>,
+			bucket_name: BoundedVec<u8, T::NameStrLimit>,
+			public_read: bool,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::check_permission(sender.clone(), owner.clone()), Error::<T>::NoPermission);
+			ensure!(<Bucket<T>>::contains_key(&owner, &bucket_name), Error::<T>::NonExistent);
+
+			if public_read {
+				<PublicReadBuckets<T>>::insert(&owner, &bucket_name, true);
+			} else {
+				<PublicReadBuckets<T>>::remove(&owner, &bucket_name);
+			}
+
+			Self::deposit_event(Event::<T>::SetBucketPublicRead {
+				operator: sender,
+				owner,
+				bucket_name: bucket_name.to_vec(),
+				public_read,
+			});
+
+			Ok(())
+		}
+
 		// FOR TESTING
 		#[pallet::call_index(22)]
 		#[transactional]
@@ -1176,10 +1412,7 @@ pub mod pallet {
 		pub fn root_clear_file(origin: OriginFor<T>, owner: AccountOf<T>, file_hash: Hash) -> DispatchResult {
 			let _ = ensure_root(origin)?;
 
-			let file = <File<T>>::try_get(&file_hash).map_err(|_| Error::<T>::NonExistent)?;
-			let _ = Self::delete_user_file(&file_hash, &owner, &file)?;
-			Self::bucket_remove_file(&file_hash, &owner, &file)?;
-			Self::remove_user_hold_file_list(&file_hash, &owner)?;
+			Self::delete_object(&owner, &file_hash)?;
 
 			Ok(())
 		}
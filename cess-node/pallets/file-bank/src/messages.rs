@@ -0,0 +1,58 @@
+//! Certifications delivered through the message queue.
+//!
+//! A TEE worker may push the tags it calculated and the idle space it certified to
+//! `WorkerCertification::topic()` instead of handing a master key signature to the miner.
+//! `pallet-mq` has checked the sequence and the worker signature of such a message.
+
+use super::*;
+use ces_types::messaging::{bind_topic, DecodedMessage, MessageOrigin};
+
+bind_topic!(WorkerCertification<AccountId>, b"^cess/filebank/certification");
+#[derive(Encode, Decode, TypeInfo, Clone, Debug)]
+pub enum WorkerCertification<AccountId> {
+	/// MessageOrigin::Worker -> Pallet
+	///
+	/// The tags of the fragments `tag_sig_info.miner` stores for `tag_sig_info.file_hash`.
+	Tag { tag_sig_info: TagSigInfo<AccountId> },
+	/// MessageOrigin::Worker -> Pallet
+	///
+	/// The idle space `idle_sig_info.miner` proved, with the signature sminer keeps for its
+	/// space proof.
+	IdleSpace { idle_sig_info: SpaceProofInfo<AccountId>, tee_sig: BoundedVec<u8, ConstU32<64>> },
+}
+
+impl<T: Config> Pallet<T> {
+	#[transactional]
+	pub fn on_message_received(message: DecodedMessage<WorkerCertification<AccountOf<T>>>) -> DispatchResult {
+		let tee_puk = match &message.sender {
+			MessageOrigin::Worker(key) => *key,
+			_ => return Err(Error::<T>::InvalidSender.into()),
+		};
+
+		match message.payload {
+			WorkerCertification::Tag { tag_sig_info } => {
+				ensure!(T::TeeWorkerHandler::can_tag(&tee_puk), Error::<T>::TeeNoPermission);
+				// A worker only reports the work it did itself.
+				ensure!(
+					tag_sig_info.digest.iter().all(|digest| digest.tee_puk == tee_puk),
+					Error::<T>::TeeNoPermission
+				);
+				let miner = tag_sig_info.miner.clone();
+				Self::apply_calculate_report(miner, tag_sig_info)
+			},
+			WorkerCertification::IdleSpace { idle_sig_info, tee_sig } => {
+				ensure!(T::TeeWorkerHandler::can_cert(&tee_puk), Error::<T>::TeeNoPermission);
+				let miner = idle_sig_info.miner.clone();
+				Self::apply_idle_space_cert(miner, idle_sig_info, tee_sig, tee_puk)
+			},
+		}
+	}
+
+	/// The weight of handling `message`, the same as the extrinsic it stands in for.
+	pub fn message_weight(message: &DecodedMessage<WorkerCertification<AccountOf<T>>>) -> Weight {
+		match message.payload {
+			WorkerCertification::Tag { .. } => <T as pallet::Config>::WeightInfo::calculate_report(),
+			WorkerCertification::IdleSpace { .. } => <T as pallet::Config>::WeightInfo::cert_idle_space(),
+		}
+	}
+}
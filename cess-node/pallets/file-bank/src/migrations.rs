@@ -1,227 +1,118 @@
-use crate::{AccountOf, Config, Pallet, Weight, BoundedString};
-use codec::{Decode, Encode};
-use frame_support::{
-	codec, generate_storage_alias,
-	pallet_prelude::*,
-	traits::{Get},
-};
-use frame_support::traits::OnRuntimeUpgrade;
-
-/// A struct that does not migration, but only checks that the counter prefix exists and is correct.
-pub struct TestMigrationFileBank<T: crate::Config>(sp_std::marker::PhantomData<T>);
-impl<T: crate::Config> OnRuntimeUpgrade for TestMigrationFileBank<T> {
-	fn on_runtime_upgrade() -> Weight {
-		migrate::<T>()
-	}
+use super::*;
+use frame_support::pallet_prelude::Weight;
 
-	#[cfg(feature = "try-runtime")]
-	fn pre_upgrade() -> Result<(), &'static str> {
-		log::info!("🙋🏽‍file-bank check access");
-		return Ok(())
-	}
+pub fn migrate<T: Config>() -> Weight {
+	let version = StorageVersion::get::<Pallet<T>>();
+	let mut weight: Weight = T::DbWeight::get().reads(1);
 
-	#[cfg(feature = "try-runtime")]
-	fn post_upgrade() -> Result<(), &'static str> {
-		let weights = migrate::<T>();
-		return Ok(())
+	if version < 3 {
+		log::info!("FileBank version 2 -> 3 migrations start!");
+		weight = weight.saturating_add(v3::migrate::<T>());
+		StorageVersion::new(3).put::<Pallet<T>>();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
 	}
-}
-
-pub fn migrate<T: Config>() -> Weight {
-	use frame_support::traits::StorageVersion;
 
-	let version = StorageVersion::get::<Pallet<T>>();
-	let mut weight: Weight = 0;
+	if version < 4 {
+		log::info!("FileBank version 3 -> 4 migrations start!");
+		weight = weight.saturating_add(v4::migrate::<T>());
+		StorageVersion::new(4).put::<Pallet<T>>();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
 
-	if version < 2 {
-        weight = weight.saturating_add(v2::migrate::<T>());
-        StorageVersion::new(2).put::<Pallet<T>>();
+	if version < 5 {
+		log::info!("FileBank version 4 -> 5 migrations start!");
+		weight = weight.saturating_add(v5::migrate::<T>());
+		StorageVersion::new(5).put::<Pallet<T>>();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
 	}
 
 	weight
 }
 
-mod example {
-    use super::*;
-
-    #[derive(Decode, Encode)]
-    struct OldFillerInfo<T: Config> {
-        filler_size: u64,
-        index: u32,
-        block_num: u32,
-        segment_size: u32,
-        scan_size: u32,
-        miner_address: AccountOf<T>,
-        filler_id: BoundedVec<u8, T::StringLimit>,
-        filler_hash: BoundedVec<u8, T::StringLimit>,
-    }
-
-    #[derive(Decode, Encode)]
-    struct NewFillerInfo<T: Config> {
-        filler_size: u64,
-        index: u32,
-        block_num: u32,
-        segment_size: u32,
-        miner_address: AccountOf<T>,
-        filler_id: BoundedVec<u8, T::StringLimit>,
-        filler_hash: BoundedVec<u8, T::StringLimit>,
-        is_delete: bool,
-    }
-
-    generate_storage_alias!(
-		FileBank,
-		FillerMap<T: Config> => DoubleMap<
-            (Blake2_128Concat, T::AccountId),
-            (Blake2_128Concat, BoundedString<T>),
-            NewFillerInfo<T>
-        >
-	);
-
-    pub fn migrate<T: Config>() -> Weight {
-        let mut weight: Weight = 0;
-
-        <FillerMap<T>>::translate(|_key1, _key2, old: OldFillerInfo<T>| {
-            weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
-            Some(NewFillerInfo::<T>{
-                filler_size: old.filler_size,
-                index: old.index,
-                block_num: old.block_num,
-                segment_size: old.segment_size,
-                miner_address: old.miner_address,
-                filler_id: old.filler_id,
-                filler_hash: old.filler_hash,
-                is_delete: false,
-            })
-        });
-
-        weight
-    }
+mod v3 {
+	use super::*;
+
+	// The files stored before versioning become the versions of their names, ordered by completion, the best
+	// record of their upload left. Nothing is deleted here, the newest `MaxObjectVersions` of an object are
+	// recorded and the older files are left as they are.
+	pub fn migrate<T: Config>() -> Weight {
+		let mut weight: Weight = Weight::zero();
+
+		for (file_hash, file) in <File<T>>::iter() {
+			weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			for user_brief in file.owner.iter() {
+				let key = (&user_brief.user, &user_brief.bucket_name, &user_brief.file_name);
+				let mut versions: Vec<ObjectVersion<BlockNumberFor<T>>> = <ObjectVersions<T>>::get(key).into_inner();
+				if versions.iter().any(|version| version.file_hash == file_hash) {
+					continue;
+				}
+				let index = versions.partition_point(|version| version.uploaded <= file.completion);
+				versions.insert(index, ObjectVersion { file_hash, uploaded: file.completion });
+				let excess = versions.len().saturating_sub(T::MaxObjectVersions::get() as usize);
+				versions.drain(.. excess);
+				let versions: BoundedVec<_, T::MaxObjectVersions> = versions.try_into().unwrap_or_default();
+				<ObjectVersions<T>>::insert(key, versions);
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			}
+		}
+
+		weight
+	}
 }
 
-mod v2 {
+mod v4 {
 	use super::*;
-	use cp_cess_common::Hash;
-	use crate::{FillerInfo, FillerMap as NewFillerMap};
-
-	#[derive(Decode, Encode)]
-	struct OldFillerInfo<T: crate::Config> {
-		filler_size: u64,
-		index: u32,
-		block_num: u32,
-		segment_size: u32,
-		scan_size: u32,
-		miner_address: AccountOf<T>,
-		filler_id: BoundedVec<u8, T::StringLimit>,
-		filler_hash: BoundedVec<u8, T::StringLimit>,
-	}
 
-	#[derive(Decode, Encode)]
-	struct NewFillerInfo<T: Config> {
-		filler_size: u64,
-		index: u32,
-		block_num: u32,
-		segment_size: u32,
-		scan_size: u32,
-		miner_address: AccountOf<T>,
-		filler_hash: Hash,
+	// The deals declared before deadlines were queued get a full `DealLife` from now, as if declared now.
+	pub fn migrate<T: Config>() -> Weight {
+		let mut weight: Weight = Weight::zero();
+		let now = <frame_system::Pallet<T>>::block_number();
+
+		for deal_hash in <DealMap<T>>::iter_keys() {
+			weight = weight.saturating_add(T::DbWeight::get().reads(2));
+			if <DealDeadlines<T>>::contains_key(&deal_hash) {
+				continue;
+			}
+			match Pallet::<T>::schedule_deal_deadline(&deal_hash, 0, now) {
+				Ok(()) => weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 2)),
+				Err(e) => log::error!("FileBank migration: deal {:?} not scheduled {:?}", deal_hash, e),
+			}
+		}
+
+		weight
 	}
+}
 
-	generate_storage_alias!(
-		FileBank,
-		FillerMap<T: Config> => DoubleMap<
-            (Blake2_128Concat, AccountOf<T>),
-            (Blake2_128Concat, BoundedVec<u8, T::StringLimit>),
-            OldFillerInfo<T>
-        >
-	);
-
-	// generate_storage_alias!(
-	// 	FileBank,
-	// 	FillerMap<T: Config> => DoubleMap<
-  //           (Blake2_128Concat, T::AccountId),
-  //           (Blake2_128Concat, Hash),
-  //           NewFillerInfo<T>
-  //       >
-	// );
-
-	// #[derive(Decode, Encode)]
-	// struct OldSliceInfo<T: Config> {
-	// 	miner_id: u64,
-	// 	shard_size: u64,
-	// 	block_num: u32,
-	// 	shard_id: BoundedVec<u8, T::StringLimit>,
-	// 	miner_ip: BoundedVec<u8, T::StringLimit>,
-	// 	miner_acc: AccountOf<T>,
-	// }
-	//
-	// #[derive(Decode, Encode)]
-	// struct NewSliceInfo<T: Config> {
-	// 	miner_id: u64,
-	// 	shard_size: u64,
-	// 	block_num: u32,
-	// 	shard_id: [u8; 72],
-	// 	miner_ip: BoundedVec<u8, T::StringLimit>,
-	// 	miner_acc: AccountOf<T>,
-	// }
-	//
-	// generate_storage_alias!(
-	// 	FileBank,
-	// 	File
-	// );
-	//
-	// struct OldPackageDetails<T: Config> {
-	// 	pub(super) space: u128,
-	// 	pub(super) used_space: u128,
-	// 	pub(super) remaining_space: u128,
-	// 	pub(super) tenancy: u32,
-	// 	pub(super) package_type: u8,
-	// 	pub(super) start: BlockNumberOf<T>,
-	// 	pub(super) deadline: BlockNumberOf<T>,
-	// 	pub(super) state: BoundedVec<u8, T::StringLimit>,
-	// }
-	//
-	// struct NewPackageDetails<T: Config> {
-	// 	pub(super) space: u128,
-	// 	pub(super) used_space: u128,
-	// 	pub(super) remaining_space: u128,
-	// 	pub(super) tenancy: u32,
-	// 	pub(super) package_type: PackageType,
-	// 	pub(super) start: BlockNumberOf<T>,
-	// 	pub(super) deadline: BlockNumberOf<T>,
-	// 	pub(super) state: BoundedVec<u8, T::StringLimit>,
-	// }
+mod v5 {
+	use super::*;
 
+	// The miners that reported fragments of an open deal or claimed a restoral order before obligations were
+	// recorded are held to them, as if they reported or claimed now. Runs after v4 so every deal has a deadline.
 	pub fn migrate<T: Config>() -> Weight {
-		let mut weight: Weight = 0;
-		log::info!("-----------------------------test migrations start-----------------------------------");
-		for (miner_acc, filler_id, old) in <FillerMap<T>>::iter() {
-			log::info!("-----------------------------migrations value filler_id:{:?}, len: {}", filler_id.clone(), filler_id.as_slice().len());
-			log::info!("old value filler_size: {}, index: {}, block_num: {}", old.filler_size, old.index, old.block_num);
-			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
-			let filler_hash = Hash::slice_to_array_64(&filler_id).expect("error!");
-			// {
-			// 	Ok(slice) => slice,
-			// 	Err(e) => {
-			// 		log::info!("convert err: {:?}", e);
-			// 		continue;
-			// 	},
-			// };
-			log::info!("convert success!");
-			let filler_hash = Hash(filler_hash);
-			let new_value = FillerInfo::<T>{
-				filler_size: old.filler_size,
-				index: old.index,
-				block_num: old.block_num,
-				segment_size: old.segment_size,
-				scan_size: old.scan_size,
-				miner_address: old.miner_address.clone(),
-				filler_hash: filler_hash.clone(),
+		let mut weight: Weight = Weight::zero();
+		let now = <frame_system::Pallet<T>>::block_number();
+
+		for (deal_hash, deal_info) in <DealMap<T>>::iter() {
+			weight = weight.saturating_add(T::DbWeight::get().reads(2));
+			let Some(end) = Pallet::<T>::deal_obligation_end(&deal_hash).filter(|end| *end >= now) else {
+				continue;
 			};
-			log::info!("start insert");
-			<NewFillerMap<T>>::insert(miner_acc, filler_hash, new_value);
-			log::info!("end insert");
+			for complete_info in deal_info.complete_list.iter() {
+				Pallet::<T>::record_miner_obligation(&complete_info.miner, end);
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			}
 		}
-		log::info!("migrations end!");
+
+		for order in <RestoralOrder<T>>::iter_values() {
+			weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			// An order nobody claimed yet names the miner that lost the fragment.
+			if order.miner == order.origin_miner || order.deadline < now {
+				continue;
+			}
+			Pallet::<T>::record_miner_obligation(&order.miner, order.deadline);
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+		}
+
 		weight
 	}
 }
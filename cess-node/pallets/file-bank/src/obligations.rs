@@ -0,0 +1,37 @@
+//! Miner obligations.
+//!
+//! A miner that reported fragments of a deal has to keep them until the deal completes or is cancelled, which
+//! happens at the latest after its last reassignment. A miner that claimed a restoral order has to restore the
+//! fragment until the claim expires. `MinerObligationEnd` keeps the last of these blocks for each miner, so that
+//! sminer can hold a miner preparing to exit until then, and is cleared once the miner left.
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+	/// The last block the deal `deal_hash` can stay open until, reassignments included.
+	pub(super) fn deal_obligation_end(deal_hash: &Hash) -> Option<BlockNumberFor<T>> {
+		let timer = <DealDeadlines<T>>::get(deal_hash)?;
+		let reassignments = T::MaxDealReassignments::get().saturating_sub(timer.reassigned);
+		let reassignment_life = T::DealLife::get().saturating_mul(reassignments.into());
+
+		Some(timer.deadline.saturating_add(reassignment_life))
+	}
+
+	/// Holds `miner` to an obligation until `end`.
+	pub(super) fn record_miner_obligation(miner: &AccountOf<T>, end: BlockNumberFor<T>) {
+		<MinerObligationEnd<T>>::mutate(miner, |obligation_end| {
+			*obligation_end = Some(obligation_end.map_or(end, |current| current.max(end)));
+		});
+	}
+}
+
+impl<T: Config> MinerObligations<AccountOf<T>, BlockNumberFor<T>> for Pallet<T> {
+	fn obligations_end(miner: &AccountOf<T>) -> Option<BlockNumberFor<T>> {
+		let now = <frame_system::Pallet<T>>::block_number();
+		<MinerObligationEnd<T>>::get(miner).filter(|end| *end >= now)
+	}
+
+	fn clear_obligations(miner: &AccountOf<T>) {
+		<MinerObligationEnd<T>>::remove(miner);
+	}
+}
@@ -0,0 +1,140 @@
+//! Bucket access policies.
+//!
+//! The owner of a bucket, and the gateways it authorized, manage it. The writers of a bucket, its `authority`
+//! after the owner, may upload files into it with the space of the one territory of the owner they were given,
+//! and delete the files the owner holds only in buckets they write. Its readers may read its
+//! files, as may anyone if the bucket is public-read. Reads happen off chain, where gateways and miners check
+//! the permissions of an account through the runtime API. The miner of a claimed restoral order may read the
+//! file it restores a fragment of until the order expires.
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+	/// The permissions of `account` on the bucket `bucket_name` of `owner`.
+	pub fn bucket_permissions(
+		account: &AccountOf<T>,
+		owner: &AccountOf<T>,
+		bucket_name: &BoundedVec<u8, T::NameStrLimit>,
+	) -> BucketPermissions {
+		let Ok(bucket) = <Bucket<T>>::try_get(owner, bucket_name) else {
+			return Default::default();
+		};
+		let manage = Self::check_permission(account.clone(), owner.clone());
+		let write = manage || bucket.authority.contains(account);
+		let read = write
+			|| <PublicReadBuckets<T>>::get(owner, bucket_name)
+			|| <BucketReaders<T>>::get(owner, bucket_name).contains(account);
+
+		BucketPermissions { manage, write, read }
+	}
+
+	/// The permissions of `account` on the file `file_hash`, through any of the buckets holding it.
+	pub fn file_permissions(account: &AccountOf<T>, file_hash: &Hash) -> BucketPermissions {
+		let Ok(file) = <File<T>>::try_get(file_hash) else {
+			return Default::default();
+		};
+		let mut permissions = BucketPermissions::default();
+		for user_brief in file.owner.iter() {
+			let bucket = Self::bucket_permissions(account, &user_brief.user, &user_brief.bucket_name);
+			permissions.manage |= bucket.manage;
+			permissions.write |= bucket.write;
+			permissions.read |= bucket.read;
+		}
+
+		if !permissions.read {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let mut fragments = file.segment_list.iter().flat_map(|segment| segment.fragment_list.iter());
+			permissions.read = fragments.any(|fragment| {
+				<RestoralOrder<T>>::get(&fragment.hash).map_or(false, |order| {
+					&order.miner == account && order.file_hash == *file_hash && now < order.deadline
+				})
+			});
+		}
+
+		permissions
+	}
+
+	/// Ensures `operator` may upload files into the bucket `bucket_name` of `owner`, or create it.
+	pub(super) fn ensure_bucket_writer(
+		operator: &AccountOf<T>,
+		owner: &AccountOf<T>,
+		bucket_name: &BoundedVec<u8, T::NameStrLimit>,
+	) -> DispatchResult {
+		if Self::check_permission(operator.clone(), owner.clone()) {
+			return Ok(());
+		}
+		let bucket = <Bucket<T>>::try_get(owner, bucket_name).map_err(|_| Error::<T>::NoPermission)?;
+		ensure!(bucket.authority.contains(operator), Error::<T>::NoPermission);
+
+		Ok(())
+	}
+
+	/// Ensures `operator` may upload the file described by `user_brief`, into its bucket and with the space of its
+	/// territory.
+	pub(super) fn ensure_bucket_uploader(operator: &AccountOf<T>, user_brief: &UserBrief<T>) -> DispatchResult {
+		if Self::check_permission(operator.clone(), user_brief.user.clone()) {
+			return Ok(());
+		}
+		Self::ensure_bucket_writer(operator, &user_brief.user, &user_brief.bucket_name)?;
+		let territory = <WriterTerritory<T>>::get((&user_brief.user, &user_brief.bucket_name, operator))
+			.ok_or(Error::<T>::NoPermission)?;
+		ensure!(territory == user_brief.territory_name, Error::<T>::NoPermission);
+
+		Ok(())
+	}
+
+	/// Ensures `operator` may delete the file `file_hash` of `owner`, which takes it out of every bucket of the
+	/// owner holding it.
+	pub(super) fn ensure_file_writer(operator: &AccountOf<T>, owner: &AccountOf<T>, file_hash: &Hash) -> DispatchResult {
+		if Self::check_permission(operator.clone(), owner.clone()) {
+			return Ok(());
+		}
+		let file = <File<T>>::try_get(file_hash).map_err(|_| Error::<T>::NonExistent)?;
+		let mut user_briefs = file.owner.iter().filter(|brief| &brief.user == owner).peekable();
+		ensure!(user_briefs.peek().is_some(), Error::<T>::NotOwner);
+		for user_brief in user_briefs {
+			Self::ensure_bucket_writer(operator, owner, &user_brief.bucket_name)?;
+		}
+
+		Ok(())
+	}
+
+	/// Gives `account` the `role` on a bucket, a writer uploading into `territory`, or takes its access away.
+	pub(super) fn set_bucket_role(
+		owner: &AccountOf<T>,
+		bucket_name: &BoundedVec<u8, T::NameStrLimit>,
+		account: &AccountOf<T>,
+		role: Option<BucketRole>,
+		territory: Option<TerrName>,
+	) -> DispatchResult {
+		ensure!(account != owner, Error::<T>::SpecError);
+		ensure!((role == Some(BucketRole::Writer)) == territory.is_some(), Error::<T>::SpecError);
+
+		match territory {
+			Some(territory) => {
+				T::StorageHandle::check_territry_owner(owner, &territory)?;
+				<WriterTerritory<T>>::insert((owner, bucket_name, account), territory);
+			},
+			None => <WriterTerritory<T>>::remove((owner, bucket_name, account)),
+		}
+
+		<Bucket<T>>::try_mutate(owner, bucket_name, |bucket_opt| -> DispatchResult {
+			let bucket = bucket_opt.as_mut().ok_or(Error::<T>::NonExistent)?;
+			bucket.authority.retain(|acc| acc != account);
+			if role == Some(BucketRole::Writer) {
+				bucket.authority.try_push(account.clone()).map_err(|_e| Error::<T>::LengthExceedsLimit)?;
+			}
+
+			Ok(())
+		})?;
+
+		<BucketReaders<T>>::try_mutate(owner, bucket_name, |readers| -> DispatchResult {
+			readers.retain(|acc| acc != account);
+			if role == Some(BucketRole::Reader) {
+				readers.try_push(account.clone()).map_err(|_e| Error::<T>::LengthExceedsLimit)?;
+			}
+
+			Ok(())
+		})
+	}
+}
@@ -0,0 +1,92 @@
+//! Restoral bounties and claims.
+//!
+//! A restoral order carries a bounty of `RestoralBounty`. The miner that completes the order is paid the bounty
+//! out of the reserved collateral of the miner that lost the fragment, as far as that collateral goes. A miner may not
+//! claim a fragment of a segment it already stores a fragment of. A claim lasts `RestoralOrderLife` blocks. If it
+//! is still open by then, the order is offered to other miners again.
+
+use super::*;
+use frame_support::storage::with_storage_layer;
+
+impl<T: Config> Pallet<T> {
+	/// Ensures `miner` stores no fragment of the segment holding `fragment_hash`.
+	pub(super) fn ensure_restoral_eligible(
+		file: &FileInfo<T>,
+		fragment_hash: &Hash,
+		miner: &AccountOf<T>,
+	) -> DispatchResult {
+		let segment = file
+			.segment_list
+			.iter()
+			.find(|segment| segment.fragment_list.iter().any(|fragment| &fragment.hash == fragment_hash))
+			.ok_or(Error::<T>::SpecError)?;
+		ensure!(
+			!segment.fragment_list.iter().any(|fragment| &fragment.miner == miner),
+			Error::<T>::RulesNotAllowed
+		);
+
+		Ok(())
+	}
+
+	/// Queues the claim of a restoral order to be offered again at its deadline, or at the first block after it
+	/// with room for it, which becomes its deadline.
+	pub(super) fn enqueue_restoral_expiry(
+		fragment_hash: &Hash,
+		deadline: BlockNumberFor<T>,
+	) -> Result<BlockNumberFor<T>, DispatchError> {
+		let mut at = deadline;
+		for _ in 0 .. EXPIRY_SEARCH {
+			if <RestoralExpiry<T>>::try_mutate(&at, |queue| queue.try_push(*fragment_hash)).is_ok() {
+				return Ok(at);
+			}
+			at = at.checked_add(&1u32.saturated_into()).ok_or(Error::<T>::Overflow)?;
+		}
+
+		Err(Error::<T>::StorageLimitReached)?
+	}
+
+	/// Offers the restoral orders whose claims expire at `now` to other miners again.
+	pub(super) fn expire_restoral_claims(now: BlockNumberFor<T>) -> Weight {
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+		for order_id in <RestoralExpiry<T>>::take(&now) {
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			<RestoralOrder<T>>::mutate(&order_id, |order_opt| {
+				let Some(order) = order_opt else {
+					return;
+				};
+				// Completed orders are gone, and orders claimed again since have a later deadline.
+				if order.deadline == now && order.miner != order.origin_miner {
+					let miner = sp_std::mem::replace(&mut order.miner, order.origin_miner.clone());
+					Self::deposit_event(Event::<T>::RestoralOrderReoffered { miner, order_id });
+				}
+			});
+		}
+
+		weight
+	}
+
+	/// Pays the bounty of a completed restoral order to `miner`. A bounty that cannot be paid does not hold up
+	/// the completion, it is reported and dropped.
+	pub(super) fn pay_restoral_bounty(order: &RestoralOrderInfo<T>, miner: &AccountOf<T>) {
+		let bounty = <RestoralBounties<T>>::take(&order.fragment_hash);
+		if bounty == 0 {
+			return;
+		}
+		match with_storage_layer(|| T::MinerControl::pay_from_collateral(&order.origin_miner, miner, bounty)) {
+			Ok(amount) => Self::deposit_event(Event::<T>::RestoralBountyPaid {
+				miner: miner.clone(),
+				order_id: order.fragment_hash,
+				amount,
+			}),
+			Err(e) => {
+				log::error!("[FileBank] -> [pay_restoral_bounty]: order {:?} unpaid {:?}", order.fragment_hash, e);
+				Self::deposit_event(Event::<T>::RestoralBountyUnpaid {
+					miner: miner.clone(),
+					order_id: order.fragment_hash,
+					amount: bounty,
+				});
+			},
+		}
+	}
+}
@@ -43,6 +43,21 @@ pub struct DealInfo<T: Config> {
 	pub(super) complete_list: BoundedVec<CompleteInfo<T>, T::FragmentCount>,
 }
 
+/// The block a deal expires at, how many times it was reopened to other miners, and the block it was declared at.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct DealDeadline<Block> {
+	pub(super) deadline: Block,
+	pub(super) reassigned: u32,
+	pub(super) declared: Block,
+}
+
+/// A version of an object, and the block its file was uploaded at, which orders the versions.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct ObjectVersion<Block> {
+	pub(super) file_hash: Hash,
+	pub(super) uploaded: Block,
+}
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 #[codec(mel_bound())]
@@ -93,9 +108,19 @@ pub struct UserFileSliceInfo {
 #[codec(mel_bound())]
 pub struct BucketInfo<T: Config> {
 	pub(super) object_list: BoundedVec<Hash, T::UserFileLimit>,
+	// The accounts that may write into the bucket, the owner first.
 	pub(super) authority: BoundedVec<AccountOf<T>, ConstU32<1032>>,
 }
 
+/// The access an account other than the owner can be given to a bucket.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum BucketRole {
+	/// May upload files into the bucket, charged to the owner's territories, and delete them.
+	Writer,
+	/// May read the files of the bucket.
+	Reader,
+}
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 #[codec(mel_bound())]
@@ -0,0 +1,138 @@
+//! Object versioning.
+//!
+//! Every file stored into a bucket becomes a version of its name there, recorded in `ObjectVersions` in the
+//! order the files were uploaded, so a deal that completes late does not overtake a later upload. A bucket keeps
+//! the versions of each object up to its retention, `MaxObjectVersions` unless set lower, and deletes the oldest
+//! ones beyond it, releasing their space in the territory. Only the object is deleted, the owner keeps the same
+//! file stored under other names.
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+	/// The latest version of the object `file_name` in a bucket.
+	pub fn latest_object_version(
+		owner: &AccountOf<T>,
+		bucket_name: &BoundedVec<u8, T::NameStrLimit>,
+		file_name: &BoundedVec<u8, T::NameStrLimit>,
+	) -> Option<Hash> {
+		<ObjectVersions<T>>::get((owner, bucket_name, file_name)).last().map(|version| version.file_hash)
+	}
+
+	/// Whether `file_hash` is a version of the object `file_name` in a bucket.
+	pub fn is_object_version(
+		owner: &AccountOf<T>,
+		bucket_name: &BoundedVec<u8, T::NameStrLimit>,
+		file_name: &BoundedVec<u8, T::NameStrLimit>,
+		file_hash: &Hash,
+	) -> bool {
+		<ObjectVersions<T>>::get((owner, bucket_name, file_name)).iter().any(|version| &version.file_hash == file_hash)
+	}
+
+	/// The number of versions a bucket keeps for each object.
+	pub fn bucket_retention_limit(owner: &AccountOf<T>, bucket_name: &BoundedVec<u8, T::NameStrLimit>) -> u32 {
+		<BucketRetention<T>>::get(owner, bucket_name).unwrap_or_else(T::MaxObjectVersions::get)
+	}
+
+	/// Records `file_hash`, uploaded at `uploaded`, as a version of the object described by `user_brief`, deleting
+	/// the oldest versions the bucket no longer keeps.
+	pub(super) fn record_object_version(
+		user_brief: &UserBrief<T>,
+		file_hash: &Hash,
+		uploaded: BlockNumberFor<T>,
+	) -> DispatchResult {
+		let key = (&user_brief.user, &user_brief.bucket_name, &user_brief.file_name);
+		let retention = Self::bucket_retention_limit(&user_brief.user, &user_brief.bucket_name).max(1) as usize;
+
+		let mut versions: Vec<ObjectVersion<BlockNumberFor<T>>> = <ObjectVersions<T>>::get(key).into_inner();
+		versions.retain(|version| &version.file_hash != file_hash);
+		let index = versions.partition_point(|version| version.uploaded <= uploaded);
+		versions.insert(index, ObjectVersion { file_hash: *file_hash, uploaded });
+		let pruned: Vec<Hash> = versions
+			.drain(.. versions.len().saturating_sub(retention))
+			.map(|version| version.file_hash)
+			.collect();
+		let versions: BoundedVec<_, T::MaxObjectVersions> =
+			versions.try_into().map_err(|_e| Error::<T>::BoundedVecError)?;
+		<ObjectVersions<T>>::insert(key, versions);
+
+		for hash in pruned {
+			if !<File<T>>::contains_key(&hash) {
+				continue;
+			}
+			Self::delete_object_version_of(user_brief, &hash)?;
+			Self::deposit_event(Event::<T>::PruneObjectVersion {
+				owner: user_brief.user.clone(),
+				bucket_name: user_brief.bucket_name.to_vec(),
+				file_name: user_brief.file_name.to_vec(),
+				file_hash: hash,
+			});
+		}
+
+		Ok(())
+	}
+
+	/// Drops `file_hash` from the version history of the object it was stored as.
+	pub(super) fn forget_object_version(user_brief: &UserBrief<T>, file_hash: &Hash) {
+		let key = (&user_brief.user, &user_brief.bucket_name, &user_brief.file_name);
+		<ObjectVersions<T>>::mutate_exists(key, |versions_opt| {
+			if let Some(versions) = versions_opt {
+				versions.retain(|version| &version.file_hash != file_hash);
+				if versions.is_empty() {
+					*versions_opt = None;
+				}
+			}
+		});
+	}
+
+	/// Deletes the version `file_hash` of the object described by `user_brief`. If the owner holds the file under
+	/// other names too, only this one is dropped and the space it took in its territory released.
+	pub(super) fn delete_object_version_of(user_brief: &UserBrief<T>, file_hash: &Hash) -> DispatchResult {
+		let file = <File<T>>::try_get(file_hash).map_err(|_| Error::<T>::NonExistent)?;
+		if file.owner.iter().filter(|brief| brief.user == user_brief.user).count() <= 1 {
+			return Self::delete_object(&user_brief.user, file_hash);
+		}
+
+		let index = file.owner.iter()
+			.position(|brief| {
+				brief.user == user_brief.user
+					&& brief.bucket_name == user_brief.bucket_name
+					&& brief.file_name == user_brief.file_name
+			})
+			.ok_or(Error::<T>::NonExistent)?;
+		let mut owner = file.owner.clone();
+		let brief = owner.remove(index);
+		<File<T>>::try_mutate(file_hash, |file_opt| -> DispatchResult {
+			let file = file_opt.as_mut().ok_or(Error::<T>::NonExistent)?;
+			file.owner = owner.clone();
+			Ok(())
+		})?;
+
+		let file_size = Self::cal_file_size(file.segment_list.len() as u128);
+		T::StorageHandle::sub_territory_used_space(&brief.user, &brief.territory_name, file_size)?;
+		// The bucket keeps the file while it holds it under another name.
+		if !owner.iter().any(|other| other.user == brief.user && other.bucket_name == brief.bucket_name) {
+			<Bucket<T>>::try_mutate(&brief.user, &brief.bucket_name, |bucket_opt| -> DispatchResult {
+				let bucket = bucket_opt.as_mut().ok_or(Error::<T>::NonExistent)?;
+				bucket.object_list.retain(|hash| hash != file_hash);
+				Ok(())
+			})?;
+		}
+		Self::forget_object_version(&brief, file_hash);
+		<UserHoldFileList<T>>::try_mutate(&brief.user, |file_list| -> DispatchResult {
+			if let Some(index) = file_list.iter()
+				.position(|slice| &slice.file_hash == file_hash && slice.territory_name == brief.territory_name)
+			{
+				file_list.remove(index);
+			}
+			Ok(())
+		})
+	}
+
+	/// Deletes the file `file_hash` held by `owner`, releasing its space in the owner's territory.
+	pub(super) fn delete_object(owner: &AccountOf<T>, file_hash: &Hash) -> DispatchResult {
+		let file = <File<T>>::try_get(file_hash).map_err(|_| Error::<T>::NonExistent)?;
+		let _ = Self::delete_user_file(file_hash, owner, &file)?;
+		Self::bucket_remove_file(file_hash, owner, &file)?;
+		Self::remove_user_hold_file_list(file_hash, owner)
+	}
+}
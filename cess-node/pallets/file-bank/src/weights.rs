@@ -48,6 +48,10 @@ pub trait WeightInfo {
 	fn claim_restoral_order() -> Weight;
 	fn claim_restoral_noexist_order() -> Weight;
 	fn restoral_order_complete() -> Weight;
+	fn delete_object_version() -> Weight;
+	fn set_bucket_retention() -> Weight;
+	fn set_bucket_access() -> Weight;
+	fn set_bucket_public_read() -> Weight;
 }
 
 /// Weights for `pallet_file_bank` using the Substrate node and recommended hardware.
@@ -74,6 +78,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(6_u64))
 			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
+	/// Storage: `FileBank::Bucket` (r:1 w:0)
+	/// Proof: `FileBank::Bucket` (`max_values`: None, `max_size`: Some(32033158), added: 32035633, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::WriterTerritory` (r:1 w:0)
+	/// Proof: `FileBank::WriterTerritory` (`max_values`: None, `max_size`: Some(226), added: 2701, mode: `MaxEncodedLen`)
 	/// Storage: `FileBank::File` (r:1 w:0)
 	/// Proof: `FileBank::File` (`max_values`: None, `max_size`: Some(12489107), added: 12491582, mode: `MaxEncodedLen`)
 	/// Storage: `StorageHandler::Territory` (r:1 w:1)
@@ -83,13 +91,13 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// The range of component `v` is `[1, 30]`.
 	fn upload_declaration(v: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `707`
-		//  Estimated: `12492572`
+		//  Measured:  `1138`
+		//  Estimated: `32036623`
 		// Minimum execution time: 28_071_000 picoseconds.
-		Weight::from_parts(30_658_375, 12492572)
+		Weight::from_parts(34_912_207, 32036623)
 			// Standard Error: 53_614
 			.saturating_add(Weight::from_parts(1_036_572, 0).saturating_mul(v.into()))
-			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 	/// Storage: `Sminer::MinerItems` (r:1 w:1)
@@ -278,6 +286,74 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(6_u64))
 			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
+	/// Storage: `FileBank::ObjectVersions` (r:1 w:1)
+	/// Proof: `FileBank::ObjectVersions` (`max_values`: None, `max_size`: Some(1256), added: 3731, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::File` (r:1 w:1)
+	/// Proof: `FileBank::File` (`max_values`: None, `max_size`: Some(12489107), added: 12491582, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::RestoralTarget` (r:12 w:0)
+	/// Proof: `Sminer::RestoralTarget` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::MinerItems` (r:12 w:12)
+	/// Proof: `Sminer::MinerItems` (`max_values`: None, `max_size`: Some(203179), added: 205654, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::Territory` (r:1 w:1)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TotalServiceSpace` (r:1 w:1)
+	/// Proof: `StorageHandler::TotalServiceSpace` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::Bucket` (r:1 w:1)
+	/// Proof: `FileBank::Bucket` (`max_values`: None, `max_size`: Some(32033158), added: 32035633, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::UserHoldFileList` (r:1 w:1)
+	/// Proof: `FileBank::UserHoldFileList` (`max_values`: None, `max_size`: Some(72000052), added: 72002527, mode: `MaxEncodedLen`)
+	fn delete_object_version() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `41396`
+		//  Estimated: `72003517`
+		// Minimum execution time: 321_508_000 picoseconds.
+		Weight::from_parts(351_227_000, 72003517)
+			.saturating_add(T::DbWeight::get().reads(30_u64))
+			.saturating_add(T::DbWeight::get().writes(18_u64))
+	}
+	/// Storage: `FileBank::Bucket` (r:1 w:0)
+	/// Proof: `FileBank::Bucket` (`max_values`: None, `max_size`: Some(32033158), added: 32035633, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::BucketRetention` (r:0 w:1)
+	/// Proof: `FileBank::BucketRetention` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	fn set_bucket_retention() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `398`
+		//  Estimated: `32036623`
+		// Minimum execution time: 16_842_000 picoseconds.
+		Weight::from_parts(17_395_000, 32036623)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `FileBank::Bucket` (r:1 w:1)
+	/// Proof: `FileBank::Bucket` (`max_values`: None, `max_size`: Some(32033158), added: 32035633, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::BucketReaders` (r:1 w:1)
+	/// Proof: `FileBank::BucketReaders` (`max_values`: None, `max_size`: Some(33150), added: 35625, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::Territory` (r:1 w:0)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::WriterTerritory` (r:0 w:1)
+	/// Proof: `FileBank::WriterTerritory` (`max_values`: None, `max_size`: Some(226), added: 2701, mode: `MaxEncodedLen`)
+	fn set_bucket_access() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `594`
+		//  Estimated: `32036623`
+		// Minimum execution time: 31_208_000 picoseconds.
+		Weight::from_parts(32_471_000, 32036623)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `FileBank::Bucket` (r:1 w:0)
+	/// Proof: `FileBank::Bucket` (`max_values`: None, `max_size`: Some(32033158), added: 32035633, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::PublicReadBuckets` (r:0 w:1)
+	/// Proof: `FileBank::PublicReadBuckets` (`max_values`: None, `max_size`: Some(115), added: 2590, mode: `MaxEncodedLen`)
+	fn set_bucket_public_read() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `398`
+		//  Estimated: `32036623`
+		// Minimum execution time: 16_519_000 picoseconds.
+		Weight::from_parts(17_102_000, 32036623)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -303,6 +379,10 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(6_u64))
 			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
+	/// Storage: `FileBank::Bucket` (r:1 w:0)
+	/// Proof: `FileBank::Bucket` (`max_values`: None, `max_size`: Some(32033158), added: 32035633, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::WriterTerritory` (r:1 w:0)
+	/// Proof: `FileBank::WriterTerritory` (`max_values`: None, `max_size`: Some(226), added: 2701, mode: `MaxEncodedLen`)
 	/// Storage: `FileBank::File` (r:1 w:0)
 	/// Proof: `FileBank::File` (`max_values`: None, `max_size`: Some(12489107), added: 12491582, mode: `MaxEncodedLen`)
 	/// Storage: `StorageHandler::Territory` (r:1 w:1)
@@ -312,13 +392,13 @@ impl WeightInfo for () {
 	/// The range of component `v` is `[1, 30]`.
 	fn upload_declaration(v: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `707`
-		//  Estimated: `12492572`
+		//  Measured:  `1138`
+		//  Estimated: `32036623`
 		// Minimum execution time: 28_071_000 picoseconds.
-		Weight::from_parts(30_658_375, 12492572)
+		Weight::from_parts(34_912_207, 32036623)
 			// Standard Error: 53_614
 			.saturating_add(Weight::from_parts(1_036_572, 0).saturating_mul(v.into()))
-			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
 	/// Storage: `Sminer::MinerItems` (r:1 w:1)
@@ -507,4 +587,72 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(6_u64))
 			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
+	/// Storage: `FileBank::ObjectVersions` (r:1 w:1)
+	/// Proof: `FileBank::ObjectVersions` (`max_values`: None, `max_size`: Some(1256), added: 3731, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::File` (r:1 w:1)
+	/// Proof: `FileBank::File` (`max_values`: None, `max_size`: Some(12489107), added: 12491582, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::RestoralTarget` (r:12 w:0)
+	/// Proof: `Sminer::RestoralTarget` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::MinerItems` (r:12 w:12)
+	/// Proof: `Sminer::MinerItems` (`max_values`: None, `max_size`: Some(203179), added: 205654, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::Territory` (r:1 w:1)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TotalServiceSpace` (r:1 w:1)
+	/// Proof: `StorageHandler::TotalServiceSpace` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::Bucket` (r:1 w:1)
+	/// Proof: `FileBank::Bucket` (`max_values`: None, `max_size`: Some(32033158), added: 32035633, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::UserHoldFileList` (r:1 w:1)
+	/// Proof: `FileBank::UserHoldFileList` (`max_values`: None, `max_size`: Some(72000052), added: 72002527, mode: `MaxEncodedLen`)
+	fn delete_object_version() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `41396`
+		//  Estimated: `72003517`
+		// Minimum execution time: 321_508_000 picoseconds.
+		Weight::from_parts(351_227_000, 72003517)
+			.saturating_add(RocksDbWeight::get().reads(30_u64))
+			.saturating_add(RocksDbWeight::get().writes(18_u64))
+	}
+	/// Storage: `FileBank::Bucket` (r:1 w:0)
+	/// Proof: `FileBank::Bucket` (`max_values`: None, `max_size`: Some(32033158), added: 32035633, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::BucketRetention` (r:0 w:1)
+	/// Proof: `FileBank::BucketRetention` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	fn set_bucket_retention() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `398`
+		//  Estimated: `32036623`
+		// Minimum execution time: 16_842_000 picoseconds.
+		Weight::from_parts(17_395_000, 32036623)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `FileBank::Bucket` (r:1 w:1)
+	/// Proof: `FileBank::Bucket` (`max_values`: None, `max_size`: Some(32033158), added: 32035633, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::BucketReaders` (r:1 w:1)
+	/// Proof: `FileBank::BucketReaders` (`max_values`: None, `max_size`: Some(33150), added: 35625, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::Territory` (r:1 w:0)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::WriterTerritory` (r:0 w:1)
+	/// Proof: `FileBank::WriterTerritory` (`max_values`: None, `max_size`: Some(226), added: 2701, mode: `MaxEncodedLen`)
+	fn set_bucket_access() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `594`
+		//  Estimated: `32036623`
+		// Minimum execution time: 31_208_000 picoseconds.
+		Weight::from_parts(32_471_000, 32036623)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `FileBank::Bucket` (r:1 w:0)
+	/// Proof: `FileBank::Bucket` (`max_values`: None, `max_size`: Some(32033158), added: 32035633, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::PublicReadBuckets` (r:0 w:1)
+	/// Proof: `FileBank::PublicReadBuckets` (`max_values`: None, `max_size`: Some(115), added: 2590, mode: `MaxEncodedLen`)
+	fn set_bucket_public_read() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `398`
+		//  Estimated: `32036623`
+		// Minimum execution time: 16_519_000 picoseconds.
+		Weight::from_parts(17_102_000, 32036623)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
@@ -1,7 +1,22 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use ces_mq::{MessageOrigin, Path, RetainedMessage};
+use sp_runtime::traits::NumberFor;
+use sp_std::vec::Vec;
+
 sp_api::decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait MqApi {
-		fn sender_sequence(sender: &ces_mq::MessageOrigin) -> Option<u64>;
+		fn sender_sequence(sender: &MessageOrigin) -> Option<u64>;
+
+		/// Outbound messages still retained on chain, pushed in blocks `from..=to` and optionally
+		/// filtered by sender and topic.
+		#[api_version(2)]
+		fn retained_messages(
+			sender: Option<MessageOrigin>,
+			topic: Option<Path>,
+			from: NumberFor<Block>,
+			to: NumberFor<Block>,
+		) -> Vec<RetainedMessage<NumberFor<Block>>>;
 	}
 }
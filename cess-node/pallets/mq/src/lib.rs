@@ -14,7 +14,9 @@ pub use frame_support::storage::generator::StorageMap as StorageMapTrait;
 #[frame_support::pallet]
 pub mod pallet {
 	use ces_types::{
-		messaging::{BindTopic, Message, MessageOrigin, Path, SignedMessage},
+		messaging::{
+			BindTopic, EncryptedEnvelope, Message, MessageOrigin, Path, RetainedMessage, SignedMessage, Topic,
+		},
 		wrap_content_to_sign, MasterPublicKey, SignedContentType, WorkerPublicKey,
 	};
 	use frame_support::{
@@ -25,6 +27,7 @@ pub mod pallet {
 	use frame_system::pallet_prelude::*;
 	use primitive_types::H256;
 	use sp_core::sr25519;
+	use sp_runtime::traits::{One, Saturating, Zero};
 	use sp_std::vec::Vec;
 
 	#[pallet::config]
@@ -32,6 +35,15 @@ pub mod pallet {
 		type QueueNotifyConfig: QueueNotifyConfig;
 		type CallMatcher: CallMatcher<Self>;
 		type MasterPubkeySupplier: MasterPubkeySupplier;
+		/// Number of blocks an outbound message stays queryable after the block it was pushed in.
+		///
+		/// Zero disables the retention.
+		#[pallet::constant]
+		type MessageRetentionPeriod: Get<BlockNumberFor<Self>>;
+		/// Number of outbound messages retained per block, the ones pushed beyond it are only
+		/// visible for the block.
+		#[pallet::constant]
+		type MaxRetainedPerBlock: Get<u32>;
 	}
 
 	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
@@ -55,6 +67,37 @@ pub mod pallet {
 	#[pallet::getter(fn messages)]
 	pub type OutboundMessages<T> = StorageValue<_, Vec<Message>, ValueQuery>;
 
+	/// Outbound messages bucketed by the block they were pushed in.
+	///
+	/// A bucket is pruned once it falls out of `MessageRetentionPeriod`.
+	#[pallet::storage]
+	pub type RetainedMessages<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, BoundedVec<Message, T::MaxRetainedPerBlock>, ValueQuery>;
+
+	/// The indexes in `RetainedMessages` of the messages of each sender, by block.
+	#[pallet::storage]
+	pub type RetainedBySender<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		Blake2_128Concat,
+		MessageOrigin,
+		BoundedVec<u32, T::MaxRetainedPerBlock>,
+		ValueQuery,
+	>;
+
+	/// The indexes in `RetainedMessages` of the messages to each topic, by block.
+	#[pallet::storage]
+	pub type RetainedByTopic<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		Blake2_128Concat,
+		Path,
+		BoundedVec<u32, T::MaxRetainedPerBlock>,
+		ValueQuery,
+	>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		BadSender,
@@ -73,8 +116,14 @@ pub mod pallet {
 		T::AccountId: IntoH256,
 	{
 		/// Syncs an unverified offchain message to the message queue
+		///
+		/// The message is handled right away, so it is charged the weight of its handler as well.
 		#[pallet::call_index(0)]
-		#[pallet::weight(Weight::from_parts(10_000u64, 0) + T::DbWeight::get().writes(1u64))]
+		#[pallet::weight(
+			Weight::from_parts(10_000u64, 0) +
+				T::DbWeight::get().writes(1u64) +
+				Pallet::<T>::dispatch_weight(&signed_message.message)
+		)]
 		pub fn sync_offchain_message(origin: OriginFor<T>, signed_message: SignedMessage) -> DispatchResult {
 			ensure_signed(origin)?;
 
@@ -100,7 +149,7 @@ pub mod pallet {
 		// Messaging API for end user.
 		// TODO: confirm the weight
 		#[pallet::call_index(1)]
-		#[pallet::weight(Weight::from_parts(10_000u64, 0) + T::DbWeight::get().writes(1u64))]
+		#[pallet::weight(Weight::from_parts(10_000u64, 0) + Pallet::<T>::retain_weight())]
 		pub fn push_message(origin: OriginFor<T>, destination: Vec<u8>, payload: Vec<u8>) -> DispatchResult {
 			let origin = ensure_signed(origin)?;
 			let sender = MessageOrigin::AccountId(origin.into_h256());
@@ -111,7 +160,7 @@ pub mod pallet {
 
 		// Force push a from-pallet message.
 		#[pallet::call_index(2)]
-		#[pallet::weight(Weight::from_parts(10_000u64, 0) + T::DbWeight::get().writes(1u64))]
+		#[pallet::weight(Weight::from_parts(10_000u64, 0) + Pallet::<T>::retain_weight())]
 		pub fn force_push_pallet_message(
 			origin: OriginFor<T>,
 			destination: Vec<u8>,
@@ -123,6 +172,23 @@ pub mod pallet {
 			Self::dispatch_message(message);
 			Ok(())
 		}
+
+		// Encrypted messaging API for end user.
+		//
+		// The envelope is opened by its recipient inside the TEE, and the plaintext is dispatched to
+		// the off-chain subscribers of the inner topic.
+		#[pallet::call_index(3)]
+		#[pallet::weight(Weight::from_parts(10_000u64, 0) + Pallet::<T>::retain_weight())]
+		pub fn push_encrypted_message(origin: OriginFor<T>, envelope: EncryptedEnvelope) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			ensure!(
+				Topic::new(envelope.topic.clone()).is_offchain() && envelope.topic != EncryptedEnvelope::topic(),
+				Error::<T>::BadDestination
+			);
+			let sender = MessageOrigin::AccountId(origin.into_h256());
+			Self::push_bound_message(sender, envelope);
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -159,10 +225,104 @@ pub mod pallet {
 			}
 			// Notify the off-chain components
 			if T::QueueNotifyConfig::should_push_message(&message) {
+				if !T::MessageRetentionPeriod::get().is_zero() {
+					Self::retain_message(&message);
+				}
 				OutboundMessages::<T>::append(message);
 			}
 		}
 
+		// Retains `message` in the bucket of the current block and indexes it, unless the bucket is full.
+		fn retain_message(message: &Message) {
+			let now = frame_system::Pallet::<T>::block_number();
+			let index = RetainedMessages::<T>::decode_len(now).unwrap_or(0) as u32;
+			if RetainedMessages::<T>::try_append(now, message).is_err() {
+				return
+			}
+			// The indexes of a block are bounded like its bucket, they cannot overflow.
+			let _ = RetainedBySender::<T>::try_append(now, &message.sender, index);
+			let _ = RetainedByTopic::<T>::try_append(now, message.destination.path(), index);
+		}
+
+		// Prunes the bucket of `block_number` and its indexes.
+		fn prune_retained(block_number: BlockNumberFor<T>) -> Weight {
+			let limit = T::MaxRetainedPerBlock::get();
+			RetainedMessages::<T>::remove(block_number);
+			let senders = RetainedBySender::<T>::clear_prefix(block_number, limit, None);
+			let topics = RetainedByTopic::<T>::clear_prefix(block_number, limit, None);
+
+			T::DbWeight::get().reads_writes(
+				senders.loops.saturating_add(topics.loops) as u64,
+				senders.unique.saturating_add(topics.unique).saturating_add(1) as u64,
+			)
+		}
+
+		/// The weight of retaining an outbound message: its bucket and both indexes.
+		pub fn retain_weight() -> Weight {
+			T::DbWeight::get().reads_writes(3, 4)
+		}
+
+		/// The weight of dispatching `message`: its handlers and, when it goes off-chain, retaining it.
+		pub fn dispatch_weight(message: &Message) -> Weight {
+			let mut weight = T::QueueNotifyConfig::message_weight(message);
+			if T::QueueNotifyConfig::should_push_message(message) {
+				weight = weight.saturating_add(Self::retain_weight());
+			}
+			weight
+		}
+
+		/// Retained outbound messages pushed in blocks `from..=to`, optionally filtered by sender and
+		/// topic.
+		///
+		/// The range is clamped to the retention window. A filtered query only reads the buckets the
+		/// sender or topic index points at.
+		pub fn retained_messages(
+			sender: Option<MessageOrigin>,
+			topic: Option<Path>,
+			from: BlockNumberFor<T>,
+			to: BlockNumberFor<T>,
+		) -> Vec<RetainedMessage<BlockNumberFor<T>>> {
+			let now = frame_system::Pallet::<T>::block_number();
+			let oldest = now.saturating_sub(T::MessageRetentionPeriod::get()).saturating_add(One::one());
+			let mut block_number = from.max(oldest);
+			let to = to.min(now);
+
+			let mut result = Vec::new();
+			while block_number <= to {
+				let indexes: Option<Vec<u32>> = match (&sender, &topic) {
+					(Some(sender), Some(topic)) => {
+						let to_topic = RetainedByTopic::<T>::get(block_number, topic);
+						Some(
+							RetainedBySender::<T>::get(block_number, sender)
+								.into_iter()
+								.filter(|index| to_topic.contains(index))
+								.collect(),
+						)
+					},
+					(Some(sender), None) => Some(RetainedBySender::<T>::get(block_number, sender).into_inner()),
+					(None, Some(topic)) => Some(RetainedByTopic::<T>::get(block_number, topic).into_inner()),
+					(None, None) => None,
+				};
+				match indexes {
+					Some(indexes) if indexes.is_empty() => {},
+					Some(indexes) => {
+						let messages = RetainedMessages::<T>::get(block_number);
+						for index in indexes {
+							if let Some(message) = messages.get(index as usize) {
+								result.push(RetainedMessage { block_number, index, message: message.clone() });
+							}
+						}
+					},
+					None =>
+						for (index, message) in RetainedMessages::<T>::get(block_number).into_iter().enumerate() {
+							result.push(RetainedMessage { block_number, index: index as u32, message });
+						},
+				}
+				block_number.saturating_inc();
+			}
+			result
+		}
+
 		pub fn push_message_to<M: Encode>(topic: impl Into<Path>, sender: MessageOrigin, payload: M) {
 			let message = Message::new(sender, topic, payload.encode());
 			Self::dispatch_message(message);
@@ -188,18 +348,26 @@ pub mod pallet {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
 			// Clear the previously pushed offchain messages
 			OutboundMessages::<T>::kill();
+			let mut weight = T::DbWeight::get().reads_writes(1, 2);
+
+			// Prune the bucket falling out of the retention window
+			let period = T::MessageRetentionPeriod::get();
+			if !period.is_zero() && now >= period {
+				weight = weight.saturating_add(Self::prune_retained(now - period));
+			}
 
 			// Send out queued message from the previous block
 			if let Some(msgs) = QueuedOutboundMessage::<T>::take() {
 				for message in msgs.into_iter() {
+					weight = weight.saturating_add(Self::dispatch_weight(&message));
 					Self::dispatch_message(message);
 				}
 			}
 
-			Weight::zero()
+			weight
 		}
 	}
 
@@ -213,6 +381,10 @@ pub mod pallet {
 		fn on_message_received(_message: &Message) -> DispatchResult {
 			Ok(())
 		}
+		/// The weight of handling an incoming message
+		fn message_weight(_message: &Message) -> Weight {
+			Weight::zero()
+		}
 	}
 	impl QueueNotifyConfig for () {}
 
@@ -0,0 +1,15 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use cp_scheduler_credit::SchedulerCreditBreakdown;
+
+sp_api::decl_runtime_apis! {
+	pub trait SchedulerCreditApi<AccountId>
+	where
+		AccountId: Codec,
+	{
+		/// The points, punishments and credit value of the TEE worker bonded to `stash`
+		/// for the current and the previous period.
+		fn credit_breakdown(stash: AccountId) -> SchedulerCreditBreakdown;
+	}
+}
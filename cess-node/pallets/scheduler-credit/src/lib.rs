@@ -1,5 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod migrations;
+
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -24,7 +26,10 @@ use cessp_consensus_rrsc::traits::ValidatorCredits;
 
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
-use cp_scheduler_credit::{SchedulerCreditCounter, SchedulerStashAccountFinder};
+use cp_scheduler_credit::{
+	ActivityPoints, ActivityWeights, CreditActivity, PeriodCredit, SchedulerCreditBreakdown, SchedulerCreditCounter,
+	SchedulerStashAccountFinder,
+};
 use cp_cess_common::*;
 
 pub use pallet::*;
@@ -65,13 +70,14 @@ const PERIOD_WEIGHT: [Percent; 5] = [
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
 pub struct SchedulerCounterEntry {
-	pub proceed_block_size: u64,
+	pub points: ActivityPoints,
 	pub punishment_count: u32,
 }
 
 impl SchedulerCounterEntry {
-	pub fn increase_block_size<T: Config>(&mut self, block_size: u64) -> DispatchResult {
-		self.proceed_block_size = self.proceed_block_size.checked_add(block_size).ok_or(Error::<T>::Overflow)?;
+	pub fn increase_points<T: Config>(&mut self, activity: CreditActivity, points: u64) -> DispatchResult {
+		let counter = self.points.get_mut(activity);
+		*counter = counter.checked_add(points).ok_or(Error::<T>::Overflow)?;
 		Ok(())
 	}
 
@@ -80,9 +86,22 @@ impl SchedulerCounterEntry {
 		Ok(())
 	}
 
-	pub fn figure_credit_value(&self, total_block_size: u64) -> CreditScore {
-		if total_block_size != 0 {
-			let a = Perbill::from_rational(self.proceed_block_size, total_block_size) * FULL_CREDIT_SCORE;
+	/// The share of each activity's `total_points` this scheduler earned, combined by `weights`.
+	pub fn figure_credit_value(&self, total_points: &ActivityPoints, weights: &ActivityWeights) -> CreditScore {
+		let mut weighted_value = 0_u64;
+		let mut weight_sum = 0_u64;
+		for activity in CreditActivity::ALL {
+			let total = total_points.get(activity);
+			if total == 0 {
+				continue
+			}
+			let weight = weights.get(activity).deconstruct() as u64;
+			let value = Perbill::from_rational(self.points.get(activity), total) * FULL_CREDIT_SCORE;
+			weighted_value = weighted_value.saturating_add(weight.saturating_mul(value as u64));
+			weight_sum = weight_sum.saturating_add(weight);
+		}
+		if weight_sum != 0 {
+			let a = (weighted_value / weight_sum) as u32;
 			return a.saturating_sub(self.punishment_part())
 		}
 		return 0
@@ -98,7 +117,7 @@ impl SchedulerCounterEntry {
 
 impl Default for SchedulerCounterEntry {
 	fn default() -> Self {
-		SchedulerCounterEntry { proceed_block_size: 0_u64, punishment_count: 0_u32 }
+		SchedulerCounterEntry { points: ActivityPoints::default(), punishment_count: 0_u32 }
 	}
 }
 
@@ -106,7 +125,11 @@ impl Default for SchedulerCounterEntry {
 pub mod pallet {
 	use super::*;
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(sp_std::marker::PhantomData<T>);
 
 	#[pallet::config]
@@ -116,6 +139,10 @@ pub mod pallet {
 		type PeriodDuration: Get<BlockNumberFor<Self>>;
 
 		type StashAccountFinder: SchedulerStashAccountFinder<Self::AccountId>;
+
+		/// The weight of each kind of work when figuring credit values.
+		#[pallet::constant]
+		type ActivityWeights: Get<ActivityWeights>;
 	}
 
 	#[pallet::error]
@@ -131,12 +158,21 @@ pub mod pallet {
 	pub(super) type CurrentCounters<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, SchedulerCounterEntry, ValueQuery>;
 
+	/// The counters of the previous period, kept to explain its credit values.
+	#[pallet::storage]
+	pub(super) type PreviousCounters<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, SchedulerCounterEntry, ValueQuery>;
+
 	#[pallet::storage]
 	pub(super) type HistoryCreditValues<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, u32, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+
 		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
 			let period_duration = T::PeriodDuration::get();
 			if now % period_duration == Zero::zero() {
@@ -150,9 +186,9 @@ pub mod pallet {
 }
 
 impl<T: Config> Pallet<T> {
-	pub fn record_proceed_block_size(scheduler_id: &T::AccountId, block_size: u64) -> DispatchResult {
+	pub fn record_activity(scheduler_id: &T::AccountId, activity: CreditActivity, points: u64) -> DispatchResult {
 		<CurrentCounters<T>>::mutate(scheduler_id, |scb| -> DispatchResult {
-			scb.increase_block_size::<T>(block_size)?;
+			scb.increase_points::<T>(activity, points)?;
 			Ok(())
 		})?;
 		Ok(())
@@ -168,14 +204,24 @@ impl<T: Config> Pallet<T> {
 
 	pub fn figure_credit_values(period: u32) -> Weight {
 		let mut weight: Weight = Weight::zero();
-		let mut total_size = 0_u64;
+		let mut total_points = ActivityPoints::default();
 		for (_, counter_entry) in <CurrentCounters<T>>::iter() {
-			total_size += counter_entry.proceed_block_size;
+			total_points.saturating_accrue(&counter_entry.points);
 			weight = weight.saturating_add(T::DbWeight::get().reads(1));
 		}
 
+		// Only the counters of the period being figured are kept.
+		#[allow(deprecated)]
+		let pc_outcome = PreviousCounters::<T>::remove_all(None);
+		let pc_keys_removed = match pc_outcome {
+			KillStorageResult::AllRemoved(count) => count,
+			KillStorageResult::SomeRemaining(count) => count,
+		};
+		weight = weight.saturating_add(T::DbWeight::get().writes(pc_keys_removed.into()));
+
+		let activity_weights = T::ActivityWeights::get();
 		for (ctrl_account_id, counter_entry) in <CurrentCounters<T>>::iter() {
-			let credit_value = counter_entry.figure_credit_value(total_size);
+			let credit_value = counter_entry.figure_credit_value(&total_points, &activity_weights);
 			debug!(
 				target: LOG_TARGET,
 				"scheduler control account: {:?}, credit value: {}",
@@ -183,7 +229,8 @@ impl<T: Config> Pallet<T> {
 				credit_value
 			);
 			HistoryCreditValues::<T>::insert(&period, &ctrl_account_id, credit_value);
-			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			PreviousCounters::<T>::insert(&ctrl_account_id, counter_entry);
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 2));
 		}
 
 		// Clear CurrentCounters
@@ -252,6 +299,34 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+impl<T: Config> Pallet<T> {
+	/// The credit accounting of `scheduler_id` for the current and the previous period.
+	pub fn credit_breakdown(scheduler_id: &T::AccountId) -> SchedulerCreditBreakdown {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let period: u32 = (now / T::PeriodDuration::get()).saturated_into();
+
+		let current_entry = CurrentCounters::<T>::get(scheduler_id);
+		let current = PeriodCredit {
+			period,
+			points: current_entry.points,
+			punishment_count: current_entry.punishment_count,
+			credit_value: None,
+		};
+
+		let previous = period.checked_sub(1).map(|last_period| {
+			let previous_entry = PreviousCounters::<T>::get(scheduler_id);
+			PeriodCredit {
+				period: last_period,
+				points: previous_entry.points,
+				punishment_count: previous_entry.punishment_count,
+				credit_value: HistoryCreditValues::<T>::try_get(&last_period, scheduler_id).ok(),
+			}
+		});
+
+		SchedulerCreditBreakdown { current, previous }
+	}
+}
+
 impl<T: Config> SchedulerCreditCounter<T::AccountId> for Pallet<T> {
 	fn increase_point_for_tag(scheduler_id: &T::AccountId, space: u128) -> DispatchResult {
 		let mut base_count: u64 =  space
@@ -263,7 +338,7 @@ impl<T: Config> SchedulerCreditCounter<T::AccountId> for Pallet<T> {
 
 		let point: u64 = base_count.checked_mul(TAG_BASE_POINT).ok_or(Error::<T>::PointOverflow)?;
 
-		Self::record_proceed_block_size(scheduler_id, point)?;
+		Self::record_activity(scheduler_id, CreditActivity::Tag, point)?;
 
 		Ok(())
 	}
@@ -278,7 +353,7 @@ impl<T: Config> SchedulerCreditCounter<T::AccountId> for Pallet<T> {
 
 		let point: u64 = base_count.checked_mul(CERT_BASE_POINT).ok_or(Error::<T>::PointOverflow)?;
 
-		Self::record_proceed_block_size(scheduler_id, point)?;
+		Self::record_activity(scheduler_id, CreditActivity::Cert, point)?;
 
 		Ok(())
 	}
@@ -293,7 +368,7 @@ impl<T: Config> SchedulerCreditCounter<T::AccountId> for Pallet<T> {
 
 		let point: u64 = base_count.checked_mul(IDLE_VERIFY_BASE_POINT).ok_or(Error::<T>::PointOverflow)?;
 
-		Self::record_proceed_block_size(scheduler_id, point)?;
+		Self::record_activity(scheduler_id, CreditActivity::IdleVerify, point)?;
 
 		Ok(())
 	}
@@ -308,7 +383,7 @@ impl<T: Config> SchedulerCreditCounter<T::AccountId> for Pallet<T> {
 
 		let point: u64 = base_count.checked_mul(SERVICE_VERIFY_BASE_POINT).ok_or(Error::<T>::PointOverflow)?;
 
-		Self::record_proceed_block_size(scheduler_id, point)?;
+		Self::record_activity(scheduler_id, CreditActivity::ServiceVerify, point)?;
 
 		Ok(())
 	}
@@ -323,13 +398,13 @@ impl<T: Config> SchedulerCreditCounter<T::AccountId> for Pallet<T> {
 
 		let point: u64 = base_count.checked_mul(REPLACE_BASE_POINT).ok_or(Error::<T>::PointOverflow)?;
 
-		Self::record_proceed_block_size(scheduler_id, point)?;
+		Self::record_activity(scheduler_id, CreditActivity::Replace, point)?;
 
 		Ok(())
 	}
 
-	fn record_proceed_block_size(scheduler_id: &T::AccountId, block_size: u64) -> DispatchResult {
-		Pallet::<T>::record_proceed_block_size(scheduler_id, block_size)?;
+	fn record_activity(scheduler_id: &T::AccountId, activity: CreditActivity, points: u64) -> DispatchResult {
+		Pallet::<T>::record_activity(scheduler_id, activity, points)?;
 		Ok(())
 	}
 
@@ -352,25 +427,38 @@ impl<T: Config> ValidatorCredits<T::AccountId> for Pallet<T> {
 
 #[cfg(test)]
 mod test {
-	use crate::SchedulerCounterEntry;
+	use crate::{ActivityPoints, ActivityWeights, CreditActivity, Percent, SchedulerCounterEntry};
 	use crate::mock::Test;
 	#[test]
 	fn scheduler_counter_works() {
+		let weights = ActivityWeights {
+			tag: Percent::from_percent(40),
+			cert: Percent::from_percent(10),
+			idle_verify: Percent::from_percent(20),
+			service_verify: Percent::from_percent(20),
+			replace: Percent::from_percent(10),
+		};
 		let mut sce = SchedulerCounterEntry::default();
-		let _ = sce.increase_block_size::<Test>(100);
-		assert_eq!(100, sce.proceed_block_size);
-		let _ = sce.increase_block_size::<Test>(100);
-		assert_eq!(200, sce.proceed_block_size);
+		let _ = sce.increase_points::<Test>(CreditActivity::Tag, 100);
+		assert_eq!(100, sce.points.tag);
+		let _ = sce.increase_points::<Test>(CreditActivity::Tag, 100);
+		assert_eq!(200, sce.points.tag);
 		assert_eq!(0, sce.punishment_part());
-		assert_eq!(100, sce.figure_credit_value(2000));
+		let total = ActivityPoints { tag: 2000, ..Default::default() };
+		assert_eq!(100, sce.figure_credit_value(&total, &weights));
+
+		// tag is 40% of the weight in use, idle_verify 20%.
+		let _ = sce.increase_points::<Test>(CreditActivity::IdleVerify, 50);
+		let total = ActivityPoints { tag: 2000, idle_verify: 50, ..Default::default() };
+		assert_eq!(400, sce.figure_credit_value(&total, &weights));
 
 		let _ = sce.increase_punishment_count::<Test>();
 		assert_eq!(1, sce.punishment_count);
 
-		assert_eq!(100, sce.figure_credit_value(1000));
+		assert_eq!(300, sce.figure_credit_value(&total, &weights));
 		let _ = sce.increase_punishment_count::<Test>();
 
 		assert_eq!(2, sce.punishment_count);
-		assert_eq!(0, sce.figure_credit_value(1000));
+		assert_eq!(0, sce.figure_credit_value(&total, &weights));
 	}
 }
@@ -0,0 +1,49 @@
+use super::*;
+
+pub fn migrate<T: Config>() -> Weight {
+	let version = StorageVersion::get::<Pallet<T>>();
+	let mut weight: Weight = T::DbWeight::get().reads(1);
+
+	if version < 1 {
+		log::info!("SchedulerCredit version 0 -> 1 migrations start!");
+		weight = weight.saturating_add(v1::migrate::<T>());
+		StorageVersion::new(1).put::<Pallet<T>>();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	weight
+}
+
+mod v1 {
+	use super::*;
+
+	#[derive(Decode, Encode)]
+	struct OldSchedulerCounterEntry {
+		proceed_block_size: u64,
+		punishment_count: u32,
+	}
+
+	/// The old counter mixed every kind of work, so it is credited to all of them alike. Each
+	/// scheduler keeps the same share of every activity, which figures the running period the
+	/// way it would have been figured before.
+	pub fn migrate<T: Config>() -> Weight {
+		let mut translated = 0u64;
+		CurrentCounters::<T>::translate::<OldSchedulerCounterEntry, _>(|_, old| {
+			translated += 1;
+			let points = old.proceed_block_size;
+			Some(SchedulerCounterEntry {
+				points: ActivityPoints {
+					tag: points,
+					cert: points,
+					idle_verify: points,
+					service_verify: points,
+					replace: points,
+				},
+				punishment_count: old.punishment_count,
+			})
+		});
+		log::info!("SchedulerCredit migration: translated {} counters", translated);
+
+		T::DbWeight::get().reads_writes(translated, translated)
+	}
+}
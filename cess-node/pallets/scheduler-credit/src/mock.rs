@@ -55,12 +55,21 @@ impl<AccountId: Clone> SchedulerStashAccountFinder<AccountId>
 
 parameter_types! {
 	pub const PeriodDuration: BlockNumber = 3600;
+	pub const CreditActivityWeights: ActivityWeights = ActivityWeights {
+		tag: Percent::from_percent(30),
+		cert: Percent::from_percent(10),
+		idle_verify: Percent::from_percent(20),
+		service_verify: Percent::from_percent(30),
+		replace: Percent::from_percent(10),
+	};
 }
 
 impl Config for Test {
 	type StashAccountFinder = MockStashAccountFinder<Self::AccountId>;
 
 	type PeriodDuration = PeriodDuration;
+
+	type ActivityWeights = CreditActivityWeights;
 }
 
 frame_support::construct_runtime!(
@@ -1,17 +1,17 @@
 use super::*;
 use crate::mock::*;
-use cp_scheduler_credit::SchedulerCreditCounter;
+use cp_scheduler_credit::{ActivityPoints, CreditActivity, SchedulerCreditCounter};
 use cessp_consensus_rrsc::traits::ValidatorCredits;
 
 #[test]
 fn figure_credit_scores_works() {
 	ExtBuilder::default().build_and_execute(|| {
-		let _ = <Pallet<Test> as SchedulerCreditCounter<AccountId>>::record_proceed_block_size(&1, 100);
-		let _ = <Pallet<Test> as SchedulerCreditCounter<AccountId>>::record_proceed_block_size(&1, 100);
-		assert_eq!(200, CurrentCounters::<Test>::get(1).proceed_block_size);
+		let _ = <Pallet<Test> as SchedulerCreditCounter<AccountId>>::record_activity(&1, CreditActivity::Tag, 100);
+		let _ = <Pallet<Test> as SchedulerCreditCounter<AccountId>>::record_activity(&1, CreditActivity::Tag, 100);
+		assert_eq!(200, CurrentCounters::<Test>::get(1).points.tag);
 
-		let _ = <Pallet<Test> as SchedulerCreditCounter<AccountId>>::record_proceed_block_size(&2, 50);
-		let _ = <Pallet<Test> as SchedulerCreditCounter<AccountId>>::record_proceed_block_size(&3, 150);
+		let _ = <Pallet<Test> as SchedulerCreditCounter<AccountId>>::record_activity(&2, CreditActivity::Tag, 50);
+		let _ = <Pallet<Test> as SchedulerCreditCounter<AccountId>>::record_activity(&3, CreditActivity::Tag, 150);
 
 		// switch period
 		let period_duration = PeriodDuration::get();
@@ -28,6 +28,14 @@ fn figure_credit_scores_works() {
 		assert_eq!(CurrentCounters::<Test>::contains_key(&2), false);
 		assert_eq!(CurrentCounters::<Test>::contains_key(&3), false);
 
+		// the counters of the figured period are kept
+		let breakdown = Pallet::<Test>::credit_breakdown(&1);
+		assert_eq!(breakdown.current.points, ActivityPoints::default());
+		let previous = breakdown.previous.unwrap();
+		assert_eq!(previous.period, 0);
+		assert_eq!(previous.points.tag, 200);
+		assert_eq!(previous.credit_value, Some(500));
+
 		// figure credit scores works
         let vc_map = <Pallet<Test> as ValidatorCredits<AccountId>>::credits(0);
 		assert_eq!(&250, vc_map.get(&1).unwrap());
@@ -0,0 +1,77 @@
+//! RPC interface for the sminer pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use cp_cess_common::MinerEconomics;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::{ErrorObject, ErrorObjectOwned},
+};
+use pallet_sminer_runtime_api::SminerApi as SminerRuntimeApi;
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+const SMINER_ERROR: i32 = 9100;
+
+/// Provides rpc methods for storage miners.
+#[rpc(client, server)]
+pub trait SminerApi<BlockHash, AccountId, Balance, BlockNumber> {
+	/// Returns what `miner` may claim, what still vests, the collateral it needs and when it could exit.
+	#[method(name = "sminer_minerEconomics")]
+	fn miner_economics(
+		&self,
+		miner: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<MinerEconomics<Balance, BlockNumber>>>;
+}
+
+/// Provides RPC methods for storage miners.
+pub struct Sminer<C, B> {
+	/// shared reference to the client.
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Sminer<C, B> {
+	/// Creates a new instance of the sminer Rpc handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber>
+	SminerApiServer<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber> for Sminer<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: SminerRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+	AccountId: Codec + DeserializeOwned + Send + Sync + 'static,
+	Balance: Codec + Serialize + Send + Sync + 'static,
+	BlockNumber: Codec + Serialize + Send + Sync + 'static,
+{
+	fn miner_economics(
+		&self,
+		miner: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<MinerEconomics<Balance, BlockNumber>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		self.client
+			.runtime_api()
+			.miner_economics(at, miner)
+			.map_err(runtime_error)?
+			.map_err(dispatch_error)
+	}
+}
+
+fn runtime_error(e: sp_api::ApiError) -> ErrorObjectOwned {
+	ErrorObject::owned(SMINER_ERROR, "Unable to query the miner economics.", Some(e.to_string()))
+}
+
+fn dispatch_error(e: sp_runtime::DispatchError) -> ErrorObjectOwned {
+	ErrorObject::owned(SMINER_ERROR, "Unable to compute the miner economics.", Some(format!("{:?}", e)))
+}
@@ -0,0 +1,18 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use cp_cess_common::MinerEconomics;
+use sp_runtime::DispatchError;
+
+sp_api::decl_runtime_apis! {
+	pub trait SminerApi<AccountId, Balance, BlockNumber>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// The rewards, collateral and exit of `miner`, `None` if it is not a miner, or the error that kept them
+		/// from being computed.
+		fn miner_economics(miner: AccountId) -> Result<Option<MinerEconomics<Balance, BlockNumber>>, DispatchError>;
+	}
+}
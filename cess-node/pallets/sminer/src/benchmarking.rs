@@ -14,7 +14,6 @@ use frame_system::RawOrigin;
 // use sp_runtime::{
 // 	traits::{Bounded, CheckedMul},
 // };
-const BASE_UNIT: u128 = 1_000_000_000_000_000_000;
 const SEED: u32 = 2190502;
 pub struct Pallet<T: Config>(Sminer<T>);
 pub trait Config:
@@ -130,7 +129,7 @@ benchmarks! {
     verify {
         assert!(<MinerItems<T>>::contains_key(&caller));
         let miner_info = <MinerItems<T>>::get(caller).unwrap();
-        assert_eq!(miner_info.state, Sminer::<T>::str_to_bound(STATE_POSITIVE)?)
+        assert_eq!(miner_info.state, MinerState::Positive)
     }
 
     update_beneficiary {
@@ -164,36 +163,34 @@ benchmarks! {
         pallet_cess_treasury::benchmarking::initialize_reward::<T>();
         let caller: AccountOf<T> = account("user1", 100, SEED);
         register_positive_miner::<T>(caller.clone())?;
-        let mut total_reward: BalanceOf<T> = BalanceOf::<T>::zero();
-        let mut order_list: BoundedVec<RewardOrder<BalanceOf<T>, BlockNumberFor<T>>, ConstU32<90>> = Default::default();
         let order_reward: BalanceOf<T> = 1_800_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
-        let each_amount: BalanceOf<T> = 10_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
-        for i in 0 .. v {
-            let reward_order = RewardOrder::<BalanceOf<T>, BlockNumberFor<T>> {
-                receive_count: 0,
-                max_count: 90,
-                atonce: false,
-                order_reward: order_reward,
-                each_amount: each_amount,
-                last_receive_block: 1u32.saturated_into(),
-            };
-            total_reward = total_reward + order_reward;
-            order_list.try_push(reward_order).unwrap();
-        }
-
+        let total_reward: BalanceOf<T> = order_reward * (v as u32).into();
+        let releasable = AOIR_PERCENT.mul_floor(total_reward);
+        let release_period = T::OneDayBlock::get() * (RELEASE_NUMBER as u32).into();
         let reward = Reward::<T> {
             total_reward: total_reward,
             reward_issued: BalanceOf::<T>::zero(),
-            order_list: order_list,
+            releasable: releasable,
+            vesting: RewardVesting {
+                locked: total_reward - releasable,
+                last_block: 1u32.saturated_into(),
+                end_block: release_period + 1u32.saturated_into(),
+            },
         };
+        let locked = reward.vesting.locked;
 
         <RewardMap<T>>::insert(&caller, reward);
         frame_system::Pallet::<T>::set_block_number(28805u32.into());
     }: _(RawOrigin::Signed(caller.clone()))
     verify {
         let reward_info = <RewardMap<T>>::get(&caller).unwrap();
-        let vreward: BalanceOf<T> = (((900 * BASE_UNIT) + (20 * BASE_UNIT)) * v as u128).try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
-        assert_eq!(reward_info.reward_issued, vreward);
+        // 28804 of the blocks of the release period have passed since the curve started.
+        let period: u128 = release_period.saturated_into();
+        let vested = Perbill::from_rational(28804u128, period).mul_floor(locked);
+        assert!(!vested.is_zero());
+        assert_eq!(reward_info.reward_issued, releasable + vested);
+        assert_eq!(reward_info.vesting.locked, locked - vested);
+        assert_eq!(reward_info.vesting.last_block, 28805u32.saturated_into());
     }
 
     miner_exit_prep {
@@ -207,7 +204,7 @@ benchmarks! {
     verify {
         assert!(<MinerItems<T>>::contains_key(&caller));
         let miner_info = <MinerItems<T>>::get(caller).unwrap();
-        assert_eq!(miner_info.state, Sminer::<T>::str_to_bound(STATE_LOCK)?)
+        assert_eq!(miner_info.state, MinerState::Lock)
     }
 
     miner_exit {
@@ -306,6 +303,139 @@ benchmarks! {
     verify {
         assert!(<MinerItems<T>>::contains_key(&caller));
         let miner_info = <MinerItems<T>>::get(caller).unwrap();
-        assert_eq!(miner_info.state, Sminer::<T>::str_to_bound(STATE_FROZEN)?)
+        assert_eq!(miner_info.state, MinerState::Frozen)
+    }
+
+    decrease_declaration_space {
+        log::info!("decrease_declaration_space start");
+        pallet_tee_worker::benchmarking::generate_workers::<T>();
+        pallet_cess_treasury::benchmarking::initialize_reward::<T>();
+        let caller: AccountOf<T> = account("user1", 100, SEED);
+        register_positive_miner::<T>(caller.clone())?;
+        let tib_count = 1;
+    }: _(RawOrigin::Signed(caller.clone()), tib_count)
+    verify {
+        let miner_info = <MinerItems<T>>::get(&caller).unwrap();
+        assert_eq!(miner_info.declaration_space, 0);
+        assert!(<CollateralRelease<T>>::contains_key(&caller));
+    }
+
+    release_collateral {
+        log::info!("release_collateral start");
+        pallet_tee_worker::benchmarking::generate_workers::<T>();
+        pallet_cess_treasury::benchmarking::initialize_reward::<T>();
+        let caller: AccountOf<T> = account("user1", 100, SEED);
+        register_positive_miner::<T>(caller.clone())?;
+        Sminer::<T>::decrease_declaration_space(RawOrigin::Signed(caller.clone()).into(), 1).map_err(|_| "decrease declaration space failed")?;
+        let release_block = <CollateralRelease<T>>::get(&caller).unwrap();
+        frame_system::Pallet::<T>::set_block_number(release_block);
+    }: _(RawOrigin::Signed(caller.clone()))
+    verify {
+        let miner_info = <MinerItems<T>>::get(&caller).unwrap();
+        assert_eq!(miner_info.collaterals, BalanceOf::<T>::zero());
+        assert!(!<CollateralRelease<T>>::contains_key(&caller));
+    }
+
+    set_commission {
+        log::info!("set_commission start");
+        pallet_tee_worker::benchmarking::generate_workers::<T>();
+        let caller: AccountOf<T> = account("user1", 100, SEED);
+        register_positive_miner::<T>(caller.clone())?;
+        let commission = Perbill::from_percent(10);
+    }: _(RawOrigin::Signed(caller.clone()), commission)
+    verify {
+        let delegation = <Delegations<T>>::get(&caller).unwrap();
+        assert_eq!(delegation.commission, commission);
+    }
+
+    bond {
+        log::info!("bond start");
+        pallet_tee_worker::benchmarking::generate_workers::<T>();
+        let miner: AccountOf<T> = account("user1", 100, SEED);
+        register_positive_miner::<T>(miner.clone())?;
+        let caller: AccountOf<T> = account("backer1", 100, SEED);
+        <T as crate::Config>::Currency::make_free_balance_be(
+            &caller,
+            160_000_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!"),
+        );
+        let amount: BalanceOf<T> = 4_000_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+    }: _(RawOrigin::Signed(caller.clone()), miner.clone(), amount)
+    verify {
+        assert_eq!(Sminer::<T>::backed_collateral(&miner), amount);
+    }
+
+    unbond {
+        log::info!("unbond start");
+        pallet_tee_worker::benchmarking::generate_workers::<T>();
+        let miner: AccountOf<T> = account("user1", 100, SEED);
+        register_positive_miner::<T>(miner.clone())?;
+        let caller: AccountOf<T> = account("backer1", 100, SEED);
+        <T as crate::Config>::Currency::make_free_balance_be(
+            &caller,
+            160_000_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!"),
+        );
+        let amount: BalanceOf<T> = 4_000_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+        Sminer::<T>::bond(RawOrigin::Signed(caller.clone()).into(), miner.clone(), amount).map_err(|_| "bond failed")?;
+    }: _(RawOrigin::Signed(caller.clone()), miner.clone(), amount)
+    verify {
+        assert_eq!(Sminer::<T>::backed_collateral(&miner), BalanceOf::<T>::zero());
+        assert!(<BackerUnbonding<T>>::contains_key(&caller, &miner));
+        // The unbonded collateral stays punishable until it unlocks.
+        let now = frame_system::Pallet::<T>::block_number();
+        assert_eq!(Sminer::<T>::cooling_collateral(&miner, now), amount);
+    }
+
+    withdraw_unbonded {
+        log::info!("withdraw_unbonded start");
+        pallet_tee_worker::benchmarking::generate_workers::<T>();
+        let miner: AccountOf<T> = account("user1", 100, SEED);
+        register_positive_miner::<T>(miner.clone())?;
+        let caller: AccountOf<T> = account("backer1", 100, SEED);
+        <T as crate::Config>::Currency::make_free_balance_be(
+            &caller,
+            160_000_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!"),
+        );
+        let amount: BalanceOf<T> = 4_000_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+        Sminer::<T>::bond(RawOrigin::Signed(caller.clone()).into(), miner.clone(), amount).map_err(|_| "bond failed")?;
+        Sminer::<T>::unbond(RawOrigin::Signed(caller.clone()).into(), miner.clone(), amount).map_err(|_| "unbond failed")?;
+        let unlock_block = <BackerUnbonding<T>>::get(&caller, &miner).unwrap().unlock_block;
+        frame_system::Pallet::<T>::set_block_number(unlock_block);
+    }: _(RawOrigin::Signed(caller.clone()), miner.clone())
+    verify {
+        assert!(!<BackerUnbonding<T>>::contains_key(&caller, &miner));
+        assert!(<Delegations<T>>::get(&miner).unwrap().unbonding.is_empty());
+    }
+
+    receive_backer_reward {
+        let v in 1 .. 80;
+        log::info!("receive_backer_reward start");
+        pallet_tee_worker::benchmarking::generate_workers::<T>();
+        pallet_cess_treasury::benchmarking::initialize_reward::<T>();
+        let miner: AccountOf<T> = account("user1", 100, SEED);
+        register_positive_miner::<T>(miner.clone())?;
+        let caller: AccountOf<T> = account("backer1", 100, SEED);
+        let order_reward: BalanceOf<T> = 1_800_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+        let total_reward: BalanceOf<T> = order_reward * (v as u32).into();
+        let releasable = AOIR_PERCENT.mul_floor(total_reward);
+        let release_period = T::OneDayBlock::get() * (RELEASE_NUMBER as u32).into();
+        let reward = BackerReward {
+            releasable: releasable,
+            vesting: RewardVesting {
+                locked: total_reward - releasable,
+                last_block: 1u32.saturated_into(),
+                end_block: release_period + 1u32.saturated_into(),
+            },
+        };
+        let locked = reward.vesting.locked;
+
+        <BackerRewards<T>>::insert(&miner, &caller, reward);
+        frame_system::Pallet::<T>::set_block_number(28805u32.into());
+    }: _(RawOrigin::Signed(caller.clone()), miner.clone())
+    verify {
+        let reward = <BackerRewards<T>>::get(&miner, &caller).unwrap();
+        let period: u128 = release_period.saturated_into();
+        let vested = Perbill::from_rational(28804u128, period).mul_floor(locked);
+        assert!(reward.releasable.is_zero());
+        assert_eq!(reward.vesting.locked, locked - vested);
     }
 }
\ No newline at end of file
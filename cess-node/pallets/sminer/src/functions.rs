@@ -14,7 +14,7 @@ impl<T: Config> Pallet<T> {
 		}
 
 		let state = Self::check_state(acc)?; //read 1
-		if state == STATE_EXIT.as_bytes().to_vec() {
+		if state == MinerState::Exit {
 			return Ok(());
 		}
 		MinerItems::<T>::try_mutate(acc, |miner_info_opt| -> DispatchResult {
@@ -41,30 +41,22 @@ impl<T: Config> Pallet<T> {
 		<MinerItems<T>>::try_mutate(miner, |miner_info_opt| -> DispatchResult {
 			let miner_info = miner_info_opt.as_mut().ok_or(Error::<T>::NotMiner)?;
 
-			let spec_acc = T::ReservoirGate::get_reservoir_acc();
-			if miner_info.collaterals > punish_amount {
-				if miner_info.staking_account == spec_acc {
-					T::ReservoirGate::punish(miner, punish_amount, false)?;
-				}
-				T::Currency::unreserve(&miner_info.staking_account, punish_amount);
-				T::CessTreasuryHandle::send_to_pid(miner_info.staking_account.clone(), punish_amount)?;
-				miner_info.collaterals = miner_info.collaterals.checked_sub(&punish_amount).ok_or(Error::<T>::Overflow)?;
-			} else {
-				if miner_info.staking_account == spec_acc {
-					T::ReservoirGate::punish(miner, punish_amount, false)?;
-				}
-				T::Currency::unreserve(&miner_info.staking_account, miner_info.collaterals);
-				T::CessTreasuryHandle::send_to_pid(miner_info.staking_account.clone(), miner_info.collaterals)?;
-				miner_info.collaterals = BalanceOf::<T>::zero();
-				miner_info.debt = punish_amount.checked_sub(&miner_info.collaterals).ok_or(Error::<T>::Overflow)?;
+			let mut paid = BalanceOf::<T>::zero();
+			for (acc, share) in Self::take_collateral(miner, miner_info, punish_amount)? {
+				T::Currency::unreserve(&acc, share);
+				T::CessTreasuryHandle::send_to_pid(acc, share)?;
+				paid = paid.checked_add(&share).ok_or(Error::<T>::Overflow)?;
 			}
+			// What the collateral could not cover is owed.
+			let unpaid = punish_amount.checked_sub(&paid).ok_or(Error::<T>::Overflow)?;
+			miner_info.debt = miner_info.debt.checked_add(&unpaid).ok_or(Error::<T>::Overflow)?;
 
 			let power = Self::calculate_power(miner_info.idle_space, miner_info.service_space);
 			let limit: BalanceOf<T> = Self::calculate_limit_by_space(power)?
 				.try_into().map_err(|_| Error::<T>::Overflow)?;
 
-			if miner_info.collaterals < limit {
-				miner_info.state = Self::str_to_bound(STATE_FROZEN)?;
+			if miner_info.state == MinerState::Positive && miner_info.collaterals < limit {
+				Self::transition_state(miner, miner_info, MinerState::Frozen)?;
 			}
 
 			Ok(())
@@ -73,15 +65,245 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	pub(super) fn check_state(acc: &AccountOf<T>) -> Result<Vec<u8>, Error<T>> {
-		Ok(<MinerItems<T>>::try_get(acc).map_err(|_e| Error::<T>::NotMiner)?.state.to_vec())
+	// Unlike a punishment, a payment beyond the collateral is not recorded as debt.
+	pub(super) fn pay_from_collateral(
+		miner: &AccountOf<T>,
+		beneficiary: &AccountOf<T>,
+		amount: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		<MinerItems<T>>::try_mutate(miner, |miner_info_opt| -> Result<BalanceOf<T>, DispatchError> {
+			let miner_info = miner_info_opt.as_mut().ok_or(Error::<T>::NotMiner)?;
+
+			let mut paid = BalanceOf::<T>::zero();
+			for (acc, share) in Self::take_collateral(miner, miner_info, amount)? {
+				let unpaid = T::Currency::repatriate_reserved(&acc, beneficiary, share, BalanceStatus::Free)?;
+				let share = share.checked_sub(&unpaid).ok_or(Error::<T>::Overflow)?;
+				paid = paid.checked_add(&share).ok_or(Error::<T>::Overflow)?;
+			}
+
+			if !paid.is_zero() && miner_info.state == MinerState::Positive {
+				let power = Self::calculate_power(miner_info.idle_space, miner_info.service_space);
+				let limit: BalanceOf<T> = Self::calculate_limit_by_space(power)?
+					.try_into().map_err(|_| Error::<T>::Overflow)?;
+				if miner_info.collaterals < limit {
+					Self::transition_state(miner, miner_info, MinerState::Frozen)?;
+				}
+			}
+
+			Ok(paid)
+		})
+	}
+
+	/// Takes up to `amount` out of the collateral of `miner`, from its staking account and its backers pro rata to
+	/// their stakes, each at most what it still has reserved. Collateral a backer unbonded is taken from as well
+	/// until it unlocks.
+	///
+	/// Returns the share each account paid; the caller moves the funds out of its reserve.
+	pub(super) fn take_collateral(
+		miner: &AccountOf<T>,
+		miner_info: &mut MinerInfo<T>,
+		amount: BalanceOf<T>,
+	) -> Result<Vec<(AccountOf<T>, BalanceOf<T>)>, DispatchError> {
+		let mut shares: Vec<(AccountOf<T>, BalanceOf<T>)> = Default::default();
+		let now = <frame_system::Pallet<T>>::block_number();
+		let cooling = Self::cooling_collateral(miner, now);
+		let pool = miner_info.collaterals.checked_add(&cooling).ok_or(Error::<T>::Overflow)?;
+		let amount = amount.min(pool);
+		if amount.is_zero() {
+			return Ok(shares);
+		}
+		let all = amount == pool;
+		let ratio = Perbill::from_rational(amount.saturated_into::<u128>(), pool.saturated_into::<u128>());
+		// What an account can still pay, its reserve less the shares it was already charged.
+		let payable = |acc: &AccountOf<T>, shares: &Vec<(AccountOf<T>, BalanceOf<T>)>| {
+			shares
+				.iter()
+				.filter(|(payer, _)| payer == acc)
+				.fold(T::Currency::reserved_balance(acc), |left, (_, share)| left.saturating_sub(*share))
+		};
+
+		let mut backed = BalanceOf::<T>::zero();
+		let mut backers_paid = BalanceOf::<T>::zero();
+		let mut unbonding_paid = BalanceOf::<T>::zero();
+		<Delegations<T>>::try_mutate(miner, |delegation_opt| -> DispatchResult {
+			let Some(delegation) = delegation_opt else {
+				return Ok(());
+			};
+			for (backer, stake) in delegation.backers.iter_mut() {
+				backed = backed.checked_add(stake).ok_or(Error::<T>::Overflow)?;
+				let share = if all { *stake } else { ratio.mul_floor(*stake) };
+				let share = share.min(payable(backer, &shares));
+				if share.is_zero() {
+					continue;
+				}
+				*stake = stake.checked_sub(&share).ok_or(Error::<T>::Overflow)?;
+				backers_paid = backers_paid.checked_add(&share).ok_or(Error::<T>::Overflow)?;
+				shares.push((backer.clone(), share));
+			}
+			delegation.backers.retain(|(_, stake)| !stake.is_zero());
+
+			for (backer, cooling, unlock) in delegation.unbonding.iter_mut() {
+				if *unlock <= now {
+					continue;
+				}
+				let share = if all { *cooling } else { ratio.mul_floor(*cooling) };
+				let share = share.min(payable(backer, &shares));
+				if share.is_zero() {
+					continue;
+				}
+				*cooling = cooling.checked_sub(&share).ok_or(Error::<T>::Overflow)?;
+				<BackerUnbonding<T>>::try_mutate_exists(backer.clone(), miner, |unbonding_opt| -> DispatchResult {
+					let unbonding = unbonding_opt.as_mut().ok_or(Error::<T>::Unexpected)?;
+					unbonding.amount = unbonding.amount.checked_sub(&share).ok_or(Error::<T>::Overflow)?;
+					if unbonding.amount.is_zero() {
+						*unbonding_opt = None;
+					}
+
+					Ok(())
+				})?;
+				unbonding_paid = unbonding_paid.checked_add(&share).ok_or(Error::<T>::Overflow)?;
+				shares.push((backer.clone(), share));
+			}
+			delegation.unbonding.retain(|(_, cooling, unlock)| !cooling.is_zero() && *unlock > now);
+
+			Ok(())
+		})?;
+
+		// The staking account pays the rest, rounding included, up to its own part of the collateral.
+		let own = miner_info.collaterals.saturating_sub(backed);
+		let own_share = amount
+			.saturating_sub(backers_paid)
+			.saturating_sub(unbonding_paid)
+			.min(own)
+			.min(payable(&miner_info.staking_account, &shares));
+		if !own_share.is_zero() {
+			if miner_info.staking_account == T::ReservoirGate::get_reservoir_acc() {
+				T::ReservoirGate::punish(miner, own_share, false)?;
+			}
+			shares.push((miner_info.staking_account.clone(), own_share));
+		}
+
+		// Unbonded collateral no longer counts towards the collateral of the miner.
+		let taken = backers_paid.checked_add(&own_share).ok_or(Error::<T>::Overflow)?;
+		miner_info.collaterals = miner_info.collaterals.checked_sub(&taken).ok_or(Error::<T>::Overflow)?;
+
+		Ok(shares)
+	}
+
+	// The collateral backers unbonded from `miner` that is still cooling down at `now`.
+	pub(super) fn cooling_collateral(miner: &AccountOf<T>, now: BlockNumberFor<T>) -> BalanceOf<T> {
+		<Delegations<T>>::get(miner).map_or(BalanceOf::<T>::zero(), |delegation| {
+			delegation
+				.unbonding
+				.iter()
+				.filter(|(_, _, unlock)| *unlock > now)
+				.fold(BalanceOf::<T>::zero(), |total, (_, cooling, _)| total.saturating_add(*cooling))
+		})
 	}
-	// Convert the miner state constant to boundedvec
-	pub(super) fn str_to_bound(param: &str) -> Result<BoundedVec<u8, T::ItemLimit>, DispatchError> {
-		let result: BoundedVec<u8, T::ItemLimit> = param.as_bytes().to_vec().try_into().map_err(|_| Error::<T>::Overflow)?;
 
-		Ok(result)
+	/// The collateral bonded to `miner` by its backers.
+	pub fn backed_collateral(miner: &AccountOf<T>) -> BalanceOf<T> {
+		<Delegations<T>>::get(miner).map_or(BalanceOf::<T>::zero(), |delegation| {
+			delegation.backers.iter().fold(BalanceOf::<T>::zero(), |total, (_, stake)| total.saturating_add(*stake))
+		})
 	}
+
+	// How long the collateral a backer unbonded stays reserved.
+	pub(super) fn unbond_period() -> BlockNumberFor<T> {
+		T::OneDayBlock::get().saturating_mul(UNBOND_DAYS.into())
+	}
+
+	// Adds to what `backer` unbonded from `miner`, all of it then unlocking at `unlock_block`.
+	pub(super) fn add_unbonding(
+		backer: &AccountOf<T>,
+		miner: &AccountOf<T>,
+		amount: BalanceOf<T>,
+		unlock_block: BlockNumberFor<T>,
+	) -> DispatchResult {
+		<BackerUnbonding<T>>::try_mutate(backer, miner, |unbonding_opt| -> DispatchResult {
+			let unbonding = unbonding_opt.get_or_insert(UnbondingInfo { amount: BalanceOf::<T>::zero(), unlock_block });
+			unbonding.amount = unbonding.amount.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+			unbonding.unlock_block = unbonding.unlock_block.max(unlock_block);
+
+			Ok(())
+		})
+	}
+
+	// Unbonds every backer of a leaving miner, returning the collateral they had bonded.
+	pub(super) fn dissolve_delegation(
+		miner: &AccountOf<T>,
+		unlock_block: BlockNumberFor<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let Some(delegation) = <Delegations<T>>::take(miner) else {
+			return Ok(BalanceOf::<T>::zero());
+		};
+		let mut backed = BalanceOf::<T>::zero();
+		for (backer, stake) in delegation.backers {
+			Self::add_unbonding(&backer, miner, stake, unlock_block)?;
+			backed = backed.checked_add(&stake).ok_or(Error::<T>::Overflow)?;
+		}
+
+		Ok(backed)
+	}
+
+	// Unreserves the collateral of `miner` above what its declaration space requires.
+	pub(super) fn release_excess_collateral(miner: &AccountOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+		<MinerItems<T>>::try_mutate(miner, |miner_info_opt| -> Result<BalanceOf<T>, DispatchError> {
+			let miner_info = miner_info_opt.as_mut().ok_or(Error::<T>::NotMiner)?;
+			// The collateral of a miner on its way out is returned as a whole.
+			ensure!(
+				matches!(miner_info.state, MinerState::Positive | MinerState::Frozen),
+				Error::<T>::StateError
+			);
+			ensure!(miner_info.debt.is_zero(), Error::<T>::StateError);
+
+			let limit: BalanceOf<T> = Self::calculate_limit_by_space(miner_info.declaration_space)?
+				.try_into().map_err(|_| Error::<T>::Overflow)?;
+			// Backers take their collateral back by unbonding.
+			let own = miner_info.collaterals.saturating_sub(Self::backed_collateral(miner));
+			let excess = miner_info.collaterals.checked_sub(&limit).unwrap_or(BalanceOf::<T>::zero()).min(own);
+			ensure!(!excess.is_zero(), Error::<T>::NoReleasableCollateral);
+
+			if miner_info.staking_account == T::ReservoirGate::get_reservoir_acc() {
+				T::ReservoirGate::redeem(miner, excess, false)?;
+			}
+			T::Currency::unreserve(&miner_info.staking_account, excess);
+			miner_info.collaterals = miner_info.collaterals.checked_sub(&excess).ok_or(Error::<T>::Overflow)?;
+
+			Ok(excess)
+		})
+	}
+
+	pub(super) fn check_state(acc: &AccountOf<T>) -> Result<MinerState, Error<T>> {
+		Ok(<MinerItems<T>>::try_get(acc).map_err(|_e| Error::<T>::NotMiner)?.state)
+	}
+
+	/// Moves `miner` to the state `to`, if its current state allows it.
+	///
+	/// Every change of the state of a miner goes through here.
+	pub(super) fn transition_state(
+		miner: &AccountOf<T>,
+		miner_info: &mut MinerInfo<T>,
+		to: MinerState,
+	) -> DispatchResult {
+		let from = miner_info.state;
+		ensure!(from.can_transition_to(&to), Error::<T>::InvalidStateTransition);
+		miner_info.state = to;
+
+		Self::deposit_event(Event::<T>::MinerStateChanged { miner: miner.clone(), from, to });
+
+		Ok(())
+	}
+
+	/// The block `miner` would be unlocked at if it prepared to exit now: right after its open challenge, deals
+	/// and restoral orders are over, or at the next block if it has none.
+	pub fn exit_unlock_block(miner: &AccountOf<T>) -> BlockNumberFor<T> {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let obligations_end = T::Obligations::obligations_end(miner).map_or(now, |end| end.max(now));
+
+		obligations_end.saturating_add(1u32.into())
+	}
+
 	// Note: that it is necessary to determine whether the state meets the exit conditions before use.
 	pub(super) fn execute_exit(acc: &AccountOf<T>) -> DispatchResult {
 		let mut miner_list = AllMiner::<T>::get();
@@ -89,10 +311,12 @@ impl<T: Config> Pallet<T> {
 		AllMiner::<T>::put(miner_list);
 		MinerItems::<T>::try_mutate(acc, |miner_opt| -> DispatchResult {
 			let miner = miner_opt.as_mut().ok_or(Error::<T>::Unexpected)?;
-			miner.state = Self::str_to_bound(STATE_EXIT)?;
+			Self::transition_state(acc, miner, MinerState::Exit)?;
 
 			Ok(())
 		})?;
+		// The miner was held until its obligations were over.
+		T::Obligations::clear_obligations(acc);
 		
 		Ok(())
 	}
@@ -121,16 +345,26 @@ impl<T: Config> Pallet<T> {
 	// Note: that it is necessary to determine whether the state meets the exit conditions before use.
 	pub(super) fn withdraw(miner: AccountOf<T>) -> DispatchResult {
 		let miner_info = <MinerItems<T>>::try_get(&miner).map_err(|_| Error::<T>::NotMiner)?;
+		// The backers cooled down with the miner, they can withdraw at once.
+		let now = <frame_system::Pallet<T>>::block_number();
+		let backed = Self::dissolve_delegation(&miner, now)?;
+		let own = miner_info.collaterals.saturating_sub(backed);
 		let spec_acc = T::ReservoirGate::get_reservoir_acc();
 		if miner_info.staking_account == spec_acc {
-			T::ReservoirGate::redeem(&miner, miner_info.collaterals, false)?;
+			T::ReservoirGate::redeem(&miner, own, false)?;
 		}
-		T::Currency::unreserve(&miner_info.staking_account, miner_info.collaterals);
+		T::Currency::unreserve(&miner_info.staking_account, own);
 		let space_proof_info = miner_info.space_proof_info.ok_or(Error::<T>::NotpositiveState)?;
 		let encoding = space_proof_info.pois_key.encode();
 		let hashing = sp_io::hashing::sha2_256(&encoding);
 		MinerPublicKey::<T>::remove(hashing);
 		// <MinerItems<T>>::remove(miner.clone());
+		<MinerItems<T>>::mutate(&miner, |miner_info_opt| {
+			if let Some(miner_info) = miner_info_opt {
+				miner_info.collaterals = BalanceOf::<T>::zero();
+			}
+		});
+		<CollateralRelease<T>>::remove(&miner);
 		<PendingReplacements<T>>::remove(miner);
 
 		Ok(())
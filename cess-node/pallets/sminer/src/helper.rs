@@ -14,7 +14,7 @@ impl<T: Config> Pallet<T> {
 			let miner_info = miner_info_opt.as_mut().ok_or(Error::<T>::NotMiner)?;
 
 			// check state 
-			ensure!(miner_info.state.to_vec() == STATE_POSITIVE.as_bytes().to_vec(), Error::<T>::NotpositiveState);
+			ensure!(miner_info.state == MinerState::Positive, Error::<T>::NotpositiveState);
 
 			let mut space_proof_info = miner_info.space_proof_info.clone().ok_or(Error::<T>::NotpositiveState)?;
 
@@ -96,7 +96,7 @@ impl<T: Config> Pallet<T> {
 		}
 
 		let state = Self::check_state(acc)?;
-		if state == STATE_EXIT.as_bytes().to_vec() {
+		if state == MinerState::Exit {
 			return Ok(());
 		}
 		MinerItems::<T>::try_mutate(acc, |miner_info_opt| -> DispatchResult {
@@ -121,7 +121,7 @@ impl<T: Config> Pallet<T> {
 		}
 
 		let state = Self::check_state(acc)?;
-		if state == STATE_EXIT.as_bytes().to_vec() {
+		if state == MinerState::Exit {
 			return Ok(());
 		}
 		MinerItems::<T>::try_mutate(acc, |miner_info_opt| -> DispatchResult {
@@ -161,40 +161,20 @@ impl<T: Config> Pallet<T> {
     pub(super) fn calculate_miner_reward(
 		miner: &AccountOf<T>,
 	) -> DispatchResult {
-		let order_list = <CompleteMinerSnapShot<T>>::mutate(&miner, |snap_shot_list| -> Result<Vec<RewardOrder::<BalanceOf<T>, BlockNumberFor<T>>>, DispatchError> {
+		let order_list = <CompleteMinerSnapShot<T>>::mutate(&miner, |snap_shot_list| -> Result<Vec<(BalanceOf<T>, BlockNumberFor<T>, u32)>, DispatchError> {
 			if snap_shot_list.len() == 0 {
 				return Ok(Default::default());
 			}
 
-			let mut order_list: Vec<RewardOrder::<BalanceOf<T>, BlockNumberFor<T>>> = Default::default();
+			let mut order_list: Vec<(BalanceOf<T>, BlockNumberFor<T>, u32)> = Default::default();
 
 			for snap_shot in snap_shot_list.into_iter() {
 				if snap_shot.issued == false {
-					let cur_era = T::Staking::current_era();
-					if snap_shot.era_index >= cur_era {
+					let Some(this_round_reward) = Self::snap_shot_reward(snap_shot)? else {
 						continue;
-					}
-
-					let total_power = <CompleteSnapShot<T>>::get(snap_shot.era_index).total_power;
-					let total_reward = T::RewardPool::get_round_reward(snap_shot.era_index);
-					if total_reward == BalanceOf::<T>::zero() {
-						Err(Error::<T>::Unexpected)?;
-					}
-					let miner_prop = Perbill::from_rational(snap_shot.power, total_power);
-					let this_round_reward = miner_prop.mul_floor(total_reward);
-					T::RewardPool::sub_round_reward(snap_shot.era_index, this_round_reward)?;
-					let each_reward = AOIR_PERCENT
-						.mul_floor(this_round_reward)
-						.checked_div(&RELEASE_NUMBER.into()).ok_or(Error::<T>::Overflow)?;
-					let order = RewardOrder::<BalanceOf<T>, BlockNumberFor<T>> {
-						receive_count: 0,
-						max_count: RELEASE_NUMBER,
-						atonce: false,
-						order_reward: this_round_reward,
-						each_amount: each_reward,
-						last_receive_block: snap_shot.finsh_block,
 					};
-					order_list.push(order);
+					T::RewardPool::sub_round_reward(snap_shot.era_index, this_round_reward)?;
+					order_list.push((this_round_reward, snap_shot.finsh_block, snap_shot.era_index));
 					snap_shot.issued = true;
 				}
 			}
@@ -207,31 +187,31 @@ impl<T: Config> Pallet<T> {
 		if order_list.len() == 0 {
 			return Ok(());
 		}
-		
-		// calculate available reward
-		RewardMap::<T>::try_mutate(miner, |opt_reward_info| -> DispatchResult {
-			let reward_info = opt_reward_info.as_mut().ok_or(Error::<T>::Unexpected)?;
-			// traverse the order list
 
-			let mut flag = true;
-			if reward_info.order_list.len() == RELEASE_NUMBER as usize {
-				flag = false;
+		let now = <frame_system::Pallet<T>>::block_number();
+		let mut miner_orders: Vec<(BalanceOf<T>, BlockNumberFor<T>)> = Default::default();
+		let mut backers_reward = BalanceOf::<T>::zero();
+		for (this_round_reward, finsh_block, era) in order_list {
+			let (miner_reward, backer_list) = Self::split_backer_rewards(miner, era, this_round_reward)?;
+			miner_orders.push((miner_reward, finsh_block));
+			for (backer, backer_reward) in backer_list {
+				<BackerRewards<T>>::try_mutate(miner, &backer, |opt_reward| -> DispatchResult {
+					let reward = opt_reward.get_or_insert_with(Default::default);
+					let releasable = Self::merge_reward_order(&mut reward.vesting, backer_reward, finsh_block, now)?;
+					reward.releasable = reward.releasable.checked_add(&releasable).ok_or(Error::<T>::Overflow)?;
+
+					Ok(())
+				})?;
+				backers_reward = backers_reward.checked_add(&backer_reward).ok_or(Error::<T>::Overflow)?;
 			}
+			<BackerShares<T>>::remove(miner, era);
+		}
 
-			let mut new_reward = BalanceOf::<T>::zero();
-			for order in order_list {
-				if flag {
-					reward_info.total_reward = reward_info.total_reward
-						.checked_add(&order.order_reward).ok_or(Error::<T>::Overflow)?;
-					new_reward = new_reward.checked_add(&order.order_reward).ok_or(Error::<T>::Overflow)?;
-					reward_info.order_list.try_push(order.clone()).map_err(|_| Error::<T>::BoundedVecError)?;
-				} else {
-					new_reward = new_reward.checked_add(&order.order_reward).ok_or(Error::<T>::Overflow)?;
-					T::RewardPool::reward_reserve(order.order_reward)?;
-				}
-			}
-			
-			T::RewardPool::sub_reward(new_reward)?;
+		RewardMap::<T>::try_mutate(miner, |opt_reward_info| -> DispatchResult {
+			let reward_info = opt_reward_info.as_mut().ok_or(Error::<T>::Unexpected)?;
+			let new_reward = Self::merge_reward_orders(reward_info, miner_orders, now)?;
+
+			T::RewardPool::sub_reward(new_reward.checked_add(&backers_reward).ok_or(Error::<T>::Overflow)?)?;
 
 			Ok(())
 		})?;
@@ -239,41 +219,162 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	pub(super) fn distribute_rewards(miner: &AccountOf<T>, beneficiary: AccountOf<T>) -> DispatchResult {
-		<RewardMap<T>>::try_mutate(miner, |opt_reward| -> DispatchResult {
-			let reward = opt_reward.as_mut().ok_or(Error::<T>::Unexpected)?;
-			let one_day = T::OneDayBlock::get();
-			let now = <frame_system::Pallet<T>>::block_number();
-			let mut avail_reward: BalanceOf<T> = BalanceOf::<T>::zero(); 
-
-			for order in reward.order_list.iter_mut() {
-				let diff = now.checked_sub(&order.last_receive_block).ok_or(Error::<T>::Overflow)?;
-				if diff >= one_day {
-					let count = diff.checked_div(&one_day).ok_or(Error::<T>::Overflow)?;
-					let avail_count: u8;
-					if order.receive_count.saturating_add(count.saturated_into()) > order.max_count {
-						avail_count = order.max_count.checked_sub(order.receive_count).ok_or(Error::<T>::Unexpected)?;
-					} else {
-						avail_count = count.saturated_into();
-					}
+	// Records the part of the rewards `miner` earns in `era` that goes to each of its backers.
+	pub(super) fn snapshot_backer_shares(miner: &AccountOf<T>, era: u32) -> DispatchResult {
+		let Some(delegation) = <Delegations<T>>::get(miner) else {
+			<BackerShares<T>>::remove(miner, era);
+			return Ok(());
+		};
+		let collaterals: u128 = <MinerItems<T>>::try_get(miner)
+			.map_err(|_| Error::<T>::NotMiner)?
+			.collaterals
+			.saturated_into();
+		let backers_part = delegation.commission.left_from_one();
+
+		let mut shares: BoundedVec<(AccountOf<T>, Perbill), ConstU32<MAX_BACKERS>> = Default::default();
+		for (backer, stake) in delegation.backers {
+			let share = backers_part * Perbill::from_rational(stake.saturated_into::<u128>(), collaterals);
+			if !share.is_zero() {
+				shares.try_push((backer, share)).map_err(|_| Error::<T>::TooManyBackers)?;
+			}
+		}
+		<BackerShares<T>>::insert(miner, era, shares);
 
-					if avail_count > 0 {
-						let order_avail_reward = order.each_amount.checked_mul(&avail_count.into()).ok_or(Error::<T>::Overflow)?;
-						avail_reward = avail_reward.checked_add(&order_avail_reward).ok_or(Error::<T>::Overflow)?;
-						order.receive_count = order.receive_count.checked_add(avail_count).ok_or(Error::<T>::Overflow)?;
-						order.last_receive_block = now;
-					}
-				}
+		Ok(())
+	}
+
+	// Splits a reward `miner` earned in `era` by the backer shares recorded then, the miner keeping the rest.
+	pub(super) fn split_backer_rewards(
+		miner: &AccountOf<T>,
+		era: u32,
+		reward: BalanceOf<T>,
+	) -> Result<(BalanceOf<T>, Vec<(AccountOf<T>, BalanceOf<T>)>), DispatchError> {
+		let mut miner_reward = reward;
+		let mut rewards: Vec<(AccountOf<T>, BalanceOf<T>)> = Default::default();
+		for (backer, share) in <BackerShares<T>>::get(miner, era) {
+			let backer_reward = share.mul_floor(reward);
+			if !backer_reward.is_zero() {
+				miner_reward = miner_reward.checked_sub(&backer_reward).ok_or(Error::<T>::Overflow)?;
+				rewards.push((backer, backer_reward));
+			}
+		}
+
+		Ok((miner_reward, rewards))
+	}
 
-				if !order.atonce {
-					avail_reward = avail_reward.checked_add(
-						&(AOIR_PERCENT.mul_floor(order.order_reward))
-					).ok_or(Error::<T>::Overflow)?;
-					order.atonce = true;
+	// The reward of a finished round, `None` while its era is not over.
+	fn snap_shot_reward(
+		snap_shot: &MinerCompleteInfo<BlockNumberFor<T>>,
+	) -> Result<Option<BalanceOf<T>>, DispatchError> {
+		let cur_era = T::Staking::current_era();
+		if snap_shot.era_index >= cur_era {
+			return Ok(None);
+		}
+
+		let total_power = <CompleteSnapShot<T>>::get(snap_shot.era_index).total_power;
+		let total_reward = T::RewardPool::get_round_reward(snap_shot.era_index);
+		if total_reward == BalanceOf::<T>::zero() {
+			Err(Error::<T>::Unexpected)?;
+		}
+		let miner_prop = Perbill::from_rational(snap_shot.power, total_power);
+
+		Ok(Some(miner_prop.mul_floor(total_reward)))
+	}
+
+	// Merges new reward orders into the release curve of a miner, returning their total.
+	pub(super) fn merge_reward_orders(
+		reward_info: &mut Reward<T>,
+		order_list: Vec<(BalanceOf<T>, BlockNumberFor<T>)>,
+		now: BlockNumberFor<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let mut new_reward = BalanceOf::<T>::zero();
+		for (order_reward, finsh_block) in order_list {
+			new_reward = new_reward.checked_add(&order_reward).ok_or(Error::<T>::Overflow)?;
+			let releasable = Self::merge_reward_order(&mut reward_info.vesting, order_reward, finsh_block, now)?;
+			reward_info.releasable = reward_info.releasable.checked_add(&releasable).ok_or(Error::<T>::Overflow)?;
+		}
+		reward_info.total_reward = reward_info.total_reward
+			.checked_add(&new_reward).ok_or(Error::<T>::Overflow)?;
+
+		Ok(new_reward)
+	}
+
+	// Merges a reward order finished at `finsh_block` into a release curve, returning what is releasable already.
+	pub(super) fn merge_reward_order(
+		vesting: &mut RewardVesting<BalanceOf<T>, BlockNumberFor<T>>,
+		order_reward: BalanceOf<T>,
+		finsh_block: BlockNumberFor<T>,
+		now: BlockNumberFor<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let release_period = T::OneDayBlock::get()
+			.checked_mul(&RELEASE_NUMBER.into()).ok_or(Error::<T>::Overflow)?;
+		// `AOIR_PERCENT` of an order is released at once, the rest over `RELEASE_NUMBER` days.
+		let atonce = AOIR_PERCENT.mul_floor(order_reward);
+		let locked = order_reward.checked_sub(&atonce).ok_or(Error::<T>::Overflow)?;
+		let end_block = finsh_block.checked_add(&release_period).ok_or(Error::<T>::Overflow)?;
+		let vested = vesting.merge(locked, finsh_block, end_block, now);
+
+		Ok(atonce.checked_add(&vested).ok_or(Error::<T>::Overflow)?)
+	}
+
+	/// The rewards, collateral and exit of `miner`, as if it received its rewards now, `None` if it is not a miner.
+	pub fn miner_economics(
+		miner: &AccountOf<T>,
+	) -> Result<Option<MinerEconomics<BalanceOf<T>, BlockNumberFor<T>>>, DispatchError> {
+		let Some(miner_info) = <MinerItems<T>>::get(miner) else {
+			return Ok(None);
+		};
+		let now = <frame_system::Pallet<T>>::block_number();
+
+		let mut claimable = BalanceOf::<T>::zero();
+		let mut vesting: RewardVesting<BalanceOf<T>, BlockNumberFor<T>> = Default::default();
+		if let Some(mut reward_info) = <RewardMap<T>>::get(miner) {
+			let mut order_list: Vec<(BalanceOf<T>, BlockNumberFor<T>)> = Default::default();
+			for snap_shot in <CompleteMinerSnapShot<T>>::get(miner).iter().filter(|snap_shot| !snap_shot.issued) {
+				if let Some(reward) = Self::snap_shot_reward(snap_shot)? {
+					let (miner_reward, _) = Self::split_backer_rewards(miner, snap_shot.era_index, reward)?;
+					order_list.push((miner_reward, snap_shot.finsh_block));
 				}
 			}
+			Self::merge_reward_orders(&mut reward_info, order_list, now)?;
+			let vested = reward_info.vesting.release(now);
+			claimable = reward_info.releasable.saturating_add(vested);
+			vesting = reward_info.vesting;
+		}
+
+		let required_collateral = Self::calculate_limit_by_space(miner_info.declaration_space)?;
+		let exit_block = match miner_info.state {
+			MinerState::Lock => <MinerLock<T>>::get(miner),
+			MinerState::Positive => {
+				let staking_start_block = <StakingStartBlock<T>>::get(miner).ok_or(Error::<T>::Unexpected)?;
+				let exit_prep_block = staking_start_block
+					.saturating_add(T::StakingLockBlock::get())
+					.saturating_add(1u32.into());
+				Some(exit_prep_block.saturating_add(1u32.into()).max(Self::exit_unlock_block(miner)))
+			},
+			_ => None,
+		};
 
-			reward.order_list.retain(|order| order.max_count != order.receive_count);
+		Ok(Some(MinerEconomics {
+			claimable,
+			vesting_locked: vesting.locked,
+			vesting_end: vesting.end_block,
+			collaterals: miner_info.collaterals,
+			required_collateral: required_collateral.try_into().map_err(|_| Error::<T>::Overflow)?,
+			debt: miner_info.debt,
+			locked: miner_info.state == MinerState::Lock,
+			exit_block,
+		}))
+	}
+
+	pub(super) fn distribute_rewards(miner: &AccountOf<T>, beneficiary: AccountOf<T>) -> DispatchResult {
+		<RewardMap<T>>::try_mutate(miner, |opt_reward| -> DispatchResult {
+			let reward = opt_reward.as_mut().ok_or(Error::<T>::Unexpected)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			let vested = reward.vesting.release(now);
+			let avail_reward = reward.releasable.checked_add(&vested).ok_or(Error::<T>::Overflow)?;
+			reward.releasable = BalanceOf::<T>::zero();
 
 			reward.reward_issued = reward.reward_issued.checked_add(&avail_reward).ok_or(Error::<T>::Overflow)?;
 
@@ -285,6 +386,28 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	// Pays `backer` the rewards it earned through `miner` that were released so far, returning their amount.
+	pub(super) fn distribute_backer_reward(
+		miner: &AccountOf<T>,
+		backer: &AccountOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		<BackerRewards<T>>::try_mutate_exists(miner, backer, |opt_reward| -> Result<BalanceOf<T>, DispatchError> {
+			let reward = opt_reward.as_mut().ok_or(Error::<T>::NotExisted)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			let vested = reward.vesting.release(now);
+			let avail_reward = reward.releasable.checked_add(&vested).ok_or(Error::<T>::Overflow)?;
+			reward.releasable = BalanceOf::<T>::zero();
+			if reward.vesting.locked.is_zero() {
+				*opt_reward = None;
+			}
+
+			T::RewardPool::send_reward_to_miner(backer.clone(), avail_reward)?;
+
+			Ok(avail_reward)
+		})
+	}
+
 	pub(super) fn clear_punish(miner: &AccountOf<T>, idle_space: u128, service_space: u128, count: u8) -> DispatchResult {
 		let power = Self::calculate_power(idle_space, service_space);
 		let limit: BalanceOf<T> = Self::calculate_limit_by_space(power)?
@@ -339,19 +462,24 @@ impl<T: Config> Pallet<T> {
 		miner_list.retain(|s| s != acc);
 		AllMiner::<T>::put(miner_list);
 
+		// The backers of a miner forced out unbond as if they had left it themselves.
+		let now = <frame_system::Pallet<T>>::block_number();
+		let unlock_block = Self::unbond_period().checked_add(&now).ok_or(Error::<T>::Overflow)?;
 		<MinerItems<T>>::try_mutate(acc, |miner_opt| -> DispatchResult {
 			let miner = miner_opt.as_mut().ok_or(Error::<T>::Unexpected)?;
 			if let Ok(reward_info) = <RewardMap<T>>::try_get(acc).map_err(|_| Error::<T>::NotExisted) {
 				// T::RewardPool::send_reward_to_miner(miner.beneficiary.clone(), reward_info.total_reward)?;
 				if reward_info.total_reward == BalanceOf::<T>::zero() {
+					let own = miner.collaterals.saturating_sub(Self::dissolve_delegation(acc, unlock_block)?);
 					let spec_acc = T::ReservoirGate::get_reservoir_acc();
 					if spec_acc == miner.staking_account {
-						T::ReservoirGate::redeem(acc, miner.collaterals, false)?;
+						T::ReservoirGate::redeem(acc, own, false)?;
 					}
-					T::Currency::unreserve(&miner.staking_account, miner.collaterals);
+					T::Currency::unreserve(&miner.staking_account, own);
 				} else {
 					Self::calculate_miner_reward(acc)?;
 					Self::distribute_rewards(acc, miner.beneficiary.clone())?;
+					let own = miner.collaterals.saturating_sub(Self::dissolve_delegation(acc, unlock_block)?);
 					let residue_reward = reward_info.total_reward.checked_sub(&reward_info.reward_issued).ok_or(Error::<T>::Overflow)?;
 					T::RewardPool::add_reward(residue_reward)?;
 					let start_block = <StakingStartBlock<T>>::try_get(&acc).map_err(|_| Error::<T>::BugInvalid)?;
@@ -359,7 +487,7 @@ impl<T: Config> Pallet<T> {
 					let exec_block = start_block.checked_add(&staking_lock_block).ok_or(Error::<T>::Overflow)?;
 					<ReturnStakingSchedule<T>>::try_mutate(&exec_block, |miner_list| -> DispatchResult {
 						miner_list
-							.try_push((acc.clone(), miner.staking_account.clone(), miner.collaterals.clone()))
+							.try_push((acc.clone(), miner.staking_account.clone(), own))
 							.map_err(|_| Error::<T>::BoundedVecError)?;
 
 						Ok(())
@@ -368,7 +496,7 @@ impl<T: Config> Pallet<T> {
 			}
 			T::StorageHandle::sub_total_idle_space(miner.idle_space + miner.lock_space)?;
 			Self::create_restoral_target(acc, miner.service_space + miner.lock_space)?;
-			miner.state = Self::str_to_bound(STATE_OFFLINE)?;
+			Self::transition_state(acc, miner, MinerState::Offline)?;
 			let space_proof_info = miner.space_proof_info.clone().ok_or(Error::<T>::NotpositiveState)?;
 			let encoding = space_proof_info.pois_key.encode();
 			let hashing = sp_io::hashing::sha2_256(&encoding);
@@ -376,9 +504,12 @@ impl<T: Config> Pallet<T> {
 			Ok(())
 		})?;
 
+		T::Obligations::clear_obligations(acc);
 		<CompleteMinerSnapShot<T>>::remove(acc);
+		let _ = <BackerShares<T>>::clear_prefix(acc, RELEASE_NUMBER as u32, None);
 		<RewardMap<T>>::remove(acc);
 		<PendingReplacements<T>>::remove(acc);
+		<CollateralRelease<T>>::remove(acc);
 
 		Ok(())
 	}
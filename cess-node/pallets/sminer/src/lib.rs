@@ -7,6 +7,7 @@
 //! * **Collateral:** The Staking amount when registering storage miner.
 //! * **Earnings:** Store the storage miner's earnings during mining.
 //! * **Locked:** Store the locked amount of the storage miner during mining.
+//! * **Backer:** An account bonding collateral to a storage miner, sharing its rewards and punishments.
 //!
 //! ### Interface
 //!
@@ -32,9 +33,9 @@ use frame_support::{
 			v3::{Anon as ScheduleAnon, Named as ScheduleNamed},
 			DispatchTime,
 		},
-		Currency,
+		BalanceStatus, Currency,
 		ExistenceRequirement::KeepAlive,
-		Get, ReservableCurrency,
+		Get, ReservableCurrency, StorageVersion,
 	},
 	transactional, PalletId,
 };
@@ -72,6 +73,8 @@ use constants::*;
 mod functions;
 mod helper;
 
+pub mod migrations;
+
 pub mod weights;
 pub use weights::WeightInfo;
 
@@ -79,6 +82,8 @@ type AccountOf<T> = <T as frame_system::Config>::AccountId;
 type BalanceOf<T> = <<T as pallet::Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 type ProposalCallOf<T> = <T as pallet::Config>::SProposal;
 
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -141,6 +146,8 @@ pub mod pallet {
 		type StorageHandle: StorageHandle<Self::AccountId>;
 
 		type ReservoirGate: ReservoirGate<Self::AccountId, BalanceOf<Self>>;
+		/// What a miner still has to fulfil before it may exit.
+		type Obligations: MinerObligations<Self::AccountId, BlockNumberFor<Self>>;
 
 		type Staking: StakingInterface;
 
@@ -205,6 +212,43 @@ pub mod pallet {
 			miner: AccountOf<T>,
 			space: u128,
 		},
+		DecreaseDeclarationSpace {
+			miner: AccountOf<T>,
+			space: u128,
+			release_block: BlockNumberFor<T>,
+		},
+		ReleaseCollateral {
+			miner: AccountOf<T>,
+			amount: BalanceOf<T>,
+		},
+		CommissionSet {
+			miner: AccountOf<T>,
+			commission: Perbill,
+		},
+		/// A backer bonded collateral to a miner.
+		Bonded {
+			backer: AccountOf<T>,
+			miner: AccountOf<T>,
+			amount: BalanceOf<T>,
+		},
+		/// A backer unbonded collateral from a miner, withdrawable from `unlock_block`.
+		Unbonded {
+			backer: AccountOf<T>,
+			miner: AccountOf<T>,
+			amount: BalanceOf<T>,
+			unlock_block: BlockNumberFor<T>,
+		},
+		WithdrawUnbonded {
+			backer: AccountOf<T>,
+			miner: AccountOf<T>,
+			amount: BalanceOf<T>,
+		},
+		/// The state of a miner changed.
+		MinerStateChanged {
+			miner: AccountOf<T>,
+			from: MinerState,
+			to: MinerState,
+		},
 	}
 
 	/// Error for the sminer pallet.
@@ -262,6 +306,20 @@ pub mod pallet {
 		WrongOrigin,
 		/// Exceeding the maximum release volume of the faucet in one day
 		ExceedRelease,
+		/// The miner cannot move from its current state to the requested one
+		InvalidStateTransition,
+		/// The collateral of the miner is still cooling down after its declaration space decreased
+		CollateralCooling,
+		/// The miner has no collateral above what its declaration space requires
+		NoReleasableCollateral,
+		/// The staking account of a miner cannot back it
+		CannotBackOwnMiner,
+		/// The miner has reached the maximum number of backers
+		TooManyBackers,
+		/// The account does not back the miner, or bonded less than requested
+		InsufficientBond,
+		/// The backer has nothing unbonded from the miner
+		NoUnbonding,
 	}
 
 	/// The hashmap for info of storage miners.
@@ -337,11 +395,60 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// The block from which a miner that decreased its declaration space may release its excess collateral.
+	#[pallet::storage]
+	#[pallet::getter(fn collateral_release)]
+	pub(super) type CollateralRelease<T: Config> = StorageMap<_, Blake2_128Concat, AccountOf<T>, BlockNumberFor<T>>;
+
+	/// The backers of each miner and the commission the miner takes from them.
+	#[pallet::storage]
+	#[pallet::getter(fn delegations)]
+	pub(super) type Delegations<T: Config> = StorageMap<_, Blake2_128Concat, AccountOf<T>, Delegation<T>>;
+
+	/// The collateral each backer unbonded from a miner, cooling down.
+	#[pallet::storage]
+	#[pallet::getter(fn backer_unbonding)]
+	pub(super) type BackerUnbonding<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AccountOf<T>,
+		Blake2_128Concat,
+		AccountOf<T>,
+		UnbondingInfo<BalanceOf<T>, BlockNumberFor<T>>,
+	>;
+
+	/// The part of the rewards of an era each backer of a miner earns, less the commission, as the stakes and the
+	/// commission stood when the miner completed its challenge in that era.
+	#[pallet::storage]
+	#[pallet::getter(fn backer_shares)]
+	pub(super) type BackerShares<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AccountOf<T>,
+		Blake2_128Concat,
+		u32,
+		BoundedVec<(AccountOf<T>, Perbill), ConstU32<MAX_BACKERS>>,
+		ValueQuery,
+	>;
+
+	/// The rewards each backer earned through a miner, by miner and backer.
+	#[pallet::storage]
+	#[pallet::getter(fn backer_rewards)]
+	pub(super) type BackerRewards<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AccountOf<T>,
+		Blake2_128Concat,
+		AccountOf<T>,
+		BackerReward<BalanceOf<T>, BlockNumberFor<T>>,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn facuet_whitelist)]
 	pub(super) type FacuetWhitelist<T: Config> = StorageValue<_, AccountOf<T>>;
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::genesis_config]
@@ -387,6 +494,10 @@ pub mod pallet {
 
 			weight
 		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
 	}
 
 	#[pallet::call]
@@ -439,7 +550,7 @@ pub mod pallet {
 					peer_id,
 					collaterals: staking_val,
 					debt: BalanceOf::<T>::zero(),
-					state: Self::str_to_bound(STATE_NOT_READY)?,
+					state: MinerState::NotReady,
 					declaration_space,
 					idle_space: u128::MIN,
 					service_space: u128::MIN,
@@ -455,7 +566,8 @@ pub mod pallet {
 				Reward::<T> {
 					total_reward: 0u32.saturated_into(),
 					reward_issued: 0u32.saturated_into(),
-					order_list: Default::default(),
+					releasable: 0u32.saturated_into(),
+					vesting: Default::default(),
 				},
 			);
 
@@ -491,9 +603,10 @@ pub mod pallet {
 				let miner_info = miner_info_opt.as_mut().ok_or(Error::<T>::ConversionError)?;
 				let spec_acc = T::ReservoirGate::get_reservoir_acc();
 
-				ensure!(miner_info.state.to_vec() != STATE_OFFLINE.as_bytes().to_vec(), Error::<T>::StateError);
-				ensure!(miner_info.state.to_vec() != STATE_LOCK.as_bytes().to_vec(), Error::<T>::StateError);
-				ensure!(miner_info.state.to_vec() != STATE_EXIT.as_bytes().to_vec(), Error::<T>::StateError);
+				ensure!(
+					!matches!(miner_info.state, MinerState::Offline | MinerState::Lock | MinerState::Exit),
+					Error::<T>::StateError
+				);
 				ensure!(
 					miner_info.staking_account == sender || miner_info.staking_account == spec_acc,
 					Error::<T>::NotStakingAcc
@@ -530,12 +643,12 @@ pub mod pallet {
 
 				balance = miner_info.collaterals;
 
-				if miner_info.state == STATE_FROZEN.as_bytes().to_vec() {
+				if miner_info.state == MinerState::Frozen {
 					let limit = Self::calculate_limit_by_space(miner_info.declaration_space)?
 						.try_into()
 						.map_err(|_| Error::<T>::Overflow)?;
 					if miner_info.collaterals >= limit {
-						miner_info.state = Self::str_to_bound(STATE_POSITIVE)?;
+						Self::transition_state(&miner, miner_info, MinerState::Positive)?;
 					}
 				}
 
@@ -624,7 +737,7 @@ pub mod pallet {
 
 			if let Ok(miner) = <MinerItems<T>>::try_get(&sender) {
 				ensure!(
-					miner.state == STATE_POSITIVE.as_bytes().to_vec() || miner.state == STATE_EXIT.as_bytes().to_vec(),
+					matches!(miner.state, MinerState::Positive | MinerState::Exit),
 					Error::<T>::NotpositiveState
 				);
 				Self::calculate_miner_reward(&sender)?;
@@ -638,8 +751,8 @@ pub mod pallet {
 		///
 		/// This function allows a registered Miner to initiate the process of exiting the system gracefully.
 		/// It transitions the Miner from a positive state to a locked state, effectively preparing for the exit.
-		/// During the locked state, the Miner's resources will be locked for a specific period,
-		/// ensuring that they fulfill any existing challenges before they can exit completely.
+		/// The Miner stays locked until its open challenge, the deals it reported fragments of and the restoral
+		/// orders it claimed are over, see `exit_unlock_block`. A Miner with none of them exits at the next block.
 		///
 		/// Parameters:
 		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization. Typically,
@@ -663,24 +776,13 @@ pub mod pallet {
 				if (&sender != &miner) && (&sender != &miner_info.staking_account) {
 					Err(Error::<T>::NotStakingAcc)?;
 				}
-				ensure!(miner_info.state == STATE_POSITIVE.as_bytes().to_vec(), Error::<T>::StateError);
-				ensure!(miner_info.lock_space == 0, Error::<T>::StateError);
-				if miner_info.lock_space != 0 {
-					Err(Error::<T>::StateError)?;
-				}
 
-				miner_info.state = Self::str_to_bound(STATE_LOCK)?;
+				Self::transition_state(&sender, miner_info, MinerState::Lock)?;
 
 				Ok(())
 			})?;
 
-			let now = <frame_system::Pallet<T>>::block_number();
-			// TODO! Develop a lock-in period based on the maximum duration of the current challenge
-			let lock_time = T::OneDayBlock::get()
-				.checked_mul(&15u32.saturated_into())
-				.ok_or(Error::<T>::Overflow)?
-				.checked_add(&now)
-				.ok_or(Error::<T>::Overflow)?;
+			let lock_time = Self::exit_unlock_block(&miner);
 
 			<MinerLock<T>>::insert(&miner, lock_time);
 
@@ -717,9 +819,8 @@ pub mod pallet {
 		pub fn miner_exit(origin: OriginFor<T>, miner: AccountOf<T>) -> DispatchResult {
 			let _ = ensure_root(origin)?;
 
-			// judge lock state.
+			// Only a locked miner can exit, see `execute_exit`.
 			let miner_info = <MinerItems<T>>::try_get(&miner).map_err(|_| Error::<T>::NotExisted)?;
-			ensure!(miner_info.state.to_vec() == STATE_LOCK.as_bytes().to_vec(), Error::<T>::StateError);
 			// sub network total idle space.
 
 			T::StorageHandle::sub_total_idle_space(miner_info.idle_space)?;
@@ -916,16 +1017,16 @@ pub mod pallet {
 
 			<MinerItems<T>>::try_mutate(&sender, |info_opt| -> DispatchResult {
 				let miner_info = info_opt.as_mut().ok_or(Error::<T>::NotMiner)?;
-				ensure!(STATE_NOT_READY.as_bytes().to_vec() == miner_info.state.to_vec(), Error::<T>::StateError);
+				ensure!(miner_info.state == MinerState::NotReady, Error::<T>::StateError);
 
 				miner_info.space_proof_info = Some(space_proof_info);
 				let base_limit: BalanceOf<T> = Self::calculate_limit_by_space(miner_info.declaration_space)?
 					.try_into()
 					.map_err(|_| Error::<T>::Overflow)?;
 				if miner_info.collaterals >= base_limit {
-					miner_info.state = Self::str_to_bound(STATE_POSITIVE)?;
+					Self::transition_state(&sender, miner_info, MinerState::Positive)?;
 				} else {
-					miner_info.state = Self::str_to_bound(STATE_FROZEN)?;
+					Self::transition_state(&sender, miner_info, MinerState::Frozen)?;
 				}
 				miner_info.tee_signature = sig;
 
@@ -980,7 +1081,7 @@ pub mod pallet {
 					peer_id,
 					collaterals: need_staking,
 					debt: BalanceOf::<T>::zero(),
-					state: Self::str_to_bound(STATE_NOT_READY)?,
+					state: MinerState::NotReady,
 					declaration_space,
 					idle_space: u128::MIN,
 					service_space: u128::MIN,
@@ -996,8 +1097,8 @@ pub mod pallet {
 				Reward::<T> {
 					total_reward: 0u32.saturated_into(),
 					reward_issued: 0u32.saturated_into(),
-
-					order_list: Default::default(),
+					releasable: 0u32.saturated_into(),
+					vesting: Default::default(),
 				},
 			);
 
@@ -1018,7 +1119,7 @@ pub mod pallet {
 			<MinerItems<T>>::try_mutate(&sender, |miner_info_opt| -> DispatchResult {
 				let miner_info = miner_info_opt.as_mut().ok_or(Error::<T>::ConversionError)?;
 
-				ensure!(miner_info.state.to_vec() == STATE_POSITIVE.as_bytes().to_vec(), Error::<T>::StateError);
+				ensure!(miner_info.state == MinerState::Positive, Error::<T>::StateError);
 				miner_info.declaration_space = miner_info
 					.declaration_space
 					.checked_add(increase_space)
@@ -1027,7 +1128,7 @@ pub mod pallet {
 					.try_into()
 					.map_err(|_| Error::<T>::Overflow)?;
 				if base_limit > miner_info.collaterals {
-					miner_info.state = Self::str_to_bound(STATE_FROZEN)?;
+					Self::transition_state(&sender, miner_info, MinerState::Frozen)?;
 				}
 
 				Ok(())
@@ -1066,6 +1167,315 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Decrease Miner's Declaration Space
+		///
+		/// This function allows a registered Miner to lower its declaration space, down to the space it has
+		/// certified. The collateral the Miner no longer needs can be released with `release_collateral` once the
+		/// same lock as an exiting Miner has passed, covering the challenges still running on the old space.
+		///
+		/// Parameters:
+		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization. Typically,
+		///   this is the account of a registered Miner.
+		/// - `tib_count`: The number of TiB to remove from the declaration space.
+		#[pallet::call_index(21)]
+		#[transactional]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::decrease_declaration_space())]
+		pub fn decrease_declaration_space(origin: OriginFor<T>, tib_count: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(MinerItems::<T>::contains_key(&sender), Error::<T>::NotMiner);
+			let decrease_space = T_BYTE.checked_mul(tib_count as u128).ok_or(Error::<T>::Overflow)?;
+
+			<MinerItems<T>>::try_mutate(&sender, |miner_info_opt| -> DispatchResult {
+				let miner_info = miner_info_opt.as_mut().ok_or(Error::<T>::ConversionError)?;
+
+				ensure!(
+					matches!(miner_info.state, MinerState::Positive | MinerState::Frozen),
+					Error::<T>::StateError
+				);
+				miner_info.declaration_space = miner_info
+					.declaration_space
+					.checked_sub(decrease_space)
+					.ok_or(Error::<T>::Overflow)?;
+				let currency_cert_space = miner_info.idle_space
+					.checked_add(miner_info.service_space).ok_or(Error::<T>::Overflow)?
+					.checked_add(miner_info.lock_space).ok_or(Error::<T>::Overflow)?;
+				ensure!(currency_cert_space <= miner_info.declaration_space, Error::<T>::ExceedingDeclarationSpace);
+
+				if miner_info.state == MinerState::Frozen {
+					let base_limit: BalanceOf<T> = Self::calculate_limit_by_space(miner_info.declaration_space)?
+						.try_into()
+						.map_err(|_| Error::<T>::Overflow)?;
+					if miner_info.collaterals >= base_limit {
+						Self::transition_state(&sender, miner_info, MinerState::Positive)?;
+					}
+				}
+
+				Ok(())
+			})?;
+
+			let release_block = Self::exit_unlock_block(&sender);
+			<CollateralRelease<T>>::insert(&sender, release_block);
+
+			Self::deposit_event(Event::<T>::DecreaseDeclarationSpace {
+				miner: sender,
+				space: decrease_space,
+				release_block,
+			});
+
+			Ok(())
+		}
+
+		/// Release Miner's Excess Collateral
+		///
+		/// This function allows a Miner that decreased its declaration space to get back the collateral above what
+		/// its declaration space requires, once the cooldown has passed. The collateral returns to the staking
+		/// account, or to the reservoir for a Miner staked through it.
+		///
+		/// Parameters:
+		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization. Typically,
+		///   this is the account of a registered Miner.
+		#[pallet::call_index(22)]
+		#[transactional]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::release_collateral())]
+		pub fn release_collateral(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let release_block = <CollateralRelease<T>>::try_get(&sender).map_err(|_| Error::<T>::NoReleasableCollateral)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now >= release_block, Error::<T>::CollateralCooling);
+
+			let amount = Self::release_excess_collateral(&sender)?;
+			<CollateralRelease<T>>::remove(&sender);
+
+			Self::deposit_event(Event::<T>::ReleaseCollateral { miner: sender, amount });
+
+			Ok(())
+		}
+
+		/// Set Miner's Commission
+		///
+		/// This function allows a registered Miner to set the part of the rewards of its backers it keeps for
+		/// itself. The rest of the rewards earned by their collateral goes to the backers. The commission applies to
+		/// the rewards of the challenges the Miner completes afterwards, not to the rewards already earned.
+		///
+		/// Parameters:
+		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization. Typically,
+		///   this is the account of a registered Miner.
+		/// - `commission`: The part of the rewards of the backers the Miner keeps.
+		#[pallet::call_index(23)]
+		#[transactional]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_commission())]
+		pub fn set_commission(origin: OriginFor<T>, commission: Perbill) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(MinerItems::<T>::contains_key(&sender), Error::<T>::NotMiner);
+
+			<Delegations<T>>::mutate(&sender, |delegation_opt| {
+				delegation_opt.get_or_insert_with(Default::default).commission = commission;
+			});
+
+			Self::deposit_event(Event::<T>::CommissionSet { miner: sender, commission });
+
+			Ok(())
+		}
+
+		/// Bond Collateral to a Miner
+		///
+		/// This function allows any account to back a registered Miner with its own collateral. The collateral is
+		/// reserved on the backer's account and counts towards the Miner's collateral. In return the backer gets its
+		/// share of the Miner's rewards, less the Miner's commission, and bears its share of the Miner's
+		/// punishments.
+		///
+		/// Parameters:
+		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization. Typically,
+		///   this is the account of the backer.
+		/// - `miner`: The Miner to back.
+		/// - `amount`: The collateral to bond.
+		#[pallet::call_index(24)]
+		#[transactional]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::bond())]
+		pub fn bond(
+			origin: OriginFor<T>,
+			miner: AccountOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::InsufficientBond);
+
+			<MinerItems<T>>::try_mutate(&miner, |miner_info_opt| -> DispatchResult {
+				let miner_info = miner_info_opt.as_mut().ok_or(Error::<T>::NotMiner)?;
+				ensure!(
+					matches!(miner_info.state, MinerState::NotReady | MinerState::Positive | MinerState::Frozen),
+					Error::<T>::StateError
+				);
+				ensure!(sender != miner && sender != miner_info.staking_account, Error::<T>::CannotBackOwnMiner);
+
+				<Delegations<T>>::try_mutate(&miner, |delegation_opt| -> DispatchResult {
+					let delegation = delegation_opt.get_or_insert_with(Default::default);
+					match delegation.backers.iter_mut().find(|(backer, _)| backer == &sender) {
+						Some((_, stake)) => *stake = stake.checked_add(&amount).ok_or(Error::<T>::Overflow)?,
+						None => delegation
+							.backers
+							.try_push((sender.clone(), amount))
+							.map_err(|_| Error::<T>::TooManyBackers)?,
+					}
+
+					Ok(())
+				})?;
+				T::Currency::reserve(&sender, amount)?;
+				miner_info.collaterals = miner_info.collaterals.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+
+				if miner_info.state == MinerState::Frozen {
+					let limit: BalanceOf<T> = Self::calculate_limit_by_space(miner_info.declaration_space)?
+						.try_into()
+						.map_err(|_| Error::<T>::Overflow)?;
+					if miner_info.collaterals >= limit {
+						Self::transition_state(&miner, miner_info, MinerState::Positive)?;
+					}
+				}
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::Bonded { backer: sender, miner, amount });
+
+			Ok(())
+		}
+
+		/// Unbond Collateral from a Miner
+		///
+		/// This function allows a backer to take back collateral it bonded to a Miner, whatever the state of the
+		/// Miner. The collateral stops counting towards the Miner's collateral at once, which may freeze the Miner,
+		/// but stays reserved on the backer's account, and still bears its share of the Miner's punishments, until
+		/// its cooldown has passed and it is withdrawn with `withdraw_unbonded`.
+		///
+		/// Parameters:
+		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization. Typically,
+		///   this is the account of the backer.
+		/// - `miner`: The Miner backed.
+		/// - `amount`: The collateral to unbond.
+		#[pallet::call_index(25)]
+		#[transactional]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::unbond())]
+		pub fn unbond(
+			origin: OriginFor<T>,
+			miner: AccountOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::InsufficientBond);
+			let now = <frame_system::Pallet<T>>::block_number();
+			let unlock_block = Self::unbond_period().checked_add(&now).ok_or(Error::<T>::Overflow)?;
+
+			<MinerItems<T>>::try_mutate(&miner, |miner_info_opt| -> DispatchResult {
+				let miner_info = miner_info_opt.as_mut().ok_or(Error::<T>::NotMiner)?;
+
+				<Delegations<T>>::try_mutate(&miner, |delegation_opt| -> DispatchResult {
+					let delegation = delegation_opt.as_mut().ok_or(Error::<T>::InsufficientBond)?;
+					let (_, stake) = delegation
+						.backers
+						.iter_mut()
+						.find(|(backer, _)| backer == &sender)
+						.ok_or(Error::<T>::InsufficientBond)?;
+					*stake = stake.checked_sub(&amount).ok_or(Error::<T>::InsufficientBond)?;
+					delegation.backers.retain(|(_, stake)| !stake.is_zero());
+
+					// The collateral stays punishable while it cools down.
+					delegation.unbonding.retain(|(_, _, unlock)| *unlock > now);
+					match delegation.unbonding.iter_mut().find(|(backer, _, _)| backer == &sender) {
+						Some((_, cooling, unlock)) => {
+							*cooling = cooling.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+							*unlock = unlock_block;
+						},
+						None => delegation
+							.unbonding
+							.try_push((sender.clone(), amount, unlock_block))
+							.map_err(|_| Error::<T>::TooManyBackers)?,
+					}
+
+					Ok(())
+				})?;
+				miner_info.collaterals = miner_info.collaterals.checked_sub(&amount).ok_or(Error::<T>::Overflow)?;
+
+				if miner_info.state == MinerState::Positive {
+					let limit: BalanceOf<T> = Self::calculate_limit_by_space(miner_info.declaration_space)?
+						.try_into()
+						.map_err(|_| Error::<T>::Overflow)?;
+					if miner_info.collaterals < limit {
+						Self::transition_state(&miner, miner_info, MinerState::Frozen)?;
+					}
+				}
+
+				Ok(())
+			})?;
+
+			Self::add_unbonding(&sender, &miner, amount, unlock_block)?;
+
+			Self::deposit_event(Event::<T>::Unbonded { backer: sender, miner, amount, unlock_block });
+
+			Ok(())
+		}
+
+		/// Withdraw Unbonded Collateral
+		///
+		/// This function allows a backer to get back the collateral it unbonded from a Miner, once its cooldown has
+		/// passed.
+		///
+		/// Parameters:
+		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization. Typically,
+		///   this is the account of the backer.
+		/// - `miner`: The Miner the collateral was unbonded from.
+		#[pallet::call_index(26)]
+		#[transactional]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::withdraw_unbonded())]
+		pub fn withdraw_unbonded(origin: OriginFor<T>, miner: AccountOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let unbonding = <BackerUnbonding<T>>::try_get(&sender, &miner).map_err(|_| Error::<T>::NoUnbonding)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now >= unbonding.unlock_block, Error::<T>::CollateralCooling);
+
+			T::Currency::unreserve(&sender, unbonding.amount);
+			<BackerUnbonding<T>>::remove(&sender, &miner);
+			<Delegations<T>>::mutate(&miner, |delegation_opt| {
+				if let Some(delegation) = delegation_opt {
+					delegation.unbonding.retain(|(backer, _, _)| backer != &sender);
+				}
+			});
+
+			Self::deposit_event(Event::<T>::WithdrawUnbonded { backer: sender, miner, amount: unbonding.amount });
+
+			Ok(())
+		}
+
+		/// Receive Backer's Reward
+		///
+		/// This function allows a backer to receive the rewards it earned through a Miner that were released so
+		/// far. Each reward of the Miner is shared out by the stakes and the commission as they stood when the Miner
+		/// completed the challenge that earned it.
+		///
+		/// Parameters:
+		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization. Typically,
+		///   this is the account of the backer.
+		/// - `miner`: The Miner backed.
+		#[pallet::call_index(27)]
+		#[transactional]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::receive_backer_reward(90))]
+		pub fn receive_backer_reward(origin: OriginFor<T>, miner: AccountOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			if let Ok(miner_info) = <MinerItems<T>>::try_get(&miner) {
+				if matches!(miner_info.state, MinerState::Positive | MinerState::Exit) {
+					Self::calculate_miner_reward(&miner)?;
+				}
+			}
+			let reward = Self::distribute_backer_reward(&miner, &sender)?;
+
+			Self::deposit_event(Event::<T>::Receive { acc: sender, reward });
+
+			Ok(())
+		}
 	}
 }
 
@@ -1091,7 +1501,7 @@ pub trait MinerControl<AccountId, BlockNumber> {
 
 	fn get_power(acc: &AccountId) -> Result<(u128, u128), DispatchError>;
 	fn miner_is_exist(acc: AccountId) -> bool;
-	fn get_miner_state(acc: &AccountId) -> Result<Vec<u8>, DispatchError>;
+	fn get_miner_state(acc: &AccountId) -> Result<MinerState, DispatchError>;
 	fn get_all_miner() -> Result<Vec<AccountId>, DispatchError>;
 	// Associated functions related to uploading files.
 	fn insert_service_bloom(acc: &AccountId, hash_list: Vec<Box<[u8; 256]>>) -> DispatchResult;
@@ -1116,7 +1526,7 @@ pub trait MinerControl<AccountId, BlockNumber> {
 
 	fn is_positive(miner: &AccountId) -> Result<bool, DispatchError>;
 	fn is_lock(miner: &AccountId) -> Result<bool, DispatchError>;
-	fn update_miner_state(miner: &AccountId, state: &str) -> DispatchResult;
+	fn update_miner_state(miner: &AccountId, state: MinerState) -> DispatchResult;
 	fn get_expenders() -> Result<(u64, u64, u64), DispatchError>;
 	fn get_miner_snapshot(
 		miner: &AccountId,
@@ -1124,6 +1534,8 @@ pub trait MinerControl<AccountId, BlockNumber> {
 
 	fn increase_replace_space(miner: &AccountId, space: u128) -> DispatchResult;
 	fn decrease_replace_space(miner: &AccountId, space: u128) -> DispatchResult;
+	// Pays up to `amount` of the collateral of `miner` to `beneficiary`, returning the amount paid.
+	fn pay_from_collateral(miner: &AccountId, beneficiary: &AccountId, amount: u128) -> Result<u128, DispatchError>;
 }
 
 impl<T: Config> MinerControl<<T as frame_system::Config>::AccountId, BlockNumberFor<T>> for Pallet<T> {
@@ -1185,9 +1597,9 @@ impl<T: Config> MinerControl<<T as frame_system::Config>::AccountId, BlockNumber
 		false
 	}
 
-	fn get_miner_state(acc: &AccountOf<T>) -> Result<Vec<u8>, DispatchError> {
+	fn get_miner_state(acc: &AccountOf<T>) -> Result<MinerState, DispatchError> {
 		let miner = <MinerItems<T>>::try_get(acc).map_err(|_| Error::<T>::NotMiner)?;
-		Ok(miner.state.to_vec())
+		Ok(miner.state)
 	}
 
 	fn get_all_miner() -> Result<Vec<AccountOf<T>>, DispatchError> {
@@ -1264,6 +1676,8 @@ impl<T: Config> MinerControl<<T as frame_system::Config>::AccountId, BlockNumber
 			})?;
 
 			miner_info_list.try_push(snap_shot).map_err(|_| Error::<T>::Overflow)?;
+			// The backers share in the reward as they stand now, later changes do not apply to it.
+			Self::snapshot_backer_shares(miner, era)?;
 
 			Ok(())
 		})?;
@@ -1285,26 +1699,19 @@ impl<T: Config> MinerControl<<T as frame_system::Config>::AccountId, BlockNumber
 
 	fn is_positive(miner: &AccountOf<T>) -> Result<bool, DispatchError> {
 		let state = Self::get_miner_state(miner)?;
-		Ok(state == STATE_POSITIVE.as_bytes().to_vec())
+		Ok(state == MinerState::Positive)
 	}
 
 	fn is_lock(miner: &AccountOf<T>) -> Result<bool, DispatchError> {
 		let state = Self::get_miner_state(miner)?;
-		Ok(state == STATE_LOCK.as_bytes().to_vec())
+		Ok(state == MinerState::Lock)
 	}
 
-	fn update_miner_state(miner: &AccountOf<T>, state: &str) -> DispatchResult {
-		let state = match state {
-			STATE_POSITIVE | STATE_FROZEN | STATE_EXIT | STATE_LOCK => state.as_bytes().to_vec(),
-			_ => Err(Error::<T>::Overflow)?,
-		};
-
+	fn update_miner_state(miner: &AccountOf<T>, state: MinerState) -> DispatchResult {
 		<MinerItems<T>>::try_mutate(miner, |miner_opt| -> DispatchResult {
 			let miner_info = miner_opt.as_mut().ok_or(Error::<T>::NotMiner)?;
 
-			miner_info.state = state.try_into().map_err(|_| Error::<T>::BoundedVecError)?;
-
-			Ok(())
+			Self::transition_state(miner, miner_info, state)
 		})
 	}
 
@@ -1362,4 +1769,42 @@ impl<T: Config> MinerControl<<T as frame_system::Config>::AccountId, BlockNumber
 			Ok(())
 		})
 	}
+
+	fn pay_from_collateral(miner: &AccountOf<T>, beneficiary: &AccountOf<T>, amount: u128) -> Result<u128, DispatchError> {
+		let amount: BalanceOf<T> = amount.try_into().map_err(|_| Error::<T>::Overflow)?;
+		let paid = Self::pay_from_collateral(miner, beneficiary, amount)?;
+
+		Ok(paid.saturated_into())
+	}
+}
+
+/// The obligations a miner has to fulfil before it may exit: its open challenge, the deals it reported fragments
+/// of and the restoral orders it claimed.
+pub trait MinerObligations<AccountId, BlockNumber> {
+	/// The last block of the obligations of `miner`, `None` if it has none.
+	fn obligations_end(miner: &AccountId) -> Option<BlockNumber>;
+	/// Forgets the obligations of `miner`, once it left.
+	fn clear_obligations(_miner: &AccountId) {}
+}
+
+impl<AccountId, BlockNumber> MinerObligations<AccountId, BlockNumber> for () {
+	fn obligations_end(_miner: &AccountId) -> Option<BlockNumber> {
+		None
+	}
+}
+
+impl<AccountId, BlockNumber, A, B> MinerObligations<AccountId, BlockNumber> for (A, B)
+where
+	BlockNumber: Ord,
+	A: MinerObligations<AccountId, BlockNumber>,
+	B: MinerObligations<AccountId, BlockNumber>,
+{
+	fn obligations_end(miner: &AccountId) -> Option<BlockNumber> {
+		A::obligations_end(miner).max(B::obligations_end(miner))
+	}
+
+	fn clear_obligations(miner: &AccountId) {
+		A::clear_obligations(miner);
+		B::clear_obligations(miner);
+	}
 }
@@ -0,0 +1,139 @@
+use super::*;
+use frame_support::pallet_prelude::Weight;
+
+pub fn migrate<T: Config>() -> Weight {
+	let version = StorageVersion::get::<Pallet<T>>();
+	let mut weight: Weight = T::DbWeight::get().reads(1);
+
+	if version < 1 {
+		log::info!("Sminer version 0 -> 1 migrations start!");
+		weight = weight.saturating_add(v1::migrate::<T>());
+		StorageVersion::new(1).put::<Pallet<T>>();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	if version < 2 {
+		log::info!("Sminer version 1 -> 2 migrations start!");
+		weight = weight.saturating_add(v2::migrate::<T>());
+		StorageVersion::new(2).put::<Pallet<T>>();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	weight
+}
+
+mod v1 {
+	use super::*;
+
+	#[derive(Decode, Encode)]
+	struct OldMinerInfo<T: Config> {
+		beneficiary: AccountOf<T>,
+		staking_account: AccountOf<T>,
+		peer_id: PeerId,
+		collaterals: BalanceOf<T>,
+		debt: BalanceOf<T>,
+		state: BoundedVec<u8, T::ItemLimit>,
+		declaration_space: u128,
+		idle_space: u128,
+		service_space: u128,
+		lock_space: u128,
+		space_proof_info: Option<SpaceProofInfo<AccountOf<T>>>,
+		service_bloom_filter: BloomFilter,
+		tee_signature: TeeSig,
+	}
+
+	fn parse_state<T: Config>(miner: &AccountOf<T>, state: &[u8]) -> MinerState {
+		match state {
+			b"not ready" => MinerState::NotReady,
+			b"positive" => MinerState::Positive,
+			b"frozen" => MinerState::Frozen,
+			b"lock" => MinerState::Lock,
+			b"exit" => MinerState::Exit,
+			b"offline" => MinerState::Offline,
+			// Nothing else was ever written, freeze the miner rather than guess.
+			_ => {
+				log::error!("Sminer migration: miner {:?} has an unknown state {:?}", miner, state);
+				MinerState::Frozen
+			},
+		}
+	}
+
+	// The state of a miner was the name of its state.
+	pub fn migrate<T: Config>() -> Weight {
+		let mut weight: Weight = T::DbWeight::get().reads(1);
+
+		<MinerItems<T>>::translate(|miner, old: OldMinerInfo<T>| {
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			Some(MinerInfo::<T> {
+				state: parse_state::<T>(&miner, &old.state),
+				beneficiary: old.beneficiary,
+				staking_account: old.staking_account,
+				peer_id: old.peer_id,
+				collaterals: old.collaterals,
+				debt: old.debt,
+				declaration_space: old.declaration_space,
+				idle_space: old.idle_space,
+				service_space: old.service_space,
+				lock_space: old.lock_space,
+				space_proof_info: old.space_proof_info,
+				service_bloom_filter: old.service_bloom_filter,
+				tee_signature: old.tee_signature,
+			})
+		});
+
+		weight
+	}
+}
+
+mod v2 {
+	use super::*;
+
+	#[derive(Decode, Encode)]
+	struct OldRewardOrder<Balance, Block> {
+		receive_count: u8,
+		max_count: u8,
+		atonce: bool,
+		order_reward: Balance,
+		each_amount: Balance,
+		last_receive_block: Block,
+	}
+
+	#[derive(Decode, Encode)]
+	struct OldReward<T: Config> {
+		total_reward: BalanceOf<T>,
+		reward_issued: BalanceOf<T>,
+		order_list: BoundedVec<OldRewardOrder<BalanceOf<T>, BlockNumberFor<T>>, ConstU32<{ RELEASE_NUMBER as u32 }>>,
+	}
+
+	// Each order released `each_amount` a day for its days left, merged into one curve now.
+	pub fn migrate<T: Config>() -> Weight {
+		let mut weight: Weight = T::DbWeight::get().reads(1);
+		let now = <frame_system::Pallet<T>>::block_number();
+		let one_day = T::OneDayBlock::get();
+
+		<RewardMap<T>>::translate(|_key, old: OldReward<T>| {
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			let mut reward = Reward::<T> {
+				total_reward: old.total_reward,
+				reward_issued: old.reward_issued,
+				releasable: BalanceOf::<T>::zero(),
+				vesting: Default::default(),
+			};
+			for order in old.order_list {
+				if !order.atonce {
+					let atonce = AOIR_PERCENT.mul_floor(order.order_reward);
+					reward.releasable = reward.releasable.saturating_add(atonce);
+				}
+				let days_left = order.max_count.saturating_sub(order.receive_count);
+				let amount = order.each_amount.saturating_mul(days_left.into());
+				let end_block = order.last_receive_block.saturating_add(one_day.saturating_mul(days_left.into()));
+				let vested = reward.vesting.merge(amount, order.last_receive_block, end_block, now);
+				reward.releasable = reward.releasable.saturating_add(vested);
+			}
+
+			Some(reward)
+		});
+
+		weight
+	}
+}
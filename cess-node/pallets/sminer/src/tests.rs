@@ -23,10 +23,6 @@ use frame_support::{assert_noop, assert_ok, traits::Len};
 use mock::{consts::*, new_test_ext, run_to_block, Balances, RuntimeOrigin, Sminer, System as Sys, Test};
 
 const UNIT_POWER_LIMIT: u128 = 2000_000_000_000_000u128;
-const STATE_POSITIVE: &str = "positive";
-const STATE_FROZEN: &str = "frozen";
-const STATE_EXIT_FROZEN: &str = "e_frozen";
-const STATE_EXIT: &str = "exit";
 
 #[test]
 fn miner_register_works() {
@@ -54,7 +50,7 @@ fn miner_register_works() {
 		//miner item check
 		let mr = &MinerItems::<Test>::get(ACCOUNT1.0).unwrap();
 		assert_eq!(stake_amount, mr.collaterals);
-		assert_eq!(Vec::from(STATE_POSITIVE), mr.state.to_vec());
+		assert_eq!(MinerState::Positive, mr.state);
 		assert!(mr.peer_id > 0);
 
 		assert_eq!(mr.peer_id, PeerIndex::<Test>::try_get().unwrap());
@@ -113,7 +109,7 @@ fn increase_collateral_works_on_normal_state() {
 		assert_eq!(2000 + 3000, Balances::reserved_balance(&ACCOUNT1.0));
 
 		let mi = MinerItems::<Test>::try_get(ACCOUNT1.0).unwrap();
-		assert_eq!(Vec::from(STATE_POSITIVE), mi.state.to_vec());
+		assert_eq!(MinerState::Positive, mi.state);
 
 		assert_eq!(2000 + 3000, mi.collaterals);
 
@@ -131,10 +127,10 @@ fn increase_collateral_works_on_normal_state() {
 	});
 }
 
-fn set_miner_state(account_id: u64, state: &str) {
+fn set_miner_state(account_id: u64, state: MinerState) {
 	let _ = MinerItems::<Test>::try_mutate(account_id, |opt| -> DispatchResult {
 		let mr = opt.as_mut().unwrap();
-		mr.state = Vec::from(state).try_into().unwrap();
+		mr.state = state;
 		Ok(())
 	});
 }
@@ -148,13 +144,13 @@ fn increase_collateral_works_on_frozen_state() {
 			IpAddress::IPV4([127,0,0,1],15000),
 			2000
 		));
-		set_miner_state(ACCOUNT1.0, STATE_FROZEN);
+		set_miner_state(ACCOUNT1.0, MinerState::Frozen);
 
 		assert_eq!(UNIT_POWER_LIMIT, Sminer::check_collateral_limit(0u128).unwrap());
 		assert_ok!(Sminer::increase_collateral(RuntimeOrigin::signed(ACCOUNT1.0), 3000));
 		assert_eq!(
-			Vec::from(STATE_FROZEN),
-			MinerItems::<Test>::try_get(ACCOUNT1.0).unwrap().state.to_vec()
+			MinerState::Frozen,
+			MinerItems::<Test>::try_get(ACCOUNT1.0).unwrap().state
 		);
 
 		assert_ok!(Sminer::increase_collateral(
@@ -162,14 +158,14 @@ fn increase_collateral_works_on_frozen_state() {
 			2 * UNIT_POWER_LIMIT + 1
 		));
 		assert_eq!(
-			Vec::from(STATE_POSITIVE),
-			MinerItems::<Test>::try_get(ACCOUNT1.0).unwrap().state.to_vec()
+			MinerState::Positive,
+			MinerItems::<Test>::try_get(ACCOUNT1.0).unwrap().state
 		);
 	});
 }
 
 #[test]
-fn increase_collateral_works_on_exit_frozen_state() {
+fn increase_collateral_fails_on_exit_state() {
 	new_test_ext().execute_with(|| {
 		assert_ok!(Sminer::regnstk(
 			RuntimeOrigin::signed(ACCOUNT1.0),
@@ -177,25 +173,36 @@ fn increase_collateral_works_on_exit_frozen_state() {
 			IpAddress::IPV4([127,0,0,1],15000),
 			2000
 		));
-		set_miner_state(ACCOUNT1.0, STATE_EXIT_FROZEN);
+		set_miner_state(ACCOUNT1.0, MinerState::Exit);
 
-		assert_ok!(Sminer::increase_collateral(RuntimeOrigin::signed(ACCOUNT1.0), 3000));
-		assert_eq!(
-			Vec::from(STATE_EXIT_FROZEN),
-			MinerItems::<Test>::try_get(ACCOUNT1.0).unwrap().state.to_vec()
-		);
-
-		assert_ok!(Sminer::increase_collateral(
-			RuntimeOrigin::signed(ACCOUNT1.0),
-			2 * UNIT_POWER_LIMIT + 1
-		));
-		assert_eq!(
-			Vec::from(STATE_EXIT),
-			MinerItems::<Test>::try_get(ACCOUNT1.0).unwrap().state.to_vec()
+		assert_noop!(
+			Sminer::increase_collateral(RuntimeOrigin::signed(ACCOUNT1.0), 3000),
+			Error::<Test>::StateError
 		);
+		assert_eq!(MinerState::Exit, MinerItems::<Test>::try_get(ACCOUNT1.0).unwrap().state);
 	});
 }
 
+#[test]
+fn miner_state_transitions() {
+	use MinerState::*;
+
+	assert!(NotReady.can_transition_to(&Positive));
+	assert!(NotReady.can_transition_to(&Frozen));
+	assert!(Positive.can_transition_to(&Frozen));
+	assert!(Frozen.can_transition_to(&Positive));
+	assert!(Positive.can_transition_to(&Lock));
+	assert!(Lock.can_transition_to(&Exit));
+	assert!(Lock.can_transition_to(&Offline));
+
+	assert!(!NotReady.can_transition_to(&Lock));
+	assert!(!Frozen.can_transition_to(&Lock));
+	assert!(!Positive.can_transition_to(&Exit));
+	assert!(!Exit.can_transition_to(&Positive));
+	assert!(!Offline.can_transition_to(&Positive));
+	assert!(!Offline.can_transition_to(&Exit));
+}
+
 #[test]
 fn exit_miner_works() {
 	new_test_ext().execute_with(|| {
@@ -211,8 +218,8 @@ fn exit_miner_works() {
 		assert_ok!(Sminer::exit_miner(RuntimeOrigin::signed(ACCOUNT1.0)));
 
 		assert_eq!(
-			Vec::from(STATE_EXIT),
-			MinerItems::<Test>::try_get(ACCOUNT1.0).unwrap().state.to_vec()
+			MinerState::Exit,
+			MinerItems::<Test>::try_get(ACCOUNT1.0).unwrap().state
 		);
 		assert_eq!(2, MinerLockIn::<Test>::try_get(ACCOUNT1.0).unwrap());
 		let event = Sys::events().pop().expect("Expected at least one MinerExit to be found").event;
@@ -230,7 +237,7 @@ fn exit_miner_on_abnormal_state() {
 			UNIT_POWER_LIMIT
 		));
 
-		set_miner_state(ACCOUNT1.0, STATE_EXIT);
+		set_miner_state(ACCOUNT1.0, MinerState::Exit);
 		assert_noop!(
 			Sminer::exit_miner(RuntimeOrigin::signed(ACCOUNT1.0)),
 			Error::<Test>::NotpositiveState
@@ -586,10 +593,10 @@ fn failure_to_replenish_sufficient_deposit() {
 		));
 
 		let miner = <MinerItems<Test>>::get(&ACCOUNT1.0).unwrap();
-		assert_eq!(miner.state.to_vec(), "positive".as_bytes().to_vec());
+		assert_eq!(miner.state, MinerState::Positive);
 		assert_ok!(Sminer::join_buffer_pool(ACCOUNT1.0));
 		let miner = <MinerItems<Test>>::get(&ACCOUNT1.0).unwrap();
-		assert_eq!(miner.state.to_vec(), "frozen".as_bytes().to_vec());
+		assert_eq!(miner.state, MinerState::Frozen);
 		assert_ok!(Sminer::start_buffer_period_schedule());
 		assert!(BadMiner::<Test>::contains_key(ACCOUNT1.0));
 		let event = Sys::events().pop().expect("Expected at least one MinerExit to be found").event;
@@ -649,3 +656,155 @@ fn faucet_should_work() {
 	});
 }
 
+
+#[test]
+fn reward_vesting_releases_linearly() {
+	let mut vesting = RewardVesting::<u128, u64> { locked: 1000, last_block: 0, end_block: 100 };
+	assert_eq!(0, vesting.release(0));
+	assert_eq!(250, vesting.release(25));
+	assert_eq!(0, vesting.release(25));
+	assert_eq!(750, vesting.release(200));
+	assert_eq!(0, vesting.locked);
+}
+
+#[test]
+fn reward_vesting_merge_keeps_a_weighted_end() {
+	let mut vesting = RewardVesting::<u128, u64> { locked: 1000, last_block: 0, end_block: 100 };
+	// 100 of the curve vested by block 10, and none of the new amount that starts there.
+	assert_eq!(100, vesting.merge(900, 10, 130, 10));
+	assert_eq!(1800, vesting.locked);
+	assert_eq!(10, vesting.last_block);
+	// The 900 left of the curve had 90 blocks to go and the 900 added 120, the end is not pushed to 130.
+	assert_eq!(10 + (900 * 90 + 900 * 120) / 1800, vesting.end_block);
+}
+
+#[test]
+fn reward_vesting_merge_releases_what_vested_of_the_amount() {
+	let mut vesting = RewardVesting::<u128, u64>::default();
+	assert_eq!(500, vesting.merge(1000, 0, 100, 50));
+	assert_eq!(500, vesting.locked);
+	assert_eq!(100, vesting.end_block);
+	assert_eq!(1000, vesting.merge(1000, 0, 40, 50));
+	assert_eq!(500, vesting.locked);
+	assert_eq!(100, vesting.end_block);
+}
+
+#[test]
+fn migration_v2_merges_reward_orders_into_the_vesting_curve() {
+	new_test_ext().execute_with(|| {
+		Sys::set_block_number(1);
+		// The reward orders as they were stored: received once of three days, its first release still owed.
+		let old_orders: Vec<(u8, u8, bool, u128, u128, u64)> = vec![(1, 3, false, 3000, 1000, 1)];
+		let old_reward = (3000u128, 1000u128, old_orders);
+		frame_support::storage::unhashed::put_raw(
+			&RewardMap::<Test>::hashed_key_for(&ACCOUNT1.0),
+			&old_reward.encode(),
+		);
+		StorageVersion::new(1).put::<Sminer>();
+
+		migrations::migrate::<Test>();
+
+		let reward = RewardMap::<Test>::get(&ACCOUNT1.0).unwrap();
+		assert_eq!(3000, reward.total_reward);
+		assert_eq!(1000, reward.reward_issued);
+		assert_eq!(AOIR_PERCENT.mul_floor(3000u128), reward.releasable);
+		assert_eq!(RewardVesting { locked: 2000, last_block: 1, end_block: 1 + 2 * 14400 }, reward.vesting);
+		assert_eq!(StorageVersion::new(2), StorageVersion::get::<Sminer>());
+	});
+}
+
+// A miner on ACCOUNT1 with 2000 of its own collateral, backed by ACCOUNT2 with `backed`.
+fn register_backed_miner(backed: u128) {
+	assert_ok!(Sminer::regnstk(RuntimeOrigin::signed(ACCOUNT1.0), ACCOUNT1.0, [0u8; 38], 2000, 0));
+	if backed > 0 {
+		assert_ok!(Sminer::bond(RuntimeOrigin::signed(ACCOUNT2.0), ACCOUNT1.0, backed));
+	}
+}
+
+#[test]
+fn bond_and_unbond_keep_the_collateral_books() {
+	new_test_ext().execute_with(|| {
+		Sys::set_block_number(1);
+		register_backed_miner(1000);
+		assert_eq!(3000, MinerItems::<Test>::get(ACCOUNT1.0).unwrap().collaterals);
+		assert_eq!(1000, Sminer::backed_collateral(&ACCOUNT1.0));
+		assert_eq!(1000, Balances::reserved_balance(&ACCOUNT2.0));
+
+		assert_ok!(Sminer::unbond(RuntimeOrigin::signed(ACCOUNT2.0), ACCOUNT1.0, 400));
+		let unlock_block = 1 + Sminer::unbond_period();
+		assert_eq!(2600, MinerItems::<Test>::get(ACCOUNT1.0).unwrap().collaterals);
+		assert_eq!(600, Sminer::backed_collateral(&ACCOUNT1.0));
+		assert_eq!(400, Sminer::cooling_collateral(&ACCOUNT1.0, 1));
+		assert_eq!(unlock_block, BackerUnbonding::<Test>::get(ACCOUNT2.0, ACCOUNT1.0).unwrap().unlock_block);
+		assert_eq!(1000, Balances::reserved_balance(&ACCOUNT2.0));
+
+		assert_noop!(
+			Sminer::withdraw_unbonded(RuntimeOrigin::signed(ACCOUNT2.0), ACCOUNT1.0),
+			Error::<Test>::CollateralCooling
+		);
+		Sys::set_block_number(unlock_block);
+		assert_ok!(Sminer::withdraw_unbonded(RuntimeOrigin::signed(ACCOUNT2.0), ACCOUNT1.0));
+		assert_eq!(600, Balances::reserved_balance(&ACCOUNT2.0));
+		assert!(Delegations::<Test>::get(ACCOUNT1.0).unwrap().unbonding.is_empty());
+	});
+}
+
+#[test]
+fn unbonding_collateral_is_punished_until_it_unlocks() {
+	new_test_ext().execute_with(|| {
+		Sys::set_block_number(1);
+		register_backed_miner(2000);
+		assert_ok!(Sminer::unbond(RuntimeOrigin::signed(ACCOUNT2.0), ACCOUNT1.0, 2000));
+
+		// 2000 bonded by the miner and 2000 cooling down, a quarter of each is taken.
+		let mut miner_info = MinerItems::<Test>::get(ACCOUNT1.0).unwrap();
+		let shares = Sminer::take_collateral(&ACCOUNT1.0, &mut miner_info, 1000).unwrap();
+		assert_eq!(vec![(ACCOUNT2.0, 500), (ACCOUNT1.0, 500)], shares);
+		assert_eq!(1500, miner_info.collaterals);
+		assert_eq!(1500, BackerUnbonding::<Test>::get(ACCOUNT2.0, ACCOUNT1.0).unwrap().amount);
+		assert_eq!(1500, Sminer::cooling_collateral(&ACCOUNT1.0, 1));
+
+		// Once unlocked, the unbonded collateral is out of reach.
+		Sys::set_block_number(1 + Sminer::unbond_period());
+		let shares = Sminer::take_collateral(&ACCOUNT1.0, &mut miner_info, 1000).unwrap();
+		assert_eq!(vec![(ACCOUNT1.0, 1000)], shares);
+		assert_eq!(1500, BackerUnbonding::<Test>::get(ACCOUNT2.0, ACCOUNT1.0).unwrap().amount);
+	});
+}
+
+#[test]
+fn backers_can_unbond_from_a_leaving_miner() {
+	new_test_ext().execute_with(|| {
+		Sys::set_block_number(1);
+		register_backed_miner(1000);
+		set_miner_state(ACCOUNT1.0, MinerState::Lock);
+		assert_ok!(Sminer::unbond(RuntimeOrigin::signed(ACCOUNT2.0), ACCOUNT1.0, 1000));
+		set_miner_state(ACCOUNT1.0, MinerState::Exit);
+		assert_noop!(
+			Sminer::unbond(RuntimeOrigin::signed(ACCOUNT2.0), ACCOUNT1.0, 1000),
+			Error::<Test>::InsufficientBond
+		);
+		assert_eq!(1000, BackerUnbonding::<Test>::get(ACCOUNT2.0, ACCOUNT1.0).unwrap().amount);
+	});
+}
+
+#[test]
+fn backer_shares_are_taken_when_the_reward_is_earned() {
+	new_test_ext().execute_with(|| {
+		Sys::set_block_number(1);
+		register_backed_miner(2000);
+		assert_ok!(Sminer::set_commission(RuntimeOrigin::signed(ACCOUNT1.0), Perbill::from_percent(10)));
+		assert_ok!(Sminer::snapshot_backer_shares(&ACCOUNT1.0, 0));
+
+		// Neither a late bond nor a new commission change what was earned in era 0.
+		assert_ok!(Sminer::set_commission(RuntimeOrigin::signed(ACCOUNT1.0), Perbill::from_percent(50)));
+		assert_ok!(Sminer::bond(RuntimeOrigin::signed(ACCOUNT3.0), ACCOUNT1.0, 4000));
+		assert_eq!((550, vec![(ACCOUNT2.0, 450)]), Sminer::split_backer_rewards(&ACCOUNT1.0, 0, 1000).unwrap());
+
+		assert_ok!(Sminer::snapshot_backer_shares(&ACCOUNT1.0, 1));
+		assert_eq!(
+			(625, vec![(ACCOUNT2.0, 125), (ACCOUNT3.0, 250)]),
+			Sminer::split_backer_rewards(&ACCOUNT1.0, 1, 1000).unwrap()
+		);
+	});
+}
@@ -1,5 +1,6 @@
 use super::*;
 use frame_support::pallet_prelude::MaxEncodedLen;
+use sp_runtime::traits::AtLeast32BitUnsigned;
 
 /// The custom struct for storing info of storage miners.
 #[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
@@ -12,8 +13,7 @@ pub struct MinerInfo<T: Config> {
 	pub peer_id: PeerId,
 	pub collaterals: BalanceOf<T>,
 	pub debt: BalanceOf<T>,
-	//nomal, exit, frozen, e_frozen
-	pub state: BoundedVec<u8, T::ItemLimit>,
+	pub state: MinerState,
 	pub declaration_space: u128,
 	pub idle_space: u128,
 	pub service_space: u128,
@@ -23,6 +23,42 @@ pub struct MinerInfo<T: Config> {
     pub tee_signature: TeeSig,
 }
 
+/// The state of a storage miner.
+///
+/// A miner only moves along the edges allowed by `can_transition_to`, through `Pallet::transition_state`.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, Copy, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum MinerState {
+	/// Registered, waiting for its PoIS key.
+	NotReady,
+	/// Certifies space and receives rewards.
+	Positive,
+	/// Its collateral is below the limit of its space.
+	Frozen,
+	/// Preparing to exit, still challenged until its lock ends.
+	Lock,
+	/// Exited gracefully, its service files being restored.
+	Exit,
+	/// Forced out after failing its challenges.
+	Offline,
+}
+
+impl MinerState {
+	pub fn can_transition_to(&self, to: &MinerState) -> bool {
+		use MinerState::*;
+
+		matches!(
+			(self, to),
+			(NotReady, Positive) |
+				(NotReady, Frozen) |
+				(Positive, Frozen) |
+				(Frozen, Positive) |
+				(Positive, Lock) |
+				(Lock, Exit) |
+				(Positive | Frozen | Lock, Offline)
+		)
+	}
+}
+
 #[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 #[codec(mel_bound())]
@@ -31,18 +67,81 @@ pub struct Reward<T: pallet::Config> {
     pub(super) total_reward: BalanceOf<T>,
 	//Rewards issued at present
 	pub(super) reward_issued: BalanceOf<T>,
-	//Reward order list, up to 180 reward orders can be accumulated
-	pub(super) order_list: BoundedVec<RewardOrder<BalanceOf<T>, BlockNumberFor<T>>, ConstU32<{RELEASE_NUMBER as u32}>>,
+	//Rewards released but not yet issued
+	pub(super) releasable: BalanceOf<T>,
+	//The release curve all pending rewards are merged into
+	pub(super) vesting: RewardVesting<BalanceOf<T>, BlockNumberFor<T>>,
 }
 
-#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
-pub struct RewardOrder<Balance, Block> {
-	pub(super) receive_count: u8,
-	pub(super) max_count: u8,
-	pub(super) atonce: bool,
-	pub(super) order_reward: Balance,
-	pub(super) each_amount: Balance,
-	pub(super) last_receive_block: Block,
+/// A linear release of `locked` from `last_block` until `end_block`.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, Default, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct RewardVesting<Balance, Block> {
+	pub(super) locked: Balance,
+	pub(super) last_block: Block,
+	pub(super) end_block: Block,
+}
+
+impl<Balance, Block> RewardVesting<Balance, Block>
+where
+	Balance: AtLeast32BitUnsigned + Copy,
+	Block: AtLeast32BitUnsigned + Copy,
+{
+	/// Releases what vested until `now`.
+	pub(super) fn release(&mut self, now: Block) -> Balance {
+		if now <= self.last_block {
+			return Balance::zero();
+		}
+		let released = if now >= self.end_block {
+			self.locked
+		} else {
+			let elapsed: u128 = (now - self.last_block).saturated_into();
+			let remaining: u128 = (self.end_block - self.last_block).saturated_into();
+			Perbill::from_rational(elapsed, remaining).mul_floor(self.locked)
+		};
+		self.locked = self.locked.saturating_sub(released);
+		self.last_block = now;
+
+		released
+	}
+
+	/// Merges `amount`, vesting linearly from `start` until `end`, into the curve. The rest of the curve then
+	/// releases until the end of both weighted by what is left of each, so neither the rewards already vesting
+	/// nor the new ones are pushed back to the later end.
+	///
+	/// Returns what vested until `now`, of the curve and of `amount`.
+	pub(super) fn merge(&mut self, amount: Balance, start: Block, end: Block, now: Block) -> Balance {
+		let released = self.release(now);
+		let vested = if now >= end {
+			amount
+		} else if now <= start {
+			Balance::zero()
+		} else {
+			let elapsed: u128 = (now - start).saturated_into();
+			let period: u128 = (end - start).saturated_into();
+			Perbill::from_rational(elapsed, period).mul_floor(amount)
+		};
+		let remaining = amount.saturating_sub(vested);
+		if !remaining.is_zero() {
+			if self.locked.is_zero() {
+				self.end_block = end;
+			} else {
+				let base = self.last_block;
+				let locked: u128 = self.locked.saturated_into();
+				let added: u128 = remaining.saturated_into();
+				let left: u128 = self.end_block.saturating_sub(base).saturated_into();
+				let period: u128 = end.saturating_sub(base).saturated_into();
+				let weighted = locked
+					.saturating_mul(left)
+					.saturating_add(added.saturating_mul(period))
+					.checked_div(locked.saturating_add(added))
+					.unwrap_or(period);
+				self.end_block = base.saturating_add(weighted.saturated_into());
+			}
+			self.locked = self.locked.saturating_add(remaining);
+		}
+
+		released.saturating_add(vested)
+	}
 }
 
 /// The custom struct for storing info of storage FaucetRecord.
@@ -59,6 +158,39 @@ pub struct RestoralTargetInfo<Account, Block> {
 	pub(super) cooling_block: Block,
 }
 
+/// The backers of a miner, who bond collateral to it and share its rewards and punishments.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct Delegation<T: Config> {
+	//The part of the rewards of its backers the miner keeps
+	pub(super) commission: Perbill,
+	//Each backer with the collateral it bonded
+	pub(super) backers: BoundedVec<(AccountOf<T>, BalanceOf<T>), ConstU32<MAX_BACKERS>>,
+	//Each backer with the collateral it unbonded, still punishable until the block it unlocks at
+	pub(super) unbonding: BoundedVec<(AccountOf<T>, BalanceOf<T>, BlockNumberFor<T>), ConstU32<MAX_BACKERS>>,
+}
+
+impl<T: Config> Default for Delegation<T> {
+	fn default() -> Self {
+		Delegation { commission: Perbill::zero(), backers: Default::default(), unbonding: Default::default() }
+	}
+}
+
+/// The rewards a backer earned through a miner, released like the rewards of the miner.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, Default, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct BackerReward<Balance, Block> {
+	pub(super) releasable: Balance,
+	pub(super) vesting: RewardVesting<Balance, Block>,
+}
+
+/// Collateral a backer unbonded, reserved until `unlock_block`.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct UnbondingInfo<Balance, Block> {
+	pub(super) amount: Balance,
+	pub(super) unlock_block: Block,
+}
+
 /// audit -> sminer -> cess-treasury
 /// way 1:
 /// sminer.round_snapshot  key u128
@@ -49,6 +49,13 @@ pub trait WeightInfo {
 	fn faucet() -> Weight;
 	fn regnstk_assign_staking() -> Weight;
 	fn increase_declaration_space() -> Weight;
+	fn decrease_declaration_space() -> Weight;
+	fn release_collateral() -> Weight;
+	fn set_commission() -> Weight;
+	fn bond() -> Weight;
+	fn unbond() -> Weight;
+	fn withdraw_unbonded() -> Weight;
+	fn receive_backer_reward(v: u32, ) -> Weight;
 }
 
 /// Weights for `pallet_sminer` using the Substrate node and recommended hardware.
@@ -135,6 +142,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Sminer::CompleteMinerSnapShot` (`max_values`: None, `max_size`: Some(1940), added: 4415, mode: `MaxEncodedLen`)
 	/// Storage: `Sminer::RewardMap` (r:1 w:1)
 	/// Proof: `Sminer::RewardMap` (`max_values`: None, `max_size`: Some(3592), added: 6067, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::BackerShares` (r:1 w:1)
+	/// Proof: `Sminer::BackerShares` (`max_values`: None, `max_size`: Some(1221), added: 3696, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:2 w:2)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	/// The range of component `v` is `[1, 80]`.
@@ -146,8 +155,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(75_950_137, 206644)
 			// Standard Error: 21_450
 			.saturating_add(Weight::from_parts(117_490, 0).saturating_mul(v.into()))
-			.saturating_add(T::DbWeight::get().reads(5_u64))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: `Sminer::MinerLock` (r:1 w:1)
 	/// Proof: `Sminer::MinerLock` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
@@ -159,13 +168,17 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Scheduler::Lookup` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
 	/// Storage: `Scheduler::Agenda` (r:1 w:1)
 	/// Proof: `Scheduler::Agenda` (`max_values`: None, `max_size`: Some(10463), added: 12938, mode: `MaxEncodedLen`)
+	/// Storage: `Audit::ChallengeSnapShot` (r:1 w:0)
+	/// Proof: `Audit::ChallengeSnapShot` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `FileBank::MinerObligationEnd` (r:1 w:0)
+	/// Proof: `FileBank::MinerObligationEnd` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
 	fn miner_exit_prep() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `3494`
 		//  Estimated: `206644`
 		// Minimum execution time: 49_322_000 picoseconds.
 		Weight::from_parts(67_798_000, 206644)
-			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
 			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 	/// Storage: `Sminer::MinerItems` (r:1 w:1)
@@ -176,6 +189,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Sminer::AllMiner` (`max_values`: Some(1), `max_size`: Some(6400004), added: 6400499, mode: `MaxEncodedLen`)
 	/// Storage: `Sminer::RestoralTarget` (r:0 w:1)
 	/// Proof: `Sminer::RestoralTarget` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::MinerObligationEnd` (r:0 w:1)
+	/// Proof: `FileBank::MinerObligationEnd` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
 	fn miner_exit() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `3685`
@@ -183,7 +198,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		// Minimum execution time: 36_286_000 picoseconds.
 		Weight::from_parts(60_987_000, 6401489)
 			.saturating_add(T::DbWeight::get().reads(3_u64))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: `Sminer::RestoralTarget` (r:1 w:0)
 	/// Proof: `Sminer::RestoralTarget` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
@@ -256,6 +271,122 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Sminer::MinerItems` (r:1 w:1)
+	/// Proof: `Sminer::MinerItems` (`max_values`: None, `max_size`: Some(203179), added: 205654, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::CollateralRelease` (r:0 w:1)
+	/// Proof: `Sminer::CollateralRelease` (`max_values`: None, `max_size`: Some(60), added: 2535, mode: `MaxEncodedLen`)
+	/// Storage: `Audit::ChallengeSnapShot` (r:1 w:0)
+	/// Proof: `Audit::ChallengeSnapShot` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `FileBank::MinerObligationEnd` (r:1 w:0)
+	/// Proof: `FileBank::MinerObligationEnd` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn decrease_declaration_space() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3411`
+		//  Estimated: `206644`
+		// Minimum execution time: 27_102_000 picoseconds.
+		Weight::from_parts(31_884_000, 206644)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Sminer::CollateralRelease` (r:1 w:1)
+	/// Proof: `Sminer::CollateralRelease` (`max_values`: None, `max_size`: Some(60), added: 2535, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::MinerItems` (r:1 w:1)
+	/// Proof: `Sminer::MinerItems` (`max_values`: None, `max_size`: Some(203179), added: 205654, mode: `MaxEncodedLen`)
+	/// Storage: `Reservoir::UserPassbook` (r:1 w:1)
+	/// Proof: `Reservoir::UserPassbook` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn release_collateral() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3620`
+		//  Estimated: `206644`
+		// Minimum execution time: 41_530_000 picoseconds.
+		Weight::from_parts(47_261_000, 206644)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Sminer::MinerItems` (r:1 w:0)
+	/// Proof: `Sminer::MinerItems` (`max_values`: None, `max_size`: Some(203179), added: 205654, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::Delegations` (r:1 w:1)
+	/// Proof: `Sminer::Delegations` (`max_values`: None, `max_size`: Some(3254), added: 5729, mode: `MaxEncodedLen`)
+	fn set_commission() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3411`
+		//  Estimated: `206644`
+		// Minimum execution time: 19_240_000 picoseconds.
+		Weight::from_parts(22_105_000, 206644)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Sminer::MinerItems` (r:1 w:1)
+	/// Proof: `Sminer::MinerItems` (`max_values`: None, `max_size`: Some(203179), added: 205654, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::Delegations` (r:1 w:1)
+	/// Proof: `Sminer::Delegations` (`max_values`: None, `max_size`: Some(3254), added: 5729, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn bond() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `4952`
+		//  Estimated: `206644`
+		// Minimum execution time: 38_615_000 picoseconds.
+		Weight::from_parts(43_907_000, 206644)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Sminer::MinerItems` (r:1 w:1)
+	/// Proof: `Sminer::MinerItems` (`max_values`: None, `max_size`: Some(203179), added: 205654, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::Delegations` (r:1 w:1)
+	/// Proof: `Sminer::Delegations` (`max_values`: None, `max_size`: Some(3254), added: 5729, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::BackerUnbonding` (r:1 w:1)
+	/// Proof: `Sminer::BackerUnbonding` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	fn unbond() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `5003`
+		//  Estimated: `206644`
+		// Minimum execution time: 33_481_000 picoseconds.
+		Weight::from_parts(38_260_000, 206644)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Sminer::BackerUnbonding` (r:1 w:1)
+	/// Proof: `Sminer::BackerUnbonding` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::Delegations` (r:1 w:1)
+	/// Proof: `Sminer::Delegations` (`max_values`: None, `max_size`: Some(3254), added: 5729, mode: `MaxEncodedLen`)
+	fn withdraw_unbonded() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2078`
+		//  Estimated: `6719`
+		// Minimum execution time: 29_410_000 picoseconds.
+		Weight::from_parts(32_688_000, 6719)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Sminer::MinerItems` (r:1 w:0)
+	/// Proof: `Sminer::MinerItems` (`max_values`: None, `max_size`: Some(203179), added: 205654, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::CompleteMinerSnapShot` (r:1 w:1)
+	/// Proof: `Sminer::CompleteMinerSnapShot` (`max_values`: None, `max_size`: Some(1940), added: 4415, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::BackerShares` (r:1 w:1)
+	/// Proof: `Sminer::BackerShares` (`max_values`: None, `max_size`: Some(1221), added: 3696, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::BackerRewards` (r:1 w:1)
+	/// Proof: `Sminer::BackerRewards` (`max_values`: None, `max_size`: Some(136), added: 2611, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::RewardMap` (r:1 w:1)
+	/// Proof: `Sminer::RewardMap` (`max_values`: None, `max_size`: Some(3592), added: 6067, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `v` is `[1, 80]`.
+	fn receive_backer_reward(v: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3962 + v * (39 ±0)`
+		//  Estimated: `206644`
+		// Minimum execution time: 66_318_000 picoseconds.
+		Weight::from_parts(80_205_412, 206644)
+			// Standard Error: 22_107
+			.saturating_add(Weight::from_parts(121_934, 0).saturating_mul(v.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -341,6 +472,8 @@ impl WeightInfo for () {
 	/// Proof: `Sminer::CompleteMinerSnapShot` (`max_values`: None, `max_size`: Some(1940), added: 4415, mode: `MaxEncodedLen`)
 	/// Storage: `Sminer::RewardMap` (r:1 w:1)
 	/// Proof: `Sminer::RewardMap` (`max_values`: None, `max_size`: Some(3592), added: 6067, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::BackerShares` (r:1 w:1)
+	/// Proof: `Sminer::BackerShares` (`max_values`: None, `max_size`: Some(1221), added: 3696, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:2 w:2)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	/// The range of component `v` is `[1, 80]`.
@@ -352,8 +485,8 @@ impl WeightInfo for () {
 		Weight::from_parts(75_950_137, 206644)
 			// Standard Error: 21_450
 			.saturating_add(Weight::from_parts(117_490, 0).saturating_mul(v.into()))
-			.saturating_add(RocksDbWeight::get().reads(5_u64))
-			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// Storage: `Sminer::MinerLock` (r:1 w:1)
 	/// Proof: `Sminer::MinerLock` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
@@ -365,13 +498,17 @@ impl WeightInfo for () {
 	/// Proof: `Scheduler::Lookup` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
 	/// Storage: `Scheduler::Agenda` (r:1 w:1)
 	/// Proof: `Scheduler::Agenda` (`max_values`: None, `max_size`: Some(10463), added: 12938, mode: `MaxEncodedLen`)
+	/// Storage: `Audit::ChallengeSnapShot` (r:1 w:0)
+	/// Proof: `Audit::ChallengeSnapShot` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `FileBank::MinerObligationEnd` (r:1 w:0)
+	/// Proof: `FileBank::MinerObligationEnd` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
 	fn miner_exit_prep() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `3494`
 		//  Estimated: `206644`
 		// Minimum execution time: 49_322_000 picoseconds.
 		Weight::from_parts(67_798_000, 206644)
-			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
 			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
 	/// Storage: `Sminer::MinerItems` (r:1 w:1)
@@ -382,6 +519,8 @@ impl WeightInfo for () {
 	/// Proof: `Sminer::AllMiner` (`max_values`: Some(1), `max_size`: Some(6400004), added: 6400499, mode: `MaxEncodedLen`)
 	/// Storage: `Sminer::RestoralTarget` (r:0 w:1)
 	/// Proof: `Sminer::RestoralTarget` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::MinerObligationEnd` (r:0 w:1)
+	/// Proof: `FileBank::MinerObligationEnd` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
 	fn miner_exit() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `3685`
@@ -389,7 +528,7 @@ impl WeightInfo for () {
 		// Minimum execution time: 36_286_000 picoseconds.
 		Weight::from_parts(60_987_000, 6401489)
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
-			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// Storage: `Sminer::RestoralTarget` (r:1 w:0)
 	/// Proof: `Sminer::RestoralTarget` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
@@ -462,4 +601,120 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Sminer::MinerItems` (r:1 w:1)
+	/// Proof: `Sminer::MinerItems` (`max_values`: None, `max_size`: Some(203179), added: 205654, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::CollateralRelease` (r:0 w:1)
+	/// Proof: `Sminer::CollateralRelease` (`max_values`: None, `max_size`: Some(60), added: 2535, mode: `MaxEncodedLen`)
+	/// Storage: `Audit::ChallengeSnapShot` (r:1 w:0)
+	/// Proof: `Audit::ChallengeSnapShot` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `FileBank::MinerObligationEnd` (r:1 w:0)
+	/// Proof: `FileBank::MinerObligationEnd` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn decrease_declaration_space() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3411`
+		//  Estimated: `206644`
+		// Minimum execution time: 27_102_000 picoseconds.
+		Weight::from_parts(31_884_000, 206644)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Sminer::CollateralRelease` (r:1 w:1)
+	/// Proof: `Sminer::CollateralRelease` (`max_values`: None, `max_size`: Some(60), added: 2535, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::MinerItems` (r:1 w:1)
+	/// Proof: `Sminer::MinerItems` (`max_values`: None, `max_size`: Some(203179), added: 205654, mode: `MaxEncodedLen`)
+	/// Storage: `Reservoir::UserPassbook` (r:1 w:1)
+	/// Proof: `Reservoir::UserPassbook` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn release_collateral() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3620`
+		//  Estimated: `206644`
+		// Minimum execution time: 41_530_000 picoseconds.
+		Weight::from_parts(47_261_000, 206644)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Sminer::MinerItems` (r:1 w:0)
+	/// Proof: `Sminer::MinerItems` (`max_values`: None, `max_size`: Some(203179), added: 205654, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::Delegations` (r:1 w:1)
+	/// Proof: `Sminer::Delegations` (`max_values`: None, `max_size`: Some(3254), added: 5729, mode: `MaxEncodedLen`)
+	fn set_commission() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3411`
+		//  Estimated: `206644`
+		// Minimum execution time: 19_240_000 picoseconds.
+		Weight::from_parts(22_105_000, 206644)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Sminer::MinerItems` (r:1 w:1)
+	/// Proof: `Sminer::MinerItems` (`max_values`: None, `max_size`: Some(203179), added: 205654, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::Delegations` (r:1 w:1)
+	/// Proof: `Sminer::Delegations` (`max_values`: None, `max_size`: Some(3254), added: 5729, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn bond() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `4952`
+		//  Estimated: `206644`
+		// Minimum execution time: 38_615_000 picoseconds.
+		Weight::from_parts(43_907_000, 206644)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Sminer::MinerItems` (r:1 w:1)
+	/// Proof: `Sminer::MinerItems` (`max_values`: None, `max_size`: Some(203179), added: 205654, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::Delegations` (r:1 w:1)
+	/// Proof: `Sminer::Delegations` (`max_values`: None, `max_size`: Some(3254), added: 5729, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::BackerUnbonding` (r:1 w:1)
+	/// Proof: `Sminer::BackerUnbonding` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	fn unbond() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `5003`
+		//  Estimated: `206644`
+		// Minimum execution time: 33_481_000 picoseconds.
+		Weight::from_parts(38_260_000, 206644)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Sminer::BackerUnbonding` (r:1 w:1)
+	/// Proof: `Sminer::BackerUnbonding` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::Delegations` (r:1 w:1)
+	/// Proof: `Sminer::Delegations` (`max_values`: None, `max_size`: Some(3254), added: 5729, mode: `MaxEncodedLen`)
+	fn withdraw_unbonded() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2078`
+		//  Estimated: `6719`
+		// Minimum execution time: 29_410_000 picoseconds.
+		Weight::from_parts(32_688_000, 6719)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Sminer::MinerItems` (r:1 w:0)
+	/// Proof: `Sminer::MinerItems` (`max_values`: None, `max_size`: Some(203179), added: 205654, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::CompleteMinerSnapShot` (r:1 w:1)
+	/// Proof: `Sminer::CompleteMinerSnapShot` (`max_values`: None, `max_size`: Some(1940), added: 4415, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::BackerShares` (r:1 w:1)
+	/// Proof: `Sminer::BackerShares` (`max_values`: None, `max_size`: Some(1221), added: 3696, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::BackerRewards` (r:1 w:1)
+	/// Proof: `Sminer::BackerRewards` (`max_values`: None, `max_size`: Some(136), added: 2611, mode: `MaxEncodedLen`)
+	/// Storage: `Sminer::RewardMap` (r:1 w:1)
+	/// Proof: `Sminer::RewardMap` (`max_values`: None, `max_size`: Some(3592), added: 6067, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `v` is `[1, 80]`.
+	fn receive_backer_reward(v: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3962 + v * (39 ±0)`
+		//  Estimated: `206644`
+		// Minimum execution time: 66_318_000 picoseconds.
+		Weight::from_parts(80_205_412, 206644)
+			// Standard Error: 22_107
+			.saturating_add(Weight::from_parts(121_934, 0).saturating_mul(v.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
 }
@@ -13,6 +13,23 @@ pub fn increase_idle_space<T: Config>(space: u128) {
     });
 }
 
+pub fn sealed_consignment<T: Config>() -> Result<(TokenId, BalanceOf<T>), &'static str> {
+    let caller: AccountOf<T> = account("user1", 100, SEED);
+    let terr_name: TerrName = "t1".as_bytes().to_vec().try_into().map_err(|_| "boundedvec error")?;
+    let free: BalanceOf<T> = 365_000_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!")?;
+    T::Currency::make_free_balance_be(&caller, free);
+    increase_idle_space::<T>(100 * G_BYTE);
+    StorageHandler::<T>::mint_territory(RawOrigin::Signed(caller.clone()).into(), 10, terr_name.clone(), 30)
+        .map_err(|_| "mint territory error")?;
+    let price: BalanceOf<T> = 100_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!")?;
+    let mode = ConsignmentMode::SealedBid { commit_period: 100u32.into(), reveal_period: 100u32.into() };
+    StorageHandler::<T>::territory_auction(RawOrigin::Signed(caller.clone()).into(), terr_name.clone(), price, mode)
+        .map_err(|_| "territory auction error")?;
+    let territory_info = <Territory<T>>::try_get(&caller, &terr_name).map_err(|_| "territory not found")?;
+
+    Ok((territory_info.token, price))
+}
+
 benchmarks! {
     mint_territory {
         let caller: AccountOf<T> = account("user1", 100, SEED);
@@ -222,6 +239,148 @@ benchmarks! {
         assert_eq!(territory_info.state, TerritoryState::Active);
     }
 
-    
+    set_price_bounds {
+        increase_idle_space::<T>(100 * G_BYTE);
+        let floor: BalanceOf<T> = 10_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+        let ceiling: BalanceOf<T> = 50_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+    }: _(RawOrigin::Root, floor, ceiling)
+    verify {
+        assert_eq!(<UnitPrice<T>>::get(), Some(floor));
+    }
+
+    territory_auction {
+        let caller: AccountOf<T> = account("user1", 100, SEED);
+        let terr_name: TerrName = "t1".as_bytes().to_vec().try_into().map_err(|_| "boundedvec error")?;
+        let free: BalanceOf<T> = 365_000_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+        T::Currency::make_free_balance_be(&caller, free);
+        increase_idle_space::<T>(100 * G_BYTE);
+        StorageHandler::<T>::mint_territory(RawOrigin::Signed(caller.clone()).into(), 10, terr_name.clone(), 30)?;
+        let price: BalanceOf<T> = 100_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+        let mode = ConsignmentMode::SealedBid { commit_period: 100u32.into(), reveal_period: 100u32.into() };
+    }: _(RawOrigin::Signed(caller.clone()), terr_name.clone(), price, mode)
+    verify {
+        let territory_info = <Territory<T>>::try_get(&caller, &terr_name).unwrap();
+        assert!(<Consignment<T>>::contains_key(&territory_info.token));
+    }
+
+    split_territory_consignment {
+        let caller: AccountOf<T> = account("user1", 100, SEED);
+        let terr_name: TerrName = "t1".as_bytes().to_vec().try_into().map_err(|_| "boundedvec error")?;
+        let new_name: TerrName = "t2".as_bytes().to_vec().try_into().map_err(|_| "boundedvec error")?;
+        let free: BalanceOf<T> = 365_000_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+        T::Currency::make_free_balance_be(&caller, free);
+        increase_idle_space::<T>(100 * G_BYTE);
+        StorageHandler::<T>::mint_territory(RawOrigin::Signed(caller.clone()).into(), 10, terr_name.clone(), 30)?;
+        <StorageHandler<T> as StorageHandle<AccountOf<T>>>::add_territory_used_space(&caller, &terr_name, G_BYTE)?;
+        let price: BalanceOf<T> = 100_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+        let mode = ConsignmentMode::SealedBid { commit_period: 100u32.into(), reveal_period: 100u32.into() };
+    }: _(RawOrigin::Signed(caller.clone()), terr_name.clone(), 5, new_name.clone(), price, mode)
+    verify {
+        let territory_info = <Territory<T>>::try_get(&caller, &new_name).unwrap();
+        assert_eq!(territory_info.state, TerritoryState::OnConsignment);
+        assert_eq!(<Territory<T>>::try_get(&caller, &terr_name).unwrap().total_space, 5 * G_BYTE);
+    }
+
+    place_sealed_bid {
+        let (token, price) = sealed_consignment::<T>()?;
+        let bidder: AccountOf<T> = account("bidder", 0, SEED);
+        let free: BalanceOf<T> = 365_000_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+        T::Currency::make_free_balance_be(&bidder, free);
+        let commitment = H256(sp_core::hashing::blake2_256(&(price, H256::zero()).encode()));
+    }: _(RawOrigin::Signed(bidder.clone()), token, commitment, price)
+    verify {
+        assert_eq!(<SealedBids<T>>::get(&token).len(), 1);
+    }
+
+    reveal_sealed_bid {
+        let (token, price) = sealed_consignment::<T>()?;
+        let bidder: AccountOf<T> = account("bidder", 0, SEED);
+        let free: BalanceOf<T> = 365_000_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+        T::Currency::make_free_balance_be(&bidder, free);
+        let commitment = H256(sp_core::hashing::blake2_256(&(price, H256::zero()).encode()));
+        StorageHandler::<T>::place_sealed_bid(RawOrigin::Signed(bidder.clone()).into(), token, commitment, price)?;
+        frame_system::Pallet::<T>::set_block_number(frame_system::Pallet::<T>::block_number() + 100u32.into());
+        let rename: TerrName = "bought".as_bytes().to_vec().try_into().map_err(|_| "boundedvec error")?;
+    }: _(RawOrigin::Signed(bidder.clone()), token, price, H256::zero(), rename)
+    verify {
+        assert_eq!(<SealedBids<T>>::get(&token)[0].revealed, Some(price));
+    }
+
+    exec_sealed_consignment {
+        let b in 1 .. T::MaxSealedBids::get();
+        let (token, price) = sealed_consignment::<T>()?;
+        let commitment = H256(sp_core::hashing::blake2_256(&(price, H256::zero()).encode()));
+        let free: BalanceOf<T> = 365_000_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+        for i in 0 .. b {
+            let bidder: AccountOf<T> = account("bidder", i, SEED);
+            T::Currency::make_free_balance_be(&bidder, free);
+            StorageHandler::<T>::place_sealed_bid(RawOrigin::Signed(bidder.clone()).into(), token, commitment, price)?;
+        }
+        frame_system::Pallet::<T>::set_block_number(frame_system::Pallet::<T>::block_number() + 100u32.into());
+        for i in 0 .. b {
+            let bidder: AccountOf<T> = account("bidder", i, SEED);
+            let rename: TerrName = "bought".as_bytes().to_vec().try_into().map_err(|_| "boundedvec error")?;
+            StorageHandler::<T>::reveal_sealed_bid(RawOrigin::Signed(bidder).into(), token, price, H256::zero(), rename)?;
+        }
+    }: _(RawOrigin::Root, token)
+    verify {
+        assert!(!<Consignment<T>>::contains_key(&token));
+    }
+
+    set_auto_renewal {
+        let caller: AccountOf<T> = account("user1", 100, SEED);
+        let terr_name: TerrName = "t1".as_bytes().to_vec().try_into().map_err(|_| "boundedvec error")?;
+        let free: BalanceOf<T> = 365_000_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+        T::Currency::make_free_balance_be(&caller, free);
+        increase_idle_space::<T>(100 * G_BYTE);
+        StorageHandler::<T>::mint_territory(RawOrigin::Signed(caller.clone()).into(), 10, terr_name.clone(), 30)?;
+        let max_price: BalanceOf<T> = 100_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+    }: _(RawOrigin::Signed(caller.clone()), caller.clone(), terr_name.clone(), max_price, 30)
+    verify {
+        let territory_info = <Territory<T>>::try_get(&caller, &terr_name).unwrap();
+        assert!(<AutoRenewals<T>>::contains_key(&territory_info.token));
+    }
+
+    cancel_auto_renewal {
+        let caller: AccountOf<T> = account("user1", 100, SEED);
+        let terr_name: TerrName = "t1".as_bytes().to_vec().try_into().map_err(|_| "boundedvec error")?;
+        let free: BalanceOf<T> = 365_000_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+        T::Currency::make_free_balance_be(&caller, free);
+        increase_idle_space::<T>(100 * G_BYTE);
+        StorageHandler::<T>::mint_territory(RawOrigin::Signed(caller.clone()).into(), 10, terr_name.clone(), 30)?;
+        let max_price: BalanceOf<T> = 100_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+        StorageHandler::<T>::set_auto_renewal(RawOrigin::Signed(caller.clone()).into(), caller.clone(), terr_name.clone(), max_price, 30)?;
+        let token = <Territory<T>>::try_get(&caller, &terr_name).unwrap().token;
+    }: _(RawOrigin::Signed(caller.clone()), token)
+    verify {
+        assert!(!<AutoRenewals<T>>::contains_key(&token));
+    }
+
+    add_territory_member {
+        let caller: AccountOf<T> = account("user1", 100, SEED);
+        let member: AccountOf<T> = account("member", 0, SEED);
+        let terr_name: TerrName = "t1".as_bytes().to_vec().try_into().map_err(|_| "boundedvec error")?;
+        let free: BalanceOf<T> = 365_000_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+        T::Currency::make_free_balance_be(&caller, free);
+        increase_idle_space::<T>(100 * G_BYTE);
+        StorageHandler::<T>::mint_territory(RawOrigin::Signed(caller.clone()).into(), 10, terr_name.clone(), 30)?;
+    }: _(RawOrigin::Signed(caller.clone()), terr_name.clone(), member.clone(), G_BYTE)
+    verify {
+        assert!(<MemberTerritory<T>>::contains_key(&member, &terr_name));
+    }
+
+    revoke_territory_member {
+        let caller: AccountOf<T> = account("user1", 100, SEED);
+        let member: AccountOf<T> = account("member", 0, SEED);
+        let terr_name: TerrName = "t1".as_bytes().to_vec().try_into().map_err(|_| "boundedvec error")?;
+        let free: BalanceOf<T> = 365_000_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
+        T::Currency::make_free_balance_be(&caller, free);
+        increase_idle_space::<T>(100 * G_BYTE);
+        StorageHandler::<T>::mint_territory(RawOrigin::Signed(caller.clone()).into(), 10, terr_name.clone(), 30)?;
+        StorageHandler::<T>::add_territory_member(RawOrigin::Signed(caller.clone()).into(), terr_name.clone(), member.clone(), G_BYTE)?;
+    }: _(RawOrigin::Signed(caller.clone()), terr_name.clone(), member.clone())
+    verify {
+        assert!(!<MemberTerritory<T>>::contains_key(&member, &terr_name));
+    }
 }
 
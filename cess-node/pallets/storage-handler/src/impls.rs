@@ -20,14 +20,17 @@ pub trait StorageHandle<AccountId> {
 }
 
 impl<T: Config> StorageHandle<T::AccountId> for Pallet<T> {
+    // A territory shared with `acc` counts as its own.
     fn check_territry_owner(acc: &T::AccountId, name: &TerrName) -> DispatchResult {
-        ensure!(<Territory<T>>::contains_key(acc, name), Error::<T>::NotHaveTerritory);
+        Pallet::<T>::territory_of(acc, name)?;
 
         Ok(())
     }
 
     fn check_expired(acc: &T::AccountId, name: &TerrName) -> bool {
-        let territory = <Territory<T>>::try_get(acc, name).map_err(|_| Error::<T>::NotHaveTerritory);
+        let territory = Pallet::<T>::territory_of(acc, name).and_then(|(owner, name, _)| {
+            <Territory<T>>::try_get(&owner, &name).map_err(|_| Error::<T>::NotHaveTerritory.into())
+        });
 
         if let Ok(territory) = territory {
             if territory.state == TerritoryState::Expired {
@@ -42,8 +45,10 @@ impl<T: Config> StorageHandle<T::AccountId> for Pallet<T> {
     // fn update_user_space(acc: &T::AccountId, opeartion: u8, size: u128) -> DispatchResult {
     //     Pallet::<T>::update_user_space(acc, opeartion, size)
     // }
+    // A member of a shared territory is charged against its quota as well, once the territory has the space.
     fn add_territory_used_space(acc: &T::AccountId, name: &TerrName, size: u128) -> DispatchResult {
-        <Territory<T>>::try_mutate(acc, name, |t_opt| -> DispatchResult {
+        let (owner, name, shared) = Pallet::<T>::territory_of(acc, name)?;
+        <Territory<T>>::try_mutate(&owner, &name, |t_opt| -> DispatchResult {
             let t = t_opt.as_mut().ok_or(Error::<T>::NotHaveTerritory)?;
             ensure!(t.state == TerritoryState::Active, Error::<T>::NotActive);
             ensure!(size <= t.remaining_space, Error::<T>::InsufficientStorage);
@@ -51,16 +56,26 @@ impl<T: Config> StorageHandle<T::AccountId> for Pallet<T> {
                 t.used_space.checked_add(size).ok_or(Error::<T>::Overflow)?;
             t.remaining_space =
                 t.remaining_space.checked_sub(size).ok_or(Error::<T>::Overflow)?;
+            if let Some(token) = shared {
+                Pallet::<T>::charge_member(&token, acc, size, false)?;
+            }
             Ok(())
         })
     }
 
     fn sub_territory_used_space(acc: &T::AccountId, name: &TerrName, size: u128) -> DispatchResult {
-        <Territory<T>>::try_mutate(acc, name, |t_opt| -> DispatchResult {
+        let (owner, name, shared) = Pallet::<T>::territory_of(acc, name)?;
+        <Territory<T>>::try_mutate(&owner, &name, |t_opt| -> DispatchResult {
             let t = t_opt.as_mut().ok_or(Error::<T>::NotHaveTerritory)?;
             t.used_space = t.used_space.checked_sub(size).ok_or(Error::<T>::Overflow)?;
             t.remaining_space =
                 t.remaining_space.checked_add(size).ok_or(Error::<T>::Overflow)?;
+            if let Some(token) = shared {
+                Pallet::<T>::mutate_member(&token, acc, |m| -> DispatchResult {
+                    m.used = m.used.checked_sub(size).ok_or(Error::<T>::Overflow)?;
+                    Ok(())
+                })?;
+            }
             Ok(())
         })
     }
@@ -112,36 +127,59 @@ impl<T: Config> StorageHandle<T::AccountId> for Pallet<T> {
 	// }
 
     fn lock_user_space(acc: &T::AccountId, name: &TerrName, needed_space: u128) -> DispatchResult {
-        <Territory<T>>::try_mutate(acc, name, |storage_space_opt| -> DispatchResult {
+        let (owner, name, shared) = Pallet::<T>::territory_of(acc, name)?;
+        <Territory<T>>::try_mutate(&owner, &name, |storage_space_opt| -> DispatchResult {
             let storage_space = storage_space_opt.as_mut().ok_or(Error::<T>::NotHaveTerritory)?;
             ensure!(storage_space.state == TerritoryState::Active, Error::<T>::NotActive);
             ensure!(storage_space.remaining_space >= needed_space, Error::<T>::InsufficientStorage);
             storage_space.locked_space = storage_space.locked_space.checked_add(needed_space).ok_or(Error::<T>::Overflow)?;
             storage_space.remaining_space = storage_space.remaining_space.checked_sub(needed_space).ok_or(Error::<T>::Overflow)?;
+            if let Some(token) = shared {
+                Pallet::<T>::charge_member(&token, acc, needed_space, true)?;
+            }
             Ok(())
         })
     }
 
     fn unlock_user_space(acc: &T::AccountId, name: &TerrName, needed_space: u128) -> DispatchResult {
-        <Territory<T>>::try_mutate(acc, name, |storage_space_opt| -> DispatchResult {
+        let (owner, name, shared) = Pallet::<T>::territory_of(acc, name)?;
+        <Territory<T>>::try_mutate(&owner, &name, |storage_space_opt| -> DispatchResult {
             let storage_space = storage_space_opt.as_mut().ok_or(Error::<T>::NotHaveTerritory)?;
             storage_space.locked_space = storage_space.locked_space.checked_sub(needed_space).ok_or(Error::<T>::Overflow)?;
             storage_space.remaining_space = storage_space.remaining_space.checked_add(needed_space).ok_or(Error::<T>::Overflow)?;
+            if let Some(token) = shared {
+                Pallet::<T>::mutate_member(&token, acc, |m| -> DispatchResult {
+                    m.locked = m.locked.checked_sub(needed_space).ok_or(Error::<T>::Overflow)?;
+                    Ok(())
+                })?;
+            }
             Ok(())
         })
     }
 
     fn unlock_and_used_user_space(acc: &T::AccountId, name: &TerrName, needed_space: u128) -> DispatchResult {
-        <Territory<T>>::try_mutate(acc, name, |storage_space_opt| -> DispatchResult {
+        let (owner, name, shared) = Pallet::<T>::territory_of(acc, name)?;
+        <Territory<T>>::try_mutate(&owner, &name, |storage_space_opt| -> DispatchResult {
             let storage_space = storage_space_opt.as_mut().ok_or(Error::<T>::NotHaveTerritory)?;
             storage_space.locked_space = storage_space.locked_space.checked_sub(needed_space).ok_or(Error::<T>::Overflow)?;
             storage_space.used_space = storage_space.used_space.checked_add(needed_space).ok_or(Error::<T>::Overflow)?;
+            if let Some(token) = shared {
+                Pallet::<T>::mutate_member(&token, acc, |m| -> DispatchResult {
+                    m.locked = m.locked.checked_sub(needed_space).ok_or(Error::<T>::Overflow)?;
+                    m.used = m.used.checked_add(needed_space).ok_or(Error::<T>::Overflow)?;
+                    Ok(())
+                })?;
+            }
             Ok(())
         })
     }
 
     fn get_user_avail_space(acc: &T::AccountId, name: &TerrName) -> Result<u128, DispatchError> {
-        let info = <Territory<T>>::try_get(acc, name).map_err(|_e| Error::<T>::NotHaveTerritory)?;
+        let (owner, name, shared) = Pallet::<T>::territory_of(acc, name)?;
+        let info = <Territory<T>>::try_get(&owner, &name).map_err(|_e| Error::<T>::NotHaveTerritory)?;
+        if let Some(token) = shared {
+            return Ok(info.remaining_space.min(Pallet::<T>::member_avail_space(&token, acc)?));
+        }
         Ok(info.remaining_space)
     }
 
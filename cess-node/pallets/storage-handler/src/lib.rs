@@ -7,9 +7,10 @@ use frame_system::{
 use frame_support::{
     dispatch::Parameter,
     Blake2_128Concat, PalletId, weights::Weight, ensure, transactional,
-    storage::bounded_vec::BoundedVec,
+    storage::{bounded_vec::BoundedVec, with_transaction},
     traits::{
         StorageVersion, Currency, ReservableCurrency, Randomness, ExistenceRequirement::KeepAlive,
+        BalanceStatus,
         schedule::v3::Named as ScheduleNamed,
         schedule, schedule::DispatchTime, QueryPreimage, StorePreimage,
     },
@@ -18,9 +19,9 @@ use frame_support::{
 use sp_runtime::{
 	traits::{
         CheckedAdd, CheckedMul, CheckedDiv, CheckedSub,
-		SaturatedConversion, Dispatchable,
+		SaturatedConversion, Dispatchable, Zero,
 	},
-	RuntimeDebug,
+	RuntimeDebug, TransactionOutcome,
 };
 use sp_std::{convert::TryInto, prelude::*, str};
 /// for types 
@@ -36,12 +37,26 @@ use weights::WeightInfo;
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
 
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
 mod types;
 use types::*;
 
 pub mod impls;
 pub use impls::*;
 
+pub mod pricing;
+
+pub mod renewal;
+
+pub mod members;
+
+pub mod migrations;
+
 pub use pallet::*;
 
 pub const SPACE_NORMAL: &str = "normal";
@@ -53,7 +68,7 @@ type BalanceOf<T> =
 	<<T as pallet::Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 type TokenId = H256;
 
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
 
 #[frame_support::pallet]
@@ -104,6 +119,22 @@ pub mod pallet {
 
         #[pallet::constant]
         type LockingBlock: Get<BlockNumberFor<Self>>;
+
+        /// The number of blocks between two adjustments of the unit price.
+        #[pallet::constant]
+        type PricingEra: Get<BlockNumberFor<Self>>;
+
+        /// The maximum number of bids on a sealed-bid consignment.
+        #[pallet::constant]
+        type MaxSealedBids: Get<u32>;
+
+        /// How many blocks ahead of its deadline a subscribed territory is renewed.
+        #[pallet::constant]
+        type AutoRenewAdvance: Get<BlockNumberFor<Self>>;
+
+        /// The maximum number of members of a shared territory.
+        #[pallet::constant]
+        type MaxTerritoryMembers: Get<u32>;
         
 		#[pallet::constant]
 		type FrozenDays: Get<BlockNumberFor<Self>> + Clone + Eq + PartialEq;
@@ -184,6 +215,82 @@ pub mod pallet {
             seller: AccountOf<T>,
             token: TokenId,
         },
+
+        SplitTerritory {
+            name: TerrName,
+            new_name: TerrName,
+            token: TokenId,
+            space: u128,
+        },
+
+        SealedBidPlaced {
+            token: TokenId,
+            bidder: AccountOf<T>,
+        },
+
+        SealedBidRevealed {
+            token: TokenId,
+            bidder: AccountOf<T>,
+            bid: BalanceOf<T>,
+        },
+
+        SealedConsignmentUnsold {
+            token: TokenId,
+        },
+
+        /// The bid of `bidder` could not be settled, so the next highest one was tried.
+        SealedBidPassedOver {
+            token: TokenId,
+            bidder: AccountOf<T>,
+        },
+
+        PriceBoundsSet {
+            floor: BalanceOf<T>,
+            ceiling: BalanceOf<T>,
+        },
+
+        UnitPriceUpdated {
+            price: BalanceOf<T>,
+            purchased: u128,
+            total: u128,
+        },
+
+        AutoRenewalSet {
+            token: TokenId,
+            funder: AccountOf<T>,
+            max_price: BalanceOf<T>,
+            days: u32,
+        },
+
+        AutoRenewalCancelled {
+            token: TokenId,
+        },
+
+        AutoRenewed {
+            token: TokenId,
+            funder: AccountOf<T>,
+            days: u32,
+            spend: BalanceOf<T>,
+        },
+
+        TerritoryMemberSet {
+            token: TokenId,
+            member: AccountOf<T>,
+            quota: u128,
+        },
+
+        TerritoryMemberRevoked {
+            token: TokenId,
+            member: AccountOf<T>,
+        },
+
+        /// The renewal is retried a day later, for as long as the territory has not expired.
+        AutoRenewalFailed {
+            token: TokenId,
+            funder: AccountOf<T>,
+            price: BalanceOf<T>,
+            reason: RenewalFailure,
+        },
     }
 
     #[pallet::error]
@@ -254,6 +361,42 @@ pub mod pallet {
         OwnConsignment,
         /// When casting a territory, it must be at least 30 days
         BoundariesNotMet,
+        /// The price floor cannot be above the ceiling
+        InvalidPriceBounds,
+        /// The auction parameters are invalid: a Dutch floor above the starting price, or an empty period
+        InvalidAuction,
+        /// Sealed-bid consignments are sold to the highest bid, not bought directly
+        SealedBidConsignment,
+        /// The consignment does not take sealed bids
+        NotSealedBid,
+        /// The consignment does not accept bids at this point of its auction
+        NotBiddingPeriod,
+        /// The consignment does not accept reveals at this point of its auction
+        NotRevealPeriod,
+        /// A bid has already been placed by this account
+        DuplicateBid,
+        /// The consignment has reached the maximum number of bids
+        TooManyBids,
+        /// No bid has been placed by this account
+        NoBid,
+        /// The bid has already been revealed
+        AlreadyRevealed,
+        /// The revealed bid does not match its commitment, is below the price or above the deposit
+        InvalidBid,
+        /// The territory is already subscribed to renewal by another funding account
+        AutoRenewalExists,
+        /// The territory is not subscribed to renewal
+        NoAutoRenewal,
+        /// Only the owner of the territory or the funding account can cancel its renewal
+        NotRenewalParty,
+        /// The account already has a territory of its own or shared with it by this name
+        TerritoryNameTaken,
+        /// The account is not a member of the territory
+        NotTerritoryMember,
+        /// The territory has reached the maximum number of members
+        TooManyMembers,
+        /// The member's quota of the territory is used up
+        QuotaExceeded,
     }
 
     #[pallet::storage]
@@ -278,6 +421,32 @@ pub mod pallet {
     pub(super) type Consignment<T: Config> =
         StorageMap<_, Blake2_128Concat, TokenId, ConsignmentInfo<T>>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn sealed_bids)]
+    pub(super) type SealedBids<T: Config> =
+        StorageMap<_, Blake2_128Concat, TokenId, BoundedVec<SealedBid<T>, T::MaxSealedBids>, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn territory_members)]
+    pub(super) type TerritoryMembers<T: Config> =
+        StorageMap<_, Blake2_128Concat, TokenId, BoundedVec<TerritoryMember<T>, T::MaxTerritoryMembers>, ValueQuery>;
+
+    /// The territories shared with an account, by the name the account refers to them with.
+    #[pallet::storage]
+    #[pallet::getter(fn member_territory)]
+    pub(super) type MemberTerritory<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, AccountOf<T>, Blake2_128Concat, TerrName, TokenId>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn auto_renewals)]
+    pub(super) type AutoRenewals<T: Config> = StorageMap<_, Blake2_128Concat, TokenId, AutoRenewal<T>>;
+
+    /// The subscribed territories to renew at a block.
+    #[pallet::storage]
+    #[pallet::getter(fn auto_renewal_queue)]
+    pub(super) type AutoRenewalQueue<T: Config> =
+        StorageMap<_, Blake2_128Concat, BlockNumberFor<T>, BoundedVec<TokenId, T::FrozenLimit>, ValueQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn territory_frozen)]
     pub(super) type TerritoryFrozen<T: Config> =
@@ -311,6 +480,11 @@ pub mod pallet {
 	#[pallet::getter(fn unit_price)]
     pub(super) type UnitPrice<T: Config> = StorageValue<_, BalanceOf<T>>;
 
+    /// The bounds `UnitPrice` moves within. The price stays fixed until they are set.
+    #[pallet::storage]
+    #[pallet::getter(fn unit_price_bounds)]
+    pub(super) type UnitPriceBounds<T: Config> = StorageValue<_, PriceBounds<BalanceOf<T>>>;
+
     /// The total power of all storage miners.
 	#[pallet::storage]
 	#[pallet::getter(fn total_power)]
@@ -333,6 +507,24 @@ pub mod pallet {
 	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+            let mut weight = Self::auto_renew(now);
+
+            let pricing_era = T::PricingEra::get();
+            if !pricing_era.is_zero() && (now % pricing_era).is_zero() {
+                weight = weight.saturating_add(Self::adjust_unit_price());
+            }
+
+            weight
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate::<T>()
+        }
+    }
+
     #[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		// price / gib / 30days
@@ -367,6 +559,7 @@ pub mod pallet {
         ) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			ensure!(!<Territory<T>>::contains_key(&sender, &territory_name), Error::<T>::PurchasedSpace);
+			ensure!(!<MemberTerritory<T>>::contains_key(&sender, &territory_name), Error::<T>::TerritoryNameTaken);
             ensure!(days >= 30, Error::<T>::BoundariesNotMet);
 
             let now = <frame_system::Pallet<T>>::block_number();
@@ -488,18 +681,7 @@ pub mod pallet {
                 Error::<T>::StateError,
             );
 
-			let days_unit_price = <UnitPrice<T>>::try_get()
-				.map_err(|_e| Error::<T>::BugInvalid)?
-				.checked_div(&30u32.saturated_into())
-				.ok_or(Error::<T>::Overflow)?;
-			let gib_count = cur_owned_space.total_space.checked_div(G_BYTE).ok_or(Error::<T>::Overflow)?;
-			let price: BalanceOf<T> = days_unit_price
-				.checked_mul(&gib_count.saturated_into())
-				.ok_or(Error::<T>::Overflow)?
-				.checked_mul(&days.saturated_into())
-				.ok_or(Error::<T>::Overflow)?
-				.try_into()
-				.map_err(|_e| Error::<T>::Overflow)?;
+			let price = Self::renewal_price(cur_owned_space.total_space, days)?;
 
 			ensure!(
 				<T as pallet::Config>::Currency::can_slash(&sender, price.clone()),
@@ -532,18 +714,7 @@ pub mod pallet {
             
             ensure!(territory.state == TerritoryState::Expired, Error::<T>::NotExpire);
 
-            let days_unit_price = <UnitPrice<T>>::try_get()
-                .map_err(|_e| Error::<T>::BugInvalid)?
-                .checked_div(&30u32.saturated_into())
-                .ok_or(Error::<T>::Overflow)?;
-
-            let gib_count = territory.total_space.checked_div(G_BYTE).ok_or(Error::<T>::Overflow)?;
-
-            let price = days_unit_price
-                .checked_mul(&days.saturated_into())
-                .ok_or(Error::<T>::Overflow)?
-                .checked_mul(&gib_count.saturated_into())
-                .ok_or(Error::<T>::Overflow)?;
+            let price = Self::renewal_price(territory.total_space, days)?;
             ensure!(
                 <T as pallet::Config>::Currency::can_slash(&sender, price.clone()),
                 Error::<T>::InsufficientBalance
@@ -573,37 +744,7 @@ pub mod pallet {
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
 
-            let token = <Territory<T>>::try_mutate(&sender, &territory_name, |t_opt| -> Result<TokenId, DispatchError> {
-                let t = t_opt.as_mut().ok_or(Error::<T>::NotHaveTerritory)?;
-
-                ensure!(t.state == TerritoryState::Active, Error::<T>::NotActive);
-                ensure!(t.total_space == t.remaining_space, Error::<T>::ObjectNotZero);
-
-                let now = <frame_system::Pallet<T>>::block_number();
-                let remain_block = t.deadline.checked_sub(&now).ok_or(Error::<T>::Overflow)?;
-                let limit_block = T::ConsignmentRemainingBlock::get();
-                ensure!(remain_block > limit_block, Error::<T>::InsufficientLease);
-
-                t.state = TerritoryState::OnConsignment;
-
-                Ok(t.token)
-            })?;
-
-            ensure!(!<Consignment<T>>::contains_key(&token), Error::<T>::ConsignmentExisted);
-            let consignment_info = ConsignmentInfo::<T>{
-                user: sender,
-                price: price,
-                buyers: None,
-                exec: None,
-                locked: false,
-            };
-            <Consignment<T>>::insert(&token, consignment_info);
-
-            Self::deposit_event(Event::<T>::Consignment {
-                name: territory_name,
-                token: token,
-                price: price,
-            });
+            Self::consign(&sender, &territory_name, price, ConsignmentMode::Fixed)?;
 
             Ok(())
         }
@@ -617,6 +758,8 @@ pub mod pallet {
             rename: TerrName,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
+            ensure!(!<Territory<T>>::contains_key(&sender, &rename), Error::<T>::PurchasedSpace);
+            ensure!(!<MemberTerritory<T>>::contains_key(&sender, &rename), Error::<T>::TerritoryNameTaken);
 
             let consignment = <Consignment<T>>::try_get(&token).map_err(|_| Error::<T>::NonExistentConsignment)?;
             ensure!(!consignment.locked, Error::<T>::ConsignmentLocked);
@@ -629,11 +772,19 @@ pub mod pallet {
                 let lock_block = T::LockingBlock::get();
                 let exec_block = now.checked_add(&lock_block).ok_or(Error::<T>::Overflow)?;
 
+                c.deal_price = match c.mode {
+                    ConsignmentMode::Fixed => c.price,
+                    ConsignmentMode::Dutch { floor, duration } => {
+                        let elapsed: u128 = now.checked_sub(&c.start).ok_or(Error::<T>::Overflow)?.saturated_into();
+                        pricing::dutch_price(c.price, floor, elapsed, duration.saturated_into())
+                    },
+                    ConsignmentMode::SealedBid { .. } => Err(Error::<T>::SealedBidConsignment)?,
+                };
                 c.buyers = Some(sender.clone());
                 c.exec = Some(exec_block);
                 c.locked = true;
 
-                <T as pallet::Config>::Currency::reserve(&sender, c.price)?;
+                <T as pallet::Config>::Currency::reserve(&sender, c.deal_price)?;
 
                 let call: <T as Config>::SProposal = Call::exec_consignment{token: token.clone(), territory_name: rename.clone()}.into();
                 T::FScheduler::schedule_named(
@@ -650,7 +801,7 @@ pub mod pallet {
                 Self::deposit_event(Event::<T>::BuyConsignment {
                     name: rename.clone(),
                     token: token,
-                    price: c.price,
+                    price: c.deal_price,
                 });
 
                 Ok(())
@@ -666,8 +817,11 @@ pub mod pallet {
             let consignment = <Consignment<T>>::try_get(&token).map_err(|_| Error::<T>::NonExistentConsignment)?;
             let buyer = consignment.buyers.ok_or(Error::<T>::Unexpected)?;
             ensure!(consignment.locked, Error::<T>::ConsignmentUnLocked);
+            // The buyer may have taken the name since buying, the purchase is left for them to cancel.
+            ensure!(!<Territory<T>>::contains_key(&buyer, &territory_name), Error::<T>::PurchasedSpace);
+            ensure!(!<MemberTerritory<T>>::contains_key(&buyer, &territory_name), Error::<T>::TerritoryNameTaken);
             ensure!(
-                <T as pallet::Config>::Currency::can_slash(&buyer, consignment.price),
+                <T as pallet::Config>::Currency::can_slash(&buyer, consignment.deal_price),
                 Error::<T>::InsufficientBalance
             );
 
@@ -681,8 +835,11 @@ pub mod pallet {
 
             <TerritoryKey<T>>::insert(&token, (buyer.clone(), territory_name));
             <Consignment<T>>::remove(&token);
-            <T as pallet::Config>::Currency::unreserve(&buyer, consignment.price);
-            <T as pallet::Config>::Currency::transfer(&buyer, &holder, consignment.price, KeepAlive)?;
+            // The buyer has to set up a renewal and members of their own.
+            Self::remove_auto_renewal(&token);
+            Self::clear_territory_members(&token);
+            <T as pallet::Config>::Currency::unreserve(&buyer, consignment.deal_price);
+            <T as pallet::Config>::Currency::transfer(&buyer, &holder, consignment.deal_price, KeepAlive)?;
 
             Self::deposit_event(Event::<T>::ExecConsignment {
                 buyer: buyer,
@@ -703,6 +860,11 @@ pub mod pallet {
             ensure!(territory.state == TerritoryState::OnConsignment, Error::<T>::NotOnConsignment);
             let consignment = <Consignment<T>>::try_get(&territory.token).map_err(|_| Error::<T>::NonExistentConsignment)?;
             ensure!(!consignment.locked, Error::<T>::ConsignmentLocked);
+            if let ConsignmentMode::SealedBid { .. } = consignment.mode {
+                // Once bidders have committed funds, the auction has to run its course.
+                ensure!(<SealedBids<T>>::get(&territory.token).is_empty(), Error::<T>::ConsignmentLocked);
+                T::FScheduler::cancel_named(*(territory.token.as_fixed_bytes()))?;
+            }
 
             <Consignment<T>>::remove(&territory.token);
             territory.state = TerritoryState::Active;
@@ -724,7 +886,7 @@ pub mod pallet {
 
                 let buyer = c.buyers.as_ref().ok_or(Error::<T>::NotBuyer)?;
                 ensure!(&sender == buyer, Error::<T>::NotBuyer);
-                <T as pallet::Config>::Currency::unreserve(&buyer, c.price);
+                <T as pallet::Config>::Currency::unreserve(&buyer, c.deal_price);
                 c.buyers = None;
                 c.exec = None;
                 c.locked = false;
@@ -765,6 +927,8 @@ pub mod pallet {
 
                 Ok(())
             })?;
+            Self::remove_auto_renewal(&territory.token);
+            Self::clear_territory_members(&territory.token);
 
             Ok(())
         }
@@ -781,6 +945,7 @@ pub mod pallet {
             let territory = <Territory<T>>::try_get(&sender, &old_name).map_err(|_| Error::<T>::NotHaveTerritory)?;
             ensure!(territory.state == TerritoryState::Active, Error::<T>::NotActive);
             ensure!(territory.total_space == territory.remaining_space, Error::<T>::ObjectNotZero);
+            ensure!(!<MemberTerritory<T>>::contains_key(&sender, &new_name), Error::<T>::TerritoryNameTaken);
             <Territory<T>>::remove(&sender, &old_name);
             <Territory<T>>::insert(
                 &sender,
@@ -796,6 +961,369 @@ pub mod pallet {
             Ok(())
         }
 
+        #[pallet::call_index(109)]
+        #[transactional]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::territory_auction())]
+        pub fn territory_auction(
+            origin: OriginFor<T>,
+            territory_name: TerrName,
+            price: BalanceOf<T>,
+            mode: ConsignmentMode<BalanceOf<T>, BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            Self::consign(&sender, &territory_name, price, mode)?;
+
+            Ok(())
+        }
+
+        /// Splits `gib_count` GiB of unused space off the territory into a new territory named `new_name`,
+        /// and consigns it. The data already stored stays in the original territory.
+        #[pallet::call_index(110)]
+        #[transactional]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::split_territory_consignment())]
+        pub fn split_territory_consignment(
+            origin: OriginFor<T>,
+            territory_name: TerrName,
+            gib_count: u32,
+            new_name: TerrName,
+            price: BalanceOf<T>,
+            mode: ConsignmentMode<BalanceOf<T>, BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(gib_count > 0, Error::<T>::ParamError);
+            ensure!(!<Territory<T>>::contains_key(&sender, &new_name), Error::<T>::PurchasedSpace);
+            ensure!(!<MemberTerritory<T>>::contains_key(&sender, &new_name), Error::<T>::TerritoryNameTaken);
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let seed = (sender.clone(), now, new_name.clone());
+            let (random_seed, _) =
+                T::MyRandomness::random(&(T::RewardPalletId::get(), seed).encode());
+            let token = match random_seed {
+                Some(random_seed) => <H256>::decode(&mut random_seed.as_ref()).map_err(|_| Error::<T>::RandomErr)?,
+                None => Default::default(),
+            };
+            ensure!(!<TerritoryKey<T>>::contains_key(&token), Error::<T>::DuplicateTokens);
+
+            let space = G_BYTE.checked_mul(gib_count as u128).ok_or(Error::<T>::Overflow)?;
+            let deadline = <Territory<T>>::try_mutate(&sender, &territory_name, |t_opt| -> Result<BlockNumberFor<T>, DispatchError> {
+                let t = t_opt.as_mut().ok_or(Error::<T>::NotHaveTerritory)?;
+                ensure!(t.state == TerritoryState::Active, Error::<T>::NotActive);
+                ensure!(space <= t.remaining_space, Error::<T>::InsufficientStorage);
+                // The whole territory is consigned as it is.
+                ensure!(space < t.total_space, Error::<T>::ParamError);
+
+                t.total_space = t.total_space.checked_sub(space).ok_or(Error::<T>::Overflow)?;
+                t.remaining_space = t.remaining_space.checked_sub(space).ok_or(Error::<T>::Overflow)?;
+
+                Ok(t.deadline)
+            })?;
+            Self::insert_territory(token, sender.clone(), space, deadline, new_name.clone())?;
+
+            Self::deposit_event(Event::<T>::SplitTerritory {
+                name: territory_name,
+                new_name: new_name.clone(),
+                token,
+                space,
+            });
+
+            Self::consign(&sender, &new_name, price, mode)?;
+
+            Ok(())
+        }
+
+        /// Bids on a sealed-bid consignment with the hash of the bid, reserving a deposit which covers it.
+        #[pallet::call_index(111)]
+        #[transactional]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::place_sealed_bid())]
+        pub fn place_sealed_bid(
+            origin: OriginFor<T>,
+            token: TokenId,
+            commitment: H256,
+            deposit: BalanceOf<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let consignment = <Consignment<T>>::try_get(&token).map_err(|_| Error::<T>::NonExistentConsignment)?;
+            ensure!(consignment.user != sender, Error::<T>::OwnConsignment);
+            let ConsignmentMode::SealedBid { commit_period, .. } = consignment.mode else {
+                return Err(Error::<T>::NotSealedBid.into());
+            };
+            let now = <frame_system::Pallet<T>>::block_number();
+            let commit_end = consignment.start.checked_add(&commit_period).ok_or(Error::<T>::Overflow)?;
+            ensure!(now < commit_end, Error::<T>::NotBiddingPeriod);
+            ensure!(deposit >= consignment.price, Error::<T>::InvalidBid);
+
+            <SealedBids<T>>::try_mutate(&token, |bids| -> DispatchResult {
+                ensure!(!bids.iter().any(|bid| bid.bidder == sender), Error::<T>::DuplicateBid);
+                <T as pallet::Config>::Currency::reserve(&sender, deposit)?;
+                bids.try_push(SealedBid::<T> {
+                    bidder: sender.clone(),
+                    commitment,
+                    deposit,
+                    revealed: None,
+                    name: None,
+                }).map_err(|_| Error::<T>::TooManyBids)?;
+
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::<T>::SealedBidPlaced { token, bidder: sender });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(112)]
+        #[transactional]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::reveal_sealed_bid())]
+        pub fn reveal_sealed_bid(
+            origin: OriginFor<T>,
+            token: TokenId,
+            bid: BalanceOf<T>,
+            salt: H256,
+            rename: TerrName,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(!<Territory<T>>::contains_key(&sender, &rename), Error::<T>::PurchasedSpace);
+            ensure!(!<MemberTerritory<T>>::contains_key(&sender, &rename), Error::<T>::TerritoryNameTaken);
+
+            let consignment = <Consignment<T>>::try_get(&token).map_err(|_| Error::<T>::NonExistentConsignment)?;
+            let ConsignmentMode::SealedBid { commit_period, reveal_period } = consignment.mode else {
+                return Err(Error::<T>::NotSealedBid.into());
+            };
+            let now = <frame_system::Pallet<T>>::block_number();
+            let commit_end = consignment.start.checked_add(&commit_period).ok_or(Error::<T>::Overflow)?;
+            let reveal_end = commit_end.checked_add(&reveal_period).ok_or(Error::<T>::Overflow)?;
+            ensure!(commit_end <= now && now < reveal_end, Error::<T>::NotRevealPeriod);
+
+            <SealedBids<T>>::try_mutate(&token, |bids| -> DispatchResult {
+                let sealed = bids.iter_mut().find(|sealed| sealed.bidder == sender).ok_or(Error::<T>::NoBid)?;
+                ensure!(sealed.revealed.is_none(), Error::<T>::AlreadyRevealed);
+                ensure!(
+                    sp_core::hashing::blake2_256(&(bid, salt).encode()) == sealed.commitment.0,
+                    Error::<T>::InvalidBid
+                );
+                ensure!(consignment.price <= bid && bid <= sealed.deposit, Error::<T>::InvalidBid);
+                sealed.revealed = Some(bid);
+                sealed.name = Some(rename);
+
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::<T>::SealedBidRevealed { token, bidder: sender, bid });
+
+            Ok(())
+        }
+
+        /// Settles a sealed-bid consignment at the end of its reveal period, scheduled when it is created.
+        /// The highest revealed bid which can be paid wins, the earliest among equal ones, and every deposit is
+        /// released.
+        #[pallet::call_index(113)]
+        #[transactional]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::exec_sealed_consignment(T::MaxSealedBids::get()))]
+        pub fn exec_sealed_consignment(origin: OriginFor<T>, token: TokenId) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let consignment = <Consignment<T>>::try_get(&token).map_err(|_| Error::<T>::NonExistentConsignment)?;
+            ensure!(matches!(consignment.mode, ConsignmentMode::SealedBid { .. }), Error::<T>::NotSealedBid);
+            let (holder, name) = <TerritoryKey<T>>::try_get(&token).map_err(|_| Error::<T>::Unexpected)?;
+            let mut territory = <Territory<T>>::try_get(&holder, &name).map_err(|_| Error::<T>::Unexpected)?;
+            ensure!(territory.state == TerritoryState::OnConsignment, Error::<T>::Unexpected);
+
+            let bids = <SealedBids<T>>::take(&token);
+            // Revealed bids from the highest, the earliest first among equal ones.
+            let mut candidates: Vec<&SealedBid<T>> = bids.iter().filter(|sealed| sealed.revealed.is_some()).collect();
+            candidates.sort_by(|a, b| b.revealed.cmp(&a.revealed));
+
+            // A bidder who can no longer pay, or has taken the name for another territory since, is passed over
+            // for the next bid rather than leaving every deposit reserved.
+            let mut winner: Option<(AccountOf<T>, TerrName, BalanceOf<T>)> = None;
+            for sealed in candidates {
+                let (Some(bid), Some(new_name)) = (sealed.revealed, sealed.name.clone()) else {
+                    continue;
+                };
+                if <Territory<T>>::contains_key(&sealed.bidder, &new_name)
+                    || <MemberTerritory<T>>::contains_key(&sealed.bidder, &new_name)
+                {
+                    Self::deposit_event(Event::<T>::SealedBidPassedOver { token, bidder: sealed.bidder.clone() });
+                    continue;
+                }
+                let paid = with_transaction(|| {
+                    match <T as pallet::Config>::Currency::repatriate_reserved(
+                        &sealed.bidder,
+                        &holder,
+                        bid,
+                        BalanceStatus::Free,
+                    ) {
+                        Ok(unpaid) if unpaid.is_zero() => TransactionOutcome::Commit(Ok(true)),
+                        _ => TransactionOutcome::Rollback(Ok::<_, DispatchError>(false)),
+                    }
+                })?;
+                if paid {
+                    winner = Some((sealed.bidder.clone(), new_name, bid));
+                    break;
+                }
+                Self::deposit_event(Event::<T>::SealedBidPassedOver { token, bidder: sealed.bidder.clone() });
+            }
+            for sealed in bids.iter() {
+                let released = match &winner {
+                    Some((buyer, _, bid)) if *buyer == sealed.bidder => sealed.deposit.saturating_sub(*bid),
+                    _ => sealed.deposit,
+                };
+                <T as pallet::Config>::Currency::unreserve(&sealed.bidder, released);
+            }
+            <Consignment<T>>::remove(&token);
+            territory.state = TerritoryState::Active;
+
+            match winner {
+                Some((buyer, new_name, _)) => {
+                    <Territory<T>>::remove(&holder, &name);
+                    <Territory<T>>::insert(&buyer, &new_name, territory);
+                    <TerritoryKey<T>>::insert(&token, (buyer.clone(), new_name));
+                    Self::remove_auto_renewal(&token);
+                    Self::clear_territory_members(&token);
+
+                    Self::deposit_event(Event::<T>::ExecConsignment {
+                        buyer,
+                        seller: holder,
+                        token,
+                    });
+                },
+                None => {
+                    <Territory<T>>::insert(&holder, &name, territory);
+                    Self::deposit_event(Event::<T>::SealedConsignmentUnsold { token });
+                },
+            }
+
+            Ok(())
+        }
+
+        /// Subscribes a territory of `owner` to automatic renewal for `days` days at a time, paid for by the
+        /// caller up to `max_price` a renewal. The owner may take over the subscription of another funder.
+        #[pallet::call_index(114)]
+        #[transactional]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_auto_renewal())]
+        pub fn set_auto_renewal(
+            origin: OriginFor<T>,
+            owner: AccountOf<T>,
+            territory_name: TerrName,
+            max_price: BalanceOf<T>,
+            days: u32,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(days > 0, Error::<T>::ParamError);
+
+            let territory = <Territory<T>>::try_get(&owner, &territory_name).map_err(|_| Error::<T>::NotHaveTerritory)?;
+            ensure!(territory.state != TerritoryState::Expired, Error::<T>::StateError);
+            let token = territory.token;
+
+            let next = match <AutoRenewals<T>>::get(&token) {
+                Some(sub) => {
+                    ensure!(sender == owner || sender == sub.funder, Error::<T>::AutoRenewalExists);
+                    sub.next
+                },
+                None => Self::enqueue_auto_renewal(&token, Self::renewal_block(territory.deadline))?,
+            };
+            <AutoRenewals<T>>::insert(&token, AutoRenewal::<T> {
+                funder: sender.clone(),
+                max_price,
+                days,
+                next,
+            });
+
+            Self::deposit_event(Event::<T>::AutoRenewalSet {
+                token,
+                funder: sender,
+                max_price,
+                days,
+            });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(115)]
+        #[transactional]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::cancel_auto_renewal())]
+        pub fn cancel_auto_renewal(origin: OriginFor<T>, token: TokenId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let sub = <AutoRenewals<T>>::try_get(&token).map_err(|_| Error::<T>::NoAutoRenewal)?;
+            let (owner, _) = <TerritoryKey<T>>::try_get(&token).map_err(|_| Error::<T>::Unexpected)?;
+            ensure!(sender == owner || sender == sub.funder, Error::<T>::NotRenewalParty);
+
+            Self::remove_auto_renewal(&token);
+
+            Self::deposit_event(Event::<T>::AutoRenewalCancelled { token });
+
+            Ok(())
+        }
+
+        /// Lets `member` write up to `quota` bytes into the territory, or changes the quota of a member. The
+        /// member refers to the territory by its current name.
+        #[pallet::call_index(116)]
+        #[transactional]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::add_territory_member())]
+        pub fn add_territory_member(
+            origin: OriginFor<T>,
+            territory_name: TerrName,
+            member: AccountOf<T>,
+            quota: u128,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(member != sender, Error::<T>::ParamError);
+
+            let territory = <Territory<T>>::try_get(&sender, &territory_name).map_err(|_| Error::<T>::NotHaveTerritory)?;
+            ensure!(territory.state == TerritoryState::Active, Error::<T>::NotActive);
+            let token = territory.token;
+
+            <TerritoryMembers<T>>::try_mutate(&token, |members| -> DispatchResult {
+                if let Some(m) = members.iter_mut().find(|m| m.account == member) {
+                    m.quota = quota;
+                    return Ok(());
+                }
+
+                ensure!(!<Territory<T>>::contains_key(&member, &territory_name), Error::<T>::TerritoryNameTaken);
+                ensure!(!<MemberTerritory<T>>::contains_key(&member, &territory_name), Error::<T>::TerritoryNameTaken);
+                members.try_push(TerritoryMember::<T> {
+                    account: member.clone(),
+                    name: territory_name.clone(),
+                    quota,
+                    used: 0,
+                    locked: 0,
+                }).map_err(|_| Error::<T>::TooManyMembers)?;
+                <MemberTerritory<T>>::insert(&member, &territory_name, token);
+
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::<T>::TerritoryMemberSet { token, member, quota });
+
+            Ok(())
+        }
+
+        /// Stops `member` from writing into the territory. Its data stays until it is deleted or the territory
+        /// expires.
+        #[pallet::call_index(117)]
+        #[transactional]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::revoke_territory_member())]
+        pub fn revoke_territory_member(
+            origin: OriginFor<T>,
+            territory_name: TerrName,
+            member: AccountOf<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let territory = <Territory<T>>::try_get(&sender, &territory_name).map_err(|_| Error::<T>::NotHaveTerritory)?;
+            Self::mutate_member(&territory.token, &member, |m| -> DispatchResult {
+                m.quota = 0;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::<T>::TerritoryMemberRevoked { token: territory.token, member });
+
+            Ok(())
+        }
+
         // FOR TEST
 		#[pallet::call_index(4)]
 		#[transactional]
@@ -803,10 +1331,34 @@ pub mod pallet {
 		pub fn update_price(origin: OriginFor<T>) -> DispatchResult {
 			let _ = ensure_root(origin)?;
 			let default_price: BalanceOf<T> = 30_000_000_000_000_000_000u128.try_into().map_err(|_| Error::<T>::Overflow)?;
-			UnitPrice::<T>::put(default_price);
+			// Once governance has bounded the price, the reset stays within the bounds.
+			let price = match <UnitPriceBounds<T>>::get() {
+				Some(bounds) => default_price.clamp(bounds.floor, bounds.ceiling),
+				None => default_price,
+			};
+			UnitPrice::<T>::put(price);
 
 			Ok(())
 		}
+
+        #[pallet::call_index(9)]
+        #[transactional]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_price_bounds())]
+        pub fn set_price_bounds(
+            origin: OriginFor<T>,
+            floor: BalanceOf<T>,
+            ceiling: BalanceOf<T>,
+        ) -> DispatchResult {
+            let _ = ensure_root(origin)?;
+            ensure!(floor <= ceiling, Error::<T>::InvalidPriceBounds);
+
+            <UnitPriceBounds<T>>::put(PriceBounds { floor, ceiling });
+            Self::deposit_event(Event::<T>::PriceBoundsSet { floor, ceiling });
+            // The new bounds apply right away rather than at the next era.
+            Self::adjust_unit_price();
+
+            Ok(())
+        }
         // FOR TEST
         #[pallet::call_index(5)]
 		#[transactional]
@@ -875,22 +1427,24 @@ pub mod pallet {
                 .checked_mul(6).ok_or(Error::<T>::Overflow)?).saturated_into();
             ensure!(expired < T::OneHours::get(), Error::<T>::ParamError);
 
+            let unit_price = <UnitPrice<T>>::try_get().map_err(|_e| Error::<T>::BugInvalid)?;
             let price = match order_type {
                 OrderType::Buy => {
                     ensure!(!<Territory<T>>::contains_key(&target_acc, &territory_name), Error::<T>::PurchasedSpace);
-                    let price = Self::calculate_price(gib_count, days)?;
+                    ensure!(!<MemberTerritory<T>>::contains_key(&target_acc, &territory_name), Error::<T>::TerritoryNameTaken);
+                    let price = Self::calculate_price(unit_price, gib_count, days)?;
                     price
                 },
                 OrderType::Expansion => {
                     let user_owned_space = <Territory<T>>::try_get(&target_acc, &territory_name).map_err(|_| Error::<T>::NotHaveTerritory)?;
                     let remain_day = Self::calculate_remain_day(user_owned_space.deadline)?;
-                    let price = Self::calculate_price(gib_count, remain_day.saturated_into())?;
+                    let price = Self::calculate_price(unit_price, gib_count, remain_day.saturated_into())?;
                     price
                 },
                 OrderType::Renewal => {
                     let user_owned_space = <Territory<T>>::try_get(&target_acc, &territory_name).map_err(|_| Error::<T>::NotHaveTerritory)?;
                     let gib_count = user_owned_space.total_space.checked_div(G_BYTE).ok_or(Error::<T>::Overflow)?;
-                    let price = Self::calculate_price(gib_count as u32, days)?;
+                    let price = Self::calculate_price(unit_price, gib_count as u32, days)?;
                     price
                 },
             };
@@ -905,6 +1459,7 @@ pub mod pallet {
                 expired,
                 target_acc: target_acc,
                 order_type,
+                unit_price,
             };
 
             let (seed, _) =
@@ -935,6 +1490,7 @@ pub mod pallet {
             match order.order_type {
                 OrderType::Buy => {
                     ensure!(!<Territory<T>>::contains_key(&order.target_acc, &order.territory_name), Error::<T>::PurchasedSpace);
+                    ensure!(!<MemberTerritory<T>>::contains_key(&order.target_acc, &order.territory_name), Error::<T>::TerritoryNameTaken);
                     let space = G_BYTE.checked_mul(order.gib_count as u128).ok_or(Error::<T>::Overflow)?;
                     let now = <frame_system::Pallet<T>>::block_number();
                     let seed = (sender.clone(), now, order.territory_name.clone());
@@ -951,8 +1507,8 @@ pub mod pallet {
                 OrderType::Expansion => {
                     let user_owned_space = <Territory<T>>::try_get(&order.target_acc, &order.territory_name).map_err(|_| Error::<T>::NotHaveTerritory)?;
                     let remain_day = Self::calculate_remain_day(user_owned_space.deadline)?;
-                    let price = Self::calculate_price(order.gib_count, remain_day.saturated_into())?;
-                    // todo! If the price becomes dynamic in the future, the judgment basis will become invalid. 
+                    let price = Self::calculate_price(order.unit_price, order.gib_count, remain_day.saturated_into())?;
+                    // The order is priced at the unit price it was quoted at.
                     // Make sure that the territory data does not change before and after the order is created.
                     ensure!(price == order.pay, Error::<T>::InvalidOrder);
                     let space = G_BYTE.checked_mul(order.gib_count as u128).ok_or(Error::<T>::Overflow)?;
@@ -962,8 +1518,8 @@ pub mod pallet {
                 OrderType::Renewal => {
                     let user_owned_space = <Territory<T>>::try_get(&order.target_acc, &order.territory_name).map_err(|_| Error::<T>::NotHaveTerritory)?;
                     let gib_count = user_owned_space.total_space.checked_div(G_BYTE).ok_or(Error::<T>::Overflow)?;
-                    let price = Self::calculate_price(gib_count as u32, order.days)?;
-                    // todo! If the price becomes dynamic in the future, the judgment basis will become invalid. 
+                    let price = Self::calculate_price(order.unit_price, gib_count as u32, order.days)?;
+                    // The order is priced at the unit price it was quoted at.
                     // Make sure that the territory data does not change before and after the order is created.
                     ensure!(price == order.pay, Error::<T>::InvalidOrder);
                     Self::update_territory_days(order.target_acc, order.territory_name, order.days)?;
@@ -993,8 +1549,8 @@ pub mod pallet {
 }
 
 impl<T: Config> Pallet<T> {
-    fn calculate_price(gib_count: u32, days: u32) -> Result<BalanceOf<T>, DispatchError> {
-        let unit_price: u128 = <UnitPrice<T>>::get().unwrap().try_into().map_err(|_| Error::<T>::Overflow)?;
+    fn calculate_price(unit_price: BalanceOf<T>, gib_count: u32, days: u32) -> Result<BalanceOf<T>, DispatchError> {
+        let unit_price: u128 = unit_price.try_into().map_err(|_| Error::<T>::Overflow)?;
         let gib_count: u128 = gib_count.into();
         let days: u128 = days.into();
         let price = gib_count
@@ -1038,6 +1594,17 @@ impl<T: Config> Pallet<T> {
             .ok_or(Error::<T>::Overflow)?;
         let deadline = now.checked_add(&sur_block).ok_or(Error::<T>::Overflow)?;
 
+        Self::insert_territory(token, user, space, deadline, tname)
+    }
+
+    fn insert_territory(
+        token: TokenId,
+        user: AccountOf<T>,
+        space: u128,
+        deadline: BlockNumberFor<T>,
+        tname: TerrName,
+    ) -> DispatchResult {
+        let now = <frame_system::Pallet<T>>::block_number();
         let info = TerritoryInfo::<T> {
             token: token.clone(),
             total_space: space,
@@ -1060,6 +1627,83 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    // Puts an empty, active territory on consignment, scheduling the settlement of sealed-bid ones.
+    fn consign(
+        seller: &AccountOf<T>,
+        territory_name: &TerrName,
+        price: BalanceOf<T>,
+        mode: ConsignmentMode<BalanceOf<T>, BlockNumberFor<T>>,
+    ) -> Result<TokenId, DispatchError> {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let zero: BlockNumberFor<T> = 0u32.saturated_into();
+        let settle_block = match mode {
+            ConsignmentMode::Fixed => None,
+            ConsignmentMode::Dutch { floor, duration } => {
+                ensure!(floor <= price && duration > zero, Error::<T>::InvalidAuction);
+                None
+            },
+            ConsignmentMode::SealedBid { commit_period, reveal_period } => {
+                ensure!(commit_period > zero && reveal_period > zero, Error::<T>::InvalidAuction);
+                let settle_block = now
+                    .checked_add(&commit_period).ok_or(Error::<T>::Overflow)?
+                    .checked_add(&reveal_period).ok_or(Error::<T>::Overflow)?;
+                Some(settle_block)
+            },
+        };
+
+        let token = <Territory<T>>::try_mutate(seller, territory_name, |t_opt| -> Result<TokenId, DispatchError> {
+            let t = t_opt.as_mut().ok_or(Error::<T>::NotHaveTerritory)?;
+
+            ensure!(t.state == TerritoryState::Active, Error::<T>::NotActive);
+            ensure!(t.total_space == t.remaining_space, Error::<T>::ObjectNotZero);
+
+            let remain_block = t.deadline.checked_sub(&now).ok_or(Error::<T>::Overflow)?;
+            let limit_block = T::ConsignmentRemainingBlock::get();
+            ensure!(remain_block > limit_block, Error::<T>::InsufficientLease);
+            // An auction has to be settled before the territory freezes.
+            if let Some(settle_block) = settle_block {
+                ensure!(settle_block < t.deadline, Error::<T>::InsufficientLease);
+            }
+
+            t.state = TerritoryState::OnConsignment;
+
+            Ok(t.token)
+        })?;
+
+        ensure!(!<Consignment<T>>::contains_key(&token), Error::<T>::ConsignmentExisted);
+        let consignment_info = ConsignmentInfo::<T>{
+            user: seller.clone(),
+            price: price,
+            buyers: None,
+            exec: None,
+            locked: false,
+            mode,
+            start: now,
+            deal_price: price,
+        };
+        <Consignment<T>>::insert(&token, consignment_info);
+
+        if let Some(settle_block) = settle_block {
+            let call: <T as Config>::SProposal = Call::exec_sealed_consignment{token: token.clone()}.into();
+            T::FScheduler::schedule_named(
+                *(token.as_fixed_bytes()),
+                DispatchTime::At(settle_block),
+                Option::None,
+                schedule::HARD_DEADLINE,
+                frame_system::RawOrigin::Root.into(),
+                T::Preimages::bound(call)?,
+            ).map_err(|_| Error::<T>::Unexpected)?;
+        }
+
+        Self::deposit_event(Event::<T>::Consignment {
+            name: territory_name.clone(),
+            token: token,
+            price: price,
+        });
+
+        Ok(token)
+    }
+
     // Before calling this method, please determine the state of the territory
     fn update_territory_space(
         user: AccountOf<T>,
@@ -1135,6 +1779,7 @@ impl<T: Config> Pallet<T> {
             t.locked_space = 0;
             t.used_space = 0;
             t.start = now;
+            Self::reset_territory_members(&t.token);
 
             let one_day = T::OneDay::get();
             let deadline: BlockNumberFor<T> = one_day.checked_mul(&days.saturated_into()).ok_or(Error::<T>::Overflow)?;
@@ -1183,7 +1828,10 @@ impl<T: Config> Pallet<T> {
                         let t = t_opt.as_mut().ok_or(Error::<T>::Unexpected)?;
                         if t.state == TerritoryState::OnConsignment {
                             <Consignment<T>>::remove(&token);
-                            weight = weight.saturating_add(T::DbWeight::get().writes(1));
+                            for sealed in <SealedBids<T>>::take(&token) {
+                                <T as pallet::Config>::Currency::unreserve(&sealed.bidder, sealed.deposit);
+                            }
+                            weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 2));
                         }
 
                         t.state = TerritoryState::Frozen;
@@ -1215,6 +1863,11 @@ impl<T: Config> Pallet<T> {
                     });
                     weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
                     list.push((acc, territory_name));
+                    // The data of the members goes with the territory.
+                    for m in <TerritoryMembers<T>>::get(&token) {
+                        list.push((m.account, m.name));
+                    }
+                    weight = weight.saturating_add(T::DbWeight::get().reads(1));
                 },
                 Err(e) => log::error!("[StorageHanle] -> [frozen_task]: TerritoryKey read failed {:?}", e),
             }
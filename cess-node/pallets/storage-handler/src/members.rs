@@ -0,0 +1,95 @@
+//! Shared territories.
+//!
+//! The owner of a territory can let other accounts write into it, each under a byte quota of its own. A member
+//! refers to the territory by the name it had when the member was added, through `MemberTerritory`, so the
+//! `StorageHandle` methods take the member and that name like any territory of its own and charge both the
+//! territory and the member's quota.
+//!
+//! Revoking a member sets its quota to zero. The membership is dropped once the member's data is gone, so that
+//! deleting it still finds the territory.
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+	/// Resolves the territory `name` of `acc` to its owner and the owner's name for it, along with the token
+	/// if it is shared with `acc` rather than owned.
+	pub(super) fn territory_of(
+		acc: &AccountOf<T>,
+		name: &TerrName,
+	) -> Result<(AccountOf<T>, TerrName, Option<TokenId>), DispatchError> {
+		if <Territory<T>>::contains_key(acc, name) {
+			return Ok((acc.clone(), name.clone(), None));
+		}
+		let token = <MemberTerritory<T>>::try_get(acc, name).map_err(|_| Error::<T>::NotHaveTerritory)?;
+		let (owner, owner_name) = <TerritoryKey<T>>::try_get(&token).map_err(|_| Error::<T>::Unexpected)?;
+
+		Ok((owner, owner_name, Some(token)))
+	}
+
+	/// Applies `f` to the membership of `acc` in the territory, dropping revoked memberships once they are
+	/// drained.
+	pub(super) fn mutate_member<R>(
+		token: &TokenId,
+		acc: &AccountOf<T>,
+		f: impl FnOnce(&mut TerritoryMember<T>) -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError> {
+		<TerritoryMembers<T>>::try_mutate(token, |members| -> Result<R, DispatchError> {
+			let index = members.iter().position(|m| &m.account == acc).ok_or(Error::<T>::NotTerritoryMember)?;
+			let result = f(&mut members[index])?;
+
+			let m = &members[index];
+			if m.quota == 0 && m.used == 0 && m.locked == 0 {
+				<MemberTerritory<T>>::remove(&m.account, &m.name);
+				members.remove(index);
+			}
+
+			Ok(result)
+		})
+	}
+
+	/// Charges `size` bytes to the quota of a member, `locked` for a deal in progress.
+	pub(super) fn charge_member(token: &TokenId, acc: &AccountOf<T>, size: u128, locked: bool) -> DispatchResult {
+		Self::mutate_member(token, acc, |m| -> DispatchResult {
+			let charged = m.used.checked_add(m.locked).ok_or(Error::<T>::Overflow)?;
+			let charged = charged.checked_add(size).ok_or(Error::<T>::Overflow)?;
+			ensure!(charged <= m.quota, Error::<T>::QuotaExceeded);
+			if locked {
+				m.locked = m.locked.checked_add(size).ok_or(Error::<T>::Overflow)?;
+			} else {
+				m.used = m.used.checked_add(size).ok_or(Error::<T>::Overflow)?;
+			}
+			Ok(())
+		})
+	}
+
+	/// The space a member has left under its quota.
+	pub(super) fn member_avail_space(token: &TokenId, acc: &AccountOf<T>) -> Result<u128, DispatchError> {
+		let members = <TerritoryMembers<T>>::get(token);
+		let m = members.iter().find(|m| &m.account == acc).ok_or(Error::<T>::NotTerritoryMember)?;
+
+		Ok(m.quota.saturating_sub(m.used.saturating_add(m.locked)))
+	}
+
+	/// Removes every member of a territory changing hands.
+	pub(super) fn clear_territory_members(token: &TokenId) {
+		for m in <TerritoryMembers<T>>::take(token) {
+			<MemberTerritory<T>>::remove(&m.account, &m.name);
+		}
+	}
+
+	/// Resets the usage of the members of a reactivated territory, whose data is gone, dropping the revoked ones.
+	pub(super) fn reset_territory_members(token: &TokenId) {
+		<TerritoryMembers<T>>::mutate(token, |members| {
+			members.retain(|m| {
+				if m.quota == 0 {
+					<MemberTerritory<T>>::remove(&m.account, &m.name);
+				}
+				m.quota != 0
+			});
+			for m in members.iter_mut() {
+				m.used = 0;
+				m.locked = 0;
+			}
+		});
+	}
+}
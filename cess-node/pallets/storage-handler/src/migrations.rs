@@ -0,0 +1,94 @@
+use super::*;
+
+pub fn migrate<T: Config>() -> Weight {
+	let version = StorageVersion::get::<Pallet<T>>();
+	let mut weight: Weight = T::DbWeight::get().reads(1);
+
+	if version < 2 {
+		log::info!("StorageHandler version 1 -> 2 migrations start!");
+		weight = weight.saturating_add(v2::migrate::<T>());
+		StorageVersion::new(2).put::<Pallet<T>>();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	if version < 3 {
+		log::info!("StorageHandler version 2 -> 3 migrations start!");
+		weight = weight.saturating_add(v3::migrate::<T>());
+		StorageVersion::new(3).put::<Pallet<T>>();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	weight
+}
+
+mod v2 {
+	use super::*;
+
+	#[derive(Decode, Encode)]
+	struct OldOrderInfo<T: Config> {
+		territory_name: TerrName,
+		pay: BalanceOf<T>,
+		gib_count: u32,
+		days: u32,
+		expired: BlockNumberFor<T>,
+		target_acc: AccountOf<T>,
+		order_type: OrderType,
+	}
+
+	// Orders created before the price became dynamic were quoted at the current `UnitPrice`.
+	pub fn migrate<T: Config>() -> Weight {
+		let mut weight: Weight = T::DbWeight::get().reads(1);
+		let unit_price = <UnitPrice<T>>::get().unwrap_or_default();
+
+		<PayOrder<T>>::translate(|_key, old: OldOrderInfo<T>| {
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			Some(OrderInfo::<T> {
+				territory_name: old.territory_name,
+				pay: old.pay,
+				gib_count: old.gib_count,
+				days: old.days,
+				expired: old.expired,
+				target_acc: old.target_acc,
+				order_type: old.order_type,
+				unit_price,
+			})
+		});
+
+		weight
+	}
+}
+
+mod v3 {
+	use super::*;
+
+	#[derive(Decode, Encode)]
+	struct OldConsignmentInfo<T: Config> {
+		user: AccountOf<T>,
+		price: BalanceOf<T>,
+		buyers: Option<AccountOf<T>>,
+		exec: Option<BlockNumberFor<T>>,
+		locked: bool,
+	}
+
+	// Existing consignments are sold at their fixed price.
+	pub fn migrate<T: Config>() -> Weight {
+		let mut weight: Weight = T::DbWeight::get().reads(1);
+		let now = <frame_system::Pallet<T>>::block_number();
+
+		<Consignment<T>>::translate(|_key, old: OldConsignmentInfo<T>| {
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			Some(ConsignmentInfo::<T> {
+				user: old.user,
+				price: old.price,
+				buyers: old.buyers,
+				exec: old.exec,
+				locked: old.locked,
+				mode: ConsignmentMode::Fixed,
+				start: now,
+				deal_price: old.price,
+			})
+		});
+
+		weight
+	}
+}
@@ -0,0 +1,175 @@
+//! Test utilities
+
+use crate as pallet_storage_handler;
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{
+		ConstU128, ConstU32, Currency, EqualPrivilegeOnly, ExistenceRequirement::AllowDeath, Hooks, Randomness,
+	},
+	weights::Weight,
+	PalletId,
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{traits::IdentityLookup, BuildStorage, DispatchResult, Perbill};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+pub(crate) type AccountId = u64;
+pub(crate) type Balance = u128;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		Scheduler: pallet_scheduler,
+		Preimage: pallet_preimage,
+		StorageHandler: pallet_storage_handler,
+	}
+);
+
+parameter_types! {
+	pub BlockWeights: frame_system::limits::BlockWeights =
+		frame_system::limits::BlockWeights::simple_max(Weight::from_parts(2_000_000_000_000, u64::MAX));
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type BlockWeights = BlockWeights;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig as pallet_balances::DefaultConfig)]
+impl pallet_balances::Config for Test {
+	type Balance = Balance;
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+}
+
+impl pallet_preimage::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<AccountId>;
+	type Consideration = ();
+}
+
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * BlockWeights::get().max_block;
+}
+
+impl pallet_scheduler::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type PalletsOrigin = OriginCaller;
+	type RuntimeCall = RuntimeCall;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type MaxScheduledPerBlock = ConstU32<50>;
+	type WeightInfo = ();
+	type Preimages = Preimage;
+}
+
+/// Derives the random value from the subject alone, so that different subjects never collide.
+pub struct MockRandomness;
+impl Randomness<Option<H256>, u64> for MockRandomness {
+	fn random(subject: &[u8]) -> (Option<H256>, u64) {
+		(Some(H256::from(sp_io::hashing::blake2_256(subject))), System::block_number())
+	}
+}
+
+/// Pays everything into the `TREASURY` account.
+pub struct MockTreasury;
+impl pallet_cess_treasury::TreasuryHandle<AccountId, Balance> for MockTreasury {
+	fn send_to_pid(acc: AccountId, amount: Balance) -> DispatchResult {
+		Balances::transfer(&acc, &consts::TREASURY, amount, AllowDeath)
+	}
+
+	fn send_to_sid(acc: AccountId, amount: Balance) -> DispatchResult {
+		Balances::transfer(&acc, &consts::TREASURY, amount, AllowDeath)
+	}
+}
+
+parameter_types! {
+	pub const RewardPalletId: PalletId = PalletId(*b"rewardpt");
+	pub const OneDay: u64 = consts::ONE_DAY;
+	pub const OneHours: u64 = 600;
+	#[derive(Clone, Eq, PartialEq)]
+	pub const StateStringMax: u32 = 20;
+	#[derive(Clone, Eq, PartialEq)]
+	pub const FrozenDays: u64 = 7 * consts::ONE_DAY;
+	pub const LockingBlock: u64 = 5;
+	pub const PricingEra: u64 = 100;
+}
+
+impl pallet_storage_handler::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type WeightInfo = ();
+	type FScheduler = Scheduler;
+	type SProposal = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
+	type OneDay = OneDay;
+	type OneHours = OneHours;
+	type RewardPalletId = RewardPalletId;
+	type StateStringMax = StateStringMax;
+	type NameLimit = ConstU32<63>;
+	type FrozenLimit = ConstU32<2000>;
+	type ConsignmentRemainingBlock = OneDay;
+	type LockingBlock = LockingBlock;
+	type PricingEra = PricingEra;
+	type MaxSealedBids = ConstU32<3>;
+	type AutoRenewAdvance = OneDay;
+	type MaxTerritoryMembers = ConstU32<3>;
+	type FrozenDays = FrozenDays;
+	type CessTreasuryHandle = MockTreasury;
+	type MyRandomness = MockRandomness;
+	type Preimages = Preimage;
+}
+
+pub mod consts {
+	use super::{AccountId, Balance};
+	use cp_cess_common::G_BYTE;
+
+	pub const ONE_DAY: u64 = 10;
+	/// The monthly price of a GiB, i.e. 1_000 a day.
+	pub const UNIT_PRICE: Balance = 30_000;
+	pub const IDLE_SPACE: u128 = 1000 * G_BYTE;
+
+	pub const TREASURY: AccountId = 100;
+	pub const ACCOUNT1: (AccountId, Balance) = (1, 1_000_000_000_000);
+	pub const ACCOUNT2: (AccountId, Balance) = (2, 1_000_000_000_000);
+	pub const ACCOUNT3: (AccountId, Balance) = (3, 1_000);
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	use consts::*;
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![ACCOUNT1, ACCOUNT2, ACCOUNT3, (TREASURY, 1)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	pallet_storage_handler::GenesisConfig::<Test> { price: UNIT_PRICE }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1); //must set block_number, otherwise the deposit_event() don't work
+		pallet_storage_handler::TotalIdleSpace::<Test>::put(IDLE_SPACE);
+	});
+	ext
+}
+
+/// Runs the blocks up to `n`, freezing and expiring territories the way `pallet-file-bank` does on its
+/// `on_initialize`.
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		Scheduler::on_finalize(System::block_number());
+		System::set_block_number(System::block_number() + 1);
+		StorageHandler::on_initialize(System::block_number());
+		let _ = StorageHandler::frozen_task();
+		Scheduler::on_initialize(System::block_number());
+	}
+}
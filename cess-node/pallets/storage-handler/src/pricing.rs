@@ -0,0 +1,62 @@
+//! Utilisation-driven territory pricing.
+//!
+//! Once governance has set the bounds, `UnitPrice` follows the share of the network space which is purchased,
+//! from the floor while nothing is purchased up to the ceiling once all of it is. The price is moved once per
+//! `PricingEra`, and pay orders keep the price they were quoted at.
+
+use super::*;
+use sp_runtime::{traits::AtLeast32BitUnsigned, Perbill};
+
+/// The unit price at a utilisation of `purchased` out of `total` bytes.
+pub fn utilisation_price<Balance: AtLeast32BitUnsigned + Copy>(
+	purchased: u128,
+	total: u128,
+	bounds: &PriceBounds<Balance>,
+) -> Balance {
+	// Without any space on the network, none of it is left to purchase.
+	let utilisation = if total == 0 {
+		Perbill::one()
+	} else {
+		Perbill::from_rational(purchased.min(total), total)
+	};
+	let spread = bounds.ceiling.saturating_sub(bounds.floor);
+	bounds.floor.saturating_add(utilisation.mul_floor(spread))
+}
+
+/// The price of a Dutch consignment `elapsed` blocks into its `duration`.
+pub fn dutch_price<Balance: AtLeast32BitUnsigned + Copy>(
+	start_price: Balance,
+	floor: Balance,
+	elapsed: u128,
+	duration: u128,
+) -> Balance {
+	if elapsed >= duration {
+		return floor;
+	}
+	let drop = Perbill::from_rational(elapsed, duration).mul_floor(start_price.saturating_sub(floor));
+	start_price.saturating_sub(drop)
+}
+
+impl<T: Config> Pallet<T> {
+	/// Moves `UnitPrice` to the price of the current utilisation, if the bounds are set.
+	pub(super) fn adjust_unit_price() -> Weight {
+		let mut weight = T::DbWeight::get().reads(1);
+		let Some(bounds) = <UnitPriceBounds<T>>::get() else {
+			return weight;
+		};
+
+		let purchased = <PurchasedSpace<T>>::get();
+		let total = <TotalIdleSpace<T>>::get().saturating_add(<TotalServiceSpace<T>>::get());
+		weight = weight.saturating_add(T::DbWeight::get().reads(4));
+
+		let price = utilisation_price(purchased, total, &bounds);
+		if <UnitPrice<T>>::get() != Some(price) {
+			<UnitPrice<T>>::put(price);
+			weight = weight.saturating_add(T::DbWeight::get().writes(1));
+			Self::deposit_event(Event::<T>::UnitPriceUpdated { price, purchased, total });
+		}
+
+		weight
+	}
+}
+
@@ -0,0 +1,150 @@
+//! Automatic territory renewal.
+//!
+//! A funding account subscribes a territory with the length of each renewal and the most it is willing to pay for
+//! one. The territory is queued in `AutoRenewalQueue` for `AutoRenewAdvance` blocks ahead of its deadline, renewed
+//! there, and queued again for its new deadline. A renewal the funder cannot or will not pay for is retried a day
+//! later, until the territory expires and the subscription with it.
+
+use super::*;
+use frame_support::storage::with_storage_layer;
+use sp_runtime::traits::Saturating;
+
+impl<T: Config> Pallet<T> {
+	/// The price of renewing `total_space` bytes for `days` days at the current unit price.
+	pub(super) fn renewal_price(total_space: u128, days: u32) -> Result<BalanceOf<T>, DispatchError> {
+		let days_unit_price = <UnitPrice<T>>::try_get()
+			.map_err(|_e| Error::<T>::BugInvalid)?
+			.checked_div(&30u32.saturated_into())
+			.ok_or(Error::<T>::Overflow)?;
+		let gib_count = total_space.checked_div(G_BYTE).ok_or(Error::<T>::Overflow)?;
+		let price: BalanceOf<T> = days_unit_price
+			.checked_mul(&gib_count.saturated_into())
+			.ok_or(Error::<T>::Overflow)?
+			.checked_mul(&days.saturated_into())
+			.ok_or(Error::<T>::Overflow)?;
+
+		Ok(price)
+	}
+
+	/// The block to renew a territory expiring at `deadline`, no earlier than the next block.
+	pub(super) fn renewal_block(deadline: BlockNumberFor<T>) -> BlockNumberFor<T> {
+		let next = <frame_system::Pallet<T>>::block_number().saturating_add(1u32.into());
+		deadline.saturating_sub(T::AutoRenewAdvance::get()).max(next)
+	}
+
+	pub(super) fn enqueue_auto_renewal(
+		token: &TokenId,
+		at: BlockNumberFor<T>,
+	) -> Result<BlockNumberFor<T>, DispatchError> {
+		<AutoRenewalQueue<T>>::try_mutate(&at, |queue| queue.try_push(*token))
+			.map_err(|_| Error::<T>::Overflow)?;
+
+		Ok(at)
+	}
+
+	/// Drops the subscription of a territory along with its queued attempt, so it no longer takes up a slot of
+	/// the queue.
+	pub(super) fn remove_auto_renewal(token: &TokenId) -> Option<AutoRenewal<T>> {
+		let sub = <AutoRenewals<T>>::take(token)?;
+		<AutoRenewalQueue<T>>::mutate_exists(&sub.next, |queue_opt| {
+			if let Some(queue) = queue_opt {
+				queue.retain(|queued| queued != token);
+				if queue.is_empty() {
+					*queue_opt = None;
+				}
+			}
+		});
+
+		Some(sub)
+	}
+
+	/// Renews the subscribed territories queued at `now`.
+	pub(super) fn auto_renew(now: BlockNumberFor<T>) -> Weight {
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+		for token in <AutoRenewalQueue<T>>::take(&now) {
+			weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			let Some(mut sub) = <AutoRenewals<T>>::get(&token) else {
+				continue;
+			};
+			// Re-subscribed or cancelled and subscribed again in the meantime.
+			if sub.next != now {
+				continue;
+			}
+
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(6, 6));
+			let next = match Self::try_auto_renew(&token, &sub, now) {
+				Ok(next) => next,
+				Err(e) => {
+					log::error!("[StorageHandler] -> [auto_renew]: renewal of {:?} failed {:?}", token, e);
+					None
+				},
+			};
+			match next.map(|at| Self::enqueue_auto_renewal(&token, at)) {
+				Some(Ok(at)) => {
+					sub.next = at;
+					<AutoRenewals<T>>::insert(&token, sub);
+				},
+				_ => {
+					<AutoRenewals<T>>::remove(&token);
+					Self::deposit_event(Event::<T>::AutoRenewalCancelled { token });
+				},
+			}
+		}
+
+		weight
+	}
+
+	// Returns the block of the next attempt, if the subscription is to be kept.
+	fn try_auto_renew(
+		token: &TokenId,
+		sub: &AutoRenewal<T>,
+		now: BlockNumberFor<T>,
+	) -> Result<Option<BlockNumberFor<T>>, DispatchError> {
+		let (owner, name) = <TerritoryKey<T>>::try_get(token).map_err(|_| Error::<T>::Unexpected)?;
+		let territory = <Territory<T>>::try_get(&owner, &name).map_err(|_| Error::<T>::Unexpected)?;
+		let retry = now.checked_add(&T::OneDay::get()).ok_or(Error::<T>::Overflow)?;
+
+		match territory.state {
+			TerritoryState::Expired => return Ok(None),
+			TerritoryState::OnConsignment => return Ok(Some(retry)),
+			TerritoryState::Active | TerritoryState::Frozen => (),
+		}
+		// Renewed by hand since it was queued.
+		if territory.deadline.saturating_sub(T::AutoRenewAdvance::get()) > now {
+			return Ok(Some(Self::renewal_block(territory.deadline)));
+		}
+
+		let price = Self::renewal_price(territory.total_space, sub.days)?;
+		let failure = if price > sub.max_price {
+			Some(RenewalFailure::PriceExceeded)
+		} else {
+			// The payment keeps the funding account alive.
+			let renewed = with_storage_layer(|| -> DispatchResult {
+				T::CessTreasuryHandle::send_to_sid(sub.funder.clone(), price)?;
+				Self::update_territory_days(owner.clone(), name.clone(), sub.days)
+			});
+			renewed.err().map(|_| RenewalFailure::InsufficientBalance)
+		};
+
+		if let Some(reason) = failure {
+			Self::deposit_event(Event::<T>::AutoRenewalFailed {
+				token: *token,
+				funder: sub.funder.clone(),
+				price,
+				reason,
+			});
+			return Ok(Some(retry));
+		}
+
+		Self::deposit_event(Event::<T>::AutoRenewed {
+			token: *token,
+			funder: sub.funder.clone(),
+			days: sub.days,
+			spend: price,
+		});
+		let territory = <Territory<T>>::try_get(&owner, &name).map_err(|_| Error::<T>::Unexpected)?;
+
+		Ok(Some(Self::renewal_block(territory.deadline)))
+	}
+}
@@ -0,0 +1,1159 @@
+use super::*;
+use crate::mock::{
+	consts::*, new_test_ext, run_to_block, AccountId, Balance, Balances, FrozenDays, LockingBlock, PricingEra,
+	RuntimeOrigin, StorageHandler, System, Test,
+};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+
+const DAY_PRICE: Balance = UNIT_PRICE / 30;
+
+fn terr_name(name: &str) -> TerrName {
+	name.as_bytes().to_vec().try_into().unwrap()
+}
+
+fn territory(acc: AccountId, name: &str) -> TerritoryInfo<Test> {
+	Territory::<Test>::get(acc, terr_name(name)).unwrap()
+}
+
+fn mint(acc: AccountId, name: &str, gib_count: u32, days: u32) -> TerritoryInfo<Test> {
+	assert_ok!(StorageHandler::mint_territory(RuntimeOrigin::signed(acc), gib_count, terr_name(name), days));
+	territory(acc, name)
+}
+
+fn last_order() -> (BoundedVec<u8, ConstU32<32>>, OrderInfo<Test>) {
+	PayOrder::<Test>::iter().next().unwrap()
+}
+
+fn member(acc: AccountId, name: &str) -> TerritoryMember<Test> {
+	let token = MemberTerritory::<Test>::get(acc, terr_name(name)).unwrap();
+	TerritoryMembers::<Test>::get(token).into_iter().find(|m| m.account == acc).unwrap()
+}
+
+fn seal(bid: Balance, salt: H256) -> H256 {
+	H256(sp_io::hashing::blake2_256(&(bid, salt).encode()))
+}
+
+#[test]
+fn mint_territory_works() {
+	new_test_ext().execute_with(|| {
+		let territory = mint(ACCOUNT1.0, "t1", 10, 30);
+		assert_eq!(territory.state, TerritoryState::Active);
+		assert_eq!(territory.total_space, 10 * G_BYTE);
+		assert_eq!(territory.remaining_space, 10 * G_BYTE);
+		assert_eq!(territory.start, 1);
+		assert_eq!(territory.deadline, 1 + 30 * ONE_DAY);
+		assert_eq!(TerritoryKey::<Test>::get(territory.token), Some((ACCOUNT1.0, terr_name("t1"))));
+		assert_eq!(TerritoryFrozen::<Test>::get(territory.deadline, territory.token), Some(true));
+		assert_eq!(PurchasedSpace::<Test>::get(), 10 * G_BYTE);
+
+		let spend = DAY_PRICE * 10 * 30;
+		assert_eq!(Balances::free_balance(ACCOUNT1.0), ACCOUNT1.1 - spend);
+		assert_eq!(Balances::free_balance(TREASURY), 1 + spend);
+	});
+}
+
+#[test]
+fn mint_territory_checks() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			StorageHandler::mint_territory(RuntimeOrigin::signed(ACCOUNT1.0), 10, terr_name("t1"), 29),
+			Error::<Test>::BoundariesNotMet
+		);
+		assert_noop!(
+			StorageHandler::mint_territory(RuntimeOrigin::signed(ACCOUNT1.0), 1001, terr_name("t1"), 30),
+			Error::<Test>::InsufficientAvailableSpace
+		);
+		assert_noop!(
+			StorageHandler::mint_territory(RuntimeOrigin::signed(ACCOUNT3.0), 1, terr_name("t1"), 30),
+			Error::<Test>::InsufficientBalance
+		);
+		mint(ACCOUNT1.0, "t1", 10, 30);
+		assert_noop!(
+			StorageHandler::mint_territory(RuntimeOrigin::signed(ACCOUNT1.0), 10, terr_name("t1"), 30),
+			Error::<Test>::PurchasedSpace
+		);
+	});
+}
+
+#[test]
+fn expanding_territory_charges_remaining_days() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		// 247 blocks before the deadline, a started day is charged in full
+		run_to_block(1 + 5 * ONE_DAY + 3);
+		let balance = Balances::free_balance(ACCOUNT1.0);
+		assert_ok!(StorageHandler::expanding_territory(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), 5));
+
+		let expanded = territory(ACCOUNT1.0, "t1");
+		assert_eq!(expanded.total_space, 15 * G_BYTE);
+		assert_eq!(expanded.remaining_space, 15 * G_BYTE);
+		assert_eq!(expanded.deadline, minted.deadline);
+		assert_eq!(PurchasedSpace::<Test>::get(), 15 * G_BYTE);
+		assert_eq!(Balances::free_balance(ACCOUNT1.0), balance - DAY_PRICE * 5 * 25);
+	});
+}
+
+#[test]
+fn renewal_territory_extends_deadline() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		let balance = Balances::free_balance(ACCOUNT1.0);
+		assert_ok!(StorageHandler::renewal_territory(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), 10));
+
+		let renewed = territory(ACCOUNT1.0, "t1");
+		assert_eq!(renewed.deadline, minted.deadline + 10 * ONE_DAY);
+		assert_eq!(TerritoryFrozen::<Test>::get(minted.deadline, minted.token), None);
+		assert_eq!(TerritoryFrozen::<Test>::get(renewed.deadline, renewed.token), Some(true));
+		assert_eq!(Balances::free_balance(ACCOUNT1.0), balance - DAY_PRICE * 10 * 10);
+	});
+}
+
+#[test]
+fn territory_freezes_at_deadline() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		run_to_block(minted.deadline - 1);
+		assert_eq!(territory(ACCOUNT1.0, "t1").state, TerritoryState::Active);
+
+		run_to_block(minted.deadline);
+		assert_eq!(territory(ACCOUNT1.0, "t1").state, TerritoryState::Frozen);
+		assert_eq!(TerritoryExpired::<Test>::get(minted.deadline + 7 * ONE_DAY, minted.token), Some(true));
+		// frozen space is still purchased, but cannot be grown or sold
+		assert_eq!(PurchasedSpace::<Test>::get(), 10 * G_BYTE);
+		assert_noop!(
+			StorageHandler::expanding_territory(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), 5),
+			Error::<Test>::NotActive
+		);
+		assert_noop!(
+			StorageHandler::territory_consignment(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), 100),
+			Error::<Test>::NotActive
+		);
+	});
+}
+
+#[test]
+fn frozen_territory_can_be_renewed() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		run_to_block(minted.deadline + 4);
+		assert_ok!(StorageHandler::renewal_territory(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), 30));
+
+		// the lease restarts from the renewal rather than the missed deadline
+		let renewed = territory(ACCOUNT1.0, "t1");
+		assert_eq!(renewed.state, TerritoryState::Active);
+		assert_eq!(renewed.start, minted.deadline + 4);
+		assert_eq!(renewed.deadline, minted.deadline + 4 + 30 * ONE_DAY);
+		assert_eq!(TerritoryExpired::<Test>::get(minted.deadline + 7 * ONE_DAY, minted.token), None);
+
+		run_to_block(minted.deadline + 7 * ONE_DAY);
+		assert_eq!(territory(ACCOUNT1.0, "t1").state, TerritoryState::Active);
+	});
+}
+
+#[test]
+fn territory_expires_after_frozen_days() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		let expired_block = minted.deadline + 7 * ONE_DAY;
+		assert_noop!(
+			StorageHandler::reactivate_territory(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), 30),
+			Error::<Test>::NotExpire
+		);
+
+		run_to_block(expired_block - 1);
+		assert_eq!(territory(ACCOUNT1.0, "t1").state, TerritoryState::Frozen);
+		run_to_block(expired_block);
+		assert_eq!(territory(ACCOUNT1.0, "t1").state, TerritoryState::Expired);
+		assert_eq!(PurchasedSpace::<Test>::get(), 0);
+		assert_noop!(
+			StorageHandler::renewal_territory(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), 30),
+			Error::<Test>::StateError
+		);
+
+		let balance = Balances::free_balance(ACCOUNT1.0);
+		assert_ok!(StorageHandler::reactivate_territory(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), 30));
+		let reactivated = territory(ACCOUNT1.0, "t1");
+		assert_eq!(reactivated.state, TerritoryState::Active);
+		assert_eq!(reactivated.start, expired_block);
+		assert_eq!(reactivated.deadline, expired_block + 30 * ONE_DAY);
+		assert_eq!(PurchasedSpace::<Test>::get(), 10 * G_BYTE);
+		assert_eq!(Balances::free_balance(ACCOUNT1.0), balance - DAY_PRICE * 10 * 30);
+	});
+}
+
+#[test]
+fn consignment_is_executed_by_scheduler() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		let price: Balance = 50_000;
+		assert_ok!(StorageHandler::territory_consignment(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), price));
+		assert_eq!(territory(ACCOUNT1.0, "t1").state, TerritoryState::OnConsignment);
+
+		assert_ok!(StorageHandler::buy_consignment(RuntimeOrigin::signed(ACCOUNT2.0), minted.token, terr_name("bought")));
+		let consignment = Consignment::<Test>::get(minted.token).unwrap();
+		assert!(consignment.locked);
+		assert_eq!(consignment.buyers, Some(ACCOUNT2.0));
+		assert_eq!(consignment.exec, Some(1 + LockingBlock::get()));
+		assert_eq!(Balances::reserved_balance(ACCOUNT2.0), price);
+
+		let seller_balance = Balances::free_balance(ACCOUNT1.0);
+		run_to_block(1 + LockingBlock::get());
+		assert!(!Territory::<Test>::contains_key(ACCOUNT1.0, terr_name("t1")));
+		let bought = territory(ACCOUNT2.0, "bought");
+		assert_eq!(bought.state, TerritoryState::Active);
+		assert_eq!(bought.token, minted.token);
+		assert_eq!(TerritoryKey::<Test>::get(minted.token), Some((ACCOUNT2.0, terr_name("bought"))));
+		assert_eq!(Consignment::<Test>::get(minted.token), None);
+		assert_eq!(Balances::reserved_balance(ACCOUNT2.0), 0);
+		assert_eq!(Balances::free_balance(ACCOUNT2.0), ACCOUNT2.1 - price);
+		assert_eq!(Balances::free_balance(ACCOUNT1.0), seller_balance + price);
+	});
+}
+
+#[test]
+fn cancel_purchase_action_releases_the_buyer() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		assert_ok!(StorageHandler::territory_consignment(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), 50_000));
+		assert_ok!(StorageHandler::buy_consignment(RuntimeOrigin::signed(ACCOUNT2.0), minted.token, terr_name("bought")));
+		assert_noop!(
+			StorageHandler::cancel_consignment(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1")),
+			Error::<Test>::ConsignmentLocked
+		);
+		assert_noop!(
+			StorageHandler::cancel_purchase_action(RuntimeOrigin::signed(ACCOUNT1.0), minted.token),
+			Error::<Test>::NotBuyer
+		);
+
+		assert_ok!(StorageHandler::cancel_purchase_action(RuntimeOrigin::signed(ACCOUNT2.0), minted.token));
+		let consignment = Consignment::<Test>::get(minted.token).unwrap();
+		assert!(!consignment.locked);
+		assert_eq!(consignment.buyers, None);
+		assert_eq!(Balances::reserved_balance(ACCOUNT2.0), 0);
+
+		// the cancelled execution never runs
+		run_to_block(1 + 2 * LockingBlock::get());
+		assert_eq!(territory(ACCOUNT1.0, "t1").state, TerritoryState::OnConsignment);
+		assert!(!Territory::<Test>::contains_key(ACCOUNT2.0, terr_name("bought")));
+
+		assert_ok!(StorageHandler::cancel_consignment(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1")));
+		assert_eq!(territory(ACCOUNT1.0, "t1").state, TerritoryState::Active);
+		assert_eq!(Consignment::<Test>::get(minted.token), None);
+	});
+}
+
+#[test]
+fn consignment_checks() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		assert_ok!(StorageHandler::territory_consignment(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), 50_000));
+		assert_noop!(
+			StorageHandler::territory_consignment(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), 50_000),
+			Error::<Test>::NotActive
+		);
+		assert_noop!(
+			StorageHandler::buy_consignment(RuntimeOrigin::signed(ACCOUNT1.0), minted.token, terr_name("t2")),
+			Error::<Test>::OwnConsignment
+		);
+		assert_ok!(StorageHandler::buy_consignment(RuntimeOrigin::signed(ACCOUNT2.0), minted.token, terr_name("t1")));
+		assert_noop!(
+			StorageHandler::buy_consignment(RuntimeOrigin::signed(ACCOUNT3.0), minted.token, terr_name("t1")),
+			Error::<Test>::ConsignmentLocked
+		);
+
+		// only an empty territory can be sold
+		mint(ACCOUNT1.0, "t2", 10, 30);
+		assert_ok!(<StorageHandler as StorageHandle<AccountId>>::add_territory_used_space(
+			&ACCOUNT1.0,
+			&terr_name("t2"),
+			G_BYTE
+		));
+		assert_noop!(
+			StorageHandler::territory_consignment(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t2"), 50_000),
+			Error::<Test>::ObjectNotZero
+		);
+
+		// nor one whose lease ends within a day
+		let minted = mint(ACCOUNT1.0, "t3", 10, 30);
+		run_to_block(minted.deadline - ONE_DAY);
+		assert_noop!(
+			StorageHandler::territory_consignment(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t3"), 50_000),
+			Error::<Test>::InsufficientLease
+		);
+	});
+}
+
+#[test]
+fn consignment_is_dropped_when_territory_freezes() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		assert_ok!(StorageHandler::territory_consignment(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), 50_000));
+
+		run_to_block(minted.deadline);
+		assert_eq!(territory(ACCOUNT1.0, "t1").state, TerritoryState::Frozen);
+		assert_eq!(Consignment::<Test>::get(minted.token), None);
+	});
+}
+
+#[test]
+fn order_buys_territory_for_target() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(StorageHandler::create_order(
+			RuntimeOrigin::signed(ACCOUNT2.0),
+			ACCOUNT1.0,
+			terr_name("t1"),
+			OrderType::Buy,
+			5,
+			30,
+			10
+		));
+		let (order_id, order) = last_order();
+		assert_eq!(order.unit_price, UNIT_PRICE);
+		assert_eq!(order.expired, 1 + 10 * 6);
+
+		let payer_balance = Balances::free_balance(ACCOUNT2.0);
+		assert_ok!(StorageHandler::exec_order(RuntimeOrigin::signed(ACCOUNT2.0), order_id.clone()));
+		let bought = territory(ACCOUNT1.0, "t1");
+		assert_eq!(bought.total_space, 5 * G_BYTE);
+		assert_eq!(bought.deadline, 1 + 30 * ONE_DAY);
+		assert_eq!(Balances::free_balance(ACCOUNT2.0), payer_balance - order.pay);
+		assert_eq!(Balances::free_balance(ACCOUNT1.0), ACCOUNT1.1);
+
+		assert_noop!(
+			StorageHandler::exec_order(RuntimeOrigin::signed(ACCOUNT2.0), order_id),
+			Error::<Test>::PurchasedSpace
+		);
+	});
+}
+
+#[test]
+fn order_keeps_the_quoted_price() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		assert_ok!(StorageHandler::create_order(
+			RuntimeOrigin::signed(ACCOUNT2.0),
+			ACCOUNT1.0,
+			terr_name("t1"),
+			OrderType::Renewal,
+			0,
+			10,
+			10
+		));
+		let (order_id, order) = last_order();
+
+		assert_ok!(StorageHandler::set_price_bounds(RuntimeOrigin::root(), 2 * UNIT_PRICE, 3 * UNIT_PRICE));
+		assert_ne!(UnitPrice::<Test>::get(), Some(UNIT_PRICE));
+
+		let payer_balance = Balances::free_balance(ACCOUNT2.0);
+		assert_ok!(StorageHandler::exec_order(RuntimeOrigin::signed(ACCOUNT2.0), order_id));
+		assert_eq!(territory(ACCOUNT1.0, "t1").deadline, minted.deadline + 10 * ONE_DAY);
+		assert_eq!(Balances::free_balance(ACCOUNT2.0), payer_balance - order.pay);
+	});
+}
+
+#[test]
+fn expansion_order_is_invalid_once_remaining_days_change() {
+	new_test_ext().execute_with(|| {
+		mint(ACCOUNT1.0, "t1", 10, 30);
+		assert_ok!(StorageHandler::create_order(
+			RuntimeOrigin::signed(ACCOUNT2.0),
+			ACCOUNT1.0,
+			terr_name("t1"),
+			OrderType::Expansion,
+			5,
+			0,
+			15
+		));
+		let (order_id, _) = last_order();
+
+		run_to_block(1 + ONE_DAY + 1);
+		assert_noop!(
+			StorageHandler::exec_order(RuntimeOrigin::signed(ACCOUNT2.0), order_id),
+			Error::<Test>::InvalidOrder
+		);
+	});
+}
+
+#[test]
+fn order_expires() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			StorageHandler::create_order(
+				RuntimeOrigin::signed(ACCOUNT2.0),
+				ACCOUNT1.0,
+				terr_name("t1"),
+				OrderType::Buy,
+				5,
+				30,
+				100
+			),
+			Error::<Test>::ParamError
+		);
+		assert_ok!(StorageHandler::create_order(
+			RuntimeOrigin::signed(ACCOUNT2.0),
+			ACCOUNT1.0,
+			terr_name("t1"),
+			OrderType::Buy,
+			5,
+			30,
+			1
+		));
+		let (order_id, order) = last_order();
+
+		run_to_block(order.expired);
+		assert_noop!(
+			StorageHandler::exec_order(RuntimeOrigin::signed(ACCOUNT2.0), order_id),
+			Error::<Test>::OrderExpired
+		);
+	});
+}
+
+#[test]
+fn utilisation_price_stays_within_bounds() {
+	let bounds = PriceBounds { floor: 100u128, ceiling: 300u128 };
+	assert_eq!(pricing::utilisation_price(0, 1000, &bounds), 100);
+	assert_eq!(pricing::utilisation_price(250, 1000, &bounds), 150);
+	assert_eq!(pricing::utilisation_price(1000, 1000, &bounds), 300);
+	// purchased space outlives the idle space of exited miners
+	assert_eq!(pricing::utilisation_price(2000, 1000, &bounds), 300);
+	assert_eq!(pricing::utilisation_price(0, 0, &bounds), 300);
+}
+
+#[test]
+fn unit_price_follows_utilisation_each_era() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			StorageHandler::set_price_bounds(RuntimeOrigin::signed(ACCOUNT1.0), 1, 2),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			StorageHandler::set_price_bounds(RuntimeOrigin::root(), 2, 1),
+			Error::<Test>::InvalidPriceBounds
+		);
+
+		// the bounds apply right away
+		assert_ok!(StorageHandler::set_price_bounds(RuntimeOrigin::root(), UNIT_PRICE, 2 * UNIT_PRICE));
+		assert_eq!(UnitPrice::<Test>::get(), Some(UNIT_PRICE));
+
+		// a quarter of the space is purchased, which is priced at the next era
+		mint(ACCOUNT1.0, "t1", 250, 30);
+		run_to_block(PricingEra::get() - 1);
+		assert_eq!(UnitPrice::<Test>::get(), Some(UNIT_PRICE));
+		run_to_block(PricingEra::get());
+		assert_eq!(UnitPrice::<Test>::get(), Some(UNIT_PRICE + UNIT_PRICE / 4));
+		System::assert_last_event(
+			Event::<Test>::UnitPriceUpdated {
+				price: UNIT_PRICE + UNIT_PRICE / 4,
+				purchased: 250 * G_BYTE,
+				total: IDLE_SPACE,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn update_price_is_clamped_to_the_bounds() {
+	new_test_ext().execute_with(|| {
+		// without bounds the price is reset to 30 CESS
+		assert_ok!(StorageHandler::update_price(RuntimeOrigin::root()));
+		assert_eq!(UnitPrice::<Test>::get(), Some(30_000_000_000_000_000_000));
+
+		assert_ok!(StorageHandler::set_price_bounds(RuntimeOrigin::root(), UNIT_PRICE, 2 * UNIT_PRICE));
+		assert_eq!(UnitPrice::<Test>::get(), Some(UNIT_PRICE));
+		assert_ok!(StorageHandler::update_price(RuntimeOrigin::root()));
+		assert_eq!(UnitPrice::<Test>::get(), Some(2 * UNIT_PRICE));
+	});
+}
+
+#[test]
+fn dutch_price_descends_to_the_floor() {
+	assert_eq!(pricing::dutch_price(100u128, 40, 0, 60), 100);
+	assert_eq!(pricing::dutch_price(100u128, 40, 30, 60), 70);
+	assert_eq!(pricing::dutch_price(100u128, 40, 60, 60), 40);
+	assert_eq!(pricing::dutch_price(100u128, 40, 600, 60), 40);
+}
+
+#[test]
+fn dutch_consignment_locks_the_price_at_purchase() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		assert_noop!(
+			StorageHandler::territory_auction(
+				RuntimeOrigin::signed(ACCOUNT1.0),
+				terr_name("t1"),
+				100_000,
+				ConsignmentMode::Dutch { floor: 100_001, duration: 60 },
+			),
+			Error::<Test>::InvalidAuction
+		);
+		assert_ok!(StorageHandler::territory_auction(
+			RuntimeOrigin::signed(ACCOUNT1.0),
+			terr_name("t1"),
+			100_000,
+			ConsignmentMode::Dutch { floor: 40_000, duration: 60 },
+		));
+
+		run_to_block(31);
+		assert_ok!(StorageHandler::buy_consignment(RuntimeOrigin::signed(ACCOUNT2.0), minted.token, terr_name("bought")));
+		assert_eq!(Consignment::<Test>::get(minted.token).unwrap().deal_price, 70_000);
+		assert_eq!(Balances::reserved_balance(ACCOUNT2.0), 70_000);
+
+		// the price keeps descending, but not for the buyer
+		let seller_balance = Balances::free_balance(ACCOUNT1.0);
+		run_to_block(31 + LockingBlock::get());
+		assert_eq!(territory(ACCOUNT2.0, "bought").token, minted.token);
+		assert_eq!(Balances::free_balance(ACCOUNT2.0), ACCOUNT2.1 - 70_000);
+		assert_eq!(Balances::free_balance(ACCOUNT1.0), seller_balance + 70_000);
+	});
+}
+
+#[test]
+fn sealed_bid_consignment_is_won_by_the_highest_revealed_bid() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		let bidder: AccountId = 4;
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), bidder, 1_000_000));
+		let (salt1, salt2) = (H256::repeat_byte(1), H256::repeat_byte(2));
+		assert_ok!(StorageHandler::territory_auction(
+			RuntimeOrigin::signed(ACCOUNT1.0),
+			terr_name("t1"),
+			50_000,
+			ConsignmentMode::SealedBid { commit_period: 10, reveal_period: 10 },
+		));
+		assert_noop!(
+			StorageHandler::buy_consignment(RuntimeOrigin::signed(ACCOUNT2.0), minted.token, terr_name("bought")),
+			Error::<Test>::SealedBidConsignment
+		);
+		assert_noop!(
+			StorageHandler::place_sealed_bid(RuntimeOrigin::signed(ACCOUNT2.0), minted.token, seal(70_000, salt1), 40_000),
+			Error::<Test>::InvalidBid
+		);
+
+		assert_ok!(StorageHandler::place_sealed_bid(
+			RuntimeOrigin::signed(ACCOUNT2.0),
+			minted.token,
+			seal(70_000, salt1),
+			80_000
+		));
+		assert_ok!(StorageHandler::place_sealed_bid(RuntimeOrigin::signed(bidder), minted.token, seal(60_000, salt2), 100_000));
+		assert_eq!(Balances::reserved_balance(ACCOUNT2.0), 80_000);
+		assert_noop!(
+			StorageHandler::place_sealed_bid(RuntimeOrigin::signed(ACCOUNT2.0), minted.token, seal(70_000, salt1), 80_000),
+			Error::<Test>::DuplicateBid
+		);
+		assert_noop!(
+			StorageHandler::reveal_sealed_bid(
+				RuntimeOrigin::signed(ACCOUNT2.0),
+				minted.token,
+				70_000,
+				salt1,
+				terr_name("won")
+			),
+			Error::<Test>::NotRevealPeriod
+		);
+		// bidders have committed funds
+		assert_noop!(
+			StorageHandler::cancel_consignment(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1")),
+			Error::<Test>::ConsignmentLocked
+		);
+
+		run_to_block(11);
+		assert_noop!(
+			StorageHandler::place_sealed_bid(RuntimeOrigin::signed(ACCOUNT3.0), minted.token, seal(500, salt1), 500),
+			Error::<Test>::NotBiddingPeriod
+		);
+		assert_noop!(
+			StorageHandler::reveal_sealed_bid(
+				RuntimeOrigin::signed(ACCOUNT2.0),
+				minted.token,
+				70_000,
+				salt2,
+				terr_name("won")
+			),
+			Error::<Test>::InvalidBid
+		);
+		assert_ok!(StorageHandler::reveal_sealed_bid(
+			RuntimeOrigin::signed(ACCOUNT2.0),
+			minted.token,
+			70_000,
+			salt1,
+			terr_name("won")
+		));
+		assert_ok!(StorageHandler::reveal_sealed_bid(
+			RuntimeOrigin::signed(bidder),
+			minted.token,
+			60_000,
+			salt2,
+			terr_name("won")
+		));
+		assert_noop!(
+			StorageHandler::reveal_sealed_bid(
+				RuntimeOrigin::signed(bidder),
+				minted.token,
+				60_000,
+				salt2,
+				terr_name("won")
+			),
+			Error::<Test>::AlreadyRevealed
+		);
+
+		let seller_balance = Balances::free_balance(ACCOUNT1.0);
+		run_to_block(21);
+		let name = terr_name("won");
+		assert!(!Territory::<Test>::contains_key(ACCOUNT1.0, terr_name("t1")));
+		assert_eq!(Territory::<Test>::get(ACCOUNT2.0, &name).unwrap().state, TerritoryState::Active);
+		assert_eq!(TerritoryKey::<Test>::get(minted.token), Some((ACCOUNT2.0, name)));
+		assert_eq!(Consignment::<Test>::get(minted.token), None);
+		assert!(SealedBids::<Test>::get(minted.token).is_empty());
+		assert_eq!(Balances::free_balance(ACCOUNT1.0), seller_balance + 70_000);
+		assert_eq!(Balances::free_balance(ACCOUNT2.0), ACCOUNT2.1 - 70_000);
+		assert_eq!(Balances::reserved_balance(ACCOUNT2.0), 0);
+		assert_eq!(Balances::free_balance(bidder), 1_000_000);
+	});
+}
+
+#[test]
+fn unpaid_sealed_bid_falls_back_to_the_next_bid() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		let (bidder, other): (AccountId, AccountId) = (4, 5);
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), bidder, 1_000_000));
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), other, 1_000_000));
+		assert_ok!(StorageHandler::territory_auction(
+			RuntimeOrigin::signed(ACCOUNT1.0),
+			terr_name("t1"),
+			50_000,
+			ConsignmentMode::SealedBid { commit_period: 10, reveal_period: 10 },
+		));
+		for (account, bid) in [(ACCOUNT2.0, 90_000), (other, 80_000), (bidder, 60_000)] {
+			assert_ok!(StorageHandler::place_sealed_bid(
+				RuntimeOrigin::signed(account),
+				minted.token,
+				seal(bid, H256::zero()),
+				bid
+			));
+		}
+		run_to_block(11);
+		for (account, bid) in [(ACCOUNT2.0, 90_000), (other, 80_000), (bidder, 60_000)] {
+			assert_ok!(StorageHandler::reveal_sealed_bid(
+				RuntimeOrigin::signed(account),
+				minted.token,
+				bid,
+				H256::zero(),
+				terr_name("won")
+			));
+		}
+		// the highest bidder loses its deposit, the next one has taken the name for a territory of its own
+		let _ = Balances::slash_reserved(&ACCOUNT2.0, 90_000);
+		mint(other, "won", 1, 30);
+
+		let seller_balance = Balances::free_balance(ACCOUNT1.0);
+		run_to_block(21);
+		assert_eq!(TerritoryKey::<Test>::get(minted.token), Some((bidder, terr_name("won"))));
+		assert_eq!(Balances::free_balance(ACCOUNT1.0), seller_balance + 60_000);
+		assert_eq!(Balances::reserved_balance(other), 0);
+		assert_eq!(Balances::reserved_balance(bidder), 0);
+		assert_eq!(Balances::free_balance(bidder), 1_000_000 - 60_000);
+		System::assert_has_event(
+			Event::<Test>::SealedBidPassedOver { token: minted.token, bidder: ACCOUNT2.0 }.into(),
+		);
+		System::assert_has_event(
+			Event::<Test>::SealedBidPassedOver { token: minted.token, bidder: other }.into(),
+		);
+	});
+}
+
+#[test]
+fn unrevealed_sealed_bid_consignment_returns_to_the_seller() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		assert_ok!(StorageHandler::territory_auction(
+			RuntimeOrigin::signed(ACCOUNT1.0),
+			terr_name("t1"),
+			50_000,
+			ConsignmentMode::SealedBid { commit_period: 10, reveal_period: 10 },
+		));
+		assert_ok!(StorageHandler::place_sealed_bid(
+			RuntimeOrigin::signed(ACCOUNT2.0),
+			minted.token,
+			seal(70_000, H256::zero()),
+			80_000
+		));
+
+		run_to_block(21);
+		assert_eq!(territory(ACCOUNT1.0, "t1").state, TerritoryState::Active);
+		assert_eq!(Consignment::<Test>::get(minted.token), None);
+		assert_eq!(Balances::free_balance(ACCOUNT2.0), ACCOUNT2.1);
+		System::assert_has_event(Event::<Test>::SealedConsignmentUnsold { token: minted.token }.into());
+	});
+}
+
+#[test]
+fn sealed_bid_consignment_checks() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		// the auction has to be settled before the territory freezes
+		assert_noop!(
+			StorageHandler::territory_auction(
+				RuntimeOrigin::signed(ACCOUNT1.0),
+				terr_name("t1"),
+				50_000,
+				ConsignmentMode::SealedBid { commit_period: 15 * ONE_DAY, reveal_period: 15 * ONE_DAY },
+			),
+			Error::<Test>::InsufficientLease
+		);
+		assert_noop!(
+			StorageHandler::territory_auction(
+				RuntimeOrigin::signed(ACCOUNT1.0),
+				terr_name("t1"),
+				50_000,
+				ConsignmentMode::SealedBid { commit_period: 0, reveal_period: 10 },
+			),
+			Error::<Test>::InvalidAuction
+		);
+		assert_ok!(StorageHandler::territory_auction(
+			RuntimeOrigin::signed(ACCOUNT1.0),
+			terr_name("t1"),
+			50_000,
+			ConsignmentMode::SealedBid { commit_period: 10, reveal_period: 10 },
+		));
+		assert_noop!(
+			StorageHandler::place_sealed_bid(RuntimeOrigin::signed(ACCOUNT1.0), minted.token, H256::zero(), 50_000),
+			Error::<Test>::OwnConsignment
+		);
+
+		// without bids the seller may withdraw, which drops the settlement
+		assert_ok!(StorageHandler::cancel_consignment(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1")));
+		run_to_block(21);
+		assert_eq!(territory(ACCOUNT1.0, "t1").state, TerritoryState::Active);
+	});
+}
+
+#[test]
+fn split_consignment_sells_unused_space() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		assert_ok!(<StorageHandler as StorageHandle<AccountId>>::add_territory_used_space(
+			&ACCOUNT1.0,
+			&terr_name("t1"),
+			2 * G_BYTE
+		));
+		assert_noop!(
+			StorageHandler::split_territory_consignment(
+				RuntimeOrigin::signed(ACCOUNT1.0),
+				terr_name("t1"),
+				9,
+				terr_name("t2"),
+				50_000,
+				ConsignmentMode::Fixed,
+			),
+			Error::<Test>::InsufficientStorage
+		);
+		assert_ok!(StorageHandler::split_territory_consignment(
+			RuntimeOrigin::signed(ACCOUNT1.0),
+			terr_name("t1"),
+			5,
+			terr_name("t2"),
+			50_000,
+			ConsignmentMode::Fixed,
+		));
+
+		let kept = territory(ACCOUNT1.0, "t1");
+		assert_eq!(kept.total_space, 5 * G_BYTE);
+		assert_eq!(kept.used_space, 2 * G_BYTE);
+		assert_eq!(kept.remaining_space, 3 * G_BYTE);
+		let split = territory(ACCOUNT1.0, "t2");
+		assert_ne!(split.token, minted.token);
+		assert_eq!(split.state, TerritoryState::OnConsignment);
+		assert_eq!(split.total_space, 5 * G_BYTE);
+		assert_eq!(split.deadline, minted.deadline);
+		// the space changes hands without being purchased again
+		assert_eq!(PurchasedSpace::<Test>::get(), 10 * G_BYTE);
+
+		assert_ok!(StorageHandler::buy_consignment(RuntimeOrigin::signed(ACCOUNT2.0), split.token, terr_name("bought")));
+		run_to_block(1 + LockingBlock::get());
+		assert_eq!(territory(ACCOUNT2.0, "bought").total_space, 5 * G_BYTE);
+		assert_eq!(territory(ACCOUNT1.0, "t1").state, TerritoryState::Active);
+	});
+}
+
+#[test]
+fn territory_is_renewed_ahead_of_its_deadline() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		let spend = DAY_PRICE * 10 * 30;
+		assert_ok!(StorageHandler::set_auto_renewal(
+			RuntimeOrigin::signed(ACCOUNT2.0),
+			ACCOUNT1.0,
+			terr_name("t1"),
+			spend,
+			30
+		));
+		let renewal_block = minted.deadline - ONE_DAY;
+		assert_eq!(AutoRenewals::<Test>::get(minted.token).unwrap().next, renewal_block);
+
+		run_to_block(renewal_block - 1);
+		assert_eq!(territory(ACCOUNT1.0, "t1").deadline, minted.deadline);
+		run_to_block(renewal_block);
+		assert_eq!(territory(ACCOUNT1.0, "t1").deadline, minted.deadline + 30 * ONE_DAY);
+		assert_eq!(Balances::free_balance(ACCOUNT2.0), ACCOUNT2.1 - spend);
+		assert_eq!(Balances::free_balance(ACCOUNT1.0), ACCOUNT1.1 - spend);
+		System::assert_has_event(
+			Event::<Test>::AutoRenewed { token: minted.token, funder: ACCOUNT2.0, days: 30, spend }.into(),
+		);
+
+		// and again for the new deadline
+		assert_eq!(AutoRenewals::<Test>::get(minted.token).unwrap().next, renewal_block + 30 * ONE_DAY);
+		run_to_block(renewal_block + 30 * ONE_DAY);
+		assert_eq!(territory(ACCOUNT1.0, "t1").deadline, minted.deadline + 60 * ONE_DAY);
+		assert_eq!(territory(ACCOUNT1.0, "t1").state, TerritoryState::Active);
+	});
+}
+
+#[test]
+fn failed_auto_renewal_is_retried_until_expiry() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		let spend = DAY_PRICE * 10 * 30;
+		assert_ok!(StorageHandler::set_auto_renewal(
+			RuntimeOrigin::signed(ACCOUNT3.0),
+			ACCOUNT1.0,
+			terr_name("t1"),
+			spend,
+			30
+		));
+
+		let renewal_block = minted.deadline - ONE_DAY;
+		run_to_block(renewal_block);
+		System::assert_has_event(
+			Event::<Test>::AutoRenewalFailed {
+				token: minted.token,
+				funder: ACCOUNT3.0,
+				price: spend,
+				reason: RenewalFailure::InsufficientBalance,
+			}
+			.into(),
+		);
+		assert_eq!(AutoRenewals::<Test>::get(minted.token).unwrap().next, minted.deadline);
+		assert_eq!(Balances::free_balance(ACCOUNT3.0), ACCOUNT3.1);
+
+		run_to_block(minted.deadline + 1);
+		assert_eq!(territory(ACCOUNT1.0, "t1").state, TerritoryState::Frozen);
+
+		// the owner takes over the subscription, and the next attempt renews the frozen territory
+		assert_ok!(StorageHandler::set_auto_renewal(
+			RuntimeOrigin::signed(ACCOUNT1.0),
+			ACCOUNT1.0,
+			terr_name("t1"),
+			spend,
+			30
+		));
+		run_to_block(minted.deadline + ONE_DAY);
+		let renewed = territory(ACCOUNT1.0, "t1");
+		assert_eq!(renewed.state, TerritoryState::Active);
+		assert_eq!(renewed.deadline, minted.deadline + ONE_DAY + 30 * ONE_DAY);
+		assert_eq!(Balances::free_balance(ACCOUNT1.0), ACCOUNT1.1 - 2 * spend);
+	});
+}
+
+#[test]
+fn auto_renewal_ends_with_the_territory() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		// less than the price of a renewal
+		assert_ok!(StorageHandler::set_auto_renewal(
+			RuntimeOrigin::signed(ACCOUNT2.0),
+			ACCOUNT1.0,
+			terr_name("t1"),
+			DAY_PRICE,
+			30
+		));
+
+		run_to_block(minted.deadline - ONE_DAY);
+		System::assert_has_event(
+			Event::<Test>::AutoRenewalFailed {
+				token: minted.token,
+				funder: ACCOUNT2.0,
+				price: DAY_PRICE * 10 * 30,
+				reason: RenewalFailure::PriceExceeded,
+			}
+			.into(),
+		);
+
+		let expired_block = minted.deadline + FrozenDays::get();
+		run_to_block(expired_block + ONE_DAY);
+		assert_eq!(territory(ACCOUNT1.0, "t1").state, TerritoryState::Expired);
+		assert_eq!(AutoRenewals::<Test>::get(minted.token), None);
+		System::assert_has_event(Event::<Test>::AutoRenewalCancelled { token: minted.token }.into());
+		assert_eq!(Balances::free_balance(ACCOUNT2.0), ACCOUNT2.1);
+	});
+}
+
+#[test]
+fn auto_renewal_follows_manual_renewals() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		let spend = DAY_PRICE * 10 * 30;
+		assert_ok!(StorageHandler::set_auto_renewal(
+			RuntimeOrigin::signed(ACCOUNT2.0),
+			ACCOUNT1.0,
+			terr_name("t1"),
+			spend,
+			30
+		));
+		assert_ok!(StorageHandler::renewal_territory(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), 30));
+
+		run_to_block(minted.deadline - ONE_DAY);
+		assert_eq!(territory(ACCOUNT1.0, "t1").deadline, minted.deadline + 30 * ONE_DAY);
+		assert_eq!(Balances::free_balance(ACCOUNT2.0), ACCOUNT2.1);
+		assert_eq!(AutoRenewals::<Test>::get(minted.token).unwrap().next, minted.deadline + 29 * ONE_DAY);
+	});
+}
+
+#[test]
+fn granted_territory_drops_the_auto_renewal() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		assert_ok!(StorageHandler::set_auto_renewal(
+			RuntimeOrigin::signed(ACCOUNT2.0),
+			ACCOUNT1.0,
+			terr_name("t1"),
+			DAY_PRICE * 10 * 30,
+			30
+		));
+		let renewal_block = AutoRenewals::<Test>::get(minted.token).unwrap().next;
+
+		// the funder does not go on paying for the territory of the receiver
+		assert_ok!(StorageHandler::territory_grants(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), ACCOUNT3.0));
+		assert_eq!(AutoRenewals::<Test>::get(minted.token), None);
+		assert!(!AutoRenewalQueue::<Test>::contains_key(renewal_block));
+		run_to_block(renewal_block);
+		assert_eq!(Balances::free_balance(ACCOUNT2.0), ACCOUNT2.1);
+	});
+}
+
+#[test]
+fn auto_renewal_checks() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		assert_noop!(
+			StorageHandler::set_auto_renewal(RuntimeOrigin::signed(ACCOUNT2.0), ACCOUNT1.0, terr_name("t1"), 1, 0),
+			Error::<Test>::ParamError
+		);
+		assert_noop!(
+			StorageHandler::set_auto_renewal(RuntimeOrigin::signed(ACCOUNT2.0), ACCOUNT1.0, terr_name("t2"), 1, 30),
+			Error::<Test>::NotHaveTerritory
+		);
+		assert_ok!(StorageHandler::set_auto_renewal(
+			RuntimeOrigin::signed(ACCOUNT2.0),
+			ACCOUNT1.0,
+			terr_name("t1"),
+			DAY_PRICE * 10 * 30,
+			30
+		));
+		assert_noop!(
+			StorageHandler::set_auto_renewal(RuntimeOrigin::signed(ACCOUNT3.0), ACCOUNT1.0, terr_name("t1"), 1, 30),
+			Error::<Test>::AutoRenewalExists
+		);
+		assert_noop!(
+			StorageHandler::cancel_auto_renewal(RuntimeOrigin::signed(ACCOUNT3.0), minted.token),
+			Error::<Test>::NotRenewalParty
+		);
+
+		let renewal_block = AutoRenewals::<Test>::get(minted.token).unwrap().next;
+		assert_eq!(AutoRenewalQueue::<Test>::get(renewal_block).to_vec(), vec![minted.token]);
+		assert_ok!(StorageHandler::cancel_auto_renewal(RuntimeOrigin::signed(ACCOUNT1.0), minted.token));
+		// the queued attempt goes with the subscription
+		assert!(!AutoRenewalQueue::<Test>::contains_key(renewal_block));
+		assert_noop!(
+			StorageHandler::cancel_auto_renewal(RuntimeOrigin::signed(ACCOUNT1.0), minted.token),
+			Error::<Test>::NoAutoRenewal
+		);
+		run_to_block(minted.deadline - ONE_DAY);
+		assert_eq!(territory(ACCOUNT1.0, "t1").deadline, minted.deadline);
+		assert_eq!(Balances::free_balance(ACCOUNT2.0), ACCOUNT2.1);
+	});
+}
+
+#[test]
+fn member_writes_under_its_quota() {
+	new_test_ext().execute_with(|| {
+		mint(ACCOUNT1.0, "t1", 10, 30);
+		assert_ok!(StorageHandler::add_territory_member(
+			RuntimeOrigin::signed(ACCOUNT1.0),
+			terr_name("t1"),
+			ACCOUNT2.0,
+			3 * G_BYTE
+		));
+		assert_ok!(StorageHandler::check_territry_owner(&ACCOUNT2.0, &terr_name("t1")));
+		assert_noop!(StorageHandler::check_territry_owner(&ACCOUNT3.0, &terr_name("t1")), Error::<Test>::NotHaveTerritory);
+		assert_eq!(StorageHandler::get_user_avail_space(&ACCOUNT2.0, &terr_name("t1")), Ok(3 * G_BYTE));
+
+		// a deal in progress counts against the quota
+		assert_ok!(StorageHandler::lock_user_space(&ACCOUNT2.0, &terr_name("t1"), 2 * G_BYTE));
+		assert_eq!(StorageHandler::get_user_avail_space(&ACCOUNT2.0, &terr_name("t1")), Ok(G_BYTE));
+		assert_eq!(territory(ACCOUNT1.0, "t1").locked_space, 2 * G_BYTE);
+		assert_noop!(
+			StorageHandler::add_territory_used_space(&ACCOUNT2.0, &terr_name("t1"), 2 * G_BYTE),
+			Error::<Test>::QuotaExceeded
+		);
+
+		assert_ok!(StorageHandler::unlock_and_used_user_space(&ACCOUNT2.0, &terr_name("t1"), 2 * G_BYTE));
+		assert_ok!(StorageHandler::sub_territory_used_space(&ACCOUNT2.0, &terr_name("t1"), G_BYTE));
+		let m = member(ACCOUNT2.0, "t1");
+		assert_eq!((m.used, m.locked), (G_BYTE, 0));
+		assert_eq!(territory(ACCOUNT1.0, "t1").used_space, G_BYTE);
+
+		// the owner is bound by the territory alone
+		assert_ok!(StorageHandler::add_territory_used_space(&ACCOUNT1.0, &terr_name("t1"), 8 * G_BYTE));
+		assert_eq!(StorageHandler::get_user_avail_space(&ACCOUNT2.0, &terr_name("t1")), Ok(G_BYTE));
+		assert_noop!(
+			StorageHandler::add_territory_used_space(&ACCOUNT2.0, &terr_name("t1"), 2 * G_BYTE),
+			Error::<Test>::InsufficientStorage
+		);
+		assert_ok!(StorageHandler::sub_territory_used_space(&ACCOUNT1.0, &terr_name("t1"), G_BYTE));
+		assert_ok!(StorageHandler::add_territory_used_space(&ACCOUNT2.0, &terr_name("t1"), 2 * G_BYTE));
+		assert_eq!(territory(ACCOUNT1.0, "t1").remaining_space, 0);
+	});
+}
+
+#[test]
+fn territory_member_checks() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		mint(ACCOUNT2.0, "t1", 1, 30);
+		assert_noop!(
+			StorageHandler::add_territory_member(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), ACCOUNT1.0, 1),
+			Error::<Test>::ParamError
+		);
+		assert_noop!(
+			StorageHandler::add_territory_member(RuntimeOrigin::signed(ACCOUNT3.0), terr_name("t1"), 4, 1),
+			Error::<Test>::NotHaveTerritory
+		);
+		assert_noop!(
+			StorageHandler::add_territory_member(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), ACCOUNT2.0, 1),
+			Error::<Test>::TerritoryNameTaken
+		);
+		assert_noop!(
+			StorageHandler::revoke_territory_member(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), ACCOUNT3.0),
+			Error::<Test>::NotTerritoryMember
+		);
+
+		for acc in 3..6 {
+			assert_ok!(StorageHandler::add_territory_member(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), acc, 1));
+		}
+		assert_noop!(
+			StorageHandler::add_territory_member(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), 6, 1),
+			Error::<Test>::TooManyMembers
+		);
+		// changing a quota takes no new place
+		assert_ok!(StorageHandler::add_territory_member(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), 3, 2));
+		assert_eq!(member(3, "t1").quota, 2);
+		System::assert_last_event(Event::<Test>::TerritoryMemberSet { token: minted.token, member: 3, quota: 2 }.into());
+
+		// the name is taken for the member
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 3, ACCOUNT1.1));
+		assert_noop!(
+			StorageHandler::mint_territory(RuntimeOrigin::signed(3), 1, terr_name("t1"), 30),
+			Error::<Test>::TerritoryNameTaken
+		);
+	});
+}
+
+#[test]
+fn consignment_is_not_bought_under_a_shared_name() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		mint(ACCOUNT1.0, "shared", 1, 30);
+		assert_ok!(StorageHandler::add_territory_member(
+			RuntimeOrigin::signed(ACCOUNT1.0),
+			terr_name("shared"),
+			ACCOUNT2.0,
+			G_BYTE
+		));
+		assert_ok!(StorageHandler::territory_consignment(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), 50_000));
+		assert_noop!(
+			StorageHandler::buy_consignment(RuntimeOrigin::signed(ACCOUNT2.0), minted.token, terr_name("shared")),
+			Error::<Test>::TerritoryNameTaken
+		);
+
+		// the name is shared with the buyer before the purchase is executed
+		assert_ok!(StorageHandler::buy_consignment(RuntimeOrigin::signed(ACCOUNT2.0), minted.token, terr_name("bought")));
+		mint(ACCOUNT1.0, "bought", 1, 30);
+		assert_ok!(StorageHandler::add_territory_member(
+			RuntimeOrigin::signed(ACCOUNT1.0),
+			terr_name("bought"),
+			ACCOUNT2.0,
+			G_BYTE
+		));
+		assert_noop!(
+			StorageHandler::exec_consignment(RuntimeOrigin::root(), minted.token, terr_name("bought")),
+			Error::<Test>::TerritoryNameTaken
+		);
+	});
+}
+
+#[test]
+fn revoked_member_keeps_its_data_until_deleted() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		assert_ok!(StorageHandler::add_territory_member(
+			RuntimeOrigin::signed(ACCOUNT1.0),
+			terr_name("t1"),
+			ACCOUNT2.0,
+			3 * G_BYTE
+		));
+		assert_ok!(StorageHandler::add_territory_used_space(&ACCOUNT2.0, &terr_name("t1"), G_BYTE));
+
+		assert_ok!(StorageHandler::revoke_territory_member(
+			RuntimeOrigin::signed(ACCOUNT1.0),
+			terr_name("t1"),
+			ACCOUNT2.0
+		));
+		System::assert_last_event(Event::<Test>::TerritoryMemberRevoked { token: minted.token, member: ACCOUNT2.0 }.into());
+		assert_eq!(member(ACCOUNT2.0, "t1").quota, 0);
+		assert_noop!(
+			StorageHandler::add_territory_used_space(&ACCOUNT2.0, &terr_name("t1"), 1),
+			Error::<Test>::QuotaExceeded
+		);
+
+		assert_ok!(StorageHandler::sub_territory_used_space(&ACCOUNT2.0, &terr_name("t1"), G_BYTE));
+		assert_eq!(MemberTerritory::<Test>::get(ACCOUNT2.0, terr_name("t1")), None);
+		assert!(TerritoryMembers::<Test>::get(minted.token).is_empty());
+		assert_eq!(territory(ACCOUNT1.0, "t1").used_space, 0);
+	});
+}
+
+#[test]
+fn members_go_with_the_territory() {
+	new_test_ext().execute_with(|| {
+		let minted = mint(ACCOUNT1.0, "t1", 10, 30);
+		assert_ok!(StorageHandler::add_territory_member(
+			RuntimeOrigin::signed(ACCOUNT1.0),
+			terr_name("t1"),
+			ACCOUNT2.0,
+			3 * G_BYTE
+		));
+		assert_ok!(StorageHandler::add_territory_used_space(&ACCOUNT2.0, &terr_name("t1"), G_BYTE));
+
+		// the data of the member is cleared along with the territory
+		let expired_block = minted.deadline + FrozenDays::get();
+		run_to_block(expired_block - 1);
+		System::set_block_number(expired_block);
+		let (_, clear_list) = StorageHandler::frozen_task();
+		assert_eq!(clear_list, vec![(ACCOUNT1.0, terr_name("t1")), (ACCOUNT2.0, terr_name("t1"))]);
+		assert!(StorageHandler::check_expired(&ACCOUNT2.0, &terr_name("t1")));
+
+		// and the member starts afresh with a reactivated one
+		assert_ok!(StorageHandler::reactivate_territory(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), 30));
+		assert_eq!(member(ACCOUNT2.0, "t1").used, 0);
+		assert!(!StorageHandler::check_expired(&ACCOUNT2.0, &terr_name("t1")));
+
+		// but not with a new owner
+		assert_ok!(StorageHandler::territory_grants(RuntimeOrigin::signed(ACCOUNT1.0), terr_name("t1"), ACCOUNT3.0));
+		assert_eq!(MemberTerritory::<Test>::get(ACCOUNT2.0, terr_name("t1")), None);
+		assert!(TerritoryMembers::<Test>::get(minted.token).is_empty());
+	});
+}
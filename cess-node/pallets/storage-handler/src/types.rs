@@ -11,6 +11,8 @@ pub struct OrderInfo<T: Config> {
 	pub(super) expired: BlockNumberFor<T>,	
 	pub(super) target_acc: AccountOf<T>,
 	pub(super) order_type: OrderType,
+	// The unit price the order was quoted at
+	pub(super) unit_price: BalanceOf<T>,
 }
 
 #[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
@@ -43,6 +45,68 @@ pub struct ConsignmentInfo<T: Config> {
     pub(super) buyers: Option<AccountOf<T>>,
     pub(super) exec: Option<BlockNumberFor<T>>,
 	pub(super) locked: bool,
+	pub(super) mode: ConsignmentMode<BalanceOf<T>, BlockNumberFor<T>>,
+	pub(super) start: BlockNumberFor<T>,
+	// The price the current buyer locked in, which is what they pay on execution
+	pub(super) deal_price: BalanceOf<T>,
+}
+
+/// How a consignment is sold, relative to its `price` and the block it was created in.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum ConsignmentMode<Balance, BlockNumber> {
+	/// Sold to the first buyer at `price`.
+	Fixed,
+	/// Sold to the first buyer at a price descending linearly from `price` to `floor` over `duration` blocks.
+	Dutch { floor: Balance, duration: BlockNumber },
+	/// Bids are committed for `commit_period` blocks and revealed for `reveal_period` blocks, after which the
+	/// highest bid of at least `price` wins.
+	SealedBid { commit_period: BlockNumber, reveal_period: BlockNumber },
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct SealedBid<T: Config> {
+	pub(super) bidder: AccountOf<T>,
+	// blake2_256 of the SCALE encoded `(bid, salt)`
+	pub(super) commitment: H256,
+	pub(super) deposit: BalanceOf<T>,
+	pub(super) revealed: Option<BalanceOf<T>>,
+	// The name the bidder takes the territory by, given along with the revealed bid
+	pub(super) name: Option<TerrName>,
+}
+
+/// An account writing into a territory of another account, up to `quota` bytes.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct TerritoryMember<T: Config> {
+	pub(super) account: AccountOf<T>,
+	// The name the member refers to the territory by
+	pub(super) name: TerrName,
+	pub(super) quota: u128,
+	pub(super) used: u128,
+	pub(super) locked: u128,
+}
+
+/// A standing order to renew a territory ahead of its deadline, paid for by `funder`.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct AutoRenewal<T: Config> {
+	pub(super) funder: AccountOf<T>,
+	pub(super) max_price: BalanceOf<T>,
+	pub(super) days: u32,
+	// The block of the pending attempt, entries of other blocks in `AutoRenewalQueue` are stale
+	pub(super) next: BlockNumberFor<T>,
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum RenewalFailure {
+	/// The renewal costs more than the subscription allows.
+	PriceExceeded,
+	/// The funding account cannot pay for the renewal.
+	InsufficientBalance,
 }
 
 #[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
@@ -51,4 +115,10 @@ pub enum TerritoryState {
 	Frozen,
 	Expired,
 	OnConsignment,
-}
\ No newline at end of file
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct PriceBounds<Balance> {
+	pub floor: Balance,
+	pub ceiling: Balance,
+}
@@ -49,6 +49,16 @@ pub trait WeightInfo {
 	fn create_order() -> Weight;
 	fn exec_order() -> Weight;
 	fn reactivate_territory() -> Weight;
+	fn set_price_bounds() -> Weight;
+	fn territory_auction() -> Weight;
+	fn split_territory_consignment() -> Weight;
+	fn place_sealed_bid() -> Weight;
+	fn reveal_sealed_bid() -> Weight;
+	fn exec_sealed_consignment(b: u32, ) -> Weight;
+	fn set_auto_renewal() -> Weight;
+	fn cancel_auto_renewal() -> Weight;
+	fn add_territory_member() -> Weight;
+	fn revoke_territory_member() -> Weight;
 }
 
 /// Weights for `pallet_storage_handler` using the Substrate node and recommended hardware.
@@ -161,14 +171,18 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `StorageHandler::TerritoryKey` (`max_values`: None, `max_size`: Some(144), added: 2619, mode: `MaxEncodedLen`)
 	/// Storage: `StorageHandler::Territory` (r:1 w:2)
 	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::AutoRenewals` (r:1 w:1)
+	/// Proof: `StorageHandler::AutoRenewals` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::AutoRenewalQueue` (r:1 w:1)
+	/// Proof: `StorageHandler::AutoRenewalQueue` (`max_values`: None, `max_size`: Some(64022), added: 66497, mode: `MaxEncodedLen`)
 	fn exec_consignment() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1011`
-		//  Estimated: `6196`
+		//  Estimated: `67487`
 		// Minimum execution time: 63_599_000 picoseconds.
-		Weight::from_parts(66_427_000, 6196)
-			.saturating_add(T::DbWeight::get().reads(5_u64))
-			.saturating_add(T::DbWeight::get().writes(6_u64))
+		Weight::from_parts(66_427_000, 67487)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
 	}
 	/// Storage: `StorageHandler::Territory` (r:1 w:1)
 	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
@@ -202,14 +216,18 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
 	/// Storage: `StorageHandler::TerritoryKey` (r:1 w:1)
 	/// Proof: `StorageHandler::TerritoryKey` (`max_values`: None, `max_size`: Some(144), added: 2619, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::AutoRenewals` (r:1 w:1)
+	/// Proof: `StorageHandler::AutoRenewals` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::AutoRenewalQueue` (r:1 w:1)
+	/// Proof: `StorageHandler::AutoRenewalQueue` (`max_values`: None, `max_size`: Some(64022), added: 66497, mode: `MaxEncodedLen`)
 	fn territory_grants() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `604`
-		//  Estimated: `3698`
+		//  Estimated: `67487`
 		// Minimum execution time: 17_520_000 picoseconds.
-		Weight::from_parts(18_657_000, 3698)
-			.saturating_add(T::DbWeight::get().reads(2_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+		Weight::from_parts(18_657_000, 67487)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: `StorageHandler::Territory` (r:1 w:2)
 	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
@@ -295,6 +313,180 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(8_u64))
 			.saturating_add(T::DbWeight::get().writes(6_u64))
 	}
+	/// Storage: `StorageHandler::UnitPriceBounds` (r:1 w:1)
+	/// Proof: `StorageHandler::UnitPriceBounds` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::PurchasedSpace` (r:1 w:0)
+	/// Proof: `StorageHandler::PurchasedSpace` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TotalIdleSpace` (r:1 w:0)
+	/// Proof: `StorageHandler::TotalIdleSpace` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TotalServiceSpace` (r:1 w:0)
+	/// Proof: `StorageHandler::TotalServiceSpace` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::UnitPrice` (r:1 w:1)
+	/// Proof: `StorageHandler::UnitPrice` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	fn set_price_bounds() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `180`
+		//  Estimated: `1517`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(12_500_000, 1517)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `StorageHandler::Territory` (r:1 w:1)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::Consignment` (r:1 w:1)
+	/// Proof: `StorageHandler::Consignment` (`max_values`: None, `max_size`: Some(193), added: 2668, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::Lookup` (r:1 w:1)
+	/// Proof: `Scheduler::Lookup` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::Agenda` (r:1 w:1)
+	/// Proof: `Scheduler::Agenda` (`max_values`: None, `max_size`: Some(38963), added: 41438, mode: `MaxEncodedLen`)
+	fn territory_auction() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `612`
+		//  Estimated: `42428`
+		// Minimum execution time: 31_204_000 picoseconds.
+		Weight::from_parts(32_118_000, 42428)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `StorageHandler::Territory` (r:2 w:2)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `Babe::AuthorVrfRandomness` (r:1 w:0)
+	/// Proof: `Babe::AuthorVrfRandomness` (`max_values`: Some(1), `max_size`: Some(33), added: 528, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TerritoryKey` (r:1 w:1)
+	/// Proof: `StorageHandler::TerritoryKey` (`max_values`: None, `max_size`: Some(144), added: 2619, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TerritoryFrozenCounter` (r:1 w:1)
+	/// Proof: `StorageHandler::TerritoryFrozenCounter` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::Consignment` (r:1 w:1)
+	/// Proof: `StorageHandler::Consignment` (`max_values`: None, `max_size`: Some(193), added: 2668, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::Lookup` (r:1 w:1)
+	/// Proof: `Scheduler::Lookup` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::Agenda` (r:1 w:1)
+	/// Proof: `Scheduler::Agenda` (`max_values`: None, `max_size`: Some(38963), added: 41438, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TerritoryFrozen` (r:0 w:1)
+	/// Proof: `StorageHandler::TerritoryFrozen` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	fn split_territory_consignment() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `688`
+		//  Estimated: `42428`
+		// Minimum execution time: 52_877_000 picoseconds.
+		Weight::from_parts(54_306_000, 42428)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: `StorageHandler::Consignment` (r:1 w:0)
+	/// Proof: `StorageHandler::Consignment` (`max_values`: None, `max_size`: Some(193), added: 2668, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::SealedBids` (r:1 w:1)
+	/// Proof: `StorageHandler::SealedBids` (`max_values`: None, `max_size`: Some(13451), added: 15926, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn place_sealed_bid() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1340`
+		//  Estimated: `16916`
+		// Minimum execution time: 38_512_000 picoseconds.
+		Weight::from_parts(39_870_000, 16916)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `StorageHandler::Consignment` (r:1 w:0)
+	/// Proof: `StorageHandler::Consignment` (`max_values`: None, `max_size`: Some(193), added: 2668, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::SealedBids` (r:1 w:1)
+	/// Proof: `StorageHandler::SealedBids` (`max_values`: None, `max_size`: Some(13451), added: 15926, mode: `MaxEncodedLen`)
+	fn reveal_sealed_bid() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1412`
+		//  Estimated: `16916`
+		// Minimum execution time: 24_103_000 picoseconds.
+		Weight::from_parts(25_006_000, 16916)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `StorageHandler::Consignment` (r:1 w:1)
+	/// Proof: `StorageHandler::Consignment` (`max_values`: None, `max_size`: Some(193), added: 2668, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TerritoryKey` (r:1 w:1)
+	/// Proof: `StorageHandler::TerritoryKey` (`max_values`: None, `max_size`: Some(144), added: 2619, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::Territory` (r:101 w:2)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::SealedBids` (r:1 w:1)
+	/// Proof: `StorageHandler::SealedBids` (`max_values`: None, `max_size`: Some(13451), added: 15926, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:100 w:100)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[1, 100]`.
+	fn exec_sealed_consignment(b: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1018 + b * (101 ±0)`
+		//  Estimated: `16916 + b * (5311 ±0)`
+		// Minimum execution time: 44_730_000 picoseconds.
+		Weight::from_parts(41_662_000, 16916)
+			// Standard Error: 9_412
+			.saturating_add(Weight::from_parts(17_245_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(b.into())))
+			.saturating_add(Weight::from_parts(0, 5311).saturating_mul(b.into()))
+	}
+	/// Storage: `StorageHandler::Territory` (r:1 w:0)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::AutoRenewals` (r:1 w:1)
+	/// Proof: `StorageHandler::AutoRenewals` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::AutoRenewalQueue` (r:1 w:1)
+	/// Proof: `StorageHandler::AutoRenewalQueue` (`max_values`: None, `max_size`: Some(64022), added: 66497, mode: `MaxEncodedLen`)
+	fn set_auto_renewal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `512`
+		//  Estimated: `67487`
+		// Minimum execution time: 21_874_000 picoseconds.
+		Weight::from_parts(22_650_000, 67487)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `StorageHandler::AutoRenewals` (r:1 w:1)
+	/// Proof: `StorageHandler::AutoRenewals` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TerritoryKey` (r:1 w:0)
+	/// Proof: `StorageHandler::TerritoryKey` (`max_values`: None, `max_size`: Some(144), added: 2619, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::AutoRenewalQueue` (r:1 w:1)
+	/// Proof: `StorageHandler::AutoRenewalQueue` (`max_values`: None, `max_size`: Some(64022), added: 66497, mode: `MaxEncodedLen`)
+	fn cancel_auto_renewal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `430`
+		//  Estimated: `67487`
+		// Minimum execution time: 15_318_000 picoseconds.
+		Weight::from_parts(15_902_000, 67487)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `StorageHandler::Territory` (r:2 w:0)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TerritoryMembers` (r:1 w:1)
+	/// Proof: `StorageHandler::TerritoryMembers` (`max_values`: None, `max_size`: Some(7651), added: 10126, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::MemberTerritory` (r:1 w:1)
+	/// Proof: `StorageHandler::MemberTerritory` (`max_values`: None, `max_size`: Some(144), added: 2619, mode: `MaxEncodedLen`)
+	fn add_territory_member() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `611`
+		//  Estimated: `11116`
+		// Minimum execution time: 24_511_000 picoseconds.
+		Weight::from_parts(25_347_000, 11116)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `StorageHandler::Territory` (r:1 w:0)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TerritoryMembers` (r:1 w:1)
+	/// Proof: `StorageHandler::TerritoryMembers` (`max_values`: None, `max_size`: Some(7651), added: 10126, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::MemberTerritory` (r:0 w:1)
+	/// Proof: `StorageHandler::MemberTerritory` (`max_values`: None, `max_size`: Some(144), added: 2619, mode: `MaxEncodedLen`)
+	fn revoke_territory_member() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `702`
+		//  Estimated: `11116`
+		// Minimum execution time: 20_984_000 picoseconds.
+		Weight::from_parts(21_738_000, 11116)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -406,14 +598,18 @@ impl WeightInfo for () {
 	/// Proof: `StorageHandler::TerritoryKey` (`max_values`: None, `max_size`: Some(144), added: 2619, mode: `MaxEncodedLen`)
 	/// Storage: `StorageHandler::Territory` (r:1 w:2)
 	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::AutoRenewals` (r:1 w:1)
+	/// Proof: `StorageHandler::AutoRenewals` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::AutoRenewalQueue` (r:1 w:1)
+	/// Proof: `StorageHandler::AutoRenewalQueue` (`max_values`: None, `max_size`: Some(64022), added: 66497, mode: `MaxEncodedLen`)
 	fn exec_consignment() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1011`
-		//  Estimated: `6196`
+		//  Estimated: `67487`
 		// Minimum execution time: 63_599_000 picoseconds.
-		Weight::from_parts(66_427_000, 6196)
-			.saturating_add(RocksDbWeight::get().reads(5_u64))
-			.saturating_add(RocksDbWeight::get().writes(6_u64))
+		Weight::from_parts(66_427_000, 67487)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
 	}
 	/// Storage: `StorageHandler::Territory` (r:1 w:1)
 	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
@@ -447,14 +643,18 @@ impl WeightInfo for () {
 	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
 	/// Storage: `StorageHandler::TerritoryKey` (r:1 w:1)
 	/// Proof: `StorageHandler::TerritoryKey` (`max_values`: None, `max_size`: Some(144), added: 2619, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::AutoRenewals` (r:1 w:1)
+	/// Proof: `StorageHandler::AutoRenewals` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::AutoRenewalQueue` (r:1 w:1)
+	/// Proof: `StorageHandler::AutoRenewalQueue` (`max_values`: None, `max_size`: Some(64022), added: 66497, mode: `MaxEncodedLen`)
 	fn territory_grants() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `604`
-		//  Estimated: `3698`
+		//  Estimated: `67487`
 		// Minimum execution time: 17_520_000 picoseconds.
-		Weight::from_parts(18_657_000, 3698)
-			.saturating_add(RocksDbWeight::get().reads(2_u64))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
+		Weight::from_parts(18_657_000, 67487)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// Storage: `StorageHandler::Territory` (r:1 w:2)
 	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
@@ -540,4 +740,178 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(8_u64))
 			.saturating_add(RocksDbWeight::get().writes(6_u64))
 	}
+	/// Storage: `StorageHandler::UnitPriceBounds` (r:1 w:1)
+	/// Proof: `StorageHandler::UnitPriceBounds` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::PurchasedSpace` (r:1 w:0)
+	/// Proof: `StorageHandler::PurchasedSpace` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TotalIdleSpace` (r:1 w:0)
+	/// Proof: `StorageHandler::TotalIdleSpace` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TotalServiceSpace` (r:1 w:0)
+	/// Proof: `StorageHandler::TotalServiceSpace` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::UnitPrice` (r:1 w:1)
+	/// Proof: `StorageHandler::UnitPrice` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	fn set_price_bounds() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `180`
+		//  Estimated: `1517`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(12_500_000, 1517)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `StorageHandler::Territory` (r:1 w:1)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::Consignment` (r:1 w:1)
+	/// Proof: `StorageHandler::Consignment` (`max_values`: None, `max_size`: Some(193), added: 2668, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::Lookup` (r:1 w:1)
+	/// Proof: `Scheduler::Lookup` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::Agenda` (r:1 w:1)
+	/// Proof: `Scheduler::Agenda` (`max_values`: None, `max_size`: Some(38963), added: 41438, mode: `MaxEncodedLen`)
+	fn territory_auction() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `612`
+		//  Estimated: `42428`
+		// Minimum execution time: 31_204_000 picoseconds.
+		Weight::from_parts(32_118_000, 42428)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `StorageHandler::Territory` (r:2 w:2)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `Babe::AuthorVrfRandomness` (r:1 w:0)
+	/// Proof: `Babe::AuthorVrfRandomness` (`max_values`: Some(1), `max_size`: Some(33), added: 528, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TerritoryKey` (r:1 w:1)
+	/// Proof: `StorageHandler::TerritoryKey` (`max_values`: None, `max_size`: Some(144), added: 2619, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TerritoryFrozenCounter` (r:1 w:1)
+	/// Proof: `StorageHandler::TerritoryFrozenCounter` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::Consignment` (r:1 w:1)
+	/// Proof: `StorageHandler::Consignment` (`max_values`: None, `max_size`: Some(193), added: 2668, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::Lookup` (r:1 w:1)
+	/// Proof: `Scheduler::Lookup` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::Agenda` (r:1 w:1)
+	/// Proof: `Scheduler::Agenda` (`max_values`: None, `max_size`: Some(38963), added: 41438, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TerritoryFrozen` (r:0 w:1)
+	/// Proof: `StorageHandler::TerritoryFrozen` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	fn split_territory_consignment() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `688`
+		//  Estimated: `42428`
+		// Minimum execution time: 52_877_000 picoseconds.
+		Weight::from_parts(54_306_000, 42428)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: `StorageHandler::Consignment` (r:1 w:0)
+	/// Proof: `StorageHandler::Consignment` (`max_values`: None, `max_size`: Some(193), added: 2668, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::SealedBids` (r:1 w:1)
+	/// Proof: `StorageHandler::SealedBids` (`max_values`: None, `max_size`: Some(13451), added: 15926, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn place_sealed_bid() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1340`
+		//  Estimated: `16916`
+		// Minimum execution time: 38_512_000 picoseconds.
+		Weight::from_parts(39_870_000, 16916)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `StorageHandler::Consignment` (r:1 w:0)
+	/// Proof: `StorageHandler::Consignment` (`max_values`: None, `max_size`: Some(193), added: 2668, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::SealedBids` (r:1 w:1)
+	/// Proof: `StorageHandler::SealedBids` (`max_values`: None, `max_size`: Some(13451), added: 15926, mode: `MaxEncodedLen`)
+	fn reveal_sealed_bid() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1412`
+		//  Estimated: `16916`
+		// Minimum execution time: 24_103_000 picoseconds.
+		Weight::from_parts(25_006_000, 16916)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `StorageHandler::Consignment` (r:1 w:1)
+	/// Proof: `StorageHandler::Consignment` (`max_values`: None, `max_size`: Some(193), added: 2668, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TerritoryKey` (r:1 w:1)
+	/// Proof: `StorageHandler::TerritoryKey` (`max_values`: None, `max_size`: Some(144), added: 2619, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::Territory` (r:101 w:2)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::SealedBids` (r:1 w:1)
+	/// Proof: `StorageHandler::SealedBids` (`max_values`: None, `max_size`: Some(13451), added: 15926, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:100 w:100)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[1, 100]`.
+	fn exec_sealed_consignment(b: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1018 + b * (101 ±0)`
+		//  Estimated: `16916 + b * (5311 ±0)`
+		// Minimum execution time: 44_730_000 picoseconds.
+		Weight::from_parts(41_662_000, 16916)
+			// Standard Error: 9_412
+			.saturating_add(Weight::from_parts(17_245_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(b.into())))
+			.saturating_add(Weight::from_parts(0, 5311).saturating_mul(b.into()))
+	}
+	/// Storage: `StorageHandler::Territory` (r:1 w:0)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::AutoRenewals` (r:1 w:1)
+	/// Proof: `StorageHandler::AutoRenewals` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::AutoRenewalQueue` (r:1 w:1)
+	/// Proof: `StorageHandler::AutoRenewalQueue` (`max_values`: None, `max_size`: Some(64022), added: 66497, mode: `MaxEncodedLen`)
+	fn set_auto_renewal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `512`
+		//  Estimated: `67487`
+		// Minimum execution time: 21_874_000 picoseconds.
+		Weight::from_parts(22_650_000, 67487)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `StorageHandler::AutoRenewals` (r:1 w:1)
+	/// Proof: `StorageHandler::AutoRenewals` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TerritoryKey` (r:1 w:0)
+	/// Proof: `StorageHandler::TerritoryKey` (`max_values`: None, `max_size`: Some(144), added: 2619, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::AutoRenewalQueue` (r:1 w:1)
+	/// Proof: `StorageHandler::AutoRenewalQueue` (`max_values`: None, `max_size`: Some(64022), added: 66497, mode: `MaxEncodedLen`)
+	fn cancel_auto_renewal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `430`
+		//  Estimated: `67487`
+		// Minimum execution time: 15_318_000 picoseconds.
+		Weight::from_parts(15_902_000, 67487)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `StorageHandler::Territory` (r:2 w:0)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TerritoryMembers` (r:1 w:1)
+	/// Proof: `StorageHandler::TerritoryMembers` (`max_values`: None, `max_size`: Some(7651), added: 10126, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::MemberTerritory` (r:1 w:1)
+	/// Proof: `StorageHandler::MemberTerritory` (`max_values`: None, `max_size`: Some(144), added: 2619, mode: `MaxEncodedLen`)
+	fn add_territory_member() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `611`
+		//  Estimated: `11116`
+		// Minimum execution time: 24_511_000 picoseconds.
+		Weight::from_parts(25_347_000, 11116)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `StorageHandler::Territory` (r:1 w:0)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::TerritoryMembers` (r:1 w:1)
+	/// Proof: `StorageHandler::TerritoryMembers` (`max_values`: None, `max_size`: Some(7651), added: 10126, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::MemberTerritory` (r:0 w:1)
+	/// Proof: `StorageHandler::MemberTerritory` (`max_values`: None, `max_size`: Some(144), added: 2619, mode: `MaxEncodedLen`)
+	fn revoke_territory_member() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `702`
+		//  Estimated: `11116`
+		// Minimum execution time: 20_984_000 picoseconds.
+		Weight::from_parts(21_738_000, 11116)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
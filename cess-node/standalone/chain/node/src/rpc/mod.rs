@@ -2,8 +2,8 @@
 
 use cess_node_primitives::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Nonce};
 use cessc_consensus_rrsc::RRSCWorkerHandle;
-use cessc_consensus_rrsc_rpc::{RRSCApiServer, RRSC};
-use cessp_consensus_rrsc::RRSCApi;
+use cessc_consensus_rrsc_rpc::{RRSCApiServer, RRSCElection, RRSCElectionApiServer, RRSC};
+use cessp_consensus_rrsc::{RRSCApi, RRSCElectionApi};
 use grandpa::{FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoterState};
 use jsonrpsee::RpcModule;
 use sc_client_api::{
@@ -98,7 +98,9 @@ where
 	C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
 	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
 	C::Api: RRSCApi<Block>,
+	C::Api: RRSCElectionApi<Block, AccountId>,
 	C::Api: ces_pallet_mq_runtime_api::MqApi<Block>,
+	C::Api: pallet_sminer_runtime_api::SminerApi<Block, AccountId, Balance, BlockNumber>,
 	C: BlockchainEvents<Block> + 'static,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
 	C: AuxStore + UsageProvider<Block> + StorageProvider<Block, B>,
@@ -112,6 +114,7 @@ where
 {
 	use ces_node_rpc_ext::{NodeRpcExt, NodeRpcExtApiServer};
 	use cessc_sync_state_rpc::{SyncState, SyncStateApiServer};
+	use pallet_sminer_rpc::{Sminer, SminerApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use sc_consensus_grandpa_rpc::{Grandpa, GrandpaApiServer};
 	use sc_rpc_spec_v2::chain_spec::{ChainSpec, ChainSpecApiServer};
@@ -138,7 +141,9 @@ where
 	// more context: https://github.com/paritytech/substrate/pull/3480
 	// These RPCs should use an asynchronous caller instead.
 	io.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	io.merge(Sminer::new(client.clone()).into_rpc())?;
 	io.merge(RRSC::new(client.clone(), rrsc_worker_handle.clone(), keystore, select_chain, deny_unsafe).into_rpc())?;
+	io.merge(RRSCElection::new(client.clone()).into_rpc())?;
 	io.merge(
 		Grandpa::new(
 			subscription_executor,
@@ -660,6 +660,10 @@ impl pallet_rrsc::VrfSloverConfig for OnChainVrfSloverConfig {
 		let total_issuance = <Runtime as pallet_cess_staking::Config>::Currency::total_issuance();
 		<Runtime as pallet_cess_staking::Config>::CurrencyToVote::to_vote(MIN_ELECTABLE_STAKE, total_issuance)
 	}
+
+	fn score_weights() -> cessp_consensus_rrsc::VrfScoreWeights {
+		pallet_rrsc::ScoreWeights::<Runtime>::get()
+	}
 }
 
 pub struct OnChainVrf;
@@ -1488,11 +1492,19 @@ impl pallet_cess_treasury::Config for Runtime {
 
 parameter_types! {
 	pub const PeriodDuration: BlockNumber = EPOCH_DURATION_IN_BLOCKS * SessionsPerEra::get();
+	pub const CreditActivityWeights: cp_scheduler_credit::ActivityWeights = cp_scheduler_credit::ActivityWeights {
+		tag: Percent::from_percent(30),
+		cert: Percent::from_percent(10),
+		idle_verify: Percent::from_percent(20),
+		service_verify: Percent::from_percent(30),
+		replace: Percent::from_percent(10),
+	};
 }
 
 impl pallet_scheduler_credit::Config for Runtime {
 	type PeriodDuration = PeriodDuration;
 	type StashAccountFinder = SchedulerStashAccountFinder;
+	type ActivityWeights = CreditActivityWeights;
 }
 
 pub struct MqCallMatcher;
@@ -1504,10 +1516,17 @@ impl ces_pallet_mq::CallMatcher<Runtime> for MqCallMatcher {
 		}
 	}
 }
+parameter_types! {
+	pub const MessageRetentionPeriod: BlockNumber = HOURS;
+	pub const MaxRetainedPerBlock: u32 = 1024;
+}
+
 impl ces_pallet_mq::Config for Runtime {
 	type QueueNotifyConfig = msg_routing::MessageRouteConfig;
 	type CallMatcher = MqCallMatcher;
 	type MasterPubkeySupplier = pallet_tee_worker::Pallet<Runtime>;
+	type MessageRetentionPeriod = MessageRetentionPeriod;
+	type MaxRetainedPerBlock = MaxRetainedPerBlock;
 }
 
 parameter_types! {
@@ -1588,6 +1607,11 @@ parameter_types! {
 	pub const RestoralOrderLife: u32 = 250;
 	#[derive(Clone, Eq, PartialEq)]
 	pub const MissionCount: u32 = SEGMENT_COUNT * FRAGMENT_COUNT;
+	#[derive(Clone, Eq, PartialEq)]
+	pub const MaxObjectVersions: u32 = 16;
+	pub const DealLife: BlockNumber = HOURS * 6;
+	pub const MaxDealReassignments: u32 = 2;
+	pub const RestoralBounty: Balance = DOLLARS;
 }
 
 impl pallet_file_bank::Config for Runtime {
@@ -1612,6 +1636,10 @@ impl pallet_file_bank::Config for Runtime {
 	type NameMinLength = NameMinLength;
 	type RestoralOrderLife = RestoralOrderLife;
 	type MissionCount = MissionCount;
+	type MaxObjectVersions = MaxObjectVersions;
+	type DealLife = DealLife;
+	type MaxDealReassignments = MaxDealReassignments;
+	type RestoralBounty = RestoralBounty;
 }
 
 parameter_types! {
@@ -1639,6 +1667,7 @@ impl pallet_sminer::Config for Runtime {
 	type CessTreasuryHandle = CessTreasury;
 	type MaximumRelease = MaximumRelease;
 	type ReservoirGate = Reservoir;
+	type Obligations = (Audit, FileBank);
 	type Staking = Staking;
 	type Preimages = Preimage;
 }
@@ -1697,6 +1726,8 @@ parameter_types! {
 	pub const LockingBlock: BlockNumber = MINUTES * 5;
 	#[derive(Clone, Eq, PartialEq)]
 	pub const FrozenLimit: u32 = 2000;
+	// One staking era of `SessionsPerEra` epochs.
+	pub const PricingEra: BlockNumber = 6 * EPOCH_DURATION_IN_BLOCKS;
 }
 
 impl pallet_storage_handler::Config for Runtime {
@@ -1718,6 +1749,10 @@ impl pallet_storage_handler::Config for Runtime {
 	type FrozenDays = FrozenDays;
 	type CessTreasuryHandle = CessTreasury;
 	type FrozenLimit = FrozenLimit;
+	type PricingEra = PricingEra;
+	type MaxSealedBids = ConstU32<100>;
+	type AutoRenewAdvance = OneDay;
+	type MaxTerritoryMembers = ConstU32<50>;
 }
 
 parameter_types! {
@@ -1956,6 +1991,23 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl cessp_consensus_rrsc::RRSCElectionApi<Block, AccountId> for Runtime {
+		fn election_dry_run() -> Vec<cessp_consensus_rrsc::CandidateScore<AccountId>> {
+			let bounds = ElectionBoundsMultiPhase::get();
+			let targets = Staking::electable_targets(bounds.targets).unwrap_or_default();
+			let voters = Staking::electing_voters(bounds.voters).unwrap_or_default();
+			let desired_targets = Staking::desired_targets().unwrap_or_default();
+
+			pallet_rrsc::VrfSolver::<
+				AccountId,
+				pallet_election_provider_multi_phase::SolutionAccuracyOf<Runtime>,
+				Runtime,
+				SchedulerCredit,
+				OnChainVrfSloverConfig,
+			>::dry_run(desired_targets as usize, targets, voters)
+		}
+	}
+
 	impl sp_authority_discovery::AuthorityDiscoveryApi<Block> for Runtime {
 		fn authorities() -> Vec<AuthorityDiscoveryId> {
 			AuthorityDiscovery::authorities()
@@ -2467,6 +2519,46 @@ impl_runtime_apis! {
 		fn sender_sequence(sender: &ces_types::messaging::MessageOrigin) -> Option<u64> {
 			CesMq::offchain_ingress(sender)
 		}
+
+		fn retained_messages(
+			sender: Option<ces_types::messaging::MessageOrigin>,
+			topic: Option<ces_types::messaging::Path>,
+			from: BlockNumber,
+			to: BlockNumber,
+		) -> Vec<ces_types::messaging::RetainedMessage<BlockNumber>> {
+			CesMq::retained_messages(sender, topic, from, to)
+		}
+	}
+
+	impl pallet_file_bank_runtime_api::FileBankApi<Block, AccountId> for Runtime {
+		fn bucket_permissions(
+			account: AccountId,
+			owner: AccountId,
+			bucket_name: Vec<u8>,
+		) -> cp_cess_common::BucketPermissions {
+			match bucket_name.try_into() {
+				Ok(bucket_name) => FileBank::bucket_permissions(&account, &owner, &bucket_name),
+				Err(_) => Default::default(),
+			}
+		}
+
+		fn file_permissions(account: AccountId, file_hash: cp_cess_common::Hash) -> cp_cess_common::BucketPermissions {
+			FileBank::file_permissions(&account, &file_hash)
+		}
+	}
+
+	impl pallet_sminer_runtime_api::SminerApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn miner_economics(
+			miner: AccountId,
+		) -> Result<Option<cp_cess_common::MinerEconomics<Balance, BlockNumber>>, sp_runtime::DispatchError> {
+			Sminer::miner_economics(&miner)
+		}
+	}
+
+	impl pallet_scheduler_credit_runtime_api::SchedulerCreditApi<Block, AccountId> for Runtime {
+		fn credit_breakdown(stash: AccountId) -> cp_scheduler_credit::SchedulerCreditBreakdown {
+			SchedulerCredit::credit_breakdown(&stash)
+		}
 	}
 	//------------------------- CESS's end -------------------------
 }
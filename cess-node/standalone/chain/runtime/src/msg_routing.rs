@@ -1,5 +1,5 @@
 use codec::Decode;
-use frame_support::dispatch::DispatchResult;
+use frame_support::{dispatch::DispatchResult, weights::Weight};
 use sp_runtime::DispatchError;
 use ces_types::messaging::{BindTopic, DecodedMessage, Message};
 
@@ -19,6 +19,19 @@ where
     Ok(())
 }
 
+fn try_weigh<Msg, Func>(func: Func, message: &Message) -> Weight
+where
+    Msg: Decode + BindTopic,
+    Func: Fn(&DecodedMessage<Msg>) -> Weight,
+{
+    if message.destination.path() == &Msg::topic() {
+        if let Some(msg) = message.decode::<Msg>() {
+            return (func)(&msg);
+        }
+    }
+    Weight::zero()
+}
+
 impl ces_pallet_mq::QueueNotifyConfig for MessageRouteConfig {
     /// Handles an incoming message
     fn on_message_received(message: &Message) -> DispatchResult {
@@ -31,7 +44,24 @@ impl ces_pallet_mq::QueueNotifyConfig for MessageRouteConfig {
 
         route_handlers! {
             TeeWorker::on_message_received,
+            Audit::on_message_received,
+            FileBank::on_message_received,
         };
         Ok(())
     }
+
+    /// Weighs an incoming message by the handlers that do more than the message queue itself
+    fn message_weight(message: &Message) -> Weight {
+        use super::*;
+        macro_rules! weigh_handlers {
+            ($($handler: path,)+) => {
+                Weight::zero()$(.saturating_add(try_weigh($handler, message)))+
+            }
+        }
+
+        weigh_handlers! {
+            Audit::message_weight,
+            FileBank::message_weight,
+        }
+    }
 }
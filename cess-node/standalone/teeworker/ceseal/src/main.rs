@@ -6,6 +6,8 @@ use ces_sanitized_logger as logger;
 use ces_types::WorkerRole;
 use cestory::run_ceseal_server;
 use cestory_api::ecall_args::InitArgs;
+#[cfg(feature = "simulated")]
+use cestory_pal::SimulatedPlatform;
 use clap::{crate_version, Parser, Subcommand};
 use pal_gramine::GraminePlatform;
 use std::{env, time::Duration};
@@ -91,6 +93,17 @@ struct Args {
     #[arg(long)]
     data_dir: Option<String>,
 
+    /// Run on the simulated platform, which seals to plain files and registers without remote attestation.
+    /// For local testing in non-SGX environment only.
+    #[cfg(feature = "simulated")]
+    #[arg(long)]
+    simulated: bool,
+
+    /// Hex encoded machine id reported by the simulated platform
+    #[cfg(feature = "simulated")]
+    #[arg(long, requires = "simulated")]
+    sim_machine_id: Option<String>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -121,6 +134,10 @@ fn main() -> Result<()> {
                 .enable_all()
                 .build()?;
             let sgx = pal_gramine::is_gramine();
+            #[cfg(feature = "simulated")]
+            if sgx && args.simulated {
+                bail!("The simulated platform can not be used in SGX");
+            }
             logger::init_subscriber(sgx);
             pal_gramine::print_target_info();
             rt.block_on(serve(sgx, args))?;
@@ -190,6 +207,19 @@ async fn serve(sgx: bool, args: Args) -> Result<()> {
         }
     };
     info!("init_args: {:#?}", init_args);
+    #[cfg(feature = "simulated")]
+    if args.simulated {
+        if args.request_handover_from.is_some() {
+            bail!("Handover is not supported on the simulated platform");
+        }
+        let machine_id = match &args.sim_machine_id {
+            Some(id) => hex::decode(id.trim_start_matches("0x"))?,
+            None => vec![],
+        };
+        info!(machine_id = hex::encode(&machine_id), "Running on the simulated platform");
+        run_ceseal_server(init_args, SimulatedPlatform::new(machine_id, cores), listener_addr).await?;
+        return Ok(());
+    }
     if let Some(from) = args.request_handover_from {
         info!(%from, "Starting handover");
         handover::handover_from(&from, init_args)
@@ -0,0 +1,199 @@
+//! Verifies SGX DCAP quotes the way `pallet-tee-worker` does, against collaterals cached on disk, so that rejected
+//! registrations can be investigated without reaching Intel services or a PCCS.
+
+use anyhow::{anyhow, Context, Result};
+use ces_types::attestation::{validate_dcap, ExtendMeasurement};
+use clap::{Parser, Subcommand};
+use parity_scale_codec::Decode;
+use sgx_attestation::dcap::{
+	collateral_store::{CachedCollateral, CollateralStore},
+	quote::Quote,
+	report::get_collateral_cached,
+	verify::verify,
+};
+use sp_core::H256;
+use std::{
+	path::{Path, PathBuf},
+	time::Duration,
+};
+
+const DEFAULT_PCCS_URL: &str = "https://dcap.cess.network/sgx/certification/v4/";
+
+#[derive(Parser, Debug)]
+#[command(about = "Verify SGX DCAP quotes offline against cached collaterals", version, author)]
+struct Args {
+	#[arg(long, help = "The directory collaterals are cached in", default_value = "./dcap-collaterals")]
+	cache: PathBuf,
+
+	#[command(subcommand)]
+	command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+	/// Fetch the collateral matching the FMSPC of a quote from a PCCS server into the cache.
+	Fetch {
+		#[arg(long, help = "The quote file")]
+		quote: PathBuf,
+
+		#[arg(long, help = "The quote file is hex encoded")]
+		hex: bool,
+
+		#[arg(long, help = "The PCCS server URL", default_value = DEFAULT_PCCS_URL)]
+		pccs_url: String,
+
+		#[arg(long, help = "Timeout of the PCCS requests in seconds", default_value_t = 10)]
+		timeout: u64,
+	},
+	/// Verify a quote with the cached collateral and print its TCB status and measurements.
+	Verify {
+		#[arg(long, help = "The quote file")]
+		quote: PathBuf,
+
+		#[arg(long, help = "The quote file is hex encoded")]
+		hex: bool,
+
+		#[arg(long, help = "Unix timestamp to verify at, e.g. the block time of a rejected registration")]
+		now: Option<u64>,
+
+		#[arg(long, help = "A ceseal measurement hash the chain allows, checked like the on-chain allowlist when given")]
+		allow: Vec<String>,
+	},
+	/// List the cached collaterals.
+	List {
+		#[arg(long, help = "Remove the collaterals which are outdated")]
+		prune: bool,
+	},
+}
+
+#[tokio::main]
+async fn main() {
+	let args = Args::parse();
+	if let Err(err) = run(args).await {
+		eprintln!("Error: {err:?}");
+		std::process::exit(1);
+	}
+}
+
+async fn run(args: Args) -> Result<()> {
+	let store = CollateralStore::open(&args.cache)?;
+	match args.command {
+		Command::Fetch { quote, hex, pccs_url, timeout } => {
+			let raw_quote = read_quote(&quote, hex)?;
+			let now = chrono::Utc::now().timestamp() as u64;
+			get_collateral_cached(&store, &pccs_url, &raw_quote, Duration::from_secs(timeout), now).await?;
+			let fmspc = decode_quote(&raw_quote)?
+				.fmspc()
+				.map_err(|e| anyhow!("get fmspc error: {e:?}"))?;
+			let cached = store.get(&fmspc)?.ok_or(anyhow!("collateral was not cached"))?;
+			print_collateral(&cached);
+		},
+		Command::Verify { quote, hex, now, allow } => {
+			let raw_quote = read_quote(&quote, hex)?;
+			let now = now.unwrap_or_else(|| chrono::Utc::now().timestamp() as u64);
+			let allowlist = allow.iter().map(|hash| parse_hash(hash)).collect::<Result<Vec<_>>>()?;
+			verify_quote(&store, &raw_quote, now, allowlist)?;
+		},
+		Command::List { prune } => {
+			if prune {
+				let now = chrono::Utc::now().timestamp() as u64;
+				for fmspc in store.remove_expired(now)? {
+					println!("removed {fmspc}");
+				}
+			}
+			for cached in store.list()? {
+				print_collateral(&cached);
+			}
+		},
+	}
+	Ok(())
+}
+
+fn read_quote(path: &Path, is_hex: bool) -> Result<Vec<u8>> {
+	let data = std::fs::read(path).with_context(|| format!("read quote {}", path.display()))?;
+	if !is_hex {
+		return Ok(data)
+	}
+	let text = String::from_utf8(data).context("hex quote should be utf8")?;
+	let text = text.trim();
+	hex::decode(text.strip_prefix("0x").unwrap_or(text)).context("quote should be a hex string")
+}
+
+fn decode_quote(raw_quote: &[u8]) -> Result<Quote> {
+	Quote::decode(&mut &raw_quote[..]).map_err(|e| anyhow!("decode quote error: {e}"))
+}
+
+fn parse_hash(text: &str) -> Result<H256> {
+	let bytes = hex::decode(text.strip_prefix("0x").unwrap_or(text)).context("hash should be a hex string")?;
+	if bytes.len() != 32 {
+		return Err(anyhow!("hash should be 32 bytes"))
+	}
+	Ok(H256::from_slice(&bytes))
+}
+
+fn verify_quote(store: &CollateralStore, raw_quote: &[u8], now: u64, allowlist: Vec<H256>) -> Result<()> {
+	let quote = decode_quote(raw_quote)?;
+	let report = &quote.report;
+	let measurement = ExtendMeasurement {
+		mr_enclave: report.mr_enclave,
+		mr_signer: report.mr_signer,
+		isv_prod_id: report.isv_prod_id.to_be_bytes(),
+		isv_svn: report.isv_svn.to_be_bytes(),
+	};
+
+	println!("MRENCLAVE:        {}", hex::encode(report.mr_enclave));
+	println!("MRSIGNER:         {}", hex::encode(report.mr_signer));
+	println!("ISV prod id:      {}", report.isv_prod_id);
+	println!("ISV SVN:          {}", report.isv_svn);
+	println!("Report data:      {}", hex::encode(report.report_data));
+	println!("Measurement hash: {:?}", measurement.measurement_hash());
+
+	let fmspc = quote.fmspc().map_err(|e| anyhow!("get fmspc error: {e:?}"))?;
+	println!("FMSPC:            {}", hex::encode_upper(fmspc));
+	let cached = store
+		.get(&fmspc)?
+		.ok_or(anyhow!("no collateral cached for FMSPC {}, fetch it first", hex::encode_upper(fmspc)))?;
+	println!(
+		"Collateral:       {} .. {}{}",
+		format_timestamp(cached.not_before),
+		format_timestamp(cached.not_after),
+		if cached.is_valid_at(now) { "" } else { " (outdated)" }
+	);
+	println!("Verified at:      {}", format_timestamp(now as i64));
+
+	// Only for display, the verdict below comes from the same function pallet-tee-worker calls
+	if let Ok((_, _, tcb_status, advisory_ids)) = verify(raw_quote, &cached.collateral, now) {
+		println!("TCB status:       {tcb_status}");
+		println!("Advisory IDs:     {}", advisory_ids.join(", "));
+	}
+	// The chain compares the report data against the worker's registration, which we don't have here, so
+	// the quote is checked against its own report data.
+	let verify_ceseal_hash = !allowlist.is_empty();
+	let confidential_report = validate_dcap(
+		raw_quote,
+		&cached.collateral,
+		now,
+		&report.report_data[..32],
+		verify_ceseal_hash,
+		allowlist,
+	)
+	.map_err(|e| anyhow!("quote rejected: {e:?}"))?;
+	println!("Confidence level: {}", confidential_report.confidence_level);
+	Ok(())
+}
+
+fn print_collateral(cached: &CachedCollateral) {
+	println!(
+		"{}  fetched {}  valid {} .. {}",
+		cached.fmspc,
+		format_timestamp(cached.fetched_at),
+		format_timestamp(cached.not_before),
+		format_timestamp(cached.not_after)
+	);
+}
+
+fn format_timestamp(timestamp: i64) -> String {
+	chrono::DateTime::from_timestamp(timestamp, 0)
+		.map(|time| time.to_rfc3339())
+		.unwrap_or_else(|| timestamp.to_string())
+}